@@ -1,7 +1,8 @@
-use super::conversions::{bxes_value_to_payload_value, global_type_to_string};
+use super::conversions::{bxes_value_to_payload_value, default_bxes_value, global_type_to_string};
 use crate::event_log::{
   core::{event::event::EventPayloadValue, event_log::EventLog, trace::trace::Trace},
   xes::{
+    constants::EVENT_TAG_NAME_STR,
     shared::{XesClassifier, XesEventLogExtension, XesProperty},
     xes_event::XesEventImpl,
     xes_event_log::XesEventLogImpl,
@@ -73,6 +74,7 @@ fn read_bxes_into_xes_internal(result: BxesEventLogReadResult) -> Result<BxesToX
   set_properties(&mut xes_log, &result.log)?;
   set_extensions(&mut xes_log, &result.log)?;
   set_globals(&mut xes_log, &result.log)?;
+  set_schema_globals(&mut xes_log, &result.system_metadata);
 
   for variant in &result.log.variants {
     xes_log.push(Rc::new(RefCell::new(read_bxes_events(&variant.events)?)));
@@ -153,6 +155,26 @@ fn set_globals(xes_log: &mut XesEventLogImpl, log: &BxesEventLog) -> Result<(),
   Ok(())
 }
 
+fn set_schema_globals(xes_log: &mut XesEventLogImpl, system_metadata: &SystemMetadata) {
+  let Some(schema) = system_metadata.event_attributes_schema.as_ref() else {
+    return;
+  };
+
+  let event_globals = xes_log.globals_mut().entry(Arc::from(EVENT_TAG_NAME_STR)).or_default();
+  for attribute in schema.attributes.iter().filter(|attr| attr.required) {
+    if event_globals.contains_key(&attribute.name) {
+      continue;
+    }
+
+    let default_value = match attribute.allowed_values.as_ref().and_then(|values| values.first()) {
+      Some(value) => value.clone(),
+      None => default_bxes_value(&attribute.type_id),
+    };
+
+    event_globals.insert(attribute.name.clone(), bxes_value_to_payload_value(&default_value));
+  }
+}
+
 fn vector_of_strings_or_err(values: &Vec<Arc<BxesValue>>, entity_name: &str) -> Result<Vec<Arc<str>>, BxesToXesReadError> {
   let mut result = vec![];
   for value in values {
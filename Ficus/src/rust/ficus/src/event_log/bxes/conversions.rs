@@ -5,6 +5,7 @@ use bxes::{
     bxes_log_metadata::BxesGlobalKind,
     bxes_value::BxesValue,
     software_event_type::SoftwareEventType,
+    type_ids::TypeIds,
   },
   read::read_utils::owned_string_or_err,
 };
@@ -83,6 +84,27 @@ pub(super) fn bxes_value_to_payload_value(value: &BxesValue) -> EventPayloadValu
   }
 }
 
+pub(super) fn default_bxes_value(type_id: &TypeIds) -> BxesValue {
+  match type_id {
+    TypeIds::Null => BxesValue::Null,
+    TypeIds::I32 => BxesValue::Int32(0),
+    TypeIds::I64 => BxesValue::Int64(0),
+    TypeIds::U32 => BxesValue::Uint32(0),
+    TypeIds::U64 => BxesValue::Uint64(0),
+    TypeIds::F32 => BxesValue::Float32(0.0),
+    TypeIds::F64 => BxesValue::Float64(0.0),
+    TypeIds::String => BxesValue::String(Arc::from("")),
+    TypeIds::Bool => BxesValue::Bool(false),
+    TypeIds::Timestamp => BxesValue::Timestamp(0),
+    TypeIds::BrafLifecycle => BxesValue::BrafLifecycle(BxesBrafLifecycle::Unspecified),
+    TypeIds::StandardLifecycle => BxesValue::StandardLifecycle(BxesStandardLifecycle::Unspecified),
+    TypeIds::Artifact => BxesValue::Artifact(BxesArtifact { items: vec![] }),
+    TypeIds::Drivers => BxesValue::Drivers(BxesDrivers { drivers: vec![] }),
+    TypeIds::Guid => BxesValue::Guid(uuid::Uuid::nil()),
+    TypeIds::SoftwareEventType => BxesValue::SoftwareEventType(SoftwareEventType::Unspecified),
+  }
+}
+
 pub(super) fn payload_value_to_bxes_value(value: &EventPayloadValue) -> BxesValue {
   match value {
     EventPayloadValue::Null => BxesValue::Null,
@@ -14,7 +14,7 @@ use bxes::{
       bxes_log_metadata::{BxesClassifier, BxesEventLogMetadata, BxesExtension, BxesGlobal},
       bxes_value::BxesValue,
     },
-    system_models::SystemMetadata,
+    system_models::{EventAttributesSchema, SystemMetadata},
   },
   writer::{
     errors::BxesWriteError,
@@ -24,6 +24,7 @@ use bxes::{
 };
 use std::{fmt::Display, sync::Arc};
 
+#[derive(Debug)]
 pub enum XesToBxesWriterError {
   BxesWriteError(BxesWriteError),
   ConversionError(String),
@@ -58,6 +59,8 @@ pub fn write_event_log_to_bxes(log: &XesEventLogImpl, metadata: Option<&SystemMe
 }
 
 fn create_bxes_write_data(log: &XesEventLogImpl, metadata: Option<&SystemMetadata>) -> Result<BxesLogWriteData, XesToBxesWriterError> {
+  let schema = metadata.and_then(|metadata| metadata.event_attributes_schema.as_ref());
+
   let bxes_log = BxesEventLog {
     metadata: BxesEventLogMetadata {
       classifiers: Some(create_bxes_classifiers(log)),
@@ -65,7 +68,7 @@ fn create_bxes_write_data(log: &XesEventLogImpl, metadata: Option<&SystemMetadat
       globals: Some(create_bxes_globals(log)?),
       properties: Some(create_bxes_properties(log)),
     },
-    variants: create_bxes_traces(log),
+    variants: create_bxes_traces(log, schema),
     version: 1,
   };
 
@@ -78,7 +81,7 @@ fn create_bxes_write_data(log: &XesEventLogImpl, metadata: Option<&SystemMetadat
   })
 }
 
-fn create_bxes_traces(log: &XesEventLogImpl) -> Vec<BxesTraceVariant> {
+fn create_bxes_traces(log: &XesEventLogImpl, schema: Option<&EventAttributesSchema>) -> Vec<BxesTraceVariant> {
   log
     .traces()
     .iter()
@@ -89,13 +92,13 @@ fn create_bxes_traces(log: &XesEventLogImpl) -> Vec<BxesTraceVariant> {
         .borrow()
         .events()
         .iter()
-        .map(|event| create_bxes_event(log, &event.borrow()))
+        .map(|event| create_bxes_event(log, schema, &event.borrow()))
         .collect(),
     })
     .collect()
 }
 
-fn create_bxes_event(log: &XesEventLogImpl, event: &XesEventImpl) -> BxesEvent {
+fn create_bxes_event(log: &XesEventLogImpl, schema: Option<&EventAttributesSchema>, event: &XesEventImpl) -> BxesEvent {
   BxesEvent {
     name: Arc::new(BxesValue::String(event.name_pointer().clone())),
     timestamp: event.timestamp().timestamp_nanos_opt().expect("timestamp_nanos_opt"),
@@ -103,13 +106,26 @@ fn create_bxes_event(log: &XesEventLogImpl, event: &XesEventImpl) -> BxesEvent {
       event
         .ordered_payload()
         .iter()
-        .filter(|kv| is_not_default_attribute(log, kv))
+        .filter(|kv| is_required_by_schema(schema, kv) || is_not_default_attribute(log, kv))
         .map(kv_pair_to_bxes_pair)
         .collect(),
     ),
   }
 }
 
+/// Converts a single event with all its attributes, should be used when the event is written without its log (i.e. to a stream).
+pub fn xes_event_to_bxes_event(event: &XesEventImpl) -> BxesEvent {
+  BxesEvent {
+    name: Arc::new(BxesValue::String(event.name_pointer().clone())),
+    timestamp: event.timestamp().timestamp_nanos_opt().expect("timestamp_nanos_opt"),
+    attributes: Some(event.ordered_payload().iter().map(kv_pair_to_bxes_pair).collect()),
+  }
+}
+
+fn is_required_by_schema(schema: Option<&EventAttributesSchema>, kv: &(&Arc<str>, &EventPayloadValue)) -> bool {
+  schema.and_then(|schema| schema.find(kv.0)).is_some_and(|attr| attr.required)
+}
+
 fn is_not_default_attribute(log: &XesEventLogImpl, kv: &(&Arc<str>, &EventPayloadValue)) -> bool {
   if let Some(event_globals) = log.globals_map().get(EVENT_TAG_NAME_STR) {
     if let Some(default_value) = event_globals.get(kv.0) {
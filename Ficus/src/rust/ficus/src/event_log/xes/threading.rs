@@ -1,4 +1,40 @@
-use crate::event_log::xes::xes_event_log::XesEventLogImpl;
+use crate::{
+  event_log::{
+    core::event_log::EventLog,
+    xes::{xes_event::XesEventImpl, xes_event_log::XesEventLogImpl},
+  },
+  pipelines::sendable_values::copy_context_values,
+  utils::user_data::user_data::UserDataOwner,
+};
+use std::{cell::RefCell, rc::Rc};
 
 unsafe impl Sync for XesEventLogImpl {}
 unsafe impl Send for XesEventLogImpl {}
+
+impl XesEventLogImpl {
+  /// Deep copy which does not share any `Rc` with the original log, so it can be moved to another thread.
+  /// User data of the log and of its events is copied too, if some value can not be copied the name of its key is returned.
+  pub fn detached_clone(&self) -> Result<Self, String> {
+    let mut log = self.clone();
+    *log.user_data_mut() = copy_context_values(self.user_data())?;
+
+    let failed_key = RefCell::new(None);
+    log.mutate_events(|event| match copy_context_values(event.user_data()) {
+      Ok(user_data) => *event.user_data_mut() = user_data,
+      Err(key) => *failed_key.borrow_mut() = Some(key),
+    });
+
+    match failed_key.into_inner() {
+      None => Ok(log),
+      Some(key) => Err(key),
+    }
+  }
+}
+
+pub(crate) fn detached_event_clone(event: &Rc<RefCell<XesEventImpl>>) -> Result<Rc<RefCell<XesEventImpl>>, String> {
+  let event = event.borrow();
+  let mut copy = event.clone();
+  *copy.user_data_mut() = copy_context_values(event.user_data())?;
+
+  Ok(Rc::new(RefCell::new(copy)))
+}
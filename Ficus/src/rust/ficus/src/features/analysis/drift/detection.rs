@@ -0,0 +1,160 @@
+use crate::{
+  event_log::{
+    core::{event::event::Event, event_log::EventLog, trace::trace::Trace},
+    xes::{xes_event_log::XesEventLogImpl, xes_trace::XesTraceImpl},
+  },
+  features::analysis::{
+    directly_follows_graph::construct_dfg,
+    drift::{
+      distributions::{DriftFeature, DriftItem},
+      statistics::{DriftStatisticalTest, DriftTestResult},
+    },
+    log_info::event_log_info::OfflineEventLogInfo,
+  },
+  utils::graph::graph::DefaultGraph,
+};
+use chrono::{DateTime, Utc};
+use std::{cell::Ref, collections::HashMap, sync::Arc};
+
+#[derive(Clone, Debug)]
+pub struct ConceptDriftDetectionParams {
+  pub feature: DriftFeature,
+  pub test: DriftStatisticalTest,
+  pub window_size: usize,
+  pub significance: f64,
+  pub max_responsible_items: usize,
+}
+
+impl ConceptDriftDetectionParams {
+  pub fn new(feature: DriftFeature, test: DriftStatisticalTest, window_size: usize, significance: f64) -> Self {
+    Self {
+      feature,
+      test,
+      window_size,
+      significance,
+      max_responsible_items: 10,
+    }
+  }
+
+  pub fn with_max_responsible_items(mut self, max_responsible_items: usize) -> Self {
+    self.max_responsible_items = max_responsible_items;
+    self
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct ConceptDriftChangePoint {
+  /// Index in the log of the first trace after the change.
+  pub trace_index: usize,
+  pub timestamp: Option<DateTime<Utc>>,
+  pub statistic: f64,
+  pub p_value: f64,
+  pub responsible_items: Vec<(DriftItem, f64)>,
+  pub before_dfg: DefaultGraph,
+  pub after_dfg: DefaultGraph,
+}
+
+/// Compares the distribution of the feature in two adjacent windows of traces sliding over the log ordered by the start time
+/// of traces, a change point is the position of the lowest p-value among consecutive positions where the distributions differ,
+/// the next change point is searched at least one window after it. DFGs are built from the segments between change points.
+pub fn detect_concept_drift(log: &XesEventLogImpl, params: &ConceptDriftDetectionParams) -> Vec<ConceptDriftChangePoint> {
+  let traces: Vec<Ref<XesTraceImpl>> = log.traces().iter().map(|trace| trace.borrow()).collect();
+
+  let mut order: Vec<usize> = (0..traces.len()).collect();
+  order.sort_by_key(|index| trace_start(&traces[*index]));
+
+  let window = params.window_size.max(1);
+  if order.len() < 2 * window {
+    return vec![];
+  }
+
+  let test_position = |position: usize| {
+    let before = params
+      .feature
+      .distribution(order[position - window..position].iter().map(|i| &*traces[*i]));
+    let after = params
+      .feature
+      .distribution(order[position..position + window].iter().map(|i| &*traces[*i]));
+
+    params.test.test(&before, &after)
+  };
+
+  let is_significant = |result: &Option<DriftTestResult>| result.as_ref().is_some_and(|r| r.p_value < params.significance);
+
+  let mut change_points: Vec<(usize, DriftTestResult)> = vec![];
+  let last_position = order.len() - window;
+  let mut position = window;
+
+  while position <= last_position {
+    let result = test_position(position);
+    if !is_significant(&result) {
+      position += 1;
+      continue;
+    }
+
+    let mut best = (position, result.unwrap());
+    let mut next = position + 1;
+    while next <= last_position {
+      let next_result = test_position(next);
+      if !is_significant(&next_result) {
+        break;
+      }
+
+      let next_result = next_result.unwrap();
+      if next_result.p_value < best.1.p_value {
+        best = (next, next_result);
+      }
+
+      next += 1;
+    }
+
+    position = next.max(best.0 + window);
+    change_points.push(best);
+  }
+
+  let mut bounds = vec![0];
+  bounds.extend(change_points.iter().map(|(position, _)| *position));
+  bounds.push(order.len());
+
+  change_points
+    .into_iter()
+    .enumerate()
+    .map(|(index, (position, mut result))| {
+      result.contributions.truncate(params.max_responsible_items);
+      let segment_dfg = |from: usize, to: usize| create_dfg(order[from..to].iter().map(|i| &*traces[*i]));
+
+      ConceptDriftChangePoint {
+        trace_index: order[position],
+        timestamp: trace_start(&traces[order[position]]),
+        statistic: result.statistic,
+        p_value: result.p_value,
+        responsible_items: result.contributions,
+        before_dfg: segment_dfg(bounds[index], bounds[index + 1]),
+        after_dfg: segment_dfg(bounds[index + 1], bounds[index + 2]),
+      }
+    })
+    .collect()
+}
+
+fn trace_start(trace: &XesTraceImpl) -> Option<DateTime<Utc>> {
+  trace.events().first().map(|event| *event.borrow().timestamp())
+}
+
+fn create_dfg<'a>(traces: impl Iterator<Item = &'a XesTraceImpl>) -> DefaultGraph {
+  let mut relations: HashMap<(Arc<str>, Arc<str>), u64> = HashMap::new();
+  let mut classes: HashMap<Arc<str>, u64> = HashMap::new();
+
+  for trace in traces {
+    let names: Vec<Arc<str>> = trace.events().iter().map(|event| event.borrow().name_pointer().clone()).collect();
+
+    for name in &names {
+      *classes.entry(name.clone()).or_default() += 1;
+    }
+
+    for window in names.windows(2) {
+      *relations.entry((window[0].clone(), window[1].clone())).or_default() += 1;
+    }
+  }
+
+  construct_dfg(&OfflineEventLogInfo::create_from_relations(&relations, &classes))
+}
@@ -0,0 +1,101 @@
+use crate::{
+  event_log::{
+    core::{event::event::Event, trace::trace::Trace},
+    xes::xes_trace::XesTraceImpl,
+  },
+  features::streaming::counters::core::StreamingCounter,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::HashMap,
+  fmt::{Display, Formatter},
+  hash::Hash,
+  str::FromStr,
+  sync::Arc,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DriftFeature {
+  DfgRelations,
+  ActivityFrequencies,
+  TraceVariants,
+}
+
+impl FromStr for DriftFeature {
+  type Err = ();
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "DfgRelations" => Ok(Self::DfgRelations),
+      "ActivityFrequencies" => Ok(Self::ActivityFrequencies),
+      "TraceVariants" => Ok(Self::TraceVariants),
+      _ => Err(()),
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DriftItem {
+  Relation(Arc<str>, Arc<str>),
+  Activity(Arc<str>),
+  Variant(Vec<Arc<str>>),
+}
+
+impl Display for DriftItem {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      DriftItem::Relation(from, to) => write!(f, "{} -> {}", from, to),
+      DriftItem::Activity(activity) => write!(f, "{}", activity),
+      DriftItem::Variant(activities) => write!(f, "<{}>", activities.join(", ")),
+    }
+  }
+}
+
+pub type DriftDistribution = HashMap<DriftItem, f64>;
+
+impl DriftFeature {
+  pub fn distribution<'a>(&self, traces: impl Iterator<Item = &'a XesTraceImpl>) -> DriftDistribution {
+    let mut distribution = DriftDistribution::new();
+
+    for trace in traces {
+      let names: Vec<Arc<str>> = trace.events().iter().map(|event| event.borrow().name_pointer().clone()).collect();
+
+      match self {
+        DriftFeature::DfgRelations => {
+          for window in names.windows(2) {
+            *distribution
+              .entry(DriftItem::Relation(window[0].clone(), window[1].clone()))
+              .or_default() += 1.;
+          }
+        }
+        DriftFeature::ActivityFrequencies => {
+          for name in names {
+            *distribution.entry(DriftItem::Activity(name)).or_default() += 1.;
+          }
+        }
+        DriftFeature::TraceVariants => *distribution.entry(DriftItem::Variant(names)).or_default() += 1.,
+      }
+    }
+
+    distribution
+  }
+}
+
+pub fn counter_distribution<TKey: Hash + Eq + Clone, TValue: Clone>(
+  counter: &dyn StreamingCounter<TKey, TValue>,
+  to_item: impl Fn(&TKey) -> DriftItem,
+) -> DriftDistribution {
+  counter
+    .all_frequencies()
+    .iter()
+    .map(|entry| (to_item(entry.key()), entry.absolute_count() as f64))
+    .collect()
+}
+
+pub fn relations_counter_distribution<TValue: Clone>(counter: &dyn StreamingCounter<(Arc<str>, Arc<str>), TValue>) -> DriftDistribution {
+  counter_distribution(counter, |(from, to)| DriftItem::Relation(from.clone(), to.clone()))
+}
+
+pub fn activities_counter_distribution<TValue: Clone>(counter: &dyn StreamingCounter<Arc<str>, TValue>) -> DriftDistribution {
+  counter_distribution(counter, |activity| DriftItem::Activity(activity.clone()))
+}
@@ -0,0 +1,4 @@
+pub mod detection;
+pub mod distributions;
+pub mod online;
+pub mod statistics;
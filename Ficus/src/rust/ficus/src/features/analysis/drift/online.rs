@@ -0,0 +1,89 @@
+use crate::features::analysis::drift::{
+  distributions::{DriftDistribution, DriftItem},
+  statistics::DriftStatisticalTest,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const MAX_CHANGE_POINTS: usize = 1_000;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OnlineDriftChangePoint {
+  pub stamp: DateTime<Utc>,
+  pub statistic: f64,
+  pub p_value: f64,
+  pub responsible_items: Vec<(DriftItem, f64)>,
+}
+
+/// Compares current distributions, i.e. the ones built from T2 streaming counters, with the reference one,
+/// the reference is replaced by the current distribution after each detected change. Counters which forget old
+/// observations (sliding window, exponential decay) should be used, as cumulative ones hide changes over time.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OnlineDriftDetector {
+  test: DriftStatisticalTest,
+  significance: f64,
+  min_observations: f64,
+  max_responsible_items: usize,
+  reference: Option<Vec<(DriftItem, f64)>>,
+  change_points: Vec<OnlineDriftChangePoint>,
+}
+
+impl OnlineDriftDetector {
+  pub fn new(test: DriftStatisticalTest, significance: f64, min_observations: f64) -> Self {
+    Self {
+      test,
+      significance,
+      min_observations,
+      max_responsible_items: 10,
+      reference: None,
+      change_points: vec![],
+    }
+  }
+
+  pub fn with_max_responsible_items(mut self, max_responsible_items: usize) -> Self {
+    self.max_responsible_items = max_responsible_items;
+    self
+  }
+
+  pub fn observe(&mut self, current: DriftDistribution, stamp: DateTime<Utc>) -> Option<&OnlineDriftChangePoint> {
+    if current.values().sum::<f64>() < self.min_observations {
+      return None;
+    }
+
+    let Some(reference) = self.reference.as_ref() else {
+      self.reference = Some(current.into_iter().collect());
+      return None;
+    };
+
+    let reference: DriftDistribution = reference.iter().cloned().collect();
+    let mut result = self.test.test(&reference, &current)?;
+    if result.p_value >= self.significance {
+      return None;
+    }
+
+    result.contributions.truncate(self.max_responsible_items);
+
+    if self.change_points.len() == MAX_CHANGE_POINTS {
+      self.change_points.remove(0);
+    }
+
+    self.reference = Some(current.into_iter().collect());
+    self.change_points.push(OnlineDriftChangePoint {
+      stamp,
+      statistic: result.statistic,
+      p_value: result.p_value,
+      responsible_items: result.contributions,
+    });
+
+    self.change_points.last()
+  }
+
+  pub fn change_points(&self) -> &Vec<OnlineDriftChangePoint> {
+    &self.change_points
+  }
+
+  pub fn reset(&mut self) {
+    self.reference = None;
+    self.change_points.clear();
+  }
+}
@@ -0,0 +1,182 @@
+use crate::features::analysis::drift::distributions::{DriftDistribution, DriftItem};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, str::FromStr};
+
+const GAMMA_MAX_ITERATIONS: usize = 500;
+const GAMMA_EPSILON: f64 = 1e-14;
+const GAMMA_MIN_VALUE: f64 = 1e-300;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DriftStatisticalTest {
+  ChiSquare,
+  GTest,
+}
+
+impl FromStr for DriftStatisticalTest {
+  type Err = ();
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "ChiSquare" => Ok(Self::ChiSquare),
+      "GTest" => Ok(Self::GTest),
+      _ => Err(()),
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DriftTestResult {
+  pub statistic: f64,
+  pub degrees_of_freedom: usize,
+  pub p_value: f64,
+  /// Items with the part of the statistic they are responsible for, in descending order of contribution.
+  pub contributions: Vec<(DriftItem, f64)>,
+}
+
+impl DriftStatisticalTest {
+  /// Test of homogeneity of two distributions of counts, None is returned when one of the distributions
+  /// is empty or there are less than two distinct items in both of them.
+  pub fn test(&self, before: &DriftDistribution, after: &DriftDistribution) -> Option<DriftTestResult> {
+    let items: BTreeSet<&DriftItem> = before.keys().chain(after.keys()).collect();
+    let before_total: f64 = before.values().sum();
+    let after_total: f64 = after.values().sum();
+
+    if items.len() < 2 || before_total <= 0. || after_total <= 0. {
+      return None;
+    }
+
+    let total = before_total + after_total;
+    let mut contributions = vec![];
+
+    for item in items {
+      let observed = [before.get(item).copied().unwrap_or(0.), after.get(item).copied().unwrap_or(0.)];
+
+      let item_total = observed[0] + observed[1];
+      let expected = [before_total * item_total / total, after_total * item_total / total];
+
+      let contribution: f64 = observed.iter().zip(expected.iter()).map(|(o, e)| self.cell_statistic(*o, *e)).sum();
+      contributions.push((item.clone(), contribution));
+    }
+
+    let statistic = contributions.iter().map(|(_, contribution)| contribution).sum();
+    let degrees_of_freedom = contributions.len() - 1;
+
+    contributions.sort_by(|first, second| second.1.total_cmp(&first.1).then_with(|| first.0.cmp(&second.0)));
+
+    Some(DriftTestResult {
+      statistic,
+      degrees_of_freedom,
+      p_value: chi_square_survival(statistic, degrees_of_freedom),
+      contributions,
+    })
+  }
+
+  fn cell_statistic(&self, observed: f64, expected: f64) -> f64 {
+    if expected <= 0. {
+      return 0.;
+    }
+
+    match self {
+      DriftStatisticalTest::ChiSquare => (observed - expected).powi(2) / expected,
+      DriftStatisticalTest::GTest => match observed > 0. {
+        true => 2. * observed * (observed / expected).ln(),
+        false => 0.,
+      },
+    }
+  }
+}
+
+/// Probability that a chi-square distributed value with the given degrees of freedom is at least `statistic`.
+pub fn chi_square_survival(statistic: f64, degrees_of_freedom: usize) -> f64 {
+  if degrees_of_freedom == 0 || statistic <= 0. {
+    return 1.;
+  }
+
+  regularized_upper_gamma(degrees_of_freedom as f64 / 2., statistic / 2.)
+}
+
+fn regularized_upper_gamma(a: f64, x: f64) -> f64 {
+  if x < a + 1. {
+    (1. - lower_gamma_series(a, x)).clamp(0., 1.)
+  } else {
+    upper_gamma_continued_fraction(a, x).clamp(0., 1.)
+  }
+}
+
+fn lower_gamma_series(a: f64, x: f64) -> f64 {
+  let mut term = 1. / a;
+  let mut sum = term;
+  let mut denominator = a;
+
+  for _ in 0..GAMMA_MAX_ITERATIONS {
+    denominator += 1.;
+    term *= x / denominator;
+    sum += term;
+
+    if term.abs() < sum.abs() * GAMMA_EPSILON {
+      break;
+    }
+  }
+
+  sum * (-x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+fn upper_gamma_continued_fraction(a: f64, x: f64) -> f64 {
+  let mut b = x + 1. - a;
+  let mut c = 1. / GAMMA_MIN_VALUE;
+  let mut d = 1. / b;
+  let mut h = d;
+
+  for i in 1..=GAMMA_MAX_ITERATIONS {
+    let an = -(i as f64) * (i as f64 - a);
+    b += 2.;
+
+    d = an * d + b;
+    if d.abs() < GAMMA_MIN_VALUE {
+      d = GAMMA_MIN_VALUE;
+    }
+
+    c = b + an / c;
+    if c.abs() < GAMMA_MIN_VALUE {
+      c = GAMMA_MIN_VALUE;
+    }
+
+    d = 1. / d;
+    let delta = d * c;
+    h *= delta;
+
+    if (delta - 1.).abs() < GAMMA_EPSILON {
+      break;
+    }
+  }
+
+  (-x + a * x.ln() - ln_gamma(a)).exp() * h
+}
+
+// Lanczos approximation, g = 7
+fn ln_gamma(x: f64) -> f64 {
+  const COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+  ];
+
+  if x < 0.5 {
+    return (std::f64::consts::PI / (std::f64::consts::PI * x).sin()).ln() - ln_gamma(1. - x);
+  }
+
+  let x = x - 1.;
+  let mut sum = COEFFICIENTS[0];
+  for (i, coefficient) in COEFFICIENTS.iter().enumerate().skip(1) {
+    sum += coefficient / (x + i as f64);
+  }
+
+  let t = x + 7.5;
+  0.5 * (2. * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
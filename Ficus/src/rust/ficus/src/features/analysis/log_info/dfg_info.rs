@@ -25,16 +25,17 @@ impl OfflineDfgInfo {
     let mut events_with_single_follower = HashSet::new();
 
     for (relation, count) in relations {
+      let relation_count = *count as usize;
       if let Some(followers_map) = followed_events.get_mut(&relation.0) {
-        *followers_map.entry(relation.1.to_owned()).or_insert(0) += 1;
+        *followers_map.entry(relation.1.to_owned()).or_insert(0) += relation_count;
       } else {
-        followed_events.insert(relation.0.to_owned(), HashMap::from_iter([(relation.1.to_owned(), 1)]));
+        followed_events.insert(relation.0.to_owned(), HashMap::from_iter([(relation.1.to_owned(), relation_count)]));
       }
 
       if let Some(precedes_map) = precedes_events.get_mut(&relation.1) {
-        *precedes_map.entry(relation.0.to_owned()).or_insert(0) += 1;
+        *precedes_map.entry(relation.0.to_owned()).or_insert(0) += relation_count;
       } else {
-        precedes_events.insert(relation.1.to_owned(), HashMap::from_iter([(relation.0.to_owned(), 1)]));
+        precedes_events.insert(relation.1.to_owned(), HashMap::from_iter([(relation.0.to_owned(), relation_count)]));
       }
 
       if *count == 1 {
@@ -1,5 +1,6 @@
 pub mod constants;
 pub mod directly_follows_graph;
+pub mod drift;
 pub mod entropy;
 pub mod log_info;
 pub mod patterns;
@@ -15,6 +15,7 @@ use crate::{
     },
   },
   utils::{
+    cancellation::CancellationToken,
     colors::ColorsHolder,
     dataset::dataset::LabeledDataset,
     distance::distance::{DistanceWrapper, FicusDistance},
@@ -82,13 +83,21 @@ fn create_k_means_model(
     .expect("KMeans fitted")
 }
 
-pub fn clusterize_activities_k_means_grid_search(params: &mut ActivitiesClusteringParams, iterations_count: usize) -> ClusteringResult {
+pub fn clusterize_activities_k_means_grid_search(
+  params: &mut ActivitiesClusteringParams,
+  iterations_count: usize,
+  cancellation_token: &CancellationToken,
+) -> ClusteringResult {
   let (dataset, processed, classes_names) = create_dataset(&params.vis_params)?;
 
   let mut best_metric = -1f64;
   let mut best_labels = None;
 
   for clusters_count in 2..processed.len() {
+    if cancellation_token.is_cancelled() {
+      return Err(ClusteringError::Cancelled);
+    }
+
     let model = create_k_means_model(clusters_count, iterations_count as u64, params.tolerance, &dataset, params.distance);
 
     let clustered_dataset = model.predict(dataset.clone());
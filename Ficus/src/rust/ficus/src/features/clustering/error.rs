@@ -11,11 +11,15 @@ pub enum ClusteringError {
   FailedToCreateNdArray,
   FailedToCalculateSilhouetteScore,
   RawError(String),
+  Cancelled,
 }
 
 impl From<ClusteringError> for PipelinePartExecutionError {
   fn from(val: ClusteringError) -> Self {
-    PipelinePartExecutionError::Raw(RawPartExecutionError::new(val.to_string()))
+    match val {
+      ClusteringError::Cancelled => PipelinePartExecutionError::Cancelled,
+      _ => PipelinePartExecutionError::Raw(RawPartExecutionError::new(val.to_string())),
+    }
   }
 }
 
@@ -27,6 +31,7 @@ impl Display for ClusteringError {
         Self::FailedToCreateNdArray => "FailedToCreateNdArray".to_owned(),
         Self::FailedToCalculateSilhouetteScore => "FailedToCalculateSilhouetteScore".to_owned(),
         Self::RawError(message) => message.clone(),
+        Self::Cancelled => "Cancelled".to_owned(),
       }
       .as_str(),
     )
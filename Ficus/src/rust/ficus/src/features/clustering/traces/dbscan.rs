@@ -6,7 +6,7 @@ use crate::{
     error::ClusteringError,
     traces::common::{BestSilhouetteLabels, calculate_distance, do_clusterize_log_by_traces},
   },
-  utils::{dataset::dataset::LabeledDataset, distance::distance::DistanceWrapper},
+  utils::{cancellation::CancellationToken, dataset::dataset::LabeledDataset, distance::distance::DistanceWrapper},
 };
 use linfa::traits::Transformer;
 use linfa_clustering::Dbscan;
@@ -34,12 +34,17 @@ pub fn clusterize_log_by_traces_dbscan_grid_search(
   min_points_vec: &Vec<usize>,
   tolerances: &Vec<f64>,
   put_noise_events_in_one_cluster: bool,
+  cancellation_token: &CancellationToken,
 ) -> Result<(Vec<XesEventLogImpl>, LabeledDataset), ClusteringError> {
   do_clusterize_log_by_traces(params, |params, nn_algo, dataset| {
     let mut best_labels = BestSilhouetteLabels::new();
 
     for min_points in min_points_vec {
       for tolerance in tolerances {
+        if cancellation_token.is_cancelled() {
+          return Err(ClusteringError::Cancelled);
+        }
+
         let clusters = Dbscan::params_with(*min_points, DistanceWrapper::new(params.distance), nn_algo.clone())
           .tolerance(*tolerance)
           .transform(dataset.records());
@@ -7,7 +7,7 @@ use crate::{
       traces_params::TracesClusteringParams,
     },
   },
-  utils::{dataset::dataset::LabeledDataset, distance::distance::DistanceWrapper},
+  utils::{cancellation::CancellationToken, dataset::dataset::LabeledDataset, distance::distance::DistanceWrapper},
 };
 use linfa::prelude::{Fit, Predict};
 use linfa_clustering::KMeans;
@@ -16,11 +16,16 @@ pub fn clusterize_log_by_traces_kmeans_grid_search(
   params: &mut TracesClusteringParams,
   max_iterations_count: u64,
   tolerance: f64,
+  cancellation_token: &CancellationToken,
 ) -> Result<(Vec<XesEventLogImpl>, LabeledDataset), ClusteringError> {
   do_clusterize_log_by_traces(params, |params, _, dataset| {
     let mut best_labels = BestSilhouetteLabels::new();
 
     for clusters_count in 2..dataset.targets().len() - 1 {
+      if cancellation_token.is_cancelled() {
+        return Err(ClusteringError::Cancelled);
+      }
+
       let model = KMeans::params_with(clusters_count, rand::thread_rng(), DistanceWrapper::new(params.distance))
         .max_n_iterations(max_iterations_count)
         .tolerance(tolerance)
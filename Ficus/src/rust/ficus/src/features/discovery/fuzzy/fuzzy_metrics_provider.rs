@@ -1,32 +1,16 @@
-use super::fuzzy_miner::FuzzyGraph;
-use crate::{
-  event_log::core::{event::event::Event, event_log::EventLog, trace::trace::Trace},
-  features::{
-    analysis::log_info::event_log_info::{EventLogInfo, OfflineEventLogInfo},
-    discovery::alpha::providers::relations_cache::RelationsCaches,
-  },
-};
+use super::{fuzzy_miner::FuzzyGraph, proximity_correlation::ProximityCorrelation};
+use crate::features::analysis::log_info::event_log_info::EventLogInfo;
 
-const PROXIMITY_CORRELATION: &str = "ProximityCorrelation";
-
-pub struct FuzzyMetricsProvider<'a, TLog>
-where
-  TLog: EventLog,
-{
-  log: &'a TLog,
+pub struct FuzzyMetricsProvider<'a> {
   log_info: &'a dyn EventLogInfo,
-  caches: RelationsCaches<f64>,
+  proximity_correlation: &'a mut dyn ProximityCorrelation,
 }
 
-impl<'a, TLog> FuzzyMetricsProvider<'a, TLog>
-where
-  TLog: EventLog,
-{
-  pub fn new(log: &'a TLog, log_info: &'a OfflineEventLogInfo) -> Self {
+impl<'a> FuzzyMetricsProvider<'a> {
+  pub fn new(log_info: &'a dyn EventLogInfo, proximity_correlation: &'a mut dyn ProximityCorrelation) -> Self {
     Self {
-      log,
       log_info,
-      caches: RelationsCaches::new(&[PROXIMITY_CORRELATION]),
+      proximity_correlation,
     }
   }
 
@@ -43,46 +27,7 @@ where
   }
 
   pub fn proximity_correlation(&mut self, first_class: &str, second_class: &str) -> f64 {
-    if let Some(value) = self.caches.cache(PROXIMITY_CORRELATION).try_get(first_class, second_class) {
-      return *value;
-    }
-
-    let mut count = 0;
-    let mut result = 0.0;
-    for trace in self.log.traces() {
-      let trace = trace.borrow();
-      let events = trace.events();
-      let mut last_seen_first = None;
-
-      for i in 0..events.len() {
-        let event = events[i].borrow();
-        let name = event.name();
-
-        if name == first_class {
-          last_seen_first = Some(i);
-          continue;
-        }
-
-        if name == second_class
-          && let Some(first_index) = last_seen_first
-        {
-          let second_stamp = event.timestamp();
-          let first_event = events.get(first_index).unwrap();
-          let first_event = first_event.borrow();
-          let first_stamp = first_event.timestamp();
-
-          result += second_stamp.signed_duration_since(*first_stamp).num_milliseconds() as f64;
-          count += 1;
-          last_seen_first = None;
-        }
-      }
-    }
-
-    result = if count != 0 { result / (count as f64) } else { 0.0 };
-
-    self.caches.cache_mut(PROXIMITY_CORRELATION).put(first_class, second_class, result);
-
-    result
+    self.proximity_correlation.get(first_class, second_class)
   }
 
   pub fn relative_significance(&self, a: &str, b: &str, graph: &FuzzyGraph) -> f64 {
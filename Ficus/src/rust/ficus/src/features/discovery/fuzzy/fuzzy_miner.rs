@@ -1,7 +1,13 @@
-use super::fuzzy_metrics_provider::FuzzyMetricsProvider;
+use super::{
+  fuzzy_metrics_provider::FuzzyMetricsProvider,
+  proximity_correlation::{OfflineProximityCorrelation, ProximityCorrelation},
+};
 use crate::{
   event_log::core::event_log::EventLog,
-  features::analysis::log_info::{event_log_info::OfflineEventLogInfo, log_info_creation_dto::EventLogInfoCreationDto},
+  features::analysis::log_info::{
+    event_log_info::{EventLogInfo, OfflineEventLogInfo},
+    log_info_creation_dto::EventLogInfoCreationDto,
+  },
   utils::{
     graph::graph::{Graph, NodesConnectionData},
     sets::one_set::OneSet,
@@ -16,6 +22,17 @@ use std::{
 
 pub type FuzzyGraph = Graph<Arc<str>, f64>;
 
+#[derive(Clone, Debug)]
+pub struct FuzzyMinerParams {
+  pub unary_frequency_threshold: f64,
+  pub binary_frequency_significance_threshold: f64,
+  pub preserve_threshold: f64,
+  pub ratio_threshold: f64,
+  pub utility_rate: f64,
+  pub edge_cutoff_threshold: f64,
+  pub node_cutoff_threshold: f64,
+}
+
 pub fn discover_graph_fuzzy(
   log: &impl EventLog,
   unary_frequency_threshold: f64,
@@ -25,31 +42,56 @@ pub fn discover_graph_fuzzy(
   utility_rate: f64,
   edge_cutoff_threshold: f64,
   node_cutoff_threshold: f64,
+) -> FuzzyGraph {
+  let info = OfflineEventLogInfo::create_from(EventLogInfoCreationDto::default(log));
+  let mut proximity_correlation = OfflineProximityCorrelation::new(log);
+  let params = FuzzyMinerParams {
+    unary_frequency_threshold,
+    binary_frequency_significance_threshold,
+    preserve_threshold,
+    ratio_threshold,
+    utility_rate,
+    edge_cutoff_threshold,
+    node_cutoff_threshold,
+  };
+
+  discover_graph_fuzzy_from_info(&info, &mut proximity_correlation, &params)
+}
+
+pub fn discover_graph_fuzzy_from_info(
+  info: &dyn EventLogInfo,
+  proximity_correlation: &mut dyn ProximityCorrelation,
+  params: &FuzzyMinerParams,
 ) -> FuzzyGraph {
   let mut graph = FuzzyGraph::default();
 
-  let info = OfflineEventLogInfo::create_from(EventLogInfoCreationDto::default(log));
-  let mut provider = FuzzyMetricsProvider::new(log, &info);
+  let mut provider = FuzzyMetricsProvider::new(info, proximity_correlation);
   let mut classes_to_ids = HashMap::new();
 
   initialize_fuzzy_graph(
     &mut graph,
     &provider,
     &mut classes_to_ids,
-    unary_frequency_threshold,
-    binary_frequency_significance_threshold,
+    params.unary_frequency_threshold,
+    params.binary_frequency_significance_threshold,
   );
 
-  resolve_conflicts(&classes_to_ids, &provider, &mut graph, preserve_threshold, ratio_threshold);
-  filter_edges(&mut provider, &mut graph, utility_rate, edge_cutoff_threshold);
-  discover_clusters(&mut provider, &mut graph, node_cutoff_threshold);
+  resolve_conflicts(
+    &classes_to_ids,
+    &provider,
+    &mut graph,
+    params.preserve_threshold,
+    params.ratio_threshold,
+  );
+  filter_edges(&mut provider, &mut graph, params.utility_rate, params.edge_cutoff_threshold);
+  discover_clusters(&mut provider, &mut graph, params.node_cutoff_threshold);
 
   graph
 }
 
-fn initialize_fuzzy_graph<TLog: EventLog>(
+fn initialize_fuzzy_graph(
   graph: &mut FuzzyGraph,
-  provider: &FuzzyMetricsProvider<TLog>,
+  provider: &FuzzyMetricsProvider,
   classes_to_ids: &mut HashMap<Arc<str>, u64>,
   unary_frequency_threshold: f64,
   binary_frequency_significance_threshold: f64,
@@ -75,9 +117,9 @@ fn initialize_fuzzy_graph<TLog: EventLog>(
   }
 }
 
-fn resolve_conflicts<TLog: EventLog>(
+fn resolve_conflicts(
   classes_to_ids: &HashMap<Arc<str>, u64>,
-  provider: &FuzzyMetricsProvider<TLog>,
+  provider: &FuzzyMetricsProvider,
   graph: &mut FuzzyGraph,
   preserve_threshold: f64,
   ratio_threshold: f64,
@@ -110,12 +152,7 @@ fn resolve_conflicts<TLog: EventLog>(
   }
 }
 
-fn filter_edges<TLog: EventLog>(
-  provider: &mut FuzzyMetricsProvider<TLog>,
-  graph: &mut FuzzyGraph,
-  utility_rate: f64,
-  edge_cutoff_threshold: f64,
-) {
+fn filter_edges(provider: &mut FuzzyMetricsProvider, graph: &mut FuzzyGraph, utility_rate: f64, edge_cutoff_threshold: f64) {
   let edges: Vec<(u64, u64)> = graph.all_edges().iter().map(|edge| (*edge.from_node(), *edge.to_node())).collect();
   let mut node_to_incoming_nodes: HashMap<u64, HashSet<u64>> = HashMap::new();
   for (from_node_id, to_node_id) in edges {
@@ -159,7 +196,7 @@ fn filter_edges<TLog: EventLog>(
 
 type ClustersMap = HashMap<u64, Rc<RefCell<OneSet<u64>>>>;
 
-fn discover_clusters<TLog: EventLog>(provider: &mut FuzzyMetricsProvider<TLog>, graph: &mut FuzzyGraph, node_cutoff_threshold: f64) {
+fn discover_clusters(provider: &mut FuzzyMetricsProvider, graph: &mut FuzzyGraph, node_cutoff_threshold: f64) {
   let mut nodes_to_clusters: HashMap<u64, u64> = HashMap::new();
   let mut clusters = ClustersMap::new();
 
@@ -168,9 +205,9 @@ fn discover_clusters<TLog: EventLog>(provider: &mut FuzzyMetricsProvider<TLog>,
   merge_nodes(graph, &clusters);
 }
 
-fn find_initial_clusters<TLog: EventLog>(
+fn find_initial_clusters(
   graph: &mut FuzzyGraph,
-  provider: &mut FuzzyMetricsProvider<TLog>,
+  provider: &mut FuzzyMetricsProvider,
   node_cutoff_threshold: f64,
   clusters: &mut ClustersMap,
   nodes_to_clusters: &mut HashMap<u64, u64>,
@@ -211,9 +248,9 @@ fn find_initial_clusters<TLog: EventLog>(
   }
 }
 
-fn merge_clusters<TLog: EventLog>(
+fn merge_clusters(
   graph: &mut FuzzyGraph,
-  provider: &mut FuzzyMetricsProvider<TLog>,
+  provider: &mut FuzzyMetricsProvider,
   nodes_to_clusters: &mut HashMap<u64, u64>,
   clusters: &mut ClustersMap,
 ) {
@@ -273,8 +310,8 @@ fn merge_nodes(graph: &mut FuzzyGraph, clusters: &ClustersMap) {
   }
 }
 
-fn try_merge_clusters<TLog: EventLog>(
-  provider: &mut FuzzyMetricsProvider<TLog>,
+fn try_merge_clusters(
+  provider: &mut FuzzyMetricsProvider,
   graph: &FuzzyGraph,
   nodes_to_clusters: &mut HashMap<u64, u64>,
   nodes: &HashSet<&u64>,
@@ -319,8 +356,8 @@ fn try_merge_clusters<TLog: EventLog>(
   false
 }
 
-fn clusters_correlation<TLog: EventLog>(
-  provider: &mut FuzzyMetricsProvider<TLog>,
+fn clusters_correlation(
+  provider: &mut FuzzyMetricsProvider,
   graph: &FuzzyGraph,
   first_cluster: &OneSet<u64>,
   second_cluster: &OneSet<u64>,
@@ -339,8 +376,8 @@ fn clusters_correlation<TLog: EventLog>(
   if count == 0 { 0.0 } else { corr / count as f64 }
 }
 
-fn find_most_correlated_cluster<TLog: EventLog>(
-  provider: &mut FuzzyMetricsProvider<TLog>,
+fn find_most_correlated_cluster(
+  provider: &mut FuzzyMetricsProvider,
   graph: &FuzzyGraph,
   cluster: &OneSet<u64>,
   candidates: &HashSet<&u64>,
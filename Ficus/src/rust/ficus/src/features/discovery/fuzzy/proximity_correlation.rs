@@ -0,0 +1,108 @@
+use crate::{
+  event_log::core::{event::event::Event, event_log::EventLog, trace::trace::Trace},
+  features::discovery::alpha::providers::relations_cache::RelationsCaches,
+};
+use std::{collections::HashMap, sync::Arc};
+
+const PROXIMITY_CORRELATION: &str = "ProximityCorrelation";
+
+/// Average time in milliseconds between the occurrence of the first class and the next occurrence of the second one.
+pub trait ProximityCorrelation {
+  fn get(&mut self, first: &str, second: &str) -> f64;
+}
+
+pub struct OfflineProximityCorrelation<'a, TLog>
+where
+  TLog: EventLog,
+{
+  log: &'a TLog,
+  caches: RelationsCaches<f64>,
+}
+
+impl<'a, TLog> OfflineProximityCorrelation<'a, TLog>
+where
+  TLog: EventLog,
+{
+  pub fn new(log: &'a TLog) -> Self {
+    Self {
+      log,
+      caches: RelationsCaches::new(&[PROXIMITY_CORRELATION]),
+    }
+  }
+}
+
+impl<TLog> ProximityCorrelation for OfflineProximityCorrelation<'_, TLog>
+where
+  TLog: EventLog,
+{
+  fn get(&mut self, first_class: &str, second_class: &str) -> f64 {
+    if let Some(value) = self.caches.cache(PROXIMITY_CORRELATION).try_get(first_class, second_class) {
+      return *value;
+    }
+
+    let mut count = 0;
+    let mut result = 0.0;
+    for trace in self.log.traces() {
+      let trace = trace.borrow();
+      let events = trace.events();
+      let mut last_seen_first = None;
+
+      for i in 0..events.len() {
+        let event = events[i].borrow();
+        let name = event.name();
+
+        if name == first_class {
+          last_seen_first = Some(i);
+          continue;
+        }
+
+        if name == second_class
+          && let Some(first_index) = last_seen_first
+        {
+          let second_stamp = event.timestamp();
+          let first_event = events.get(first_index).unwrap();
+          let first_event = first_event.borrow();
+          let first_stamp = first_event.timestamp();
+
+          result += second_stamp.signed_duration_since(*first_stamp).num_milliseconds() as f64;
+          count += 1;
+          last_seen_first = None;
+        }
+      }
+    }
+
+    result = if count != 0 { result / (count as f64) } else { 0.0 };
+
+    self.caches.cache_mut(PROXIMITY_CORRELATION).put(first_class, second_class, result);
+
+    result
+  }
+}
+
+/// Proximity correlation built from already computed averages, i.e. by streaming counters.
+#[derive(Clone, Debug, Default)]
+pub struct PrecomputedProximityCorrelation {
+  correlations: HashMap<Arc<str>, HashMap<Arc<str>, f64>>,
+}
+
+impl PrecomputedProximityCorrelation {
+  pub fn new(correlations: impl IntoIterator<Item = ((Arc<str>, Arc<str>), f64)>) -> Self {
+    let mut map: HashMap<Arc<str>, HashMap<Arc<str>, f64>> = HashMap::new();
+    for ((first, second), correlation) in correlations {
+      map.entry(first).or_default().insert(second, correlation);
+    }
+
+    Self { correlations: map }
+  }
+}
+
+impl ProximityCorrelation for PrecomputedProximityCorrelation {
+  fn get(&mut self, first: &str, second: &str) -> f64 {
+    self
+      .correlations
+      .get(first)
+      .and_then(|followers| followers.get(second))
+      .copied()
+      .unwrap_or(0.)
+  }
+}
@@ -1,6 +1,6 @@
 use crate::features::discovery::petri_net::ids::next_id;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PetriNetArc<TArcData> {
   id: u64,
   place_id: u64,
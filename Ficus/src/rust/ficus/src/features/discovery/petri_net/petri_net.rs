@@ -1,10 +1,13 @@
 use super::arc::PetriNetArc;
-use crate::features::discovery::petri_net::{marking::Marking, place::Place, transition::Transition};
+use crate::{
+  features::discovery::petri_net::{marking::Marking, place::Place, transition::Transition},
+  utils::user_data::user_data::UserDataImpl,
+};
 use std::{collections::HashMap, sync::Arc};
 
 pub type DefaultPetriNet = PetriNet<Arc<str>, ()>;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 struct PlaceTransitions {
   incoming_transitions: Vec<u64>,
   outgoing_transitions: Vec<u64>,
@@ -171,3 +174,25 @@ where
     self.map_transitions(&self.get_place_transitions(place_id).outgoing_transitions)
   }
 }
+
+impl<TTransitionData, TArcData> PetriNet<TTransitionData, TArcData>
+where
+  TTransitionData: ToString + Clone,
+  TArcData: Clone,
+{
+  /// Clones the net with the same ids, user data of places is copied with `copy_user_data`.
+  pub(crate) fn try_clone_with(&self, copy_user_data: &impl Fn(&UserDataImpl) -> Result<UserDataImpl, String>) -> Result<Self, String> {
+    let mut places = HashMap::new();
+    for (id, place) in &self.places {
+      places.insert(*id, place.clone_with_user_data(copy_user_data(place.user_data())?));
+    }
+
+    Ok(Self {
+      places,
+      transitions: self.transitions.clone(),
+      places_to_transitions: self.places_to_transitions.clone(),
+      initial_marking: self.initial_marking.clone(),
+      final_marking: self.final_marking.clone(),
+    })
+  }
+}
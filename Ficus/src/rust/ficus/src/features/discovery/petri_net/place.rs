@@ -34,6 +34,14 @@ impl Place {
     &self.name
   }
 
+  pub(crate) fn clone_with_user_data(&self, user_data: UserDataImpl) -> Self {
+    Self {
+      id: self.id,
+      name: self.name.clone(),
+      user_data,
+    }
+  }
+
   pub fn user_data(&self) -> &UserDataImpl {
     &self.user_data
   }
@@ -4,7 +4,7 @@ use std::{
   sync::Arc,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Transition<TTransitionData, TArcData>
 where
   TTransitionData: ToString,
@@ -1,5 +1,5 @@
 use crate::event_log::core::{event::event::Event, event_log::EventLog, trace::trace::Trace};
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 pub trait TriangleRelation {
   fn get(&self, first: &str, second: &str) -> Option<usize>;
@@ -45,3 +45,26 @@ impl TriangleRelation for OfflineTriangleRelation {
     self.relations.get(&(first.to_owned(), second.to_owned())).copied()
   }
 }
+
+/// Triangle relation built from already counted `a b a` patterns, i.e. by streaming counters.
+#[derive(Clone, Debug, Default)]
+pub struct PrecomputedTriangleRelation {
+  relations: HashMap<Arc<str>, HashMap<Arc<str>, usize>>,
+}
+
+impl PrecomputedTriangleRelation {
+  pub fn new(counts: impl IntoIterator<Item = ((Arc<str>, Arc<str>), usize)>) -> Self {
+    let mut relations: HashMap<Arc<str>, HashMap<Arc<str>, usize>> = HashMap::new();
+    for ((first, second), count) in counts {
+      *relations.entry(first).or_default().entry(second).or_default() += count;
+    }
+
+    Self { relations }
+  }
+}
+
+impl TriangleRelation for PrecomputedTriangleRelation {
+  fn get(&self, first: &str, second: &str) -> Option<usize> {
+    self.relations.get(first).and_then(|followers| followers.get(second)).copied()
+  }
+}
@@ -0,0 +1,261 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{collections::HashMap, hash::Hash};
+
+/// Reference model which can replay a case event by event, the returned cost of a move is zero when the event fits the model.
+pub trait OnlineConformanceModel {
+  type State: Clone + Serialize + DeserializeOwned;
+
+  fn initial_state(&self) -> Self::State;
+  fn replay_event(&self, state: &mut Self::State, activity: &str) -> f64;
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CaseConformance {
+  pub events_count: u64,
+  pub deviating_events_count: u64,
+  pub cost: f64,
+  pub alerted: bool,
+}
+
+impl CaseConformance {
+  pub fn deviating_events_rate(&self) -> f64 {
+    match self.events_count {
+      0 => 0.,
+      count => self.deviating_events_count as f64 / count as f64,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConformanceObservation {
+  pub move_cost: f64,
+  pub case: CaseConformance,
+  /// Set only for the event which made the case cost exceed the threshold for the first time.
+  pub threshold_exceeded: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConformanceStatistics {
+  pub cases_count: u64,
+  pub deviating_cases_count: u64,
+  pub alerted_cases_count: u64,
+  pub events_count: u64,
+  pub deviating_events_count: u64,
+  pub total_cost: f64,
+  pub max_case_cost: f64,
+}
+
+impl ConformanceStatistics {
+  pub fn mean_case_cost(&self) -> f64 {
+    match self.cases_count {
+      0 => 0.,
+      count => self.total_cost / count as f64,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConformanceReport {
+  pub cost_threshold: f64,
+  pub statistics: ConformanceStatistics,
+  pub cases: Vec<(String, CaseConformance)>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CaseConformanceState<TState> {
+  model_state: TState,
+  conformance: CaseConformance,
+  #[serde(default)]
+  last_observation: u64,
+  #[serde(default)]
+  last_event_time: Option<DateTime<Utc>>,
+}
+
+pub type ConformanceCheckerSnapshot<TKey, TState> = Vec<(TKey, CaseConformanceState<TState>)>;
+
+/// Keeps the replay state of each running case against the reference model and updates case costs on each event.
+/// When the number of cases exceeds `max_cases`, the least recently observed cases are evicted
+/// until only `max_cases - max_cases / 10` are left, so that eviction does not happen on each new case.
+pub struct OnlineConformanceChecker<TKey: Hash + Eq + Clone, TModel: OnlineConformanceModel> {
+  model: TModel,
+  cost_threshold: f64,
+  cases: HashMap<TKey, CaseConformanceState<TModel::State>>,
+  max_cases: Option<usize>,
+  inactivity_timeout: Option<Duration>,
+  observations_count: u64,
+}
+
+impl<TKey: Hash + Eq + Clone, TModel: OnlineConformanceModel> OnlineConformanceChecker<TKey, TModel> {
+  pub fn new(model: TModel, cost_threshold: f64) -> Self {
+    Self {
+      model,
+      cost_threshold,
+      cases: HashMap::new(),
+      max_cases: None,
+      inactivity_timeout: None,
+      observations_count: 0,
+    }
+  }
+
+  pub fn with_max_cases(mut self, max_cases: usize) -> Self {
+    self.max_cases = Some(max_cases.max(1));
+    self
+  }
+
+  pub fn with_inactivity_timeout(mut self, inactivity_timeout: Duration) -> Self {
+    self.inactivity_timeout = Some(inactivity_timeout);
+    self
+  }
+
+  pub fn cost_threshold(&self) -> f64 {
+    self.cost_threshold
+  }
+
+  pub fn observe(&mut self, case: TKey, activity: &str) -> ConformanceObservation {
+    self.observe_internal(case, activity, None)
+  }
+
+  pub fn observe_at(&mut self, case: TKey, activity: &str, stamp: DateTime<Utc>) -> ConformanceObservation {
+    self.observe_internal(case, activity, Some(stamp))
+  }
+
+  fn observe_internal(&mut self, case: TKey, activity: &str, stamp: Option<DateTime<Utc>>) -> ConformanceObservation {
+    self.observations_count += 1;
+
+    let is_new_case = !self.cases.contains_key(&case);
+    let state = self.cases.entry(case).or_insert_with(|| CaseConformanceState {
+      model_state: self.model.initial_state(),
+      conformance: CaseConformance::default(),
+      last_observation: 0,
+      last_event_time: None,
+    });
+
+    state.last_observation = self.observations_count;
+    if stamp.is_some() {
+      state.last_event_time = stamp.max(state.last_event_time);
+    }
+
+    let move_cost = self.model.replay_event(&mut state.model_state, activity);
+
+    let conformance = &mut state.conformance;
+    conformance.events_count += 1;
+    conformance.cost += move_cost;
+    if move_cost > 0. {
+      conformance.deviating_events_count += 1;
+    }
+
+    let threshold_exceeded = !conformance.alerted && conformance.cost > self.cost_threshold;
+    if threshold_exceeded {
+      conformance.alerted = true;
+    }
+
+    let observation = ConformanceObservation {
+      move_cost,
+      case: conformance.clone(),
+      threshold_exceeded,
+    };
+
+    if is_new_case {
+      self.evict_least_recent_cases();
+    }
+
+    observation
+  }
+
+  fn evict_least_recent_cases(&mut self) {
+    let Some(max_cases) = self.max_cases else {
+      return;
+    };
+
+    if self.cases.len() <= max_cases {
+      return;
+    }
+
+    let retained_count = max_cases - max_cases / 10;
+    let mut observations: Vec<u64> = self.cases.values().map(|state| state.last_observation).collect();
+    observations.sort_unstable();
+
+    let min_retained_observation = observations[observations.len() - retained_count];
+    self.cases.retain(|_, state| state.last_observation >= min_retained_observation);
+  }
+
+  /// Removes cases whose last event is older than `now` by more than the inactivity timeout, cases
+  /// which were observed without timestamps are never removed this way.
+  pub fn evict_inactive_cases(&mut self, now: DateTime<Utc>) -> Vec<(TKey, CaseConformance)> {
+    let Some(inactivity_timeout) = self.inactivity_timeout else {
+      return vec![];
+    };
+
+    let inactive_cases: Vec<TKey> = self
+      .cases
+      .iter()
+      .filter(|(_, state)| state.last_event_time.is_some_and(|stamp| stamp + inactivity_timeout < now))
+      .map(|(key, _)| key.clone())
+      .collect();
+
+    inactive_cases
+      .into_iter()
+      .filter_map(|key| self.remove_case(&key).map(|conformance| (key, conformance)))
+      .collect()
+  }
+
+  pub fn case(&self, case: &TKey) -> Option<&CaseConformance> {
+    self.cases.get(case).map(|state| &state.conformance)
+  }
+
+  pub fn cases(&self) -> impl Iterator<Item = (&TKey, &CaseConformance)> {
+    self.cases.iter().map(|(key, state)| (key, &state.conformance))
+  }
+
+  pub fn remove_case(&mut self, case: &TKey) -> Option<CaseConformance> {
+    self.cases.remove(case).map(|state| state.conformance)
+  }
+
+  pub fn statistics(&self) -> ConformanceStatistics {
+    let mut statistics = ConformanceStatistics::default();
+
+    for state in self.cases.values() {
+      let conformance = &state.conformance;
+
+      statistics.cases_count += 1;
+      statistics.events_count += conformance.events_count;
+      statistics.deviating_events_count += conformance.deviating_events_count;
+      statistics.total_cost += conformance.cost;
+      statistics.max_case_cost = statistics.max_case_cost.max(conformance.cost);
+
+      if conformance.cost > 0. {
+        statistics.deviating_cases_count += 1;
+      }
+
+      if conformance.alerted {
+        statistics.alerted_cases_count += 1;
+      }
+    }
+
+    statistics
+  }
+
+  pub fn report(&self) -> ConformanceReport
+  where
+    TKey: ToString,
+  {
+    let mut cases: Vec<(String, CaseConformance)> = self.cases().map(|(key, case)| (key.to_string(), case.clone())).collect();
+    cases.sort_by(|first, second| first.0.cmp(&second.0));
+
+    ConformanceReport {
+      cost_threshold: self.cost_threshold,
+      statistics: self.statistics(),
+      cases,
+    }
+  }
+
+  pub fn snapshot(&self) -> ConformanceCheckerSnapshot<TKey, TModel::State> {
+    self.cases.iter().map(|(key, state)| (key.clone(), state.clone())).collect()
+  }
+
+  pub fn restore(&mut self, snapshot: ConformanceCheckerSnapshot<TKey, TModel::State>) {
+    self.observations_count = snapshot.iter().map(|(_, state)| state.last_observation).max().unwrap_or(0);
+    self.cases = snapshot.into_iter().collect();
+  }
+}
@@ -0,0 +1,67 @@
+use crate::{features::streaming::conformance::core::OnlineConformanceModel, utils::graph::graph::DefaultGraph};
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::{HashMap, HashSet},
+  sync::Arc,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DfgReplayState {
+  last_activity: Option<Arc<str>>,
+}
+
+/// Checks each directly-follows relation of a case against the reference DFG, an activity which is absent in the DFG
+/// and a relation which is absent in the DFG both cost one.
+#[derive(Clone, Debug)]
+pub struct DfgConformanceModel {
+  activities: HashSet<Arc<str>>,
+  relations: HashSet<(Arc<str>, Arc<str>)>,
+}
+
+impl DfgConformanceModel {
+  pub fn new(activities: HashSet<Arc<str>>, relations: HashSet<(Arc<str>, Arc<str>)>) -> Self {
+    Self { activities, relations }
+  }
+
+  pub fn from_graph(graph: &DefaultGraph) -> Self {
+    let names: HashMap<u64, Arc<str>> = graph
+      .all_nodes()
+      .iter()
+      .filter_map(|node| node.data().map(|data| (*node.id(), data.clone())))
+      .collect();
+
+    let relations = graph
+      .all_edges()
+      .iter()
+      .filter_map(|edge| match (names.get(edge.from_node()), names.get(edge.to_node())) {
+        (Some(from), Some(to)) => Some((from.clone(), to.clone())),
+        _ => None,
+      })
+      .collect();
+
+    Self::new(names.into_values().collect(), relations)
+  }
+}
+
+impl OnlineConformanceModel for DfgConformanceModel {
+  type State = DfgReplayState;
+
+  fn initial_state(&self) -> Self::State {
+    DfgReplayState::default()
+  }
+
+  fn replay_event(&self, state: &mut Self::State, activity: &str) -> f64 {
+    let Some(activity) = self.activities.get(activity) else {
+      state.last_activity = None;
+      return 1.;
+    };
+
+    let cost = match state.last_activity.as_ref() {
+      Some(last_activity) if !self.relations.contains(&(last_activity.clone(), activity.clone())) => 1.,
+      _ => 0.,
+    };
+
+    state.last_activity = Some(activity.clone());
+    cost
+  }
+}
@@ -0,0 +1,3 @@
+pub mod core;
+pub mod dfg;
+pub mod token_replay;
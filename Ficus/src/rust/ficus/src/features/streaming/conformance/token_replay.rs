@@ -0,0 +1,252 @@
+use crate::features::{discovery::petri_net::petri_net::DefaultPetriNet, streaming::conformance::core::OnlineConformanceModel};
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::{BTreeMap, HashMap, HashSet, VecDeque},
+  sync::Arc,
+};
+
+const DEFAULT_MAX_SILENT_STEPS: usize = 3;
+const UNKNOWN_ACTIVITY_COST: f64 = 1.;
+
+type TokensMarking = BTreeMap<u64, usize>;
+
+#[derive(Clone, Debug)]
+struct ReplayTransition {
+  consumes: Vec<(u64, usize)>,
+  produces: Vec<(u64, usize)>,
+}
+
+impl ReplayTransition {
+  fn missing_tokens(&self, marking: &TokensMarking) -> usize {
+    self
+      .consumes
+      .iter()
+      .map(|(place, count)| count.saturating_sub(*marking.get(place).unwrap_or(&0)))
+      .sum()
+  }
+
+  fn is_enabled(&self, marking: &TokensMarking) -> bool {
+    self.missing_tokens(marking) == 0
+  }
+
+  fn fire(&self, marking: &mut TokensMarking) {
+    for (place, count) in &self.consumes {
+      let tokens = marking.entry(*place).or_insert(0);
+      *tokens = tokens.saturating_sub(*count);
+      if *tokens == 0 {
+        marking.remove(place);
+      }
+    }
+
+    for (place, count) in &self.produces {
+      *marking.entry(*place).or_insert(0) += count;
+    }
+  }
+
+  fn consumed_tokens(&self) -> usize {
+    self.consumes.iter().map(|(_, count)| count).sum()
+  }
+
+  fn produced_tokens(&self) -> usize {
+    self.produces.iter().map(|(_, count)| count).sum()
+  }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenReplayState {
+  marking: TokensMarking,
+  consumed: u64,
+  produced: u64,
+  missing: u64,
+}
+
+impl TokenReplayState {
+  pub fn missing_tokens(&self) -> u64 {
+    self.missing
+  }
+
+  /// Fitness of the replayed prefix, remaining tokens are not taken into account as the case may still be running.
+  pub fn prefix_fitness(&self) -> f64 {
+    match self.consumed {
+      0 => 1.,
+      consumed => 1. - self.missing as f64 / consumed as f64,
+    }
+  }
+
+  fn fire(&mut self, transition: &ReplayTransition) {
+    self.consumed += transition.consumed_tokens() as u64;
+    self.produced += transition.produced_tokens() as u64;
+    transition.fire(&mut self.marking);
+  }
+}
+
+/// Token-based replay of each case on a Petri net: a visible transition with the label of the event is fired,
+/// at most `max_silent_steps` silent transitions may be fired before to enable it, otherwise missing tokens are created
+/// and their number is the cost of the move.
+#[derive(Clone, Debug)]
+pub struct TokenReplayConformanceModel {
+  labeled_transitions: HashMap<Arc<str>, Vec<ReplayTransition>>,
+  silent_transitions: Vec<ReplayTransition>,
+  initial_marking: TokensMarking,
+  max_silent_steps: usize,
+}
+
+impl TokenReplayConformanceModel {
+  pub fn new(net: &DefaultPetriNet) -> Option<Self> {
+    // Markings are kept in terms of places indices, so that the state of a case stays valid for the same net
+    // discovered once again (i.e. after restoring from a checkpoint), when places get new ids. Places are ordered
+    // by names and then by names of transitions they connect; only places which are equal in all of these
+    // are ordered by ids, and the order of such places may change between discoveries.
+    let places_signatures = Self::places_signatures(net);
+    let mut places = net.all_places();
+    places.sort_by(|first, second| {
+      first
+        .name()
+        .cmp(second.name())
+        .then_with(|| places_signatures.get(&first.id()).cmp(&places_signatures.get(&second.id())))
+        .then(first.id().cmp(&second.id()))
+    });
+
+    let indices: HashMap<u64, u64> = places.iter().enumerate().map(|(index, place)| (place.id(), index as u64)).collect();
+    let index = |place_id: u64| *indices.get(&place_id).expect("Place must be present in the net");
+
+    let initial_marking = net
+      .initial_marking()?
+      .active_places()
+      .iter()
+      .map(|marking| (index(marking.place_id()), marking.tokens_count()))
+      .collect();
+
+    let mut labeled_transitions: HashMap<Arc<str>, Vec<ReplayTransition>> = HashMap::new();
+    let mut silent_transitions = vec![];
+
+    for transition in net.all_transitions() {
+      let replay_transition = ReplayTransition {
+        consumes: transition
+          .incoming_arcs()
+          .iter()
+          .map(|arc| (index(arc.place_id()), arc.tokens_count()))
+          .collect(),
+        produces: transition
+          .outgoing_arcs()
+          .iter()
+          .map(|arc| (index(arc.place_id()), arc.tokens_count()))
+          .collect(),
+      };
+
+      if transition.is_silent() {
+        silent_transitions.push(replay_transition);
+      } else {
+        labeled_transitions
+          .entry(transition.name().into())
+          .or_default()
+          .push(replay_transition);
+      }
+    }
+
+    Some(Self {
+      labeled_transitions,
+      silent_transitions,
+      initial_marking,
+      max_silent_steps: DEFAULT_MAX_SILENT_STEPS,
+    })
+  }
+
+  // (sorted names of transitions producing into the place, sorted names of transitions consuming from the place)
+  fn places_signatures(net: &DefaultPetriNet) -> HashMap<u64, (Vec<&str>, Vec<&str>)> {
+    let mut signatures: HashMap<u64, (Vec<&str>, Vec<&str>)> = HashMap::new();
+    for transition in net.all_transitions() {
+      for arc in transition.outgoing_arcs() {
+        signatures.entry(arc.place_id()).or_default().0.push(transition.name());
+      }
+
+      for arc in transition.incoming_arcs() {
+        signatures.entry(arc.place_id()).or_default().1.push(transition.name());
+      }
+    }
+
+    for (producers, consumers) in signatures.values_mut() {
+      producers.sort();
+      consumers.sort();
+    }
+
+    signatures
+  }
+
+  pub fn with_max_silent_steps(mut self, max_silent_steps: usize) -> Self {
+    self.max_silent_steps = max_silent_steps;
+    self
+  }
+
+  fn enable_with_silent_transitions(&self, state: &TokenReplayState, candidates: &[ReplayTransition]) -> Option<TokenReplayState> {
+    let mut visited = HashSet::from([state.marking.clone()]);
+    let mut queue = VecDeque::from([(state.clone(), 0)]);
+
+    while let Some((state, steps)) = queue.pop_front() {
+      if steps >= self.max_silent_steps {
+        continue;
+      }
+
+      for silent_transition in self.silent_transitions.iter().filter(|t| t.is_enabled(&state.marking)) {
+        let mut next_state = state.clone();
+        next_state.fire(silent_transition);
+
+        if candidates.iter().any(|candidate| candidate.is_enabled(&next_state.marking)) {
+          return Some(next_state);
+        }
+
+        if visited.insert(next_state.marking.clone()) {
+          queue.push_back((next_state, steps + 1));
+        }
+      }
+    }
+
+    None
+  }
+}
+
+impl OnlineConformanceModel for TokenReplayConformanceModel {
+  type State = TokenReplayState;
+
+  fn initial_state(&self) -> Self::State {
+    TokenReplayState {
+      marking: self.initial_marking.clone(),
+      ..TokenReplayState::default()
+    }
+  }
+
+  fn replay_event(&self, state: &mut Self::State, activity: &str) -> f64 {
+    let Some(candidates) = self.labeled_transitions.get(activity) else {
+      return UNKNOWN_ACTIVITY_COST;
+    };
+
+    if let Some(transition) = candidates.iter().find(|t| t.is_enabled(&state.marking)) {
+      state.fire(transition);
+      return 0.;
+    }
+
+    if let Some(enabled_state) = self.enable_with_silent_transitions(state, candidates) {
+      *state = enabled_state;
+
+      let transition = candidates.iter().find(|t| t.is_enabled(&state.marking)).expect("Must be enabled");
+      state.fire(transition);
+      return 0.;
+    }
+
+    let transition = candidates
+      .iter()
+      .min_by_key(|t| t.missing_tokens(&state.marking))
+      .expect("Candidates must not be empty");
+
+    let missing = transition.missing_tokens(&state.marking);
+    for (place, count) in &transition.consumes {
+      let tokens = state.marking.entry(*place).or_insert(0);
+      *tokens = (*tokens).max(*count);
+    }
+
+    state.missing += missing as u64;
+    state.fire(transition);
+
+    missing as f64
+  }
+}
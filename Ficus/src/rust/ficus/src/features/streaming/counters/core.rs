@@ -1,4 +1,10 @@
-use std::{collections::HashMap, hash::Hash};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::HashMap,
+  fmt::{Display, Formatter},
+  hash::Hash,
+};
 
 #[derive(Debug)]
 pub struct StreamingCounterEntry<TKey, TValue> {
@@ -37,12 +43,69 @@ pub enum ValueUpdateKind<TValue> {
   DoNothing,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum StreamingCounterSnapshot<TKey, TValue> {
+  LossyCount {
+    entries: Vec<(TKey, Option<TValue>, u64, f64)>,
+    observed_items_count: u64,
+  },
+  SlidingWindow {
+    entries: Vec<(TKey, Option<TValue>, u64, DateTime<Utc>)>,
+  },
+  SpaceSaving {
+    entries: Vec<(TKey, Option<TValue>, u64, u64)>,
+    observed_items_count: u64,
+  },
+  CountMinSketch {
+    sketch: Vec<Vec<u64>>,
+    tracked_keys: Vec<(TKey, Option<TValue>)>,
+    observed_items_count: u64,
+  },
+  ExponentialDecay {
+    entries: Vec<(TKey, Option<TValue>, f64, DateTime<Utc>)>,
+    total_weight: f64,
+    total_weight_timestamp: DateTime<Utc>,
+  },
+}
+
+#[derive(Debug)]
+pub enum StreamingCounterRestoreError {
+  SnapshotKindMismatch,
+  CapacityExceeded(usize, usize),
+  SketchDimensionsMismatch,
+}
+
+impl Display for StreamingCounterRestoreError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      StreamingCounterRestoreError::SnapshotKindMismatch => write!(f, "Snapshot was created by the counter of another kind"),
+      StreamingCounterRestoreError::CapacityExceeded(entries_count, capacity) => {
+        write!(
+          f,
+          "Snapshot contains {} entries while counter capacity is {}",
+          entries_count, capacity
+        )
+      }
+      StreamingCounterRestoreError::SketchDimensionsMismatch => write!(f, "Snapshot sketch dimensions differ from counter ones"),
+    }
+  }
+}
+
 pub trait StreamingCounter<TKey: Hash + Eq + Clone, TValue: Clone> {
   fn observe(&mut self, key: TKey, value: ValueUpdateKind<TValue>);
+
+  /// Observes the key which was seen at the given event time, counters which do not depend on time ignore the stamp.
+  fn observe_at(&mut self, key: TKey, value: ValueUpdateKind<TValue>, _stamp: DateTime<Utc>) {
+    self.observe(key, value);
+  }
+
   fn get(&self, key: &TKey) -> Option<StreamingCounterEntry<TKey, TValue>>;
   fn above_threshold(&self, threshold: f64) -> Vec<StreamingCounterEntry<TKey, TValue>>;
   fn invalidate(&mut self);
 
+  fn snapshot(&self) -> StreamingCounterSnapshot<TKey, TValue>;
+  fn restore(&mut self, snapshot: StreamingCounterSnapshot<TKey, TValue>) -> Result<(), StreamingCounterRestoreError>;
+
   fn all_frequencies(&self) -> Vec<StreamingCounterEntry<TKey, TValue>> {
     self.above_threshold(0.0)
   }
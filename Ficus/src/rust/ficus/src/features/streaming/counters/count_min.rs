@@ -0,0 +1,199 @@
+use crate::{
+  features::streaming::counters::core::{
+    StreamingCounter, StreamingCounterEntry, StreamingCounterRestoreError, StreamingCounterSnapshot, ValueUpdateKind,
+  },
+  utils::hash_utils::StableHasher,
+};
+use std::{
+  collections::HashMap,
+  fmt::{Display, Formatter},
+  hash::{Hash, Hasher},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CountMinSketchCreationError {
+  InvalidError(f64),
+  InvalidFailureProbability(f64),
+  InvalidSupport(f64),
+}
+
+impl Display for CountMinSketchCreationError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      CountMinSketchCreationError::InvalidError(error) => write!(f, "Error must be in (0, 1), got {}", error),
+      CountMinSketchCreationError::InvalidFailureProbability(probability) => {
+        write!(f, "Failure probability must be in (0, 1), got {}", probability)
+      }
+      CountMinSketchCreationError::InvalidSupport(support) => write!(f, "Support must be in [0, 1], got {}", support),
+    }
+  }
+}
+
+pub fn validate_count_min_sketch_parameters(error: f64, failure_probability: f64, support: f64) -> Result<(), CountMinSketchCreationError> {
+  if !(error > 0.0 && error < 1.0) {
+    return Err(CountMinSketchCreationError::InvalidError(error));
+  }
+
+  if !(failure_probability > 0.0 && failure_probability < 1.0) {
+    return Err(CountMinSketchCreationError::InvalidFailureProbability(failure_probability));
+  }
+
+  if !(0.0..=1.0).contains(&support) {
+    return Err(CountMinSketchCreationError::InvalidSupport(support));
+  }
+
+  Ok(())
+}
+
+/// Counter backed by a Count-Min sketch. Counts of all keys are stored in the `depth x width` sketch,
+/// which overestimates them by at most `error * observed_items_count` with probability `1 - failure_probability`.
+/// Only keys whose estimated frequency is not less than `support` are kept, so memory does not depend on the key space size.
+#[derive(Clone)]
+pub struct CountMinSketch<TKey, TValue>
+where
+  TKey: Hash + Eq,
+{
+  sketch: Vec<Vec<u64>>,
+  width: usize,
+  tracked_keys: HashMap<TKey, Option<TValue>>,
+  support: f64,
+  observed_items_count: u64,
+}
+
+impl<TKey, TValue> StreamingCounter<TKey, TValue> for CountMinSketch<TKey, TValue>
+where
+  TKey: Hash + Eq + Clone,
+  TValue: Clone,
+{
+  fn observe(&mut self, key: TKey, value: ValueUpdateKind<TValue>) {
+    self.observed_items_count += 1;
+
+    for row in 0..self.sketch.len() {
+      let column = self.column(row, &key);
+      self.sketch[row][column] += 1;
+    }
+
+    let value = match value {
+      ValueUpdateKind::Replace(value) => Some(value),
+      ValueUpdateKind::DoNothing => None,
+    };
+
+    if let Some(tracked_value) = self.tracked_keys.get_mut(&key) {
+      if value.is_some() {
+        *tracked_value = value;
+      }
+    } else if self.is_frequent(&key) {
+      self.tracked_keys.insert(key, value);
+    }
+
+    if self.tracked_keys.len() > self.max_tracked_keys_count() {
+      self.prune();
+    }
+  }
+
+  fn get(&self, key: &TKey) -> Option<StreamingCounterEntry<TKey, TValue>> {
+    self.tracked_keys.get(key).map(|value| self.to_streaming_counter_entry(key, value))
+  }
+
+  fn above_threshold(&self, threshold: f64) -> Vec<StreamingCounterEntry<TKey, TValue>> {
+    self
+      .tracked_keys
+      .iter()
+      .map(|(key, value)| self.to_streaming_counter_entry(key, value))
+      .filter(|entry| entry.approx_frequency() >= threshold)
+      .collect()
+  }
+
+  fn invalidate(&mut self) {
+    self.prune();
+  }
+
+  fn snapshot(&self) -> StreamingCounterSnapshot<TKey, TValue> {
+    StreamingCounterSnapshot::CountMinSketch {
+      sketch: self.sketch.clone(),
+      tracked_keys: self.tracked_keys.iter().map(|(key, value)| (key.clone(), value.clone())).collect(),
+      observed_items_count: self.observed_items_count,
+    }
+  }
+
+  fn restore(&mut self, snapshot: StreamingCounterSnapshot<TKey, TValue>) -> Result<(), StreamingCounterRestoreError> {
+    let StreamingCounterSnapshot::CountMinSketch {
+      sketch,
+      tracked_keys,
+      observed_items_count,
+    } = snapshot
+    else {
+      return Err(StreamingCounterRestoreError::SnapshotKindMismatch);
+    };
+
+    if sketch.len() != self.sketch.len() || sketch.iter().any(|row| row.len() != self.width) {
+      return Err(StreamingCounterRestoreError::SketchDimensionsMismatch);
+    }
+
+    self.sketch = sketch;
+    self.tracked_keys = tracked_keys.into_iter().collect();
+    self.observed_items_count = observed_items_count;
+
+    Ok(())
+  }
+}
+
+impl<TKey, TValue> CountMinSketch<TKey, TValue>
+where
+  TKey: Hash + Eq + Clone,
+  TValue: Clone,
+{
+  pub fn new(error: f64, failure_probability: f64, support: f64) -> Result<Self, CountMinSketchCreationError> {
+    validate_count_min_sketch_parameters(error, failure_probability, support)?;
+
+    let width = (std::f64::consts::E / error).ceil().max(1.0) as usize;
+    let depth = (1.0 / failure_probability).ln().ceil().max(1.0) as usize;
+
+    Ok(Self {
+      sketch: vec![vec![0; width]; depth],
+      width,
+      tracked_keys: HashMap::new(),
+      support,
+      observed_items_count: 0,
+    })
+  }
+
+  pub fn estimate(&self, key: &TKey) -> u64 {
+    (0..self.sketch.len())
+      .map(|row| self.sketch[row][self.column(row, key)])
+      .min()
+      .unwrap_or(0)
+  }
+
+  fn column(&self, row: usize, key: &TKey) -> usize {
+    let mut hasher = StableHasher::with_seed(row as u64);
+    key.hash(&mut hasher);
+
+    (hasher.finish() % self.width as u64) as usize
+  }
+
+  fn is_frequent(&self, key: &TKey) -> bool {
+    self.estimate(key) as f64 >= self.support * self.observed_items_count as f64
+  }
+
+  fn max_tracked_keys_count(&self) -> usize {
+    if self.support > 0.0 {
+      2 * (1.0 / self.support).ceil() as usize
+    } else {
+      usize::MAX
+    }
+  }
+
+  fn prune(&mut self) {
+    let infrequent_keys: Vec<TKey> = self.tracked_keys.keys().filter(|key| !self.is_frequent(key)).cloned().collect();
+
+    for key in infrequent_keys {
+      self.tracked_keys.remove(&key);
+    }
+  }
+
+  fn to_streaming_counter_entry(&self, key: &TKey, value: &Option<TValue>) -> StreamingCounterEntry<TKey, TValue> {
+    let count = self.estimate(key);
+    StreamingCounterEntry::new(key.clone(), value.clone(), count as f64 / self.observed_items_count as f64, count)
+  }
+}
@@ -0,0 +1,177 @@
+use crate::features::streaming::{
+  counters::core::{StreamingCounter, StreamingCounterEntry, StreamingCounterRestoreError, StreamingCounterSnapshot, ValueUpdateKind},
+  time::StreamingTime,
+};
+use chrono::{DateTime, Utc};
+use std::{collections::HashMap, hash::Hash, time::Duration};
+
+// Entries with smaller weight would be reported with zero count, so they are dropped on invalidation
+const MIN_RETAINED_WEIGHT: f64 = 0.5;
+
+#[derive(Clone)]
+struct DecayedWeight {
+  weight: f64,
+  timestamp: DateTime<Utc>,
+}
+
+impl DecayedWeight {
+  pub fn new(timestamp: DateTime<Utc>) -> Self {
+    Self { weight: 0.0, timestamp }
+  }
+
+  pub fn weight_at(&self, now: DateTime<Utc>, half_life: Duration) -> f64 {
+    let elapsed_ms = (now - self.timestamp).num_milliseconds().max(0) as f64;
+    let half_life_ms = half_life.as_millis().max(1) as f64;
+
+    self.weight * 0.5f64.powf(elapsed_ms / half_life_ms)
+  }
+
+  pub fn increment(&mut self, now: DateTime<Utc>, half_life: Duration) {
+    self.weight = self.weight_at(now, half_life) + 1.0;
+    self.timestamp = self.timestamp.max(now);
+  }
+}
+
+#[derive(Clone)]
+struct ExponentialDecayEntry<TValue> {
+  value: Option<TValue>,
+  weight: DecayedWeight,
+}
+
+/// Counter in which every observation loses half of its weight each `half_life`, so recent observations dominate.
+#[derive(Clone)]
+pub struct ExponentialDecay<TKey: Hash + Eq + Clone, TValue: Clone> {
+  storage: HashMap<TKey, ExponentialDecayEntry<TValue>>,
+  total_weight: DecayedWeight,
+  half_life: Duration,
+  time: StreamingTime,
+}
+
+impl<TKey: Hash + Eq + Clone, TValue: Clone> StreamingCounter<TKey, TValue> for ExponentialDecay<TKey, TValue> {
+  fn observe(&mut self, key: TKey, value: ValueUpdateKind<TValue>) {
+    let now = self.now();
+
+    let value = match value {
+      ValueUpdateKind::Replace(value) => Some(value),
+      ValueUpdateKind::DoNothing => None,
+    };
+
+    let entry = self.storage.entry(key).or_insert_with(|| ExponentialDecayEntry {
+      value: None,
+      weight: DecayedWeight::new(now),
+    });
+
+    entry.weight.increment(now, self.half_life);
+    if value.is_some() {
+      entry.value = value;
+    }
+
+    self.total_weight.increment(now, self.half_life);
+  }
+
+  fn get(&self, key: &TKey) -> Option<StreamingCounterEntry<TKey, TValue>> {
+    let now = self.now();
+    let total_weight = self.total_weight.weight_at(now, self.half_life);
+
+    self
+      .storage
+      .get(key)
+      .map(|entry| self.to_streaming_counter_entry(key, entry, now, total_weight))
+  }
+
+  fn above_threshold(&self, threshold: f64) -> Vec<StreamingCounterEntry<TKey, TValue>> {
+    let now = self.now();
+    let total_weight = self.total_weight.weight_at(now, self.half_life);
+
+    self
+      .storage
+      .iter()
+      .map(|(key, entry)| self.to_streaming_counter_entry(key, entry, now, total_weight))
+      .filter(|entry| entry.approx_frequency() >= threshold)
+      .collect()
+  }
+
+  fn invalidate(&mut self) {
+    let now = self.now();
+    let half_life = self.half_life;
+
+    self
+      .storage
+      .retain(|_, entry| entry.weight.weight_at(now, half_life) >= MIN_RETAINED_WEIGHT);
+  }
+
+  fn snapshot(&self) -> StreamingCounterSnapshot<TKey, TValue> {
+    StreamingCounterSnapshot::ExponentialDecay {
+      entries: self
+        .storage
+        .iter()
+        .map(|(key, entry)| (key.clone(), entry.value.clone(), entry.weight.weight, entry.weight.timestamp))
+        .collect(),
+      total_weight: self.total_weight.weight,
+      total_weight_timestamp: self.total_weight.timestamp,
+    }
+  }
+
+  fn restore(&mut self, snapshot: StreamingCounterSnapshot<TKey, TValue>) -> Result<(), StreamingCounterRestoreError> {
+    let StreamingCounterSnapshot::ExponentialDecay {
+      entries,
+      total_weight,
+      total_weight_timestamp,
+    } = snapshot
+    else {
+      return Err(StreamingCounterRestoreError::SnapshotKindMismatch);
+    };
+
+    self.storage = entries
+      .into_iter()
+      .map(|(key, value, weight, timestamp)| {
+        let weight = DecayedWeight { weight, timestamp };
+        (key, ExponentialDecayEntry { value, weight })
+      })
+      .collect();
+
+    self.total_weight = DecayedWeight {
+      weight: total_weight,
+      timestamp: total_weight_timestamp,
+    };
+
+    Ok(())
+  }
+}
+
+impl<TKey: Hash + Eq + Clone, TValue: Clone> ExponentialDecay<TKey, TValue> {
+  pub fn new(half_life: Duration) -> Self {
+    Self::new_with(half_life, StreamingTime::default())
+  }
+
+  pub fn new_with(half_life: Duration, time: StreamingTime) -> Self {
+    Self {
+      storage: HashMap::new(),
+      total_weight: DecayedWeight::new(DateTime::<Utc>::MIN_UTC),
+      half_life,
+      time,
+    }
+  }
+
+  pub fn weight(&self, key: &TKey) -> Option<f64> {
+    let now = self.now();
+    self.storage.get(key).map(|entry| entry.weight.weight_at(now, self.half_life))
+  }
+
+  fn now(&self) -> DateTime<Utc> {
+    self.time.now().unwrap_or(DateTime::<Utc>::MIN_UTC)
+  }
+
+  fn to_streaming_counter_entry(
+    &self,
+    key: &TKey,
+    entry: &ExponentialDecayEntry<TValue>,
+    now: DateTime<Utc>,
+    total_weight: f64,
+  ) -> StreamingCounterEntry<TKey, TValue> {
+    let weight = entry.weight.weight_at(now, self.half_life);
+    let approx_frequency = if total_weight > 0.0 { weight / total_weight } else { 0.0 };
+
+    StreamingCounterEntry::new(key.clone(), entry.value.clone(), approx_frequency, weight.round() as u64)
+  }
+}
@@ -1,4 +1,6 @@
-use crate::features::streaming::counters::core::{StreamingCounter, StreamingCounterEntry, ValueUpdateKind};
+use crate::features::streaming::counters::core::{
+  StreamingCounter, StreamingCounterEntry, StreamingCounterRestoreError, StreamingCounterSnapshot, ValueUpdateKind,
+};
 use std::{collections::HashMap, hash::Hash};
 
 #[derive(Clone)]
@@ -71,6 +73,36 @@ where
   }
 
   fn invalidate(&mut self) {}
+
+  fn snapshot(&self) -> StreamingCounterSnapshot<TKey, TValue> {
+    StreamingCounterSnapshot::LossyCount {
+      entries: self
+        .state
+        .iter()
+        .map(|(key, state)| (key.clone(), state.value.clone(), state.freq, state.delta))
+        .collect(),
+      observed_items_count: self.observed_items_count,
+    }
+  }
+
+  fn restore(&mut self, snapshot: StreamingCounterSnapshot<TKey, TValue>) -> Result<(), StreamingCounterRestoreError> {
+    let StreamingCounterSnapshot::LossyCount {
+      entries,
+      observed_items_count,
+    } = snapshot
+    else {
+      return Err(StreamingCounterRestoreError::SnapshotKindMismatch);
+    };
+
+    self.state = entries
+      .into_iter()
+      .map(|(key, value, freq, delta)| (key, LossyCountState { value, freq, delta }))
+      .collect();
+
+    self.observed_items_count = observed_items_count;
+
+    Ok(())
+  }
 }
 
 impl<TKey, TValue> LossyCount<TKey, TValue>
@@ -1,3 +1,6 @@
 pub mod core;
+pub mod count_min;
+pub mod exponential_decay;
 pub mod lossy_count;
 pub mod sliding_window;
+pub mod space_saving;
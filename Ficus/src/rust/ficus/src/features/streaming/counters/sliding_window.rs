@@ -1,4 +1,7 @@
-use crate::features::streaming::counters::core::{StreamingCounter, StreamingCounterEntry, ValueUpdateKind};
+use crate::features::streaming::{
+  counters::core::{StreamingCounter, StreamingCounterEntry, StreamingCounterRestoreError, StreamingCounterSnapshot, ValueUpdateKind},
+  time::{StreamingTime, StreamingTimeMode},
+};
 use chrono::{DateTime, Utc};
 use std::{collections::HashMap, hash::Hash, ops::Add, sync::Arc, time::Duration};
 
@@ -35,6 +38,7 @@ pub type Invalidator<TValue> = Arc<Box<dyn Fn(Option<&TValue>, &DateTime<Utc>) -
 pub struct SlidingWindow<TKey: Hash + Eq + Clone, TValue: Clone> {
   storage: HashMap<TKey, SlidingWindowEntry<TValue>>,
   invalidator: Invalidator<TValue>,
+  time: StreamingTime,
 }
 
 unsafe impl<TKey: Hash + Eq + Clone, TValue: Clone> Sync for SlidingWindow<TKey, TValue> {}
@@ -45,6 +49,13 @@ impl<TKey: Hash + Eq + Clone, TValue: Clone> StreamingCounter<TKey, TValue> for
     self.add_current_stamp(key, value);
   }
 
+  fn observe_at(&mut self, key: TKey, value: ValueUpdateKind<TValue>, stamp: DateTime<Utc>) {
+    match self.time.mode() {
+      StreamingTimeMode::EventTime => self.add(key, value, stamp),
+      StreamingTimeMode::ProcessingTime => self.add_current_stamp(key, value),
+    }
+  }
+
   fn get(&self, key: &TKey) -> Option<StreamingCounterEntry<TKey, TValue>> {
     self
       .storage
@@ -70,6 +81,29 @@ impl<TKey: Hash + Eq + Clone, TValue: Clone> StreamingCounter<TKey, TValue> for
       .storage
       .retain(|_, value| invalidator(value.value.as_ref(), &value.timestamp) == InvalidationResult::Retain)
   }
+
+  fn snapshot(&self) -> StreamingCounterSnapshot<TKey, TValue> {
+    StreamingCounterSnapshot::SlidingWindow {
+      entries: self
+        .storage
+        .iter()
+        .map(|(key, entry)| (key.clone(), entry.value.clone(), entry.count, entry.timestamp))
+        .collect(),
+    }
+  }
+
+  fn restore(&mut self, snapshot: StreamingCounterSnapshot<TKey, TValue>) -> Result<(), StreamingCounterRestoreError> {
+    let StreamingCounterSnapshot::SlidingWindow { entries } = snapshot else {
+      return Err(StreamingCounterRestoreError::SnapshotKindMismatch);
+    };
+
+    self.storage = entries
+      .into_iter()
+      .map(|(key, value, count, timestamp)| (key, SlidingWindowEntry { value, count, timestamp }))
+      .collect();
+
+    Ok(())
+  }
 }
 
 impl<TKey: Hash + Eq + Clone, TValue: Clone> SlidingWindow<TKey, TValue> {
@@ -77,21 +111,30 @@ impl<TKey: Hash + Eq + Clone, TValue: Clone> SlidingWindow<TKey, TValue> {
     Self {
       storage: HashMap::new(),
       invalidator,
+      time: StreamingTime::default(),
     }
   }
 
   pub fn new_time(invalidation_duration: Duration) -> Self {
+    Self::new_time_with(invalidation_duration, StreamingTime::default())
+  }
+
+  pub fn new_time_with(invalidation_duration: Duration, time: StreamingTime) -> Self {
+    let invalidation_time = time.clone();
+
     Self {
       storage: HashMap::new(),
-      invalidator: Arc::new(Box::new(move |_, stamp| match stamp.add(invalidation_duration) < Utc::now() {
-        true => InvalidationResult::Invalidate,
-        false => InvalidationResult::Retain,
+      invalidator: Arc::new(Box::new(move |_, stamp| match invalidation_time.watermark() {
+        Some(watermark) if stamp.add(invalidation_duration) < watermark => InvalidationResult::Invalidate,
+        _ => InvalidationResult::Retain,
       })),
+      time,
     }
   }
 
   fn add_current_stamp(&mut self, key: TKey, value: ValueUpdateKind<TValue>) {
-    self.add(key, value, Utc::now());
+    let stamp = self.time.now().unwrap_or(DateTime::<Utc>::MIN_UTC);
+    self.add(key, value, stamp);
   }
 
   fn counts_sum(&self) -> u64 {
@@ -101,7 +144,7 @@ impl<TKey: Hash + Eq + Clone, TValue: Clone> SlidingWindow<TKey, TValue> {
   pub fn add(&mut self, key: TKey, value: ValueUpdateKind<TValue>, stamp: DateTime<Utc>) {
     if let Some(entry) = self.storage.get_mut(&key) {
       entry.count += 1;
-      entry.timestamp = stamp;
+      entry.timestamp = entry.timestamp.max(stamp);
       if let ValueUpdateKind::Replace(new_value) = value {
         entry.value = Some(new_value);
       }
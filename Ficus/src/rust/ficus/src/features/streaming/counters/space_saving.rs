@@ -0,0 +1,180 @@
+use crate::features::streaming::counters::core::{
+  StreamingCounter, StreamingCounterEntry, StreamingCounterRestoreError, StreamingCounterSnapshot, ValueUpdateKind,
+};
+use std::{
+  collections::{BTreeMap, HashMap},
+  hash::Hash,
+};
+
+#[derive(Clone)]
+struct SpaceSavingState<TValue> {
+  value: Option<TValue>,
+  count: u64,
+  error: u64,
+  stamp: u64,
+}
+
+/// Space-Saving top-k counter: at most `capacity` keys are monitored, the count of each monitored key
+/// overestimates its real frequency by at most `observed_items_count / capacity`.
+#[derive(Clone)]
+pub struct SpaceSaving<TKey, TValue>
+where
+  TKey: Hash + Eq,
+{
+  state: HashMap<TKey, SpaceSavingState<TValue>>,
+  // Monitored keys ordered by (count, stamp), so the key to evict is the first one
+  keys_by_count: BTreeMap<(u64, u64), TKey>,
+  next_stamp: u64,
+  capacity: usize,
+  observed_items_count: u64,
+}
+
+impl<TKey, TValue> StreamingCounter<TKey, TValue> for SpaceSaving<TKey, TValue>
+where
+  TKey: Hash + Eq + Clone,
+  TValue: Clone,
+{
+  fn observe(&mut self, key: TKey, value: ValueUpdateKind<TValue>) {
+    self.observed_items_count += 1;
+
+    let value = match value {
+      ValueUpdateKind::Replace(value) => Some(value),
+      ValueUpdateKind::DoNothing => None,
+    };
+
+    let stamp = self.next_stamp;
+    self.next_stamp += 1;
+
+    if let Some(state) = self.state.get_mut(&key) {
+      let indexed_key = self.keys_by_count.remove(&(state.count, state.stamp)).unwrap();
+
+      state.count += 1;
+      state.stamp = stamp;
+      if let Some(value) = value {
+        state.value = Some(value);
+      }
+
+      self.keys_by_count.insert((state.count, state.stamp), indexed_key);
+      return;
+    }
+
+    let (count, error) = match self.state.len() < self.capacity {
+      true => (1, 0),
+      false => {
+        let Some(((min_count, _), min_key)) = self.keys_by_count.pop_first() else {
+          return;
+        };
+
+        self.state.remove(&min_key);
+        (min_count + 1, min_count)
+      }
+    };
+
+    self.keys_by_count.insert((count, stamp), key.clone());
+    self.state.insert(
+      key,
+      SpaceSavingState {
+        value,
+        count,
+        error,
+        stamp,
+      },
+    );
+  }
+
+  fn get(&self, key: &TKey) -> Option<StreamingCounterEntry<TKey, TValue>> {
+    self.state.get(key).map(|state| self.to_streaming_counter_entry((key, state)))
+  }
+
+  fn above_threshold(&self, threshold: f64) -> Vec<StreamingCounterEntry<TKey, TValue>> {
+    self
+      .state
+      .iter()
+      .filter(|(_, state)| state.count as f64 >= threshold * self.observed_items_count as f64)
+      .map(|pair| self.to_streaming_counter_entry(pair))
+      .collect()
+  }
+
+  fn invalidate(&mut self) {}
+
+  fn snapshot(&self) -> StreamingCounterSnapshot<TKey, TValue> {
+    StreamingCounterSnapshot::SpaceSaving {
+      entries: self
+        .state
+        .iter()
+        .map(|(key, state)| (key.clone(), state.value.clone(), state.count, state.error))
+        .collect(),
+      observed_items_count: self.observed_items_count,
+    }
+  }
+
+  fn restore(&mut self, snapshot: StreamingCounterSnapshot<TKey, TValue>) -> Result<(), StreamingCounterRestoreError> {
+    let StreamingCounterSnapshot::SpaceSaving {
+      entries,
+      observed_items_count,
+    } = snapshot
+    else {
+      return Err(StreamingCounterRestoreError::SnapshotKindMismatch);
+    };
+
+    if entries.len() > self.capacity {
+      return Err(StreamingCounterRestoreError::CapacityExceeded(entries.len(), self.capacity));
+    }
+
+    self.state = HashMap::new();
+    self.keys_by_count = BTreeMap::new();
+
+    for (stamp, (key, value, count, error)) in entries.into_iter().enumerate() {
+      let stamp = stamp as u64;
+      self.keys_by_count.insert((count, stamp), key.clone());
+      self.state.insert(
+        key,
+        SpaceSavingState {
+          value,
+          count,
+          error,
+          stamp,
+        },
+      );
+    }
+
+    self.next_stamp = self.state.len() as u64;
+    self.observed_items_count = observed_items_count;
+
+    Ok(())
+  }
+}
+
+impl<TKey, TValue> SpaceSaving<TKey, TValue>
+where
+  TKey: Hash + Eq + Clone,
+  TValue: Clone,
+{
+  pub fn new(capacity: usize) -> Self {
+    Self {
+      state: HashMap::new(),
+      keys_by_count: BTreeMap::new(),
+      next_stamp: 0,
+      capacity: capacity.max(1),
+      observed_items_count: 0,
+    }
+  }
+
+  pub fn error_bound(&self) -> u64 {
+    self.observed_items_count / self.capacity as u64
+  }
+
+  /// Returns the lower bound of the real key frequency, i.e. the count minus the overestimation of the key.
+  pub fn guaranteed_count(&self, key: &TKey) -> Option<u64> {
+    self.state.get(key).map(|state| state.count - state.error)
+  }
+
+  fn to_streaming_counter_entry(&self, pair: (&TKey, &SpaceSavingState<TValue>)) -> StreamingCounterEntry<TKey, TValue> {
+    StreamingCounterEntry::new(
+      pair.0.clone(),
+      pair.1.value.clone(),
+      pair.1.count as f64 / self.observed_items_count as f64,
+      pair.1.count,
+    )
+  }
+}
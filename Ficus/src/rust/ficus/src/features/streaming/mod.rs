@@ -1,2 +1,7 @@
+pub mod conformance;
 pub mod counters;
+pub mod replay;
 pub mod t1;
+pub mod t2;
+pub mod time;
+pub mod triggers;
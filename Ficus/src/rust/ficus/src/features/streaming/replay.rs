@@ -0,0 +1,180 @@
+use crate::{
+  event_log::{
+    bxes::bxes_to_xes_converter::{BxesToXesReadError, read_bxes_into_xes_log},
+    core::{event::event::Event, event_log::EventLog, trace::trace::Trace},
+    xes::{reader::file_xes_log_reader::read_event_log, xes_event::XesEventImpl, xes_event_log::XesEventLogImpl},
+  },
+  features::streaming::time::{ManualClock, StreamingClock, StreamingTime},
+};
+use chrono::{DateTime, Duration, Utc};
+use std::{
+  fmt::{Display, Formatter},
+  path::Path,
+  sync::{Arc, Mutex},
+  thread,
+  time::Instant,
+};
+
+#[derive(Debug)]
+pub enum StreamReplayError {
+  FailedToReadXes(String),
+  FailedToReadBxes(BxesToXesReadError),
+  UnsupportedLogFormat(String),
+  InvalidSpeedup(f64),
+}
+
+impl Display for StreamReplayError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      StreamReplayError::FailedToReadXes(path) => write!(f, "Failed to read xes log from {}", path),
+      StreamReplayError::FailedToReadBxes(err) => write!(f, "Failed to read bxes log: {}", err),
+      StreamReplayError::UnsupportedLogFormat(path) => write!(f, "Unsupported log format of {}, expected .xes or .bxes", path),
+      StreamReplayError::InvalidSpeedup(speedup) => write!(f, "Speedup must be a positive number, got {}", speedup),
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplayClockMode {
+  /// Events are emitted without delays, the clock jumps to the timestamp of each emitted event.
+  Simulated,
+  /// Gaps between events are reproduced in real time divided by the speedup.
+  Accelerated(f64),
+}
+
+#[derive(Clone, Debug)]
+pub struct ReplayedEvent {
+  pub trace_index: usize,
+  pub event_index: usize,
+  pub is_last_in_trace: bool,
+  pub event: XesEventImpl,
+}
+
+impl ReplayedEvent {
+  pub fn timestamp(&self) -> DateTime<Utc> {
+    *self.event.timestamp()
+  }
+}
+
+/// Clock which runs `speedup` times faster than the system clock starting from the given origin.
+pub struct AcceleratedClock {
+  origin: DateTime<Utc>,
+  started_at: Mutex<Instant>,
+  speedup: f64,
+}
+
+impl AcceleratedClock {
+  pub fn new(origin: DateTime<Utc>, speedup: f64) -> Self {
+    Self {
+      origin,
+      started_at: Mutex::new(Instant::now()),
+      speedup,
+    }
+  }
+
+  fn restart(&self) {
+    *self.started_at.lock().unwrap() = Instant::now();
+  }
+
+  fn real_delay(&self, stamp: DateTime<Utc>) -> std::time::Duration {
+    let elapsed = (stamp - self.origin).to_std().unwrap_or_default().div_f64(self.speedup);
+    elapsed.saturating_sub(self.started_at.lock().unwrap().elapsed())
+  }
+}
+
+impl StreamingClock for AcceleratedClock {
+  fn now(&self) -> DateTime<Utc> {
+    let elapsed = self.started_at.lock().unwrap().elapsed().mul_f64(self.speedup);
+    Duration::from_std(elapsed)
+      .ok()
+      .and_then(|elapsed| self.origin.checked_add_signed(elapsed))
+      .unwrap_or(DateTime::<Utc>::MAX_UTC)
+  }
+}
+
+enum ReplayClock {
+  Simulated(Arc<ManualClock>),
+  Accelerated(Arc<AcceleratedClock>),
+}
+
+/// Replays an event log as a stream: events of all traces are merged and emitted in the order of their timestamps,
+/// events with equal timestamps keep the order of traces in the log.
+pub struct StreamReplay {
+  events: Vec<ReplayedEvent>,
+  clock: ReplayClock,
+}
+
+impl StreamReplay {
+  pub fn new(log: &XesEventLogImpl, mode: ReplayClockMode) -> Result<Self, StreamReplayError> {
+    let mut events = vec![];
+    for (trace_index, trace) in log.traces().iter().enumerate() {
+      let trace = trace.borrow();
+      let events_count = trace.events().len();
+
+      for (event_index, event) in trace.events().iter().enumerate() {
+        events.push(ReplayedEvent {
+          trace_index,
+          event_index,
+          is_last_in_trace: event_index + 1 == events_count,
+          event: event.borrow().clone(),
+        });
+      }
+    }
+
+    events.sort_by_key(|event| event.timestamp());
+
+    let origin = events.first().map(|event| event.timestamp()).unwrap_or(DateTime::<Utc>::MIN_UTC);
+    let clock = match mode {
+      ReplayClockMode::Simulated => ReplayClock::Simulated(Arc::new(ManualClock::new(origin))),
+      ReplayClockMode::Accelerated(speedup) if speedup > 0. && speedup.is_finite() => {
+        ReplayClock::Accelerated(Arc::new(AcceleratedClock::new(origin, speedup)))
+      }
+      ReplayClockMode::Accelerated(speedup) => return Err(StreamReplayError::InvalidSpeedup(speedup)),
+    };
+
+    Ok(Self { events, clock })
+  }
+
+  pub fn read(path: &str, mode: ReplayClockMode) -> Result<Self, StreamReplayError> {
+    let extension = Path::new(path).extension().and_then(|extension| extension.to_str());
+
+    let log = match extension {
+      Some("xes") => read_event_log(path).ok_or_else(|| StreamReplayError::FailedToReadXes(path.to_owned()))?,
+      Some("bxes") => read_bxes_into_xes_log(path).map_err(StreamReplayError::FailedToReadBxes)?.xes_log,
+      _ => return Err(StreamReplayError::UnsupportedLogFormat(path.to_owned())),
+    };
+
+    Self::new(&log, mode)
+  }
+
+  pub fn events(&self) -> &Vec<ReplayedEvent> {
+    &self.events
+  }
+
+  pub fn clock(&self) -> Arc<dyn StreamingClock> {
+    match &self.clock {
+      ReplayClock::Simulated(clock) => clock.clone(),
+      ReplayClock::Accelerated(clock) => clock.clone(),
+    }
+  }
+
+  /// Processing time which is driven by the replay clock instead of the system one.
+  pub fn processing_time(&self) -> StreamingTime {
+    StreamingTime::processing_time(self.clock())
+  }
+
+  pub fn replay(&self, mut consumer: impl FnMut(&ReplayedEvent)) {
+    if let ReplayClock::Accelerated(clock) = &self.clock {
+      clock.restart();
+    }
+
+    for event in &self.events {
+      match &self.clock {
+        ReplayClock::Simulated(clock) => clock.set(event.timestamp()),
+        ReplayClock::Accelerated(clock) => thread::sleep(clock.real_delay(event.timestamp())),
+      }
+
+      consumer(event);
+    }
+  }
+}
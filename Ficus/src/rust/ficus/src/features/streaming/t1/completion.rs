@@ -0,0 +1,118 @@
+use crate::{
+  event_log::{
+    core::{
+      event::event::{Event, EventPayloadValue},
+      event_log::EventLog,
+      trace::trace::Trace,
+    },
+    xes::{xes_event_log::XesEventLogImpl, xes_trace::XesTraceImpl},
+  },
+  features::streaming::time::StreamingTime,
+};
+use fancy_regex::Regex;
+use std::{
+  cell::RefCell,
+  fmt::{Display, Formatter},
+  ops::Sub,
+  rc::Rc,
+};
+
+pub const CASE_COMPLETED_MARKER: &str = "case_completed";
+
+#[derive(Clone, Debug)]
+pub enum CaseCompletionRule {
+  EndActivity(Regex),
+  InactivityGap(u64),
+  EndMarker,
+}
+
+impl Display for CaseCompletionRule {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      CaseCompletionRule::EndActivity(regex) => write!(f, "EndActivity({})", regex.as_str()),
+      CaseCompletionRule::InactivityGap(gap_ms) => write!(f, "InactivityGap({}ms)", gap_ms),
+      CaseCompletionRule::EndMarker => write!(f, "EndMarker"),
+    }
+  }
+}
+
+impl CaseCompletionRule {
+  pub fn end_activity(pattern: &str) -> Result<Self, fancy_regex::Error> {
+    Ok(CaseCompletionRule::EndActivity(Regex::new(pattern)?))
+  }
+
+  pub fn is_satisfied(&self, trace: &XesTraceImpl, time: &StreamingTime) -> bool {
+    let Some(last_event) = trace.events().last() else {
+      return false;
+    };
+
+    match self {
+      CaseCompletionRule::EndActivity(regex) => regex.is_match(last_event.borrow().name()).unwrap_or(false),
+      CaseCompletionRule::InactivityGap(gap_ms) => match time.watermark() {
+        Some(current_stamp) => current_stamp.sub(last_event.borrow().timestamp()).num_milliseconds() > *gap_ms as i64,
+        None => false,
+      },
+      CaseCompletionRule::EndMarker => matches!(trace.metadata().get(CASE_COMPLETED_MARKER), Some(EventPayloadValue::Boolean(true))),
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct CompletedCase {
+  pub trace: Rc<RefCell<XesTraceImpl>>,
+  pub rule: CaseCompletionRule,
+}
+
+/// Moves traces which satisfy any of the completion rules from the log of running cases to the log of closed ones.
+#[derive(Clone, Debug, Default)]
+pub struct CaseCompletionDetector {
+  rules: Vec<CaseCompletionRule>,
+}
+
+impl CaseCompletionDetector {
+  pub fn new(rules: Vec<CaseCompletionRule>) -> Self {
+    Self { rules }
+  }
+
+  pub fn rules(&self) -> &Vec<CaseCompletionRule> {
+    &self.rules
+  }
+
+  pub fn satisfied_rule(&self, trace: &XesTraceImpl, time: &StreamingTime) -> Option<&CaseCompletionRule> {
+    self.rules.iter().find(|rule| rule.is_satisfied(trace, time))
+  }
+
+  pub fn close_completed_cases(
+    &self,
+    running_cases: &mut XesEventLogImpl,
+    closed_cases: &mut XesEventLogImpl,
+    time: &StreamingTime,
+  ) -> Vec<CompletedCase> {
+    if self.rules.is_empty() {
+      return vec![];
+    }
+
+    let completed_cases: Vec<CompletedCase> = running_cases
+      .traces()
+      .iter()
+      .filter_map(|trace| {
+        self.satisfied_rule(&trace.borrow(), time).map(|rule| CompletedCase {
+          trace: trace.clone(),
+          rule: rule.clone(),
+        })
+      })
+      .collect();
+
+    if completed_cases.is_empty() {
+      return completed_cases;
+    }
+
+    running_cases.filter_traces(&|trace, _| completed_cases.iter().any(|case| std::ptr::eq(case.trace.as_ptr(), trace)));
+
+    for case in &completed_cases {
+      closed_cases.push(case.trace.clone());
+    }
+
+    completed_cases
+  }
+}
@@ -3,9 +3,11 @@ use crate::{
     core::{event::event::Event, event_log::EventLog, trace::trace::Trace},
     xes::xes_event_log::XesEventLogImpl,
   },
-  features::streaming::t1::configs::{EventsTimeoutConfiguration, TracesQueueConfiguration, TracesTimeoutConfiguration},
+  features::streaming::{
+    t1::configs::{EventsTimeoutConfiguration, TracesQueueConfiguration, TracesTimeoutConfiguration},
+    time::StreamingTime,
+  },
 };
-use chrono::Utc;
 use log::debug;
 use std::ops::Sub;
 
@@ -18,11 +20,11 @@ pub enum T1LogFilterer {
 }
 
 impl T1LogFilterer {
-  pub fn filter(&self, log: &mut XesEventLogImpl) {
+  pub fn filter(&self, log: &mut XesEventLogImpl, time: &StreamingTime) {
     match self {
       T1LogFilterer::None => {}
-      T1LogFilterer::EventsTimeoutFilterer(filterer) => filterer.filter(log),
-      T1LogFilterer::TracesTimeoutFilterer(filterer) => filterer.filter(log),
+      T1LogFilterer::EventsTimeoutFilterer(filterer) => filterer.filter(log, time),
+      T1LogFilterer::TracesTimeoutFilterer(filterer) => filterer.filter(log, time),
       T1LogFilterer::TracesQueueFilterer(filterer) => filterer.filter(log),
     }
   }
@@ -38,8 +40,11 @@ impl EventsTimeoutFiltererImpl {
     Self { config }
   }
 
-  pub fn filter(&self, log: &mut XesEventLogImpl) {
-    let current_stamp = Utc::now();
+  pub fn filter(&self, log: &mut XesEventLogImpl, time: &StreamingTime) {
+    let Some(current_stamp) = time.watermark() else {
+      return;
+    };
+
     let timeout = self.config.timeout_ms() as i64;
     log.filter_events_by(|e| current_stamp.sub(e.timestamp()).num_milliseconds() > timeout);
   }
@@ -55,8 +60,11 @@ impl TracesTimeoutFiltererImpl {
     Self { config }
   }
 
-  pub fn filter(&self, log: &mut XesEventLogImpl) {
-    let current_stamp = Utc::now();
+  pub fn filter(&self, log: &mut XesEventLogImpl, time: &StreamingTime) {
+    let Some(current_stamp) = time.watermark() else {
+      return;
+    };
+
     let timeout = self.config.timeout_ms() as i64;
     log.filter_traces(&|t, _| {
       let last_event = t.events().last().unwrap().borrow();
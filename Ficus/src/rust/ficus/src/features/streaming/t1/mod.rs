@@ -1,2 +1,3 @@
+pub mod completion;
 pub mod configs;
 pub mod filterers;
@@ -0,0 +1,87 @@
+use crate::features::{
+  discovery::{fuzzy::proximity_correlation::PrecomputedProximityCorrelation, relations::triangle_relation::PrecomputedTriangleRelation},
+  streaming::counters::core::{StreamingCounter, ValueUpdateKind},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+
+pub type ClassesPair = (Arc<str>, Arc<str>);
+
+/// Relations which an event adds to the streaming counters of its case.
+#[derive(Debug, Default)]
+pub struct CaseEventRelations {
+  /// `(a, b)` when the event closes the `a b a` pattern.
+  pub triangle: Option<ClassesPair>,
+  /// Time in milliseconds from the last occurrence of each class which was not yet followed by this event class.
+  pub proximities: Vec<(ClassesPair, f64)>,
+}
+
+/// The part of the case seen so far which is needed to compute relations of its next events.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CaseHistory {
+  events_count: u64,
+  last_occurrences: HashMap<Arc<str>, (u64, DateTime<Utc>)>,
+  last_classes: Vec<Arc<str>>,
+}
+
+impl CaseHistory {
+  pub fn observe(&mut self, class: Arc<str>, stamp: DateTime<Utc>) -> CaseEventRelations {
+    let mut relations = CaseEventRelations::default();
+
+    if self.last_classes.len() == 2 && self.last_classes[0] == class {
+      relations.triangle = Some((self.last_classes[0].clone(), self.last_classes[1].clone()));
+    }
+
+    let previous_position = self.last_occurrences.get(&class).map(|(position, _)| *position);
+    for (first_class, (position, first_stamp)) in &self.last_occurrences {
+      if *first_class == class || previous_position.is_some_and(|previous| *position < previous) {
+        continue;
+      }
+
+      let proximity = stamp.signed_duration_since(*first_stamp).num_milliseconds() as f64;
+      relations.proximities.push(((first_class.clone(), class.clone()), proximity));
+    }
+
+    self.last_occurrences.insert(class.clone(), (self.events_count, stamp));
+    self.events_count += 1;
+
+    self.last_classes.push(class);
+    if self.last_classes.len() > 2 {
+      self.last_classes.remove(0);
+    }
+
+    relations
+  }
+}
+
+/// Keeps the running mean of proximities of the pair as the counter value.
+pub fn observe_proximity(counter: &mut dyn StreamingCounter<ClassesPair, f64>, pair: ClassesPair, proximity: f64) {
+  let mean = match counter.get(&pair) {
+    Some(entry) => match entry.value() {
+      Some(mean) => mean + (proximity - mean) / (entry.absolute_count() + 1) as f64,
+      None => proximity,
+    },
+    None => proximity,
+  };
+
+  counter.observe(pair, ValueUpdateKind::Replace(mean));
+}
+
+pub fn triangle_relation_from_counter<TValue: Clone>(counter: &dyn StreamingCounter<ClassesPair, TValue>) -> PrecomputedTriangleRelation {
+  PrecomputedTriangleRelation::new(
+    counter
+      .all_frequencies()
+      .into_iter()
+      .map(|entry| (entry.key().clone(), entry.absolute_count() as usize)),
+  )
+}
+
+pub fn proximity_correlation_from_counter(counter: &dyn StreamingCounter<ClassesPair, f64>) -> PrecomputedProximityCorrelation {
+  PrecomputedProximityCorrelation::new(
+    counter
+      .all_frequencies()
+      .into_iter()
+      .filter_map(|entry| entry.value().map(|mean| (entry.key().clone(), *mean))),
+  )
+}
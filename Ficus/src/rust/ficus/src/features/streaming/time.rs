@@ -0,0 +1,168 @@
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex};
+
+pub trait StreamingClock: Send + Sync {
+  fn now(&self) -> DateTime<Utc>;
+}
+
+#[derive(Default)]
+pub struct SystemClock;
+
+impl StreamingClock for SystemClock {
+  fn now(&self) -> DateTime<Utc> {
+    Utc::now()
+  }
+}
+
+pub struct ManualClock {
+  now: Mutex<DateTime<Utc>>,
+}
+
+impl ManualClock {
+  pub fn new(now: DateTime<Utc>) -> Self {
+    Self { now: Mutex::new(now) }
+  }
+
+  pub fn set(&self, now: DateTime<Utc>) {
+    *self.now.lock().unwrap() = now;
+  }
+
+  pub fn advance(&self, delta: Duration) {
+    let mut now = self.now.lock().unwrap();
+    *now += delta;
+  }
+}
+
+impl StreamingClock for ManualClock {
+  fn now(&self) -> DateTime<Utc> {
+    *self.now.lock().unwrap()
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamingTimeMode {
+  ProcessingTime,
+  EventTime,
+}
+
+#[derive(Clone, Debug)]
+pub struct Watermark {
+  allowed_lateness: Duration,
+  max_event_time: Option<DateTime<Utc>>,
+}
+
+impl Watermark {
+  pub fn new(allowed_lateness: Duration) -> Self {
+    Self {
+      allowed_lateness,
+      max_event_time: None,
+    }
+  }
+
+  pub fn observe(&mut self, stamp: DateTime<Utc>) {
+    if self.max_event_time.is_none_or(|max| stamp > max) {
+      self.max_event_time = Some(stamp);
+    }
+  }
+
+  pub fn max_event_time(&self) -> Option<DateTime<Utc>> {
+    self.max_event_time
+  }
+
+  pub fn current(&self) -> Option<DateTime<Utc>> {
+    self.max_event_time.map(|max| max - self.allowed_lateness)
+  }
+
+  pub fn is_late(&self, stamp: DateTime<Utc>) -> bool {
+    self.current().is_some_and(|watermark| stamp < watermark)
+  }
+}
+
+/// Notion of current time of a single stream. In processing-time mode the current time is taken from the clock,
+/// in event-time mode it is the largest observed event timestamp, and events which are behind the watermark
+/// (largest observed timestamp minus allowed lateness) are considered late.
+#[derive(Clone)]
+pub struct StreamingTime {
+  mode: StreamingTimeMode,
+  clock: Arc<dyn StreamingClock>,
+  watermark: Arc<Mutex<Watermark>>,
+}
+
+impl Default for StreamingTime {
+  fn default() -> Self {
+    Self::processing_time(Arc::new(SystemClock))
+  }
+}
+
+impl StreamingTime {
+  pub fn processing_time(clock: Arc<dyn StreamingClock>) -> Self {
+    Self {
+      mode: StreamingTimeMode::ProcessingTime,
+      clock,
+      watermark: Arc::new(Mutex::new(Watermark::new(Duration::zero()))),
+    }
+  }
+
+  pub fn event_time(allowed_lateness: Duration) -> Self {
+    Self {
+      mode: StreamingTimeMode::EventTime,
+      clock: Arc::new(SystemClock),
+      watermark: Arc::new(Mutex::new(Watermark::new(allowed_lateness))),
+    }
+  }
+
+  pub fn mode(&self) -> StreamingTimeMode {
+    self.mode
+  }
+
+  /// Creates a time with the same settings but with its own watermark, should be used for each new stream.
+  pub fn fork(&self) -> Self {
+    let allowed_lateness = self.watermark.lock().unwrap().allowed_lateness;
+
+    Self {
+      mode: self.mode,
+      clock: self.clock.clone(),
+      watermark: Arc::new(Mutex::new(Watermark::new(allowed_lateness))),
+    }
+  }
+
+  /// Advances the watermark with the event timestamp, returns false if the event is late and should be dropped.
+  pub fn accept(&self, stamp: DateTime<Utc>) -> bool {
+    if self.mode == StreamingTimeMode::ProcessingTime {
+      return true;
+    }
+
+    let mut watermark = self.watermark.lock().unwrap();
+    if watermark.is_late(stamp) {
+      return false;
+    }
+
+    watermark.observe(stamp);
+    true
+  }
+
+  pub fn now(&self) -> Option<DateTime<Utc>> {
+    match self.mode {
+      StreamingTimeMode::ProcessingTime => Some(self.clock.now()),
+      StreamingTimeMode::EventTime => self.watermark.lock().unwrap().max_event_time(),
+    }
+  }
+
+  pub fn max_event_time(&self) -> Option<DateTime<Utc>> {
+    self.watermark.lock().unwrap().max_event_time()
+  }
+
+  /// Restores the watermark of a stream from the previously saved largest observed event timestamp.
+  pub fn restore_max_event_time(&self, max_event_time: Option<DateTime<Utc>>) {
+    if let Some(max_event_time) = max_event_time {
+      self.watermark.lock().unwrap().observe(max_event_time);
+    }
+  }
+
+  pub fn watermark(&self) -> Option<DateTime<Utc>> {
+    match self.mode {
+      StreamingTimeMode::ProcessingTime => Some(self.clock.now()),
+      StreamingTimeMode::EventTime => self.watermark.lock().unwrap().current(),
+    }
+  }
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Duration, Utc};
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerPolicy {
+  EveryTraces(u64),
+  EventTimeInterval(Duration),
+  CaseCompletion,
+  SignificantChange,
+}
+
+impl Display for TriggerPolicy {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      TriggerPolicy::EveryTraces(count) => write!(f, "EveryTraces({})", count),
+      TriggerPolicy::EventTimeInterval(interval) => write!(f, "EventTimeInterval({}ms)", interval.num_milliseconds()),
+      TriggerPolicy::CaseCompletion => write!(f, "CaseCompletion"),
+      TriggerPolicy::SignificantChange => write!(f, "SignificantChange"),
+    }
+  }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TriggerObservation {
+  pub event_time: Option<DateTime<Utc>>,
+  pub case_completed: bool,
+  pub significant_change: bool,
+}
+
+/// Decides after each observed trace whether the downstream pipeline should be executed.
+#[derive(Clone, Debug)]
+pub struct PipelineTrigger {
+  policy: TriggerPolicy,
+  traces_since_last_fire: u64,
+  last_fire_time: Option<DateTime<Utc>>,
+  fires_count: u64,
+}
+
+impl PipelineTrigger {
+  pub fn new(policy: TriggerPolicy) -> Self {
+    Self {
+      policy,
+      traces_since_last_fire: 0,
+      last_fire_time: None,
+      fires_count: 0,
+    }
+  }
+
+  pub fn policy(&self) -> TriggerPolicy {
+    self.policy
+  }
+
+  pub fn fires_count(&self) -> u64 {
+    self.fires_count
+  }
+
+  pub fn observe(&mut self, observation: &TriggerObservation) -> bool {
+    self.traces_since_last_fire += 1;
+
+    let should_fire = match self.policy {
+      TriggerPolicy::EveryTraces(count) => self.traces_since_last_fire >= count.max(1),
+      TriggerPolicy::EventTimeInterval(interval) => self.should_fire_by_event_time(observation.event_time, interval),
+      TriggerPolicy::CaseCompletion => observation.case_completed,
+      TriggerPolicy::SignificantChange => observation.significant_change,
+    };
+
+    if should_fire {
+      self.traces_since_last_fire = 0;
+      self.fires_count += 1;
+    }
+
+    should_fire
+  }
+
+  // The first observed event time starts the interval, late traces (with event time before the last fire) never fire
+  fn should_fire_by_event_time(&mut self, event_time: Option<DateTime<Utc>>, interval: Duration) -> bool {
+    let Some(event_time) = event_time else {
+      return false;
+    };
+
+    let Some(last_fire_time) = self.last_fire_time else {
+      self.last_fire_time = Some(event_time);
+      return false;
+    };
+
+    if event_time - last_fire_time < interval {
+      return false;
+    }
+
+    self.last_fire_time = Some(event_time);
+    true
+  }
+}
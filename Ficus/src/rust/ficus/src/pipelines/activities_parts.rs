@@ -2,7 +2,7 @@ use super::{
   aliases::TracesActivities,
   context::PipelineContext,
   errors::pipeline_errors::{PipelinePartExecutionError, RawPartExecutionError},
-  pipelines::{DefaultPipelinePart, PipelinePart, PipelinePartFactory},
+  pipelines::{DefaultPipelinePart, PipelinePart},
 };
 use crate::{
   event_log::{
@@ -32,9 +32,10 @@ use crate::{
       ADJUSTING_MODE_KEY, DISCOVER_ACTIVITY_INSTANCES_STRICT_KEY, EVENT_CLASS_REGEX_KEY, EVENT_LOG_KEY, EVENTS_COUNT_KEY,
       EXECUTE_ONLY_ON_LAST_EXTRACTION_KEY, HASHES_EVENT_LOG_KEY, LOG_SERIALIZATION_FORMAT_KEY, MIN_ACTIVITY_LENGTH_KEY,
       NARROW_ACTIVITIES_KEY, PATH_KEY, PATTERNS_DISCOVERY_STRATEGY_KEY, PATTERNS_KEY, PATTERNS_KIND_KEY, PIPELINE_KEY, REGEX_KEY,
-      REGEXES_KEY, REPEAT_SETS_KEY, TRACE_ACTIVITIES_KEY, UNDEF_ACTIVITY_HANDLING_STRATEGY_KEY, UNDERLYING_EVENTS_COUNT_KEY,
+      REGEXES_KEY, REPEAT_SETS_KEY, TANDEM_ARRAY_LENGTH_KEY, TRACE_ACTIVITIES_KEY, UNDEF_ACTIVITY_HANDLING_STRATEGY_KEY,
+      UNDERLYING_EVENTS_COUNT_KEY,
     },
-    pipeline_parts::PipelineParts,
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
   },
   utils::{
     log_serialization_format::LogSerializationFormat,
@@ -44,6 +45,7 @@ use crate::{
 use chrono::TimeDelta;
 use std::{cell::RefCell, collections::HashMap, path::Path, rc::Rc, str::FromStr, sync::Arc};
 
+#[derive(Clone)]
 pub enum UndefActivityHandlingStrategyDto {
   DontInsert,
   InsertAsSingleEvent,
@@ -63,6 +65,7 @@ impl FromStr for UndefActivityHandlingStrategyDto {
   }
 }
 
+#[derive(Clone)]
 pub enum ActivitiesLogsSourceDto {
   Log,
   TracesActivities,
@@ -81,10 +84,21 @@ impl FromStr for ActivitiesLogsSourceDto {
 }
 
 impl PipelineParts {
-  pipeline_part!(discover_activities, |context: &mut PipelineContext, _, config: &UserDataImpl| {
-    let activity_level = Self::get_user_data(config, &ACTIVITY_LEVEL_KEY)?;
-    Self::do_discover_activities(context, *activity_level, config)
-  });
+  pipeline_part!(
+    discover_activities,
+    |metadata| metadata
+      .config(&ACTIVITY_LEVEL_KEY)
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&HASHES_EVENT_LOG_KEY)
+      .input(&PATTERNS_KEY)
+      .optional_input(&UNDERLYING_PATTERN_KIND_KEY)
+      .output(&ACTIVITIES_KEY),
+    |context: &mut PipelineContext, _, config: &UserDataImpl| {
+      let activity_level = Self::get_user_data(config, &ACTIVITY_LEVEL_KEY)?;
+      Self::do_discover_activities(context, *activity_level, config)
+    }
+  );
 
   pub(super) fn do_discover_activities(
     context: &mut PipelineContext,
@@ -114,6 +128,14 @@ impl PipelineParts {
 
   pipeline_part!(
     discover_activities_instances,
+    |metadata| metadata
+      .config(&ACTIVITY_IN_TRACE_FILTER_KIND_KEY)
+      .config(&DISCOVER_ACTIVITY_INSTANCES_STRICT_KEY)
+      .config(&MIN_ACTIVITY_LENGTH_KEY)
+      .config(&NARROW_ACTIVITIES_KEY)
+      .input(&ACTIVITIES_KEY)
+      .input(&HASHES_EVENT_LOG_KEY)
+      .output(&TRACE_ACTIVITIES_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       Self::do_discover_activities_instances(context, config)?;
       Ok(())
@@ -140,10 +162,18 @@ impl PipelineParts {
     Ok(())
   }
 
-  pipeline_part!(create_log_from_activities, &|context, _, config| {
-    Self::do_create_log_from_activities(context, config)?;
-    Ok(())
-  });
+  pipeline_part!(
+    create_log_from_activities,
+    |metadata| metadata
+      .config(&UNDEF_ACTIVITY_HANDLING_STRATEGY_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&TRACE_ACTIVITIES_KEY)
+      .output(&EVENT_LOG_KEY),
+    &|context, _, config| {
+      Self::do_create_log_from_activities(context, config)?;
+      Ok(())
+    }
+  );
 
   pub(super) fn do_create_log_from_activities(
     context: &mut PipelineContext,
@@ -182,6 +212,22 @@ impl PipelineParts {
 
   pipeline_part!(
     discover_activities_for_several_levels,
+    |metadata| metadata
+      .config(&ACTIVITY_IN_TRACE_FILTER_KIND_KEY)
+      .config(&ACTIVITY_LEVEL_KEY)
+      .config(&ADJUSTING_MODE_KEY)
+      .config(&EVENTS_COUNT_KEY)
+      .config(&MIN_ACTIVITY_LENGTH_KEY)
+      .config(&NARROW_ACTIVITIES_KEY)
+      .config(&PATTERNS_DISCOVERY_STRATEGY_KEY)
+      .config(&PATTERNS_KIND_KEY)
+      .config(&REGEXES_KEY)
+      .optional_config(&TANDEM_ARRAY_LENGTH_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&ACTIVITIES_KEY)
+      .optional_input(&TRACE_ACTIVITIES_KEY)
+      .output(&ACTIVITIES_KEY)
+      .output(&TRACE_ACTIVITIES_KEY),
     |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
       let event_classes = Self::get_user_data(config, &REGEXES_KEY)?;
       let initial_activity_level = *Self::get_user_data(config, &ACTIVITY_LEVEL_KEY)?;
@@ -192,6 +238,7 @@ impl PipelineParts {
       let events_count = Self::get_user_data(config, &EVENTS_COUNT_KEY)?;
       let min_events_in_activity = Self::get_user_data(config, &MIN_ACTIVITY_LENGTH_KEY)?;
       let activity_filter_kind = Self::get_user_data(config, &ACTIVITY_IN_TRACE_FILTER_KIND_KEY)?;
+      let tandem_array_length = config.concrete(TANDEM_ARRAY_LENGTH_KEY.key()).cloned();
 
       for (index, event_class_regex) in event_classes.iter().rev().enumerate() {
         let mut config: UserDataImpl = Default::default();
@@ -206,6 +253,10 @@ impl PipelineParts {
         config.put_concrete(MIN_ACTIVITY_LENGTH_KEY.key(), *min_events_in_activity);
         config.put_concrete(ACTIVITY_IN_TRACE_FILTER_KIND_KEY.key(), *activity_filter_kind);
 
+        if let Some(tandem_array_length) = tandem_array_length {
+          config.put_concrete(TANDEM_ARRAY_LENGTH_KEY.key(), tandem_array_length);
+        }
+
         Self::adjust_with_activities_from_unattached_events(context, infra, &config)?;
       }
 
@@ -254,6 +305,16 @@ impl PipelineParts {
 
   pipeline_part!(
     discover_activities_in_unattached_subtraces,
+    |metadata| metadata
+      .config(&ACTIVITY_IN_TRACE_FILTER_KIND_KEY)
+      .config(&EVENTS_COUNT_KEY)
+      .config(&MIN_ACTIVITY_LENGTH_KEY)
+      .config(&NARROW_ACTIVITIES_KEY)
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .input(&ACTIVITIES_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&TRACE_ACTIVITIES_KEY)
+      .output(&TRACE_ACTIVITIES_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
       let mut existing_activities = &Self::create_empty_activities(log);
@@ -302,7 +363,7 @@ impl PipelineParts {
     activities
   }
 
-  pipeline_part!(clear_activities, |context: &mut PipelineContext, _, _| {
+  pipeline_part!(clear_activities, |metadata| metadata, |context: &mut PipelineContext, _, _| {
     Self::do_clear_activities_related_stuff(context);
     Ok(())
   });
@@ -314,16 +375,18 @@ impl PipelineParts {
     context.remove_concrete(REPEAT_SETS_KEY.key());
   }
 
-  pipeline_part!(get_underlying_events_count, |context: &mut PipelineContext,
-                                               infra: &PipelineInfrastructure,
-                                               _| {
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    let count = count_underlying_events(log);
-    infra.log(format!("Number of underlying events: {}", &count).as_str())?;
+  pipeline_part!(
+    get_underlying_events_count,
+    |metadata| metadata.input(&EVENT_LOG_KEY).output(&UNDERLYING_EVENTS_COUNT_KEY),
+    |context: &mut PipelineContext, infra: &PipelineInfrastructure, _| {
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      let count = count_underlying_events(log);
+      infra.log(format!("Number of underlying events: {}", &count).as_str())?;
 
-    context.put_concrete(UNDERLYING_EVENTS_COUNT_KEY.key(), count);
-    Ok(())
-  });
+      context.put_concrete(UNDERLYING_EVENTS_COUNT_KEY.key(), count);
+      Ok(())
+    }
+  );
 
   pub(super) fn execute_with_activities_instances(
     activities: &Vec<ActivityInTraceInfo>,
@@ -349,12 +412,30 @@ impl PipelineParts {
 
   pipeline_part!(
     discover_activities_until_no_more,
+    |metadata| metadata
+      .config(&ACTIVITY_IN_TRACE_FILTER_KIND_KEY)
+      .config(&ACTIVITY_LEVEL_KEY)
+      .config(&DISCOVER_ACTIVITY_INSTANCES_STRICT_KEY)
+      .config(&EXECUTE_ONLY_ON_LAST_EXTRACTION_KEY)
+      .config(&MIN_ACTIVITY_LENGTH_KEY)
+      .config(&NARROW_ACTIVITIES_KEY)
+      .config(&PATTERNS_DISCOVERY_STRATEGY_KEY)
+      .config(&PATTERNS_KIND_KEY)
+      .config(&UNDEF_ACTIVITY_HANDLING_STRATEGY_KEY)
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .optional_config(&PIPELINE_KEY)
+      .optional_config(&TANDEM_ARRAY_LENGTH_KEY)
+      .input(&EVENT_LOG_KEY)
+      .output(&EVENT_LOG_KEY),
     |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
       let activity_level = *Self::get_user_data(config, &ACTIVITY_LEVEL_KEY)?;
       let after_activities_extraction_pipeline = Self::get_user_data(config, &PIPELINE_KEY);
       let execute_only_after_last_extraction = *Self::get_user_data(config, &EXECUTE_ONLY_ON_LAST_EXTRACTION_KEY)?;
 
       loop {
+        infra.check_cancelled()?;
+        infra.check_budget()?;
+
         let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
         let events_count = count_events(log);
 
@@ -404,11 +485,20 @@ impl PipelineParts {
 
   pipeline_part!(
     execute_with_each_activity_log,
+    |metadata| metadata
+      .config(&ACTIVITIES_LOGS_SOURCE_KEY)
+      .config(&ACTIVITY_LEVEL_KEY)
+      .config(&PIPELINE_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&TRACE_ACTIVITIES_KEY),
     |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
       let pipeline = Self::get_user_data(config, &PIPELINE_KEY)?;
       let activities_to_logs = Self::create_activities_to_logs(context, config)?;
 
       for (activity_name, activity_log) in activities_to_logs {
+        infra.check_cancelled()?;
+        infra.check_budget()?;
+
         let mut temp_context = context.clone();
         temp_context.put_concrete(EVENT_LOG_KEY.key(), activity_log.borrow().clone());
         temp_context.put_concrete(ACTIVITY_NAME_KEY.key(), activity_name.clone());
@@ -439,65 +529,80 @@ impl PipelineParts {
     }
   }
 
-  pipeline_part!(substitute_underlying_events, |context: &mut PipelineContext, _, _| {
-    let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
-    let mut new_log = XesEventLogImpl::default();
+  pipeline_part!(
+    substitute_underlying_events,
+    |metadata| metadata.input(&EVENT_LOG_KEY).output(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
+      let mut new_log = XesEventLogImpl::default();
 
-    for trace in log.traces() {
-      let mut new_trace = XesTraceImpl::default();
-      for event in trace.borrow().events() {
-        substitute_underlying_events(event, &mut new_trace);
+      for trace in log.traces() {
+        let mut new_trace = XesTraceImpl::default();
+        for event in trace.borrow().events() {
+          substitute_underlying_events(event, &mut new_trace);
+        }
+
+        new_log.push(Rc::new(RefCell::new(new_trace)));
       }
 
-      new_log.push(Rc::new(RefCell::new(new_trace)));
+      context.put_concrete(EVENT_LOG_KEY.key(), new_log);
+      Ok(())
     }
+  );
 
-    context.put_concrete(EVENT_LOG_KEY.key(), new_log);
-    Ok(())
-  });
-
-  pipeline_part!(apply_class_extractor, |context: &mut PipelineContext, _, config: &UserDataImpl| {
-    let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
+  pipeline_part!(
+    apply_class_extractor,
+    |metadata| metadata.config(&EVENT_CLASS_REGEX_KEY).config(&REGEX_KEY).input(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, config: &UserDataImpl| {
+      let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
 
-    let event_class_regex = Self::get_user_data(config, &EVENT_CLASS_REGEX_KEY)?;
-    let event_class_regex = Self::try_parse_regex(event_class_regex)?;
+      let event_class_regex = Self::get_user_data(config, &EVENT_CLASS_REGEX_KEY)?;
+      let event_class_regex = Self::try_parse_regex(event_class_regex)?;
 
-    let filter_regex = Self::get_user_data(config, &REGEX_KEY)?;
-    let filter_regex = Self::try_parse_regex(filter_regex)?;
+      let filter_regex = Self::get_user_data(config, &REGEX_KEY)?;
+      let filter_regex = Self::try_parse_regex(filter_regex)?;
 
-    for trace in log.traces() {
-      for event in trace.borrow().events() {
-        if !filter_regex.is_match(event.borrow().name()).ok().unwrap() {
-          continue;
-        }
+      for trace in log.traces() {
+        for event in trace.borrow().events() {
+          if !filter_regex.is_match(event.borrow().name()).ok().unwrap() {
+            continue;
+          }
 
-        let borrowed_event = event.borrow();
-        let found_match = event_class_regex.find(borrowed_event.name());
-        if found_match.is_err() {
-          continue;
-        }
+          let borrowed_event = event.borrow();
+          let found_match = event_class_regex.find(borrowed_event.name());
+          if found_match.is_err() {
+            continue;
+          }
 
-        let (start, end) = if let Ok(Some(found_match)) = found_match {
-          (found_match.start(), found_match.end())
-        } else {
-          (0, borrowed_event.name().len())
-        };
+          let (start, end) = if let Ok(Some(found_match)) = found_match {
+            (found_match.start(), found_match.end())
+          } else {
+            (0, borrowed_event.name().len())
+          };
 
-        drop(found_match);
-        drop(borrowed_event);
+          drop(found_match);
+          drop(borrowed_event);
 
-        if start == 0 {
-          let new_name = Arc::from(event.borrow().name()[start..end].to_owned());
-          event.borrow_mut().set_name(new_name);
+          if start == 0 {
+            let new_name = Arc::from(event.borrow().name()[start..end].to_owned());
+            event.borrow_mut().set_name(new_name);
+          }
         }
       }
-    }
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 
   pipeline_part!(
     serialize_activities_logs,
+    |metadata| metadata
+      .config(&ACTIVITIES_LOGS_SOURCE_KEY)
+      .config(&ACTIVITY_LEVEL_KEY)
+      .config(&LOG_SERIALIZATION_FORMAT_KEY)
+      .config(&PATH_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&TRACE_ACTIVITIES_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let logs_to_activities = Self::create_activities_to_logs(context, config)?;
       let path = Path::new(Self::get_user_data(config, &PATH_KEY)?.as_ref());
@@ -528,6 +633,7 @@ impl PipelineParts {
 
   pipeline_part!(
     reverse_hierarchy_indices,
+    |metadata| metadata.input(&EVENT_LOG_KEY),
     |context: &mut PipelineContext, _, _config: &UserDataImpl| {
       let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
 
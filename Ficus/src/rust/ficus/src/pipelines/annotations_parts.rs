@@ -18,8 +18,7 @@ use crate::{
       PETRI_NET_FREQUENCY_ANNOTATION_KEY, PETRI_NET_KEY, PETRI_NET_TRACE_FREQUENCY_ANNOTATION_KEY, TERMINATE_ON_UNREPLAYABLE_TRACES_KEY,
       TIME_ANNOTATION_KIND_KEY,
     },
-    pipeline_parts::PipelineParts,
-    pipelines::PipelinePartFactory,
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
   },
   utils::{
     context_key::DefaultContextKey,
@@ -30,6 +29,11 @@ use crate::{
 impl PipelineParts {
   pipeline_part!(
     annotate_petri_net_count,
+    |metadata| metadata
+      .config(&TERMINATE_ON_UNREPLAYABLE_TRACES_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&PETRI_NET_KEY)
+      .output(&PETRI_NET_COUNT_ANNOTATION_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       Self::annotate_petri_net(&PETRI_NET_COUNT_ANNOTATION_KEY, context, config, annotate_with_counts)
     }
@@ -57,6 +61,11 @@ impl PipelineParts {
 
   pipeline_part!(
     annotate_petri_net_frequency,
+    |metadata| metadata
+      .config(&TERMINATE_ON_UNREPLAYABLE_TRACES_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&PETRI_NET_KEY)
+      .output(&PETRI_NET_FREQUENCY_ANNOTATION_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       Self::annotate_petri_net(&PETRI_NET_FREQUENCY_ANNOTATION_KEY, context, config, annotate_with_frequencies)
     }
@@ -64,6 +73,11 @@ impl PipelineParts {
 
   pipeline_part!(
     annotate_petri_net_trace_frequency,
+    |metadata| metadata
+      .config(&TERMINATE_ON_UNREPLAYABLE_TRACES_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&PETRI_NET_KEY)
+      .output(&PETRI_NET_TRACE_FREQUENCY_ANNOTATION_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       Self::annotate_petri_net(
         &PETRI_NET_TRACE_FREQUENCY_ANNOTATION_KEY,
@@ -76,6 +90,11 @@ impl PipelineParts {
 
   pipeline_part!(
     annotate_graph_with_time,
+    |metadata| metadata
+      .config(&TIME_ANNOTATION_KIND_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&GRAPH_KEY)
+      .output(&GRAPH_TIME_ANNOTATION_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
       let graph = Self::get_user_data(context, &GRAPH_KEY)?;
@@ -94,18 +113,22 @@ impl PipelineParts {
     }
   );
 
-  pipeline_part!(create_ocel_annotation_for_dag, |context: &mut PipelineContext, _, _| {
-    let graph = Self::get_user_data(context, &GRAPH_KEY)?;
+  pipeline_part!(
+    create_ocel_annotation_for_dag,
+    |metadata| metadata.input(&GRAPH_KEY).output(&OCEL_ANNOTATION_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let graph = Self::get_user_data(context, &GRAPH_KEY)?;
 
-    match create_ocel_annotation_for_dag(graph) {
-      Ok(annotation) => {
-        context.put_concrete(OCEL_ANNOTATION_KEY.key(), annotation);
-        Ok(())
-      }
-      Err(err) => {
-        let message = format!("Failed to create ocel annotation, error: {}", err);
-        Err(PipelinePartExecutionError::new_raw(message))
+      match create_ocel_annotation_for_dag(graph) {
+        Ok(annotation) => {
+          context.put_concrete(OCEL_ANNOTATION_KEY.key(), annotation);
+          Ok(())
+        }
+        Err(err) => {
+          let message = format!("Failed to create ocel annotation, error: {}", err);
+          Err(PipelinePartExecutionError::new_raw(message))
+        }
       }
     }
-  });
+  );
 }
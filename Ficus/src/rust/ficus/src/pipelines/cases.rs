@@ -4,29 +4,36 @@ use crate::{
   pipelines::{
     context::{PipelineContext, PipelineInfrastructure},
     keys::context_keys::{END_CASE_REGEX_KEY, EVENT_LOG_KEY, INLINE_INNER_CASES_KEY, PIPELINE_KEY, START_CASE_REGEX_KEY},
-    pipeline_parts::PipelineParts,
-    pipelines::{PipelinePart, PipelinePartFactory},
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
+    pipelines::PipelinePart,
   },
   utils::user_data::user_data::{UserData, UserDataImpl},
 };
 
 impl PipelineParts {
-  pipeline_part!(discover_cases, |context: &mut PipelineContext,
-                                  infra: &PipelineInfrastructure,
-                                  config: &UserDataImpl| {
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    let pipeline = Self::get_user_data(config, &PIPELINE_KEY)?;
-    let start_case_regex = Self::get_user_data(config, &START_CASE_REGEX_KEY)?;
-    let end_case_regex = Self::get_user_data(config, &END_CASE_REGEX_KEY)?;
-    let inline_inner_cases = *Self::get_user_data(config, &INLINE_INNER_CASES_KEY)?;
+  pipeline_part!(
+    discover_cases,
+    |metadata| metadata
+      .config(&END_CASE_REGEX_KEY)
+      .config(&INLINE_INNER_CASES_KEY)
+      .config(&PIPELINE_KEY)
+      .config(&START_CASE_REGEX_KEY)
+      .input(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      let pipeline = Self::get_user_data(config, &PIPELINE_KEY)?;
+      let start_case_regex = Self::get_user_data(config, &START_CASE_REGEX_KEY)?;
+      let end_case_regex = Self::get_user_data(config, &END_CASE_REGEX_KEY)?;
+      let inline_inner_cases = *Self::get_user_data(config, &INLINE_INNER_CASES_KEY)?;
 
-    let new_log = discover_cases(log, start_case_regex.as_ref(), end_case_regex.as_ref(), inline_inner_cases);
+      let new_log = discover_cases(log, start_case_regex.as_ref(), end_case_regex.as_ref(), inline_inner_cases);
 
-    let mut new_context = context.clone();
-    new_context.put_concrete(EVENT_LOG_KEY.key(), new_log);
+      let mut new_context = context.clone();
+      new_context.put_concrete(EVENT_LOG_KEY.key(), new_log);
 
-    pipeline.execute(&mut new_context, infra)?;
+      pipeline.execute(&mut new_context, infra)?;
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 }
@@ -26,8 +26,8 @@ use crate::{
       TRACES_ACTIVITIES_DATASET_KEY, TRACES_REPR_SOURCE_KEY,
     },
     multithreading::FeatureCountKindDto,
-    pipeline_parts::PipelineParts,
-    pipelines::{Pipeline, PipelinePart, PipelinePartFactory},
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
+    pipelines::{Pipeline, PipelinePart},
   },
   utils::user_data::user_data::{UserData, UserDataImpl},
 };
@@ -35,6 +35,18 @@ use crate::{
 impl PipelineParts {
   pipeline_part!(
     clusterize_activities_from_traces_kmeans,
+    |metadata| metadata
+      .config(&ACTIVITIES_REPR_SOURCE_KEY)
+      .config(&ACTIVITY_LEVEL_KEY)
+      .config(&CLUSTERS_COUNT_KEY)
+      .config(&DISTANCE_KEY)
+      .config(&LEARNING_ITERATIONS_COUNT_KEY)
+      .config(&TOLERANCE_KEY)
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&TRACE_ACTIVITIES_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&LABELED_TRACES_ACTIVITIES_DATASET_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let mut params = Self::create_activities_clustering_params(context, config)?;
       let clusters_count = *Self::get_user_data(config, &CLUSTERS_COUNT_KEY)? as usize;
@@ -103,11 +115,23 @@ impl PipelineParts {
 
   pipeline_part!(
     clusterize_activities_from_traces_kmeans_grid_search,
-    |context: &mut PipelineContext, _, config: &UserDataImpl| {
+    |metadata| metadata
+      .config(&ACTIVITIES_REPR_SOURCE_KEY)
+      .config(&ACTIVITY_LEVEL_KEY)
+      .config(&DISTANCE_KEY)
+      .config(&LEARNING_ITERATIONS_COUNT_KEY)
+      .config(&TOLERANCE_KEY)
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&TRACE_ACTIVITIES_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&LABELED_TRACES_ACTIVITIES_DATASET_KEY),
+    |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
       let learning_iterations_count = *Self::get_user_data(config, &LEARNING_ITERATIONS_COUNT_KEY)? as usize;
       let mut params = Self::create_activities_clustering_params(context, config)?;
 
-      let labeled_dataset = match clusterize_activities_k_means_grid_search(&mut params, learning_iterations_count) {
+      let token = infra.cancellation_token();
+      let labeled_dataset = match clusterize_activities_k_means_grid_search(&mut params, learning_iterations_count, token) {
         Ok(labeled_dataset) => labeled_dataset,
         Err(error) => return Err(error.into()),
       };
@@ -119,6 +143,18 @@ impl PipelineParts {
 
   pipeline_part!(
     clusterize_activities_from_traces_dbscan,
+    |metadata| metadata
+      .config(&ACTIVITIES_REPR_SOURCE_KEY)
+      .config(&ACTIVITY_LEVEL_KEY)
+      .config(&DISTANCE_KEY)
+      .config(&MIN_EVENTS_IN_CLUSTERS_COUNT_KEY)
+      .config(&PUT_NOISE_EVENTS_IN_ONE_CLUSTER_KEY)
+      .config(&TOLERANCE_KEY)
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&TRACE_ACTIVITIES_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&LABELED_TRACES_ACTIVITIES_DATASET_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let min_points_in_cluster = *Self::get_user_data(config, &MIN_EVENTS_IN_CLUSTERS_COUNT_KEY)? as usize;
       let put_noise_events_in_one_cluster = *Self::get_user_data(config, &PUT_NOISE_EVENTS_IN_ONE_CLUSTER_KEY)?;
@@ -136,18 +172,35 @@ impl PipelineParts {
 
   pipeline_part!(
     clusterize_log_traces_k_means_grid_search,
+    |metadata| metadata
+      .config(&DISTANCE_KEY)
+      .config(&FEATURE_COUNT_KIND_KEY)
+      .config(&LEARNING_ITERATIONS_COUNT_KEY)
+      .config(&TOLERANCE_KEY)
+      .config(&TRACES_REPR_SOURCE_KEY)
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .optional_config(&PERCENT_FROM_MAX_VALUE_KEY)
+      .optional_config(&PIPELINE_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&LABELED_LOG_TRACES_DATASET_KEY),
     |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
       let mut params = Self::create_traces_clustering_params(context, config)?;
       let learning_iterations_count = *Self::get_user_data(config, &LEARNING_ITERATIONS_COUNT_KEY)? as u64;
       let tolerance = *Self::get_user_data(config, &TOLERANCE_KEY)?;
 
-      let (logs, labeled_dataset) = match clusterize_log_by_traces_kmeans_grid_search(&mut params, learning_iterations_count, tolerance) {
-        Ok(new_logs) => new_logs,
-        Err(error) => return Err(error.into()),
-      };
+      let token = infra.cancellation_token();
+      let (logs, labeled_dataset) =
+        match clusterize_log_by_traces_kmeans_grid_search(&mut params, learning_iterations_count, tolerance, token) {
+          Ok(new_logs) => new_logs,
+          Err(error) => return Err(error.into()),
+        };
 
       if let Ok(after_clusterization_pipeline) = Self::get_user_data(config, &PIPELINE_KEY) {
         for log in logs {
+          infra.check_cancelled()?;
+          infra.check_budget()?;
+
           let mut new_context = context.clone();
           new_context.put_concrete(EVENT_LOG_KEY.key(), log);
 
@@ -163,6 +216,14 @@ impl PipelineParts {
 
   pipeline_part!(
     create_traces_activities_dataset,
+    |metadata| metadata
+      .config(&ACTIVITIES_REPR_SOURCE_KEY)
+      .config(&ACTIVITY_LEVEL_KEY)
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&TRACE_ACTIVITIES_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&TRACES_ACTIVITIES_DATASET_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let params = Self::create_activities_visualization_params(context, config)?;
 
@@ -206,6 +267,20 @@ impl PipelineParts {
 
   pipeline_part!(
     clusterize_log_traces,
+    |metadata| metadata
+      .config(&DISTANCE_KEY)
+      .config(&FEATURE_COUNT_KIND_KEY)
+      .config(&MIN_EVENTS_IN_CLUSTERS_COUNT_KEY)
+      .config(&PIPELINE_KEY)
+      .config(&PUT_NOISE_EVENTS_IN_ONE_CLUSTER_KEY)
+      .config(&TOLERANCE_KEY)
+      .config(&TRACES_REPR_SOURCE_KEY)
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .optional_config(&PERCENT_FROM_MAX_VALUE_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&EVENT_LOG_KEY)
+      .output(&LABELED_LOG_TRACES_DATASET_KEY),
     |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
       let mut params = Self::create_traces_clustering_params(context, config)?;
       let after_clusterization_pipeline = Self::get_user_data(config, &PIPELINE_KEY)?;
@@ -228,6 +303,19 @@ impl PipelineParts {
 
   pipeline_part!(
     clusterize_log_traces_dbscan_grid_search,
+    |metadata| metadata
+      .config(&DISTANCE_KEY)
+      .config(&FEATURE_COUNT_KIND_KEY)
+      .config(&MIN_POINTS_IN_CLUSTER_ARRAY_KEY)
+      .config(&PIPELINE_KEY)
+      .config(&PUT_NOISE_EVENTS_IN_ONE_CLUSTER_KEY)
+      .config(&TOLERANCES_KEY)
+      .config(&TRACES_REPR_SOURCE_KEY)
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .optional_config(&PERCENT_FROM_MAX_VALUE_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&LABELED_LOG_TRACES_DATASET_KEY),
     |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
       let mut params = Self::create_traces_clustering_params(context, config)?;
       let after_clusterization_pipeline = Self::get_user_data(config, &PIPELINE_KEY)?;
@@ -238,8 +326,9 @@ impl PipelineParts {
       let tolerances = Self::get_user_data(config, &TOLERANCES_KEY)?;
       let put_noise_events_in_one_cluster = *Self::get_user_data(config, &PUT_NOISE_EVENTS_IN_ONE_CLUSTER_KEY)?;
 
+      let token = infra.cancellation_token();
       let new_logs =
-        match clusterize_log_by_traces_dbscan_grid_search(&mut params, &min_points, tolerances, put_noise_events_in_one_cluster) {
+        match clusterize_log_by_traces_dbscan_grid_search(&mut params, &min_points, tolerances, put_noise_events_in_one_cluster, token) {
           Ok(new_logs) => new_logs,
           Err(error) => return Err(error.into()),
         };
@@ -262,6 +351,9 @@ impl PipelineParts {
     let original_log = Self::get_user_data(context, &EVENT_LOG_KEY).ok().cloned();
 
     for log in logs {
+      infra.check_cancelled()?;
+      infra.check_budget()?;
+
       context.put_concrete(EVENT_LOG_KEY.key(), log);
       pipeline.execute(context, infra)?;
     }
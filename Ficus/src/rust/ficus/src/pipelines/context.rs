@@ -1,6 +1,13 @@
 use crate::{
-  pipelines::pipeline_parts::PipelineParts,
+  pipelines::{
+    pipeline_parts::PipelineParts,
+    pipelines::PipelinePart,
+    results_cache::{CacheHitHandler, PipelinePartsResultsCache},
+    sendable_values::copy_context_values,
+  },
   utils::{
+    cancellation::CancellationToken,
+    execution_budget::ExecutionBudget,
     performance::performance_cookie::PerformanceLogger,
     user_data::{
       keys::{DefaultKey, Key},
@@ -18,6 +25,10 @@ pub trait LogMessageHandler: Send + Sync {
 
 pub struct PipelineInfrastructure {
   log_message_handler: Option<Arc<dyn LogMessageHandler>>,
+  cancellation_token: CancellationToken,
+  execution_budget: ExecutionBudget,
+  results_cache: Option<Arc<PipelinePartsResultsCache>>,
+  cache_hit_handler: Option<Arc<dyn CacheHitHandler>>,
 }
 
 impl PerformanceLogger<PipelinePartExecutionError> for PipelineInfrastructure {
@@ -29,7 +40,69 @@ impl PerformanceLogger<PipelinePartExecutionError> for PipelineInfrastructure {
 
 impl PipelineInfrastructure {
   pub fn new(log_message_handler: Option<Arc<dyn LogMessageHandler>>) -> Self {
-    Self { log_message_handler }
+    Self {
+      log_message_handler,
+      cancellation_token: CancellationToken::new(),
+      execution_budget: ExecutionBudget::unlimited(),
+      results_cache: None,
+      cache_hit_handler: None,
+    }
+  }
+
+  pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+    self.cancellation_token = cancellation_token;
+    self
+  }
+
+  pub fn with_execution_budget(mut self, execution_budget: ExecutionBudget) -> Self {
+    self.execution_budget = execution_budget;
+    self
+  }
+
+  pub fn with_results_cache(mut self, results_cache: Arc<PipelinePartsResultsCache>) -> Self {
+    self.results_cache = Some(results_cache);
+    self
+  }
+
+  pub fn with_cache_hit_handler(mut self, cache_hit_handler: Arc<dyn CacheHitHandler>) -> Self {
+    self.cache_hit_handler = Some(cache_hit_handler);
+    self
+  }
+
+  pub fn results_cache(&self) -> Option<&PipelinePartsResultsCache> {
+    self.results_cache.as_deref()
+  }
+
+  pub fn report_cache_hit(&self, part_name: &str) {
+    if let Some(handler) = self.cache_hit_handler.as_ref() {
+      handler.handle(part_name);
+    }
+  }
+
+  pub fn cancellation_token(&self) -> &CancellationToken {
+    &self.cancellation_token
+  }
+
+  pub fn check_cancelled(&self) -> Result<(), PipelinePartExecutionError> {
+    match self.cancellation_token.is_cancelled() {
+      true => Err(PipelinePartExecutionError::Cancelled),
+      false => Ok(()),
+    }
+  }
+
+  pub fn check_budget(&self) -> Result<(), PipelinePartExecutionError> {
+    self.execution_budget.check().map_err(PipelinePartExecutionError::BudgetExceeded)
+  }
+
+  /// Executes `part` while the execution budget is watched, so that the budget is enforced inside long parts too
+  pub fn execute_watched(&self, part: &dyn PipelinePart, context: &mut PipelineContext) -> Result<(), PipelinePartExecutionError> {
+    let watcher = self.execution_budget.watch(self.cancellation_token.clone());
+    let result = part.execute(context, self);
+
+    match (result, watcher.stop()) {
+      (Err(PipelinePartExecutionError::Cancelled), Some(violation)) => Err(PipelinePartExecutionError::BudgetExceeded(violation)),
+      (result, _) => result,
+    }
   }
 
   pub fn log(&self, message: &str) -> Result<(), PipelinePartExecutionError> {
@@ -45,6 +118,7 @@ impl PipelineInfrastructure {
 pub struct PipelineContext<'a> {
   user_data: UserDataImpl,
   pipeline_parts: Option<&'a PipelineParts>,
+  config_overrides: UserDataImpl,
 }
 
 impl<'a> PipelineContext<'a> {
@@ -52,6 +126,7 @@ impl<'a> PipelineContext<'a> {
     Self {
       user_data: Default::default(),
       pipeline_parts: Some(parts),
+      config_overrides: Default::default(),
     }
   }
 
@@ -59,6 +134,7 @@ impl<'a> PipelineContext<'a> {
     Self {
       user_data: Default::default(),
       pipeline_parts: other.pipeline_parts,
+      config_overrides: other.config_overrides.clone(),
     }
   }
 }
@@ -109,4 +185,53 @@ impl<'a> PipelineContext<'a> {
   pub fn devastate_user_data(self) -> UserDataImpl {
     self.user_data
   }
+
+  pub(crate) fn with_user_data(user_data: UserDataImpl, pipeline_parts: Option<&'a PipelineParts>) -> Self {
+    Self {
+      user_data,
+      pipeline_parts,
+      config_overrides: Default::default(),
+    }
+  }
+
+  /// Copy of the context which does not share any value with this context, fails if some value can not be copied
+  pub(crate) fn detached_copy(&self) -> Result<Self, PipelinePartExecutionError> {
+    let copy_values = |user_data: &UserDataImpl| {
+      copy_context_values(user_data).map_err(|key| PipelinePartExecutionError::new_raw(format!("Context value {} can not be copied", key)))
+    };
+
+    Ok(Self {
+      user_data: copy_values(&self.user_data)?,
+      pipeline_parts: self.pipeline_parts,
+      config_overrides: copy_values(&self.config_overrides)?,
+    })
+  }
+
+  pub(crate) fn config_overrides(&self) -> &UserDataImpl {
+    &self.config_overrides
+  }
+
+  pub(crate) fn set_config_overrides(&mut self, config_overrides: UserDataImpl) {
+    self.config_overrides = config_overrides;
+  }
+
+  /// Values of `config_overrides` replace the corresponding values of the config of every part executed with this context
+  pub(crate) fn apply_config_overrides(&self, config: &UserDataImpl) -> Option<UserDataImpl> {
+    if self.config_overrides.len() == 0 {
+      return None;
+    }
+
+    let mut config = config.clone();
+    self.config_overrides.clone().move_values_to(&mut config);
+
+    Some(config)
+  }
+
+  pub(crate) fn user_data(&self) -> &UserDataImpl {
+    &self.user_data
+  }
+
+  pub(crate) fn user_data_mut(&mut self) -> &mut UserDataImpl {
+    &mut self.user_data
+  }
 }
@@ -0,0 +1,376 @@
+use crate::{
+  event_log::core::{event::event::Event, event_log::EventLog, trace::trace::Trace},
+  features::analysis::log_info::event_log_info::count_events,
+  pipeline_part,
+  pipelines::{
+    context::{PipelineContext, PipelineInfrastructure},
+    errors::pipeline_errors::PipelinePartExecutionError,
+    keys::context_keys::{
+      ACTIVITIES_TO_LOGS, ACTIVITIES_TO_LOGS_KEY, ACTIVITY_NAME_KEY, ATTRIBUTE_KEY, ATTRIBUTES, ATTRIBUTES_KEY, CONDITION_METRIC_KEY,
+      CONDITION_OPERATOR_KEY, CONDITION_THRESHOLD_KEY, ELSE_PIPELINE_KEY, EVENT_LOG_KEY, FOR_EACH_COLLECTION_KEY, GRAPH_KEY, GRAPHS,
+      GRAPHS_KEY, PATH_KEY, PATHS, PATHS_KEY, PIPELINE_KEY, REGEX_KEY, REGEXES, REGEXES_KEY, SWEEP_GRID_KEY, SWEEP_RESULTS_KEY,
+      TOLERANCE_KEY, TOLERANCES, TOLERANCES_KEY, find_context_key,
+    },
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
+    pipelines::{Pipeline, PipelinePart},
+  },
+  utils::user_data::user_data::{UserData, UserDataImpl},
+};
+use std::{collections::HashSet, str::FromStr, sync::Arc};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConditionMetric {
+  EventsCount,
+  TracesCount,
+  EventClassesCount,
+}
+
+impl FromStr for ConditionMetric {
+  type Err = ();
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "EventsCount" => Ok(Self::EventsCount),
+      "TracesCount" => Ok(Self::TracesCount),
+      "EventClassesCount" => Ok(Self::EventClassesCount),
+      _ => Err(()),
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComparisonOperator {
+  Less,
+  LessOrEqual,
+  Equal,
+  NotEqual,
+  GreaterOrEqual,
+  Greater,
+}
+
+impl FromStr for ComparisonOperator {
+  type Err = ();
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "Less" => Ok(Self::Less),
+      "LessOrEqual" => Ok(Self::LessOrEqual),
+      "Equal" => Ok(Self::Equal),
+      "NotEqual" => Ok(Self::NotEqual),
+      "GreaterOrEqual" => Ok(Self::GreaterOrEqual),
+      "Greater" => Ok(Self::Greater),
+      _ => Err(()),
+    }
+  }
+}
+
+impl ComparisonOperator {
+  pub fn compare(&self, value: f64, threshold: f64) -> bool {
+    match self {
+      ComparisonOperator::Less => value < threshold,
+      ComparisonOperator::LessOrEqual => value <= threshold,
+      ComparisonOperator::Equal => value == threshold,
+      ComparisonOperator::NotEqual => value != threshold,
+      ComparisonOperator::GreaterOrEqual => value >= threshold,
+      ComparisonOperator::Greater => value > threshold,
+    }
+  }
+}
+
+pub struct SweepRunResult {
+  index: usize,
+  parameters: Vec<(Arc<str>, Arc<str>)>,
+  outputs: UserDataImpl,
+}
+
+impl SweepRunResult {
+  pub fn index(&self) -> usize {
+    self.index
+  }
+
+  pub fn parameters(&self) -> &Vec<(Arc<str>, Arc<str>)> {
+    &self.parameters
+  }
+
+  pub fn outputs(&self) -> &UserDataImpl {
+    &self.outputs
+  }
+}
+
+struct SweepAxis {
+  key_name: Arc<str>,
+  raw_values: Vec<Arc<str>>,
+}
+
+impl PipelineParts {
+  pub(super) fn conditional() -> PipelinePartDefinition {
+    Self::create_pipeline_part(
+      Self::IF,
+      |metadata| {
+        metadata
+          .config(&CONDITION_METRIC_KEY)
+          .config(&CONDITION_OPERATOR_KEY)
+          .config(&CONDITION_THRESHOLD_KEY)
+          .config(&PIPELINE_KEY)
+          .optional_config(&ELSE_PIPELINE_KEY)
+          .input(&EVENT_LOG_KEY)
+      },
+      &|context, infra, config| {
+        let metric = *Self::get_user_data(config, &CONDITION_METRIC_KEY)?;
+        let operator = *Self::get_user_data(config, &CONDITION_OPERATOR_KEY)?;
+        let threshold = *Self::get_user_data(config, &CONDITION_THRESHOLD_KEY)?;
+
+        let value = Self::evaluate_condition_metric(context, metric)?;
+        let pipeline = match operator.compare(value, threshold) {
+          true => Some(Self::get_user_data(config, &PIPELINE_KEY)?),
+          false => Self::get_user_data(config, &ELSE_PIPELINE_KEY).ok(),
+        };
+
+        match pipeline {
+          Some(pipeline) => pipeline.execute(context, infra),
+          None => Ok(()),
+        }
+      },
+    )
+  }
+
+  fn evaluate_condition_metric(context: &PipelineContext, metric: ConditionMetric) -> Result<f64, PipelinePartExecutionError> {
+    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+
+    Ok(match metric {
+      ConditionMetric::EventsCount => count_events(log) as f64,
+      ConditionMetric::TracesCount => log.traces().len() as f64,
+      ConditionMetric::EventClassesCount => {
+        let mut classes = HashSet::new();
+        for trace in log.traces() {
+          for event in trace.borrow().events() {
+            classes.insert(event.borrow().name_pointer().clone());
+          }
+        }
+
+        classes.len() as f64
+      }
+    })
+  }
+
+  pipeline_part!(
+    for_each,
+    |metadata| metadata
+      .config(&FOR_EACH_COLLECTION_KEY)
+      .config(&PIPELINE_KEY)
+      .optional_input(&ACTIVITIES_TO_LOGS_KEY)
+      .optional_input(&ATTRIBUTES_KEY)
+      .optional_input(&GRAPHS_KEY)
+      .optional_input(&PATHS_KEY)
+      .optional_input(&REGEXES_KEY)
+      .optional_input(&TOLERANCES_KEY),
+    |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
+      let pipeline = Self::get_user_data(config, &PIPELINE_KEY)?;
+      let collection_name = Self::get_user_data(config, &FOR_EACH_COLLECTION_KEY)?;
+
+      match collection_name.as_ref() {
+        PATHS => Self::for_each_item(
+          context,
+          infra,
+          pipeline,
+          Self::get_user_data(context, &PATHS_KEY)?.clone(),
+          |c, p| c.put_concrete(PATH_KEY.key(), p),
+        ),
+        REGEXES => Self::for_each_item(
+          context,
+          infra,
+          pipeline,
+          Self::get_user_data(context, &REGEXES_KEY)?.clone(),
+          |c, r| c.put_concrete(REGEX_KEY.key(), r),
+        ),
+        ATTRIBUTES => Self::for_each_item(
+          context,
+          infra,
+          pipeline,
+          Self::get_user_data(context, &ATTRIBUTES_KEY)?.clone(),
+          |c, a| c.put_concrete(ATTRIBUTE_KEY.key(), a),
+        ),
+        TOLERANCES => Self::for_each_item(
+          context,
+          infra,
+          pipeline,
+          Self::get_user_data(context, &TOLERANCES_KEY)?.clone(),
+          |c, t| c.put_concrete(TOLERANCE_KEY.key(), t),
+        ),
+        GRAPHS => Self::for_each_item(
+          context,
+          infra,
+          pipeline,
+          Self::get_user_data(context, &GRAPHS_KEY)?.clone(),
+          |c, g| c.put_concrete(GRAPH_KEY.key(), g),
+        ),
+        ACTIVITIES_TO_LOGS => {
+          let mut activities_logs = Self::get_user_data(context, &ACTIVITIES_TO_LOGS_KEY)?
+            .iter()
+            .map(|(name, log)| (name.clone(), log.clone()))
+            .collect::<Vec<_>>();
+
+          activities_logs.sort_by(|first, second| first.0.cmp(&second.0));
+
+          Self::for_each_item(context, infra, pipeline, activities_logs, |c, (name, log)| {
+            c.put_concrete(ACTIVITY_NAME_KEY.key(), name);
+            c.put_concrete(EVENT_LOG_KEY.key(), log);
+          })
+        }
+        _ => Err(PipelinePartExecutionError::new_raw(format!(
+          "Context value {} is not a supported ForEach collection",
+          collection_name
+        ))),
+      }
+    }
+  );
+
+  /// Every iteration is executed on a detached copy of the context in which the item is put, so parts which change values in place
+  /// do not affect the original context, values produced or changed in place by the iteration are then put to the original context
+  /// and are visible to the next iterations.
+  fn for_each_item<T>(
+    context: &mut PipelineContext,
+    infra: &PipelineInfrastructure,
+    pipeline: &Pipeline,
+    items: Vec<T>,
+    item_setter: impl Fn(&mut PipelineContext, T),
+  ) -> Result<(), PipelinePartExecutionError> {
+    for item in items {
+      infra.check_cancelled()?;
+      infra.check_budget()?;
+
+      let mut iteration_context = context.detached_copy()?;
+      item_setter(&mut iteration_context, item);
+
+      let outputs = Self::execute_and_collect_outputs(iteration_context, infra, pipeline)?;
+      outputs.move_values_to(context.user_data_mut());
+    }
+
+    Ok(())
+  }
+
+  fn execute_and_collect_outputs(
+    mut context: PipelineContext,
+    infra: &PipelineInfrastructure,
+    pipeline: &Pipeline,
+  ) -> Result<UserDataImpl, PipelinePartExecutionError> {
+    let addresses = context.user_data().values_addresses();
+    pipeline.execute(&mut context, infra)?;
+
+    let mut outputs = context.devastate_user_data();
+    outputs.retain_changed_values(&addresses);
+
+    Ok(outputs)
+  }
+
+  pipeline_part!(
+    sweep,
+    |metadata| metadata.config(&PIPELINE_KEY).config(&SWEEP_GRID_KEY).output(&SWEEP_RESULTS_KEY),
+    |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
+      let pipeline = Self::get_user_data(config, &PIPELINE_KEY)?;
+      let axes = Self::parse_sweep_grid(Self::get_user_data(config, &SWEEP_GRID_KEY)?)?;
+
+      let mut results = vec![];
+      for (index, point) in Self::create_sweep_points(&axes).into_iter().enumerate() {
+        infra.check_cancelled()?;
+        infra.check_budget()?;
+
+        let mut overrides = context.config_overrides().clone();
+        let mut parameters = vec![];
+
+        for (axis, value_index) in axes.iter().zip(point) {
+          let raw_value = axis.raw_values[value_index].clone();
+          Self::put_sweep_value(&mut overrides, &axis.key_name, &raw_value)?;
+          parameters.push((axis.key_name.clone(), raw_value));
+        }
+
+        infra.log(format!("Sweep run {}: {:?}", index, parameters).as_str())?;
+
+        let mut run_context = context.detached_copy()?;
+        run_context.set_config_overrides(overrides);
+
+        results.push(SweepRunResult {
+          index,
+          parameters,
+          outputs: Self::execute_and_collect_outputs(run_context, infra, pipeline)?,
+        });
+      }
+
+      context.put_concrete(SWEEP_RESULTS_KEY.key(), results);
+
+      Ok(())
+    }
+  );
+
+  /// Every grid entry has the form `key_name=value_1,value_2,...`
+  fn parse_sweep_grid(grid: &[Arc<str>]) -> Result<Vec<SweepAxis>, PipelinePartExecutionError> {
+    let mut axes = vec![];
+    for entry in grid {
+      let Some((key_name, raw_values)) = entry.split_once('=') else {
+        return Err(PipelinePartExecutionError::new_raw(format!("Invalid sweep grid entry {}", entry)));
+      };
+
+      let raw_values = raw_values
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(Arc::from)
+        .collect::<Vec<Arc<str>>>();
+
+      if raw_values.is_empty() {
+        return Err(PipelinePartExecutionError::new_raw(format!("No values for sweep key {}", key_name)));
+      }
+
+      axes.push(SweepAxis {
+        key_name: Arc::from(key_name.trim()),
+        raw_values,
+      });
+    }
+
+    Ok(axes)
+  }
+
+  fn create_sweep_points(axes: &[SweepAxis]) -> Vec<Vec<usize>> {
+    let mut points = vec![vec![]];
+    for axis in axes {
+      let mut new_points = vec![];
+      for point in &points {
+        for value_index in 0..axis.raw_values.len() {
+          let mut new_point = point.clone();
+          new_point.push(value_index);
+          new_points.push(new_point);
+        }
+      }
+
+      points = new_points;
+    }
+
+    points
+  }
+
+  fn put_sweep_value(overrides: &mut UserDataImpl, key_name: &str, raw_value: &str) -> Result<(), PipelinePartExecutionError> {
+    let Some(context_key) = find_context_key(key_name) else {
+      return Err(PipelinePartExecutionError::new_raw(format!("Unknown sweep key {}", key_name)));
+    };
+
+    let parse_error = || PipelinePartExecutionError::new_raw(format!("Failed to parse value {} of sweep key {}", raw_value, key_name));
+    let key = context_key.key();
+
+    match context_key.type_name().as_str() {
+      "f64" => overrides.put_any::<f64>(key, raw_value.parse().map_err(|_| parse_error())?),
+      "u32" => overrides.put_any::<u32>(key, raw_value.parse().map_err(|_| parse_error())?),
+      "u64" => overrides.put_any::<u64>(key, raw_value.parse().map_err(|_| parse_error())?),
+      "usize" => overrides.put_any::<usize>(key, raw_value.parse().map_err(|_| parse_error())?),
+      "bool" => overrides.put_any::<bool>(key, raw_value.parse().map_err(|_| parse_error())?),
+      "Arc<str>" => overrides.put_any::<Arc<str>>(key, Arc::from(raw_value)),
+      type_name => {
+        return Err(PipelinePartExecutionError::new_raw(format!(
+          "Sweep key {} has unsupported type {}",
+          key_name, type_name
+        )));
+      }
+    }
+
+    Ok(())
+  }
+}
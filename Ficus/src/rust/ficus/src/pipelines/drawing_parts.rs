@@ -1,4 +1,4 @@
-use super::{context::PipelineContext, errors::pipeline_errors::PipelinePartExecutionError, pipelines::PipelinePartFactory};
+use super::{context::PipelineContext, errors::pipeline_errors::PipelinePartExecutionError};
 use crate::{
   event_log::{
     core::{event::event::Event, event_log::EventLog, trace::trace::Trace},
@@ -10,7 +10,7 @@ use crate::{
     keys::context_keys::{
       ATTRIBUTE_KEY, COLORS_EVENT_LOG_KEY, COLORS_HOLDER_KEY, EVENT_LOG_KEY, EVENT_NAME_KEY, REGEX_KEY, TRACE_ACTIVITIES_KEY,
     },
-    pipeline_parts::PipelineParts,
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
   },
   utils::{
     colors::{Color, ColoredRectangle, ColorsEventLog, ColorsHolder},
@@ -21,15 +21,22 @@ use fancy_regex::Regex;
 use std::{collections::HashMap, sync::Arc};
 
 impl PipelineParts {
-  pipeline_part!(traces_diversity_diagram, |context: &mut PipelineContext, _, _| {
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    let colors_holder = context.concrete_mut(COLORS_HOLDER_KEY.key()).expect("Should be initialized");
-    let colors_log = Self::create_traces_diversity_colors_log(log, colors_holder, |e| e.name_pointer().clone());
+  pipeline_part!(
+    traces_diversity_diagram,
+    |metadata| metadata
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&COLORS_EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      let colors_holder = context.concrete_mut(COLORS_HOLDER_KEY.key()).expect("Should be initialized");
+      let colors_log = Self::create_traces_diversity_colors_log(log, colors_holder, |e| e.name_pointer().clone());
 
-    context.put_concrete(COLORS_EVENT_LOG_KEY.key(), colors_log);
+      context.put_concrete(COLORS_EVENT_LOG_KEY.key(), colors_log);
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 
   fn create_traces_diversity_colors_log(
     log: &XesEventLogImpl,
@@ -63,6 +70,11 @@ impl PipelineParts {
 
   pipeline_part!(
     draw_placement_of_event_by_name,
+    |metadata| metadata
+      .config(&EVENT_NAME_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&COLORS_EVENT_LOG_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let event_name = Self::get_user_data(config, &EVENT_NAME_KEY)?;
       Self::draw_events_placement(context, &|event| event.name() == event_name.as_ref())
@@ -110,6 +122,11 @@ impl PipelineParts {
 
   pipeline_part!(
     draw_placement_of_event_by_regex,
+    |metadata| metadata
+      .config(&REGEX_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&COLORS_EVENT_LOG_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let regex = Self::get_user_data(config, &REGEX_KEY)?;
       let regex = Regex::new(regex).ok().unwrap();
@@ -117,91 +134,112 @@ impl PipelineParts {
     }
   );
 
-  pipeline_part!(draw_full_activities_diagram, |context: &mut PipelineContext, _, _| {
-    let traces_activities = Self::get_user_data(context, &TRACE_ACTIVITIES_KEY)?;
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    let colors_holder = Self::get_user_data_mut(context, &COLORS_HOLDER_KEY)?;
-
-    let mut traces = vec![];
-    let mut mapping = HashMap::new();
-    mapping.insert(Arc::from(UNDEF_ACTIVITY_NAME), Color::black());
-
-    for (activities, trace) in traces_activities.iter().zip(log.traces().iter()) {
-      let mut colors_trace = vec![];
-      let trace_length = trace.borrow().events().len();
-
-      Self::execute_with_activities_instances(activities, trace_length, &mut |sub_trace| match sub_trace {
-        SubTraceKind::Attached(activity) => {
-          let color = colors_holder.get_or_create(activity.node().borrow().name());
-          let name = activity.node().borrow().name().clone();
-          if !mapping.contains_key(name.as_ref()) {
-            mapping.insert(name.clone(), color);
-          }
-
-          colors_trace.push(ColoredRectangle::new(name, *activity.start_pos() as f64, *activity.length() as f64));
-        }
-        SubTraceKind::Unattached(start_pos, length) => {
-          colors_trace.push(ColoredRectangle::new(
-            Arc::from(UNDEF_ACTIVITY_NAME.to_string()),
-            start_pos as f64,
-            length as f64,
-          ));
-        }
-      })?;
-
-      traces.push(colors_trace);
-    }
-
-    context.put_concrete(COLORS_EVENT_LOG_KEY.key(), ColorsEventLog { mapping, traces });
-
-    Ok(())
-  });
+  pipeline_part!(
+    draw_full_activities_diagram,
+    |metadata| metadata
+      .input(&EVENT_LOG_KEY)
+      .input(&TRACE_ACTIVITIES_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&COLORS_EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let traces_activities = Self::get_user_data(context, &TRACE_ACTIVITIES_KEY)?;
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      let colors_holder = Self::get_user_data_mut(context, &COLORS_HOLDER_KEY)?;
 
-  pipeline_part!(draw_short_activities_diagram, |context: &mut PipelineContext, _, _| {
-    let traces_activities = Self::get_user_data(context, &TRACE_ACTIVITIES_KEY)?;
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    let colors_holder = Self::get_user_data_mut(context, &COLORS_HOLDER_KEY)?;
+      let mut traces = vec![];
+      let mut mapping = HashMap::new();
+      mapping.insert(Arc::from(UNDEF_ACTIVITY_NAME), Color::black());
 
-    let mut traces = vec![];
-    let mut mapping = HashMap::new();
-    mapping.insert(Arc::<str>::from(UNDEF_ACTIVITY_NAME), Color::black());
+      for (activities, trace) in traces_activities.iter().zip(log.traces().iter()) {
+        let mut colors_trace = vec![];
+        let trace_length = trace.borrow().events().len();
 
-    for (activities, trace) in traces_activities.iter().zip(log.traces().iter()) {
-      let mut colors_trace = vec![];
-      let mut index = 0;
-      let trace_length = trace.borrow().events().len();
-      Self::execute_with_activities_instances(activities, trace_length, &mut |sub_trace| {
-        match sub_trace {
+        Self::execute_with_activities_instances(activities, trace_length, &mut |sub_trace| match sub_trace {
           SubTraceKind::Attached(activity) => {
             let color = colors_holder.get_or_create(activity.node().borrow().name());
-            let node = activity.node().borrow();
-            let name = node.name();
-
-            if !mapping.contains_key(name) {
+            let name = activity.node().borrow().name().clone();
+            if !mapping.contains_key(name.as_ref()) {
               mapping.insert(name.clone(), color);
             }
 
-            colors_trace.push(ColoredRectangle::new(name.clone(), index as f64, 1.));
+            colors_trace.push(ColoredRectangle::new(name, *activity.start_pos() as f64, *activity.length() as f64));
           }
-          SubTraceKind::Unattached(_, _) => {
-            let ptr = Arc::from(UNDEF_ACTIVITY_NAME.to_owned());
-            colors_trace.push(ColoredRectangle::new(ptr, index as f64, 1.));
+          SubTraceKind::Unattached(start_pos, length) => {
+            colors_trace.push(ColoredRectangle::new(
+              Arc::from(UNDEF_ACTIVITY_NAME.to_string()),
+              start_pos as f64,
+              length as f64,
+            ));
           }
-        }
+        })?;
+
+        traces.push(colors_trace);
+      }
 
-        index += 1;
-      })?;
+      context.put_concrete(COLORS_EVENT_LOG_KEY.key(), ColorsEventLog { mapping, traces });
 
-      traces.push(colors_trace);
+      Ok(())
     }
+  );
 
-    context.put_concrete(COLORS_EVENT_LOG_KEY.key(), ColorsEventLog { mapping, traces });
+  pipeline_part!(
+    draw_short_activities_diagram,
+    |metadata| metadata
+      .input(&EVENT_LOG_KEY)
+      .input(&TRACE_ACTIVITIES_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&COLORS_EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let traces_activities = Self::get_user_data(context, &TRACE_ACTIVITIES_KEY)?;
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      let colors_holder = Self::get_user_data_mut(context, &COLORS_HOLDER_KEY)?;
+
+      let mut traces = vec![];
+      let mut mapping = HashMap::new();
+      mapping.insert(Arc::<str>::from(UNDEF_ACTIVITY_NAME), Color::black());
+
+      for (activities, trace) in traces_activities.iter().zip(log.traces().iter()) {
+        let mut colors_trace = vec![];
+        let mut index = 0;
+        let trace_length = trace.borrow().events().len();
+        Self::execute_with_activities_instances(activities, trace_length, &mut |sub_trace| {
+          match sub_trace {
+            SubTraceKind::Attached(activity) => {
+              let color = colors_holder.get_or_create(activity.node().borrow().name());
+              let node = activity.node().borrow();
+              let name = node.name();
+
+              if !mapping.contains_key(name) {
+                mapping.insert(name.clone(), color);
+              }
+
+              colors_trace.push(ColoredRectangle::new(name.clone(), index as f64, 1.));
+            }
+            SubTraceKind::Unattached(_, _) => {
+              let ptr = Arc::from(UNDEF_ACTIVITY_NAME.to_owned());
+              colors_trace.push(ColoredRectangle::new(ptr, index as f64, 1.));
+            }
+          }
 
-    Ok(())
-  });
+          index += 1;
+        })?;
+
+        traces.push(colors_trace);
+      }
+
+      context.put_concrete(COLORS_EVENT_LOG_KEY.key(), ColorsEventLog { mapping, traces });
+
+      Ok(())
+    }
+  );
 
   pipeline_part!(
     traces_diversity_diagram_by_attribute,
+    |metadata| metadata
+      .config(&ATTRIBUTE_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .output(&COLORS_EVENT_LOG_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
       let colors_holder = context.concrete_mut(COLORS_HOLDER_KEY.key()).expect("Should be initialized");
@@ -0,0 +1,48 @@
+use crate::{
+  features::analysis::drift::{
+    detection::{ConceptDriftDetectionParams, detect_concept_drift},
+    distributions::DriftFeature,
+    statistics::DriftStatisticalTest,
+  },
+  pipeline_part,
+  pipelines::{
+    context::PipelineContext,
+    keys::context_keys::{
+      CONCEPT_DRIFT_CHANGE_POINTS_KEY, DRIFT_FEATURE_KEY, DRIFT_STATISTICAL_TEST_KEY, DRIFT_WINDOW_SIZE_KEY, EVENT_LOG_KEY,
+      SIGNIFICANCE_LEVEL_KEY,
+    },
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
+  },
+  utils::user_data::user_data::{UserData, UserDataImpl},
+};
+
+const DEFAULT_DRIFT_WINDOW_SIZE: u32 = 100;
+const DEFAULT_SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+impl PipelineParts {
+  pipeline_part!(
+    detect_concept_drift,
+    |metadata| metadata
+      .config_with_default(&DRIFT_FEATURE_KEY, "DfgRelations")
+      .config_with_default(&DRIFT_STATISTICAL_TEST_KEY, "ChiSquare")
+      .config_with_default(&DRIFT_WINDOW_SIZE_KEY, "100")
+      .config_with_default(&SIGNIFICANCE_LEVEL_KEY, "0.05")
+      .input(&EVENT_LOG_KEY)
+      .output(&CONCEPT_DRIFT_CHANGE_POINTS_KEY),
+    |context: &mut PipelineContext, _, config: &UserDataImpl| {
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+
+      let feature = *Self::get_user_data(config, &DRIFT_FEATURE_KEY).unwrap_or(&DriftFeature::DfgRelations);
+      let test = *Self::get_user_data(config, &DRIFT_STATISTICAL_TEST_KEY).unwrap_or(&DriftStatisticalTest::ChiSquare);
+      let window_size = *Self::get_user_data(config, &DRIFT_WINDOW_SIZE_KEY).unwrap_or(&DEFAULT_DRIFT_WINDOW_SIZE);
+      let significance = *Self::get_user_data(config, &SIGNIFICANCE_LEVEL_KEY).unwrap_or(&DEFAULT_SIGNIFICANCE_LEVEL);
+
+      let params = ConceptDriftDetectionParams::new(feature, test, window_size as usize, significance);
+      let change_points = detect_concept_drift(log, &params);
+
+      context.put_concrete(CONCEPT_DRIFT_CHANGE_POINTS_KEY.key(), change_points);
+
+      Ok(())
+    }
+  );
+}
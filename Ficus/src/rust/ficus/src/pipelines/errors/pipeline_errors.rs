@@ -1,3 +1,4 @@
+use crate::utils::execution_budget::ExecutionBudgetViolation;
 use std::{
   error::Error,
   fmt::{Debug, Display, Formatter},
@@ -8,6 +9,8 @@ pub enum PipelinePartExecutionError {
   Raw(RawPartExecutionError),
   MissingContext(MissingContextError),
   MissingRequiredMetadata(String),
+  Cancelled,
+  BudgetExceeded(ExecutionBudgetViolation),
 }
 
 impl Display for PipelinePartExecutionError {
@@ -18,6 +21,8 @@ impl Display for PipelinePartExecutionError {
       PipelinePartExecutionError::MissingRequiredMetadata(missing_metadata) => {
         f.write_str(format!("Missing required metadata {} in the context", missing_metadata).as_str())
       }
+      PipelinePartExecutionError::Cancelled => f.write_str("Pipeline execution was cancelled"),
+      PipelinePartExecutionError::BudgetExceeded(violation) => Display::fmt(violation, f),
     }
   }
 }
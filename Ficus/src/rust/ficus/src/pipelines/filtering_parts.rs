@@ -2,10 +2,7 @@ use std::collections::HashSet;
 
 use fancy_regex::Regex;
 
-use super::{
-  errors::pipeline_errors::{PipelinePartExecutionError, RawPartExecutionError},
-  pipelines::PipelinePartFactory,
-};
+use super::errors::pipeline_errors::{PipelinePartExecutionError, RawPartExecutionError};
 use crate::{
   event_log::{
     core::{event_log::EventLog, trace::trace::Trace},
@@ -19,23 +16,29 @@ use crate::{
   pipelines::{
     context::PipelineContext,
     keys::context_keys::{EVENT_LOG_KEY, EVENT_NAME_KEY, EVENTS_COUNT_KEY, REGEX_KEY},
-    pipeline_parts::PipelineParts,
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
   },
   utils::user_data::user_data::UserDataImpl,
 };
 
 impl PipelineParts {
-  pipeline_part!(filter_events_by_name, |context: &mut PipelineContext, _, config: &UserDataImpl| {
-    let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
-    let event_name = Self::get_user_data(config, &EVENT_NAME_KEY)?;
-    filter_log_by_name(log, event_name);
+  pipeline_part!(
+    filter_events_by_name,
+    |metadata| metadata.config(&EVENT_NAME_KEY).input(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, config: &UserDataImpl| {
+      let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
+      let event_name = Self::get_user_data(config, &EVENT_NAME_KEY)?;
+      filter_log_by_name(log, event_name);
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 
-  pipeline_part!(filter_events_by_regex, |context: &mut PipelineContext, _, config: &UserDataImpl| {
-    Self::filter_log_by_regex_internal(context, config, filter_log_by_regex)
-  });
+  pipeline_part!(
+    filter_events_by_regex,
+    |metadata| metadata.config(&REGEX_KEY).input(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, config: &UserDataImpl| { Self::filter_log_by_regex_internal(context, config, filter_log_by_regex) }
+  );
 
   fn filter_log_by_regex_internal(
     context: &mut PipelineContext,
@@ -54,25 +57,34 @@ impl PipelineParts {
     }
   }
 
-  pipeline_part!(remain_events_by_regex, |context: &mut PipelineContext, _, config: &UserDataImpl| {
-    Self::filter_log_by_regex_internal(context, config, remain_events_in_event_log)
-  });
+  pipeline_part!(
+    remain_events_by_regex,
+    |metadata| metadata.config(&REGEX_KEY).input(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, config: &UserDataImpl| {
+      Self::filter_log_by_regex_internal(context, config, remain_events_in_event_log)
+    }
+  );
 
-  pipeline_part!(filter_log_by_variants, |context: &mut PipelineContext, _, _| {
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    let groups_indices: HashSet<usize> = get_traces_groups_indices(log)
-      .into_iter()
-      .map(|group| *(group.first().unwrap()))
-      .collect();
+  pipeline_part!(
+    filter_log_by_variants,
+    |metadata| metadata.input(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      let groups_indices: HashSet<usize> = get_traces_groups_indices(log)
+        .into_iter()
+        .map(|group| *(group.first().unwrap()))
+        .collect();
 
-    let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
-    log.filter_traces(&|_, index| !groups_indices.contains(index));
+      let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
+      log.filter_traces(&|_, index| !groups_indices.contains(index));
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 
   pipeline_part!(
     filter_traces_by_events_count,
+    |metadata| metadata.config(&EVENTS_COUNT_KEY).input(&EVENT_LOG_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
       let min_events_count = *Self::get_user_data(config, &EVENTS_COUNT_KEY)? as usize;
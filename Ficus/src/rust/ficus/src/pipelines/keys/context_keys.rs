@@ -2,6 +2,9 @@ use crate::{
   event_log::xes::xes_event_log::XesEventLogImpl,
   features::{
     analysis::{
+      drift::{
+        detection::ConceptDriftChangePoint, distributions::DriftFeature, online::OnlineDriftChangePoint, statistics::DriftStatisticalTest,
+      },
       log_info::event_log_info::OfflineEventLogInfo,
       patterns::{
         activity_instances::{ActivityInTraceFilterKind, ActivityInTraceInfo, ActivityNarrowingKind, AdjustingMode},
@@ -14,13 +17,17 @@ use crate::{
     clustering::{activities::activities_params::ActivityRepresentationSource, traces::traces_params::TracesRepresentationSource},
     discovery::{
       ecfg::models::RootSequenceKind,
+      fuzzy::proximity_correlation::PrecomputedProximityCorrelation,
       ocel::graph_annotation::OcelAnnotation,
       petri_net::{annotations::TimeAnnotationKind, petri_net::DefaultPetriNet},
+      relations::triangle_relation::PrecomputedTriangleRelation,
       timeline::{discovery::LogTimelineDiagram, software_data::extraction_config::SoftwareDataExtractionConfig},
     },
+    streaming::conformance::core::ConformanceReport,
   },
   pipelines::{
     activities_parts::{ActivitiesLogsSourceDto, UndefActivityHandlingStrategyDto},
+    control_flow_parts::{ComparisonOperator, ConditionMetric, SweepRunResult},
     multithreading::FeatureCountKindDto,
     patterns_parts::PatternsKindDto,
     pipelines::Pipeline,
@@ -138,6 +145,23 @@ pub const SOFTWARE_DATA_EXTRACTION_CONFIG: &str = "software_data_extraction_conf
 pub const DISCOVER_ACTIVITY_INSTANCES_STRICT: &str = "discover_activity_instances_strict";
 pub const PUT_NOISE_EVENTS_IN_ONE_CLUSTER: &str = "put_noise_events_in_one_cluster";
 pub const OCEL_ANNOTATION: &str = "ocel_annotation";
+pub const CONDITION_METRIC: &str = "condition_metric";
+pub const CONDITION_OPERATOR: &str = "condition_operator";
+pub const CONDITION_THRESHOLD: &str = "condition_threshold";
+pub const ELSE_PIPELINE: &str = "else_pipeline";
+pub const FOR_EACH_COLLECTION: &str = "for_each_collection";
+pub const SWEEP_GRID: &str = "sweep_grid";
+pub const SWEEP_RESULTS: &str = "sweep_results";
+pub const CONFORMANCE_REPORT: &str = "conformance_report";
+pub const DRIFT_FEATURE: &str = "drift_feature";
+pub const DRIFT_STATISTICAL_TEST: &str = "drift_statistical_test";
+pub const DRIFT_WINDOW_SIZE: &str = "drift_window_size";
+pub const SIGNIFICANCE_LEVEL: &str = "significance_level";
+pub const CONCEPT_DRIFT_CHANGE_POINTS: &str = "concept_drift_change_points";
+pub const ONLINE_CONCEPT_DRIFT_CHANGE_POINTS: &str = "online_concept_drift_change_points";
+pub const TRIANGLE_RELATION: &str = "triangle_relation";
+pub const PROXIMITY_CORRELATION: &str = "proximity_correlation";
+pub const CLOSED_CASES_EVENT_LOG: &str = "closed_cases_event_log";
 
 #[macro_export]
 macro_rules! context_key {
@@ -248,6 +272,23 @@ context_key! { SOFTWARE_DATA_EXTRACTION_CONFIG, SoftwareDataExtractionConfig }
 context_key! { DISCOVER_ACTIVITY_INSTANCES_STRICT, bool }
 context_key! { PUT_NOISE_EVENTS_IN_ONE_CLUSTER, bool }
 context_key! { OCEL_ANNOTATION, OcelAnnotation }
+context_key! { CONDITION_METRIC, ConditionMetric }
+context_key! { CONDITION_OPERATOR, ComparisonOperator }
+context_key! { CONDITION_THRESHOLD, f64 }
+context_key! { ELSE_PIPELINE, Pipeline }
+context_key! { FOR_EACH_COLLECTION, Arc<str> }
+context_key! { SWEEP_GRID, Vec<Arc<str>> }
+context_key! { SWEEP_RESULTS, Vec<SweepRunResult> }
+context_key! { CONFORMANCE_REPORT, ConformanceReport }
+context_key! { DRIFT_FEATURE, DriftFeature }
+context_key! { DRIFT_STATISTICAL_TEST, DriftStatisticalTest }
+context_key! { DRIFT_WINDOW_SIZE, u32 }
+context_key! { SIGNIFICANCE_LEVEL, f64 }
+context_key! { CONCEPT_DRIFT_CHANGE_POINTS, Vec<ConceptDriftChangePoint> }
+context_key! { ONLINE_CONCEPT_DRIFT_CHANGE_POINTS, Vec<OnlineDriftChangePoint> }
+context_key! { TRIANGLE_RELATION, PrecomputedTriangleRelation }
+context_key! { PROXIMITY_CORRELATION, PrecomputedProximityCorrelation }
+context_key! { CLOSED_CASES_EVENT_LOG, XesEventLogImpl }
 
 pub fn find_context_key(name: &str) -> Option<&dyn ContextKey> {
   match name {
@@ -342,6 +383,23 @@ pub fn find_context_key(name: &str) -> Option<&dyn ContextKey> {
     DISCOVER_ACTIVITY_INSTANCES_STRICT => Some(DISCOVER_ACTIVITY_INSTANCES_STRICT_KEY.deref() as &dyn ContextKey),
     PUT_NOISE_EVENTS_IN_ONE_CLUSTER => Some(PUT_NOISE_EVENTS_IN_ONE_CLUSTER_KEY.deref() as &dyn ContextKey),
     OCEL_ANNOTATION => Some(OCEL_ANNOTATION_KEY.deref() as &dyn ContextKey),
+    CONDITION_METRIC => Some(CONDITION_METRIC_KEY.deref() as &dyn ContextKey),
+    CONDITION_OPERATOR => Some(CONDITION_OPERATOR_KEY.deref() as &dyn ContextKey),
+    CONDITION_THRESHOLD => Some(CONDITION_THRESHOLD_KEY.deref() as &dyn ContextKey),
+    ELSE_PIPELINE => Some(ELSE_PIPELINE_KEY.deref() as &dyn ContextKey),
+    FOR_EACH_COLLECTION => Some(FOR_EACH_COLLECTION_KEY.deref() as &dyn ContextKey),
+    SWEEP_GRID => Some(SWEEP_GRID_KEY.deref() as &dyn ContextKey),
+    SWEEP_RESULTS => Some(SWEEP_RESULTS_KEY.deref() as &dyn ContextKey),
+    CONFORMANCE_REPORT => Some(CONFORMANCE_REPORT_KEY.deref() as &dyn ContextKey),
+    DRIFT_FEATURE => Some(DRIFT_FEATURE_KEY.deref() as &dyn ContextKey),
+    DRIFT_STATISTICAL_TEST => Some(DRIFT_STATISTICAL_TEST_KEY.deref() as &dyn ContextKey),
+    DRIFT_WINDOW_SIZE => Some(DRIFT_WINDOW_SIZE_KEY.deref() as &dyn ContextKey),
+    SIGNIFICANCE_LEVEL => Some(SIGNIFICANCE_LEVEL_KEY.deref() as &dyn ContextKey),
+    CONCEPT_DRIFT_CHANGE_POINTS => Some(CONCEPT_DRIFT_CHANGE_POINTS_KEY.deref() as &dyn ContextKey),
+    ONLINE_CONCEPT_DRIFT_CHANGE_POINTS => Some(ONLINE_CONCEPT_DRIFT_CHANGE_POINTS_KEY.deref() as &dyn ContextKey),
+    TRIANGLE_RELATION => Some(TRIANGLE_RELATION_KEY.deref() as &dyn ContextKey),
+    PROXIMITY_CORRELATION => Some(PROXIMITY_CORRELATION_KEY.deref() as &dyn ContextKey),
+    CLOSED_CASES_EVENT_LOG => Some(CLOSED_CASES_EVENT_LOG_KEY.deref() as &dyn ContextKey),
     _ => None,
   }
 }
@@ -4,16 +4,24 @@ pub mod annotations_parts;
 pub mod cases;
 pub mod clustering;
 pub mod context;
+pub mod control_flow_parts;
 pub mod discovery_parts;
 pub mod drawing_parts;
+pub mod drift_parts;
 pub mod errors;
 pub mod filtering_parts;
 pub mod keys;
 pub mod multithreading;
 pub mod mutations_parts;
+pub mod parallel;
+pub mod parts_metadata;
 pub mod parts_names;
 pub mod patterns_parts;
 pub mod pipeline_parts;
 pub mod pipelines;
+pub mod results_cache;
+pub mod scheduler;
+pub mod sendable_values;
 pub mod util_parts;
+pub mod validation;
 pub mod xes_parts;
@@ -28,12 +28,13 @@ use crate::{
     context::{PipelineContext, PipelineInfrastructure},
     errors::pipeline_errors::{PipelinePartExecutionError, RawPartExecutionError},
     keys::context_keys::{
-      DISCOVER_EVENTS_GROUPS_IN_EACH_TRACE_KEY, EVENT_LOG_KEY, GRAPH_KEY, LABELED_LOG_TRACES_DATASET_KEY, LOG_THREADS_DIAGRAM_KEY,
-      MIN_EVENTS_IN_CLUSTERS_COUNT_KEY, PIPELINE_KEY, PUT_NOISE_EVENTS_IN_ONE_CLUSTER_KEY, REGEXES_KEY,
-      SOFTWARE_DATA_EXTRACTION_CONFIG_KEY, THREAD_ATTRIBUTE_KEY, TIME_ATTRIBUTE_KEY, TIME_DELTA_KEY, TOLERANCE_KEY,
+      COLORS_HOLDER_KEY, DISCOVER_EVENTS_GROUPS_IN_EACH_TRACE_KEY, DISTANCE_KEY, EVENT_CLASS_REGEX_KEY, EVENT_LOG_KEY,
+      FEATURE_COUNT_KIND_KEY, GRAPH_KEY, LABELED_LOG_TRACES_DATASET_KEY, LOG_THREADS_DIAGRAM_KEY, MIN_EVENTS_IN_CLUSTERS_COUNT_KEY,
+      PERCENT_FROM_MAX_VALUE_KEY, PIPELINE_KEY, PUT_NOISE_EVENTS_IN_ONE_CLUSTER_KEY, REGEXES_KEY, SOFTWARE_DATA_EXTRACTION_CONFIG_KEY,
+      THREAD_ATTRIBUTE_KEY, TIME_ATTRIBUTE_KEY, TIME_DELTA_KEY, TOLERANCE_KEY, TRACES_REPR_SOURCE_KEY,
     },
-    pipeline_parts::PipelineParts,
-    pipelines::{PipelinePart, PipelinePartFactory},
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
+    pipelines::PipelinePart,
   },
   utils::{
     display_name::DISPLAY_NAME_KEY,
@@ -66,6 +67,13 @@ impl FromStr for FeatureCountKindDto {
 impl PipelineParts {
   pipeline_part!(
     discover_log_timeline_diagram,
+    |metadata| metadata
+      .config(&THREAD_ATTRIBUTE_KEY)
+      .optional_config(&TIME_ATTRIBUTE_KEY)
+      .optional_config(&TIME_DELTA_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&SOFTWARE_DATA_EXTRACTION_CONFIG_KEY)
+      .output(&LOG_THREADS_DIAGRAM_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
       let thread_attribute = Self::get_user_data(config, &THREAD_ATTRIBUTE_KEY)?;
@@ -96,16 +104,35 @@ impl PipelineParts {
       .map_err(|message| PipelinePartExecutionError::Raw(RawPartExecutionError::new(message)))
   }
 
-  pipeline_part!(create_threads_log, |context: &mut PipelineContext, _, config: &UserDataImpl| {
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    let thread_attribute = Self::get_user_data(config, &THREAD_ATTRIBUTE_KEY)?;
-    context.put_concrete(EVENT_LOG_KEY.key(), create_threads_log_by_attribute(log, thread_attribute));
+  pipeline_part!(
+    create_threads_log,
+    |metadata| metadata.config(&THREAD_ATTRIBUTE_KEY).input(&EVENT_LOG_KEY).output(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, config: &UserDataImpl| {
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      let thread_attribute = Self::get_user_data(config, &THREAD_ATTRIBUTE_KEY)?;
+      context.put_concrete(EVENT_LOG_KEY.key(), create_threads_log_by_attribute(log, thread_attribute));
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 
   pipeline_part!(
     abstract_timeline_diagram,
+    |metadata| metadata
+      .config(&DISTANCE_KEY)
+      .config(&FEATURE_COUNT_KIND_KEY)
+      .config(&MIN_EVENTS_IN_CLUSTERS_COUNT_KEY)
+      .config(&PUT_NOISE_EVENTS_IN_ONE_CLUSTER_KEY)
+      .config(&TOLERANCE_KEY)
+      .config(&TRACES_REPR_SOURCE_KEY)
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .optional_config(&PERCENT_FROM_MAX_VALUE_KEY)
+      .optional_config(&PIPELINE_KEY)
+      .input(&EVENT_LOG_KEY)
+      .input(&LOG_THREADS_DIAGRAM_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .optional_input(&SOFTWARE_DATA_EXTRACTION_CONFIG_KEY)
+      .output(&LABELED_LOG_TRACES_DATASET_KEY),
     |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
       let (thread_attribute, time_attribute) = Self::extract_thread_and_time_attribute(context)?;
 
@@ -172,6 +199,23 @@ impl PipelineParts {
 
   pipeline_part!(
     abstract_multithreaded_events_groups,
+    |metadata| metadata
+      .config(&DISTANCE_KEY)
+      .config(&FEATURE_COUNT_KIND_KEY)
+      .config(&MIN_EVENTS_IN_CLUSTERS_COUNT_KEY)
+      .config(&PUT_NOISE_EVENTS_IN_ONE_CLUSTER_KEY)
+      .config(&THREAD_ATTRIBUTE_KEY)
+      .config(&TOLERANCE_KEY)
+      .config(&TRACES_REPR_SOURCE_KEY)
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .optional_config(&PERCENT_FROM_MAX_VALUE_KEY)
+      .optional_config(&PIPELINE_KEY)
+      .optional_config(&REGEXES_KEY)
+      .optional_config(&TIME_ATTRIBUTE_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&COLORS_HOLDER_KEY)
+      .optional_input(&SOFTWARE_DATA_EXTRACTION_CONFIG_KEY)
+      .output(&LABELED_LOG_TRACES_DATASET_KEY),
     |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
       let thread_attribute = Self::get_user_data(config, &THREAD_ATTRIBUTE_KEY)?.to_owned();
       let time_attribute = Self::get_user_data(config, &TIME_ATTRIBUTE_KEY).ok().cloned();
@@ -214,6 +258,13 @@ impl PipelineParts {
 
   pipeline_part!(
     discover_traces_timeline_diagram,
+    |metadata| metadata
+      .config(&DISCOVER_EVENTS_GROUPS_IN_EACH_TRACE_KEY)
+      .optional_config(&TIME_ATTRIBUTE_KEY)
+      .optional_config(&TIME_DELTA_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&SOFTWARE_DATA_EXTRACTION_CONFIG_KEY)
+      .output(&LOG_THREADS_DIAGRAM_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let time_attribute = Self::get_user_data(config, &TIME_ATTRIBUTE_KEY).ok();
       let event_group_delta = Self::get_user_data(config, &TIME_DELTA_KEY).ok();
@@ -240,6 +291,11 @@ impl PipelineParts {
 
   pipeline_part!(
     prepare_software_event_log,
+    |metadata| metadata
+      .optional_config(&TIME_ATTRIBUTE_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&SOFTWARE_DATA_EXTRACTION_CONFIG_KEY)
+      .output(&EVENT_LOG_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
       let software_data_extraction_config = Self::get_software_data_extraction_config(context);
@@ -256,38 +312,42 @@ impl PipelineParts {
     }
   );
 
-  pipeline_part!(shorten_allocation_type, |context: &mut PipelineContext, _, _| {
-    let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
-    let software_data_extraction_config = Self::get_software_data_extraction_config(context);
-    if let Some(config) = software_data_extraction_config.allocation() {
-      let alloc_regex = config.event_class_regex();
-      let alloc_regex = match Regex::new(alloc_regex) {
-        Ok(regex) => regex,
-        Err(err) => {
-          return Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(format!(
-            "Failed to create regex from {}, error: {}",
-            alloc_regex, err
-          ))));
-        }
-      };
+  pipeline_part!(
+    shorten_allocation_type,
+    |metadata| metadata.input(&EVENT_LOG_KEY).optional_input(&SOFTWARE_DATA_EXTRACTION_CONFIG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
+      let software_data_extraction_config = Self::get_software_data_extraction_config(context);
+      if let Some(config) = software_data_extraction_config.allocation() {
+        let alloc_regex = config.event_class_regex();
+        let alloc_regex = match Regex::new(alloc_regex) {
+          Ok(regex) => regex,
+          Err(err) => {
+            return Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(format!(
+              "Failed to create regex from {}, error: {}",
+              alloc_regex, err
+            ))));
+          }
+        };
 
-      for trace in log.traces() {
-        let trace = trace.borrow_mut();
-        for event in trace.events() {
-          if alloc_regex.is_match(event.borrow().name()).unwrap_or(false) {
-            let mut event = event.borrow_mut();
-            if let Some(map) = event.payload_map_mut()
-              && let Some(type_name) = map.get_mut(config.info().type_name_attr().as_ref())
-            {
-              *type_name = EventPayloadValue::String(Arc::from(Self::shorten_type_or_method_name(type_name.to_string_repr().as_ref())));
+        for trace in log.traces() {
+          let trace = trace.borrow_mut();
+          for event in trace.events() {
+            if alloc_regex.is_match(event.borrow().name()).unwrap_or(false) {
+              let mut event = event.borrow_mut();
+              if let Some(map) = event.payload_map_mut()
+                && let Some(type_name) = map.get_mut(config.info().type_name_attr().as_ref())
+              {
+                *type_name = EventPayloadValue::String(Arc::from(Self::shorten_type_or_method_name(type_name.to_string_repr().as_ref())));
+              }
             }
           }
         }
       }
-    }
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 
   fn shorten_type_or_method_name(name: &str) -> String {
     let mut result = String::new();
@@ -334,57 +394,61 @@ impl PipelineParts {
     result
   }
 
-  pipeline_part!(shorten_method_names, |context: &mut PipelineContext, _, _| {
-    let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
-
-    let methods_id_factories: Vec<&dyn Fn(&Arc<str>, &Arc<str>, &Arc<str>) -> Arc<str>> = vec![
-      &|_, name, _| name.to_owned(),
-      &|_, name, signature| Arc::from(name.as_ref().to_owned() + signature.as_ref()),
-      &|namespace, name, _| Arc::from(namespace.to_string() + name),
-    ];
-
-    let configs = Self::create_processed_method_extraction_configs(context);
-    if configs.is_empty() {
-      return Ok(());
-    }
+  pipeline_part!(
+    shorten_method_names,
+    |metadata| metadata.input(&EVENT_LOG_KEY).optional_input(&SOFTWARE_DATA_EXTRACTION_CONFIG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
+
+      let methods_id_factories: Vec<&dyn Fn(&Arc<str>, &Arc<str>, &Arc<str>) -> Arc<str>> = vec![
+        &|_, name, _| name.to_owned(),
+        &|_, name, signature| Arc::from(name.as_ref().to_owned() + signature.as_ref()),
+        &|namespace, name, _| Arc::from(namespace.to_string() + name),
+      ];
+
+      let configs = Self::create_processed_method_extraction_configs(context);
+      if configs.is_empty() {
+        return Ok(());
+      }
 
-    for config in &configs {
-      for method_id_factory in &methods_id_factories {
-        if Self::check_if_can_use_method_id(log, config, method_id_factory) {
-          for trace in log.traces() {
-            let trace = trace.borrow_mut();
-            for event in trace.events() {
-              let mut event = event.borrow_mut();
-              let name = if let Some(payload) = event.payload_map() {
-                if let Some((namespace, name, signature)) = Self::extract_method_name_parts(payload, config) {
-                  method_id_factory(&namespace, &name, &signature)
+      for config in &configs {
+        for method_id_factory in &methods_id_factories {
+          if Self::check_if_can_use_method_id(log, config, method_id_factory) {
+            for trace in log.traces() {
+              let trace = trace.borrow_mut();
+              for event in trace.events() {
+                let mut event = event.borrow_mut();
+                let name = if let Some(payload) = event.payload_map() {
+                  if let Some((namespace, name, signature)) = Self::extract_method_name_parts(payload, config) {
+                    method_id_factory(&namespace, &name, &signature)
+                  } else {
+                    continue;
+                  }
                 } else {
                   continue;
-                }
-              } else {
-                continue;
-              };
+                };
 
-              event.set_name(name);
+                event.set_name(name);
+              }
             }
-          }
 
-          return Ok(());
+            return Ok(());
+          }
         }
-      }
 
-      for trace in log.traces() {
-        let trace = trace.borrow_mut();
-        for event in trace.events() {
-          if config.event_regex.is_match(event.borrow().name()).unwrap_or(false) {
-            Self::shorten_method_name(config, &mut event.borrow_mut());
+        for trace in log.traces() {
+          let trace = trace.borrow_mut();
+          for event in trace.events() {
+            if config.event_regex.is_match(event.borrow().name()).unwrap_or(false) {
+              Self::shorten_method_name(config, &mut event.borrow_mut());
+            }
           }
         }
       }
-    }
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 
   fn create_processed_method_extraction_configs(context: &PipelineContext) -> Vec<ProcessedMethodStartEndConfig> {
     let software_data_extraction_config = Self::get_software_data_extraction_config(context);
@@ -489,42 +553,48 @@ impl PipelineParts {
     true
   }
 
-  pipeline_part!(set_methods_display_name, |context: &mut PipelineContext, _, _| {
-    let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
-    let configs = Self::create_processed_method_extraction_configs(context);
-
-    for trace in log.traces() {
-      let trace = trace.borrow();
-      for event in trace.events() {
-        let mut display_name = None;
-        if let Some(payload) = event.borrow().payload_map() {
-          for config in &configs {
-            if config.event_regex.is_match(event.borrow().name()).unwrap_or(false)
-              && let Some((_, name, _)) = Self::extract_method_name_parts(payload, config)
-            {
-              display_name = Some(match config.prefix.as_ref() {
-                None => name,
-                Some(prefix) => Arc::from(prefix.to_string() + name.as_ref()),
-              });
+  pipeline_part!(
+    set_methods_display_name,
+    |metadata| metadata.input(&EVENT_LOG_KEY).optional_input(&SOFTWARE_DATA_EXTRACTION_CONFIG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
+      let configs = Self::create_processed_method_extraction_configs(context);
+
+      for trace in log.traces() {
+        let trace = trace.borrow();
+        for event in trace.events() {
+          let mut display_name = None;
+          if let Some(payload) = event.borrow().payload_map() {
+            for config in &configs {
+              if config.event_regex.is_match(event.borrow().name()).unwrap_or(false)
+                && let Some((_, name, _)) = Self::extract_method_name_parts(payload, config)
+              {
+                display_name = Some(match config.prefix.as_ref() {
+                  None => name,
+                  Some(prefix) => Arc::from(prefix.to_string() + name.as_ref()),
+                });
+              }
             }
           }
-        }
 
-        if let Some(display_name) = display_name {
-          event
-            .borrow_mut()
-            .user_data_mut()
-            .put_concrete(DISPLAY_NAME_KEY.key(), display_name);
+          if let Some(display_name) = display_name {
+            event
+              .borrow_mut()
+              .user_data_mut()
+              .put_concrete(DISPLAY_NAME_KEY.key(), display_name);
+          }
         }
       }
-    }
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 
-  pipeline_part!(remain_only_method_start_events, |context: &mut PipelineContext, _, _| {
-    Self::remain_only_method_start_or_end_events(context, true)
-  });
+  pipeline_part!(
+    remain_only_method_start_events,
+    |metadata| metadata.input(&EVENT_LOG_KEY).optional_input(&SOFTWARE_DATA_EXTRACTION_CONFIG_KEY),
+    |context: &mut PipelineContext, _, _| { Self::remain_only_method_start_or_end_events(context, true) }
+  );
 
   fn remain_only_method_start_or_end_events(
     context: &PipelineContext,
@@ -544,12 +614,19 @@ impl PipelineParts {
     Ok(())
   }
 
-  pipeline_part!(remain_only_method_end_events, |context: &mut PipelineContext, _, _| {
-    Self::remain_only_method_start_or_end_events(context, false)
-  });
+  pipeline_part!(
+    remain_only_method_end_events,
+    |metadata| metadata.input(&EVENT_LOG_KEY).optional_input(&SOFTWARE_DATA_EXTRACTION_CONFIG_KEY),
+    |context: &mut PipelineContext, _, _| { Self::remain_only_method_start_or_end_events(context, false) }
+  );
 
   pipeline_part!(
     discover_multithreaded_dfg,
+    |metadata| metadata
+      .config(&THREAD_ATTRIBUTE_KEY)
+      .optional_config(&REGEXES_KEY)
+      .input(&EVENT_LOG_KEY)
+      .output(&GRAPH_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
       let thread_attribute = Self::get_user_data(config, &THREAD_ATTRIBUTE_KEY)?;
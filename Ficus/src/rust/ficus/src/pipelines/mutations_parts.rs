@@ -5,8 +5,7 @@ use crate::{
     context::PipelineContext,
     errors::pipeline_errors::PipelinePartExecutionError,
     keys::context_keys::{ATTRIBUTES_KEY, EVENT_LOG_KEY},
-    pipeline_parts::PipelineParts,
-    pipelines::PipelinePartFactory,
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
   },
   utils::user_data::user_data::UserDataImpl,
 };
@@ -14,6 +13,7 @@ use crate::{
 impl PipelineParts {
   pipeline_part!(
     add_artificial_start_end_events,
+    |metadata| metadata.optional_config(&ATTRIBUTES_KEY).input(&EVENT_LOG_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| { Self::create_add_start_end_events_internal(context, config, true, true) }
   );
 
@@ -36,16 +36,19 @@ impl PipelineParts {
 
   pipeline_part!(
     add_artificial_start_events,
+    |metadata| metadata.optional_config(&ATTRIBUTES_KEY).input(&EVENT_LOG_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| { Self::create_add_start_end_events_internal(context, config, true, false) }
   );
 
   pipeline_part!(
     add_artificial_end_events,
+    |metadata| metadata.optional_config(&ATTRIBUTES_KEY).input(&EVENT_LOG_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| { Self::create_add_start_end_events_internal(context, config, false, true) }
   );
 
   pipeline_part!(
     append_attributes_to_name,
+    |metadata| metadata.config(&ATTRIBUTES_KEY).input(&EVENT_LOG_KEY),
     |context: &mut PipelineContext, _, config: &UserDataImpl| {
       let log = Self::get_user_data_mut(context, &EVENT_LOG_KEY)?;
       let attributes = Self::get_user_data(config, &ATTRIBUTES_KEY)?;
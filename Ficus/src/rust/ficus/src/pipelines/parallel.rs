@@ -0,0 +1,150 @@
+use crate::{
+  pipelines::{
+    context::{PipelineContext, PipelineInfrastructure},
+    errors::pipeline_errors::PipelinePartExecutionError,
+    pipeline_parts::PipelineParts,
+    pipelines::{Pipeline, PipelinePart},
+    sendable_values::copy_context_values,
+  },
+  utils::{
+    allocations_tracking::AllocationsCounter,
+    user_data::user_data::{UserData, UserDataImpl},
+  },
+};
+use std::{collections::HashMap, thread};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParallelOutputsMergePolicy {
+  /// Outputs of the later branches overwrite outputs of the earlier ones
+  #[default]
+  LastBranchWins,
+  /// Execution fails if several branches put the same key
+  FailOnConflict,
+}
+
+struct BranchSnapshot {
+  user_data: UserDataImpl,
+  config_overrides: UserDataImpl,
+  addresses: HashMap<u64, usize>,
+}
+
+/// Pipelines and contexts of the branches are only touched by the thread which executes the branch,
+/// while the parent thread waits for all branches to finish.
+struct SendableBranch<'a> {
+  pipeline: &'a Pipeline,
+  snapshot: BranchSnapshot,
+}
+
+unsafe impl Send for SendableBranch<'_> {}
+
+pub(super) fn execute_parallel(
+  branches: &[Pipeline],
+  merge_policy: ParallelOutputsMergePolicy,
+  context: &mut PipelineContext,
+  infra: &PipelineInfrastructure,
+) -> Result<(), PipelinePartExecutionError> {
+  Pipeline::put_default_concrete_keys(context);
+
+  let pipeline_parts = context.pipeline_parts();
+  let mut sendable_branches = vec![];
+  for pipeline in branches {
+    sendable_branches.push(SendableBranch {
+      pipeline,
+      snapshot: create_snapshot(context)?,
+    });
+  }
+
+  let allocations_counter = AllocationsCounter::current();
+  let results = thread::scope(|scope| {
+    let handles = sendable_branches
+      .into_iter()
+      .map(|branch| {
+        let allocations_counter = allocations_counter.clone();
+        scope.spawn(move || {
+          let _scope = allocations_counter.as_ref().map(|counter| counter.enter());
+          execute_branch(branch, pipeline_parts, infra)
+        })
+      })
+      .collect::<Vec<_>>();
+
+    handles
+      .into_iter()
+      .map(|handle| match handle.join() {
+        Ok(result) => result,
+        Err(_) => Err(PipelinePartExecutionError::new_raw("Parallel pipeline branch panicked".to_string())),
+      })
+      .collect::<Vec<_>>()
+  });
+
+  let mut outputs = vec![];
+  for result in results {
+    outputs.push(result?);
+  }
+
+  merge_outputs(outputs, merge_policy, context)
+}
+
+fn execute_branch(
+  branch: SendableBranch,
+  pipeline_parts: Option<&PipelineParts>,
+  infra: &PipelineInfrastructure,
+) -> Result<SendableOutputs, PipelinePartExecutionError> {
+  let mut context = PipelineContext::with_user_data(branch.snapshot.user_data, pipeline_parts);
+  context.set_config_overrides(branch.snapshot.config_overrides);
+  branch.pipeline.execute(&mut context, infra)?;
+
+  let mut outputs = context.devastate_user_data();
+  outputs.retain_changed_values(&branch.snapshot.addresses);
+
+  Ok(SendableOutputs(outputs))
+}
+
+struct SendableOutputs(UserDataImpl);
+
+unsafe impl Send for SendableOutputs {}
+
+fn merge_outputs(
+  outputs: Vec<SendableOutputs>,
+  merge_policy: ParallelOutputsMergePolicy,
+  context: &mut PipelineContext,
+) -> Result<(), PipelinePartExecutionError> {
+  if merge_policy == ParallelOutputsMergePolicy::FailOnConflict {
+    let mut produced_keys: HashMap<u64, usize> = HashMap::new();
+    for (index, SendableOutputs(branch_outputs)) in outputs.iter().enumerate() {
+      for (key, _) in branch_outputs.items().unwrap_or_default() {
+        if let Some(previous_index) = produced_keys.insert(key.id(), index) {
+          let message = format!(
+            "Parallel branches {} and {} both produced context value {}",
+            previous_index,
+            index,
+            key.name()
+          );
+
+          return Err(PipelinePartExecutionError::new_raw(message));
+        }
+      }
+    }
+  }
+
+  for SendableOutputs(branch_outputs) in outputs {
+    branch_outputs.move_values_to(context.user_data_mut());
+  }
+
+  Ok(())
+}
+
+/// Every branch gets its own copy of the context values, execution fails if some value can not be copied.
+fn create_snapshot(context: &PipelineContext) -> Result<BranchSnapshot, PipelinePartExecutionError> {
+  let user_data = copy_branch_values(context.user_data())?;
+
+  Ok(BranchSnapshot {
+    addresses: user_data.values_addresses(),
+    config_overrides: copy_branch_values(context.config_overrides())?,
+    user_data,
+  })
+}
+
+fn copy_branch_values(user_data: &UserDataImpl) -> Result<UserDataImpl, PipelinePartExecutionError> {
+  copy_context_values(user_data)
+    .map_err(|key| PipelinePartExecutionError::new_raw(format!("Context value {} can not be copied to a parallel branch", key)))
+}
@@ -0,0 +1,107 @@
+use crate::utils::{
+  context_key::{ContextKey, DefaultContextKey},
+  user_data::keys::Key,
+};
+
+#[derive(Clone, Debug)]
+pub struct PipelinePartKeyDescriptor {
+  name: String,
+  type_name: String,
+  required: bool,
+  default_value: Option<String>,
+}
+
+impl PipelinePartKeyDescriptor {
+  fn new<T>(key: &DefaultContextKey<T>, required: bool) -> Self {
+    Self {
+      name: key.key().name().to_owned(),
+      type_name: key.type_name(),
+      required,
+      default_value: None,
+    }
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn type_name(&self) -> &str {
+    &self.type_name
+  }
+
+  pub fn required(&self) -> bool {
+    self.required
+  }
+
+  pub fn default_value(&self) -> Option<&str> {
+    self.default_value.as_deref()
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct PipelinePartMetadata {
+  name: String,
+  config_keys: Vec<PipelinePartKeyDescriptor>,
+  input_keys: Vec<PipelinePartKeyDescriptor>,
+  output_keys: Vec<PipelinePartKeyDescriptor>,
+}
+
+impl PipelinePartMetadata {
+  pub fn new(name: &str) -> Self {
+    Self {
+      name: name.to_owned(),
+      config_keys: vec![],
+      input_keys: vec![],
+      output_keys: vec![],
+    }
+  }
+
+  pub fn config<T>(mut self, key: &DefaultContextKey<T>) -> Self {
+    self.config_keys.push(PipelinePartKeyDescriptor::new(key, true));
+    self
+  }
+
+  pub fn optional_config<T>(mut self, key: &DefaultContextKey<T>) -> Self {
+    self.config_keys.push(PipelinePartKeyDescriptor::new(key, false));
+    self
+  }
+
+  pub fn config_with_default<T>(mut self, key: &DefaultContextKey<T>, default_value: &str) -> Self {
+    let mut descriptor = PipelinePartKeyDescriptor::new(key, false);
+    descriptor.default_value = Some(default_value.to_owned());
+
+    self.config_keys.push(descriptor);
+    self
+  }
+
+  pub fn input<T>(mut self, key: &DefaultContextKey<T>) -> Self {
+    self.input_keys.push(PipelinePartKeyDescriptor::new(key, true));
+    self
+  }
+
+  pub fn optional_input<T>(mut self, key: &DefaultContextKey<T>) -> Self {
+    self.input_keys.push(PipelinePartKeyDescriptor::new(key, false));
+    self
+  }
+
+  pub fn output<T>(mut self, key: &DefaultContextKey<T>) -> Self {
+    self.output_keys.push(PipelinePartKeyDescriptor::new(key, true));
+    self
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn config_keys(&self) -> &Vec<PipelinePartKeyDescriptor> {
+    &self.config_keys
+  }
+
+  pub fn input_keys(&self) -> &Vec<PipelinePartKeyDescriptor> {
+    &self.input_keys
+  }
+
+  pub fn output_keys(&self) -> &Vec<PipelinePartKeyDescriptor> {
+    &self.output_keys
+  }
+}
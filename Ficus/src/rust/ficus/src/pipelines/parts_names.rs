@@ -27,6 +27,7 @@ impl PipelineParts {
   pub const GET_NAMES_EVENT_LOG: &'static str = "GetNamesEventLog";
   pub const GET_HASHES_EVENT_LOG: &'static str = "GetHashesEventLog";
   pub const USE_NAMES_EVENT_LOG: &'static str = "UseNamesEventLog";
+  pub const USE_CLOSED_CASES_EVENT_LOG: &'static str = "UseClosedCasesEventLog";
   pub const DISCOVER_ACTIVITIES_FOR_SEVERAL_LEVELS: &'static str = "DiscoverActivitiesForSeveralLevels";
   pub const DISCOVER_ACTIVITIES_IN_UNATTACHED_SUBTRACES: &'static str = "DiscoverActivitiesInUnattachedSubTraces";
   pub const DISCOVER_ACTIVITIES_UNTIL_NO_MORE: &'static str = "DiscoverActivitiesUntilNoMore";
@@ -46,6 +47,8 @@ impl PipelineParts {
   pub const DISCOVER_DFG: &'static str = "DiscoverDirectlyFollowsGraph";
   pub const DISCOVER_PETRI_NET_HEURISTIC: &'static str = "DiscoverPetriNetHeuristic";
   pub const DISCOVER_FUZZY_GRAPH: &'static str = "DiscoverFuzzyGraph";
+  pub const DISCOVER_PETRI_NET_HEURISTIC_STREAM: &'static str = "DiscoverPetriNetHeuristicStream";
+  pub const DISCOVER_FUZZY_GRAPH_STREAM: &'static str = "DiscoverFuzzyGraphStream";
   pub const READ_LOG_FROM_BXES: &'static str = "ReadLogFromBxes";
   pub const WRITE_LOG_TO_BXES: &'static str = "WriteLogToBxes";
   pub const DISCOVER_CASES: &'static str = "DiscoverCases";
@@ -72,6 +75,9 @@ impl PipelineParts {
   pub const ADD_GRAPH_TO_GRAPHS: &'static str = "AddGraphToGraphs";
   pub const CLEAR_GRAPHS: &'static str = "ClearGraphs";
   pub const TERMINATE_IF_EMPTY_LOG: &'static str = "TerminateIfEmptyLog";
+  pub const IF: &'static str = "If";
+  pub const FOR_EACH: &'static str = "ForEach";
+  pub const SWEEP: &'static str = "Sweep";
 
   pub const ANNOTATE_PETRI_NET_COUNT: &'static str = "AnnotatePetriNetWithCount";
   pub const ANNOTATE_PETRI_NET_FREQUENCY: &'static str = "AnnotatePetriNetWithFrequency";
@@ -99,4 +105,6 @@ impl PipelineParts {
   pub const WRITE_BXES_LOG_TO_BYTES: &'static str = "WriteBxesLogToBytes";
 
   pub const MERGE_XES_LOGS_FROM_PATHS: &'static str = "MergeXesLogsFromPaths";
+
+  pub const DETECT_CONCEPT_DRIFT: &'static str = "DetectConceptDrift";
 }
@@ -1,4 +1,4 @@
-use super::{context::PipelineContext, errors::pipeline_errors::PipelinePartExecutionError, pipelines::PipelinePartFactory};
+use super::{context::PipelineContext, errors::pipeline_errors::PipelinePartExecutionError};
 use crate::{
   features::analysis::patterns::{
     contexts::PatternsDiscoveryStrategy,
@@ -9,10 +9,10 @@ use crate::{
   },
   pipelines::{
     keys::context_keys::{
-      ACTIVITY_LEVEL_KEY, EVENT_LOG_KEY, HASHES_EVENT_LOG_KEY, PATTERNS_DISCOVERY_STRATEGY_KEY, PATTERNS_KEY, PATTERNS_KIND_KEY,
-      TANDEM_ARRAY_LENGTH_KEY, TRACE_ACTIVITIES_KEY,
+      ACTIVITY_LEVEL_KEY, EVENT_CLASS_REGEX_KEY, EVENT_LOG_KEY, HASHES_EVENT_LOG_KEY, PATTERNS_DISCOVERY_STRATEGY_KEY, PATTERNS_KEY,
+      PATTERNS_KIND_KEY, TANDEM_ARRAY_LENGTH_KEY, TRACE_ACTIVITIES_KEY,
     },
-    pipeline_parts::PipelineParts,
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
   },
   utils::user_data::user_data::{UserData, UserDataImpl},
 };
@@ -56,54 +56,114 @@ impl FromStr for PatternsKindDto {
 }
 
 impl PipelineParts {
-  pub(super) fn find_maximal_repeats() -> (String, PipelinePartFactory) {
-    Self::create_pipeline_part(Self::FIND_MAXIMAL_REPEATS, &|context, _, config| {
-      Self::find_repeats_and_put_to_context(context, config, find_maximal_repeats, UnderlyingPatternKind::MaximalRepeat)
-    })
+  pub(super) fn find_maximal_repeats() -> PipelinePartDefinition {
+    Self::create_pipeline_part(
+      Self::FIND_MAXIMAL_REPEATS,
+      |metadata| {
+        metadata
+          .config(&PATTERNS_DISCOVERY_STRATEGY_KEY)
+          .optional_config(&EVENT_CLASS_REGEX_KEY)
+          .input(&EVENT_LOG_KEY)
+          .output(&HASHES_EVENT_LOG_KEY)
+          .output(&PATTERNS_KEY)
+          .output(&UNDERLYING_PATTERN_KIND_KEY)
+      },
+      &|context, _, config| {
+        Self::find_repeats_and_put_to_context(context, config, find_maximal_repeats, UnderlyingPatternKind::MaximalRepeat)
+      },
+    )
   }
 
-  pub(super) fn find_super_maximal_repeats() -> (String, PipelinePartFactory) {
-    Self::create_pipeline_part(Self::FIND_SUPER_MAXIMAL_REPEATS, &|context, _, config| {
-      Self::find_repeats_and_put_to_context(
-        context,
-        config,
-        find_super_maximal_repeats,
-        UnderlyingPatternKind::SuperMaximalRepeat,
-      )
-    })
+  pub(super) fn find_super_maximal_repeats() -> PipelinePartDefinition {
+    Self::create_pipeline_part(
+      Self::FIND_SUPER_MAXIMAL_REPEATS,
+      |metadata| {
+        metadata
+          .config(&PATTERNS_DISCOVERY_STRATEGY_KEY)
+          .optional_config(&EVENT_CLASS_REGEX_KEY)
+          .input(&EVENT_LOG_KEY)
+          .output(&HASHES_EVENT_LOG_KEY)
+          .output(&PATTERNS_KEY)
+          .output(&UNDERLYING_PATTERN_KIND_KEY)
+      },
+      &|context, _, config| {
+        Self::find_repeats_and_put_to_context(
+          context,
+          config,
+          find_super_maximal_repeats,
+          UnderlyingPatternKind::SuperMaximalRepeat,
+        )
+      },
+    )
   }
 
-  pub(super) fn find_near_super_maximal_repeats() -> (String, PipelinePartFactory) {
-    Self::create_pipeline_part(Self::FIND_NEAR_SUPER_MAXIMAL_REPEATS, &|context, _, config| {
-      Self::find_repeats_and_put_to_context(
-        context,
-        config,
-        find_near_super_maximal_repeats,
-        UnderlyingPatternKind::NearSuperMaximalRepeat,
-      )
-    })
+  pub(super) fn find_near_super_maximal_repeats() -> PipelinePartDefinition {
+    Self::create_pipeline_part(
+      Self::FIND_NEAR_SUPER_MAXIMAL_REPEATS,
+      |metadata| {
+        metadata
+          .config(&PATTERNS_DISCOVERY_STRATEGY_KEY)
+          .optional_config(&EVENT_CLASS_REGEX_KEY)
+          .input(&EVENT_LOG_KEY)
+          .output(&HASHES_EVENT_LOG_KEY)
+          .output(&PATTERNS_KEY)
+          .output(&UNDERLYING_PATTERN_KIND_KEY)
+      },
+      &|context, _, config| {
+        Self::find_repeats_and_put_to_context(
+          context,
+          config,
+          find_near_super_maximal_repeats,
+          UnderlyingPatternKind::NearSuperMaximalRepeat,
+        )
+      },
+    )
   }
 
-  pub(super) fn find_primitive_tandem_arrays() -> (String, PipelinePartFactory) {
-    Self::create_pipeline_part(Self::FIND_PRIMITIVE_TANDEM_ARRAYS, &|context, _, config| {
-      Self::find_tandem_arrays_and_put_to_context(
-        context,
-        config,
-        find_primitive_tandem_arrays,
-        UnderlyingPatternKind::PrimitiveTandemArray,
-      )
-    })
+  pub(super) fn find_primitive_tandem_arrays() -> PipelinePartDefinition {
+    Self::create_pipeline_part(
+      Self::FIND_PRIMITIVE_TANDEM_ARRAYS,
+      |metadata| {
+        metadata
+          .config(&TANDEM_ARRAY_LENGTH_KEY)
+          .optional_config(&EVENT_CLASS_REGEX_KEY)
+          .input(&EVENT_LOG_KEY)
+          .output(&HASHES_EVENT_LOG_KEY)
+          .output(&PATTERNS_KEY)
+          .output(&UNDERLYING_PATTERN_KIND_KEY)
+      },
+      &|context, _, config| {
+        Self::find_tandem_arrays_and_put_to_context(
+          context,
+          config,
+          find_primitive_tandem_arrays,
+          UnderlyingPatternKind::PrimitiveTandemArray,
+        )
+      },
+    )
   }
 
-  pub(super) fn find_maximal_tandem_arrays() -> (String, PipelinePartFactory) {
-    Self::create_pipeline_part(Self::FIND_MAXIMAL_TANDEM_ARRAYS, &|context, _, config| {
-      Self::find_tandem_arrays_and_put_to_context(
-        context,
-        config,
-        find_maximal_tandem_arrays,
-        UnderlyingPatternKind::MaximalTandemArray,
-      )
-    })
+  pub(super) fn find_maximal_tandem_arrays() -> PipelinePartDefinition {
+    Self::create_pipeline_part(
+      Self::FIND_MAXIMAL_TANDEM_ARRAYS,
+      |metadata| {
+        metadata
+          .config(&TANDEM_ARRAY_LENGTH_KEY)
+          .optional_config(&EVENT_CLASS_REGEX_KEY)
+          .input(&EVENT_LOG_KEY)
+          .output(&HASHES_EVENT_LOG_KEY)
+          .output(&PATTERNS_KEY)
+          .output(&UNDERLYING_PATTERN_KIND_KEY)
+      },
+      &|context, _, config| {
+        Self::find_tandem_arrays_and_put_to_context(
+          context,
+          config,
+          find_maximal_tandem_arrays,
+          UnderlyingPatternKind::MaximalTandemArray,
+        )
+      },
+    )
   }
 
   pub(super) fn find_tandem_arrays_and_put_to_context(
@@ -113,7 +173,7 @@ impl PipelineParts {
     underlying_pattern_kind: UnderlyingPatternKind,
   ) -> Result<(), PipelinePartExecutionError> {
     let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    let array_length = *config.concrete(TANDEM_ARRAY_LENGTH_KEY.key()).unwrap() as usize;
+    let array_length = *Self::get_user_data(config, &TANDEM_ARRAY_LENGTH_KEY)? as usize;
 
     let hashed_log = Self::create_hashed_event_log(config, log);
 
@@ -173,19 +233,30 @@ impl PipelineParts {
     Ok(())
   }
 
-  pub(super) fn discover_loops_strict() -> (String, PipelinePartFactory) {
-    Self::create_pipeline_part(Self::DISCOVER_LOOPS_STRICT, &|context, _, config| {
-      let max_array_length = Self::get_user_data(config, &TANDEM_ARRAY_LENGTH_KEY)?;
-      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-      let hashed_log = Self::create_hashed_event_log(config, log);
+  pub(super) fn discover_loops_strict() -> PipelinePartDefinition {
+    Self::create_pipeline_part(
+      Self::DISCOVER_LOOPS_STRICT,
+      |metadata| {
+        metadata
+          .config(&TANDEM_ARRAY_LENGTH_KEY)
+          .optional_config(&EVENT_CLASS_REGEX_KEY)
+          .input(&EVENT_LOG_KEY)
+          .output(&TRACE_ACTIVITIES_KEY)
+          .output(&UNDERLYING_PATTERN_KIND_KEY)
+      },
+      &|context, _, config| {
+        let max_array_length = Self::get_user_data(config, &TANDEM_ARRAY_LENGTH_KEY)?;
+        let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+        let hashed_log = Self::create_hashed_event_log(config, log);
 
-      context.put_concrete(
-        TRACE_ACTIVITIES_KEY.key(),
-        find_loops_strict(log, &hashed_log, *max_array_length as usize),
-      );
-      context.put_concrete(UNDERLYING_PATTERN_KIND_KEY.key(), UnderlyingPatternKind::StrictLoop);
+        context.put_concrete(
+          TRACE_ACTIVITIES_KEY.key(),
+          find_loops_strict(log, &hashed_log, *max_array_length as usize),
+        );
+        context.put_concrete(UNDERLYING_PATTERN_KIND_KEY.key(), UnderlyingPatternKind::StrictLoop);
 
-      Ok(())
-    })
+        Ok(())
+      },
+    )
   }
 }
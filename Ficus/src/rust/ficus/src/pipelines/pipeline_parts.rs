@@ -2,6 +2,7 @@ use crate::{
   pipelines::{
     context::{PipelineContext, PipelineInfrastructure},
     errors::pipeline_errors::{MissingContextError, PipelinePartExecutionError, RawPartExecutionError},
+    parts_metadata::PipelinePartMetadata,
     pipelines::{DefaultPipelinePart, PipelinePartFactory},
   },
   utils::{
@@ -19,23 +20,36 @@ use std::collections::HashMap;
 
 pub struct PipelineParts {
   names_to_parts: HashMap<String, PipelinePartFactory>,
+  names_to_metadata: HashMap<String, PipelinePartMetadata>,
 }
 
+/// Name of a part, its factory and metadata which describes keys read and written by the part
+pub(super) type PipelinePartDefinition = (String, PipelinePartFactory, PipelinePartMetadata);
+
 #[derive(new)]
 pub struct PipelinePartDescriptor {
   name: String,
+  metadata: PipelinePartMetadata,
 }
 
 impl PipelinePartDescriptor {
   pub fn name(self) -> String {
     self.name
   }
+
+  pub fn metadata(&self) -> &PipelinePartMetadata {
+    &self.metadata
+  }
 }
 
 impl PipelineParts {
   pub fn find_part(&self, name: &str) -> Option<&PipelinePartFactory> {
     self.names_to_parts.get(name)
   }
+
+  pub fn find_part_metadata(&self, name: &str) -> Option<&PipelinePartMetadata> {
+    self.names_to_metadata.get(name)
+  }
 }
 
 unsafe impl Sync for PipelineParts {}
@@ -43,10 +57,10 @@ unsafe impl Send for PipelineParts {}
 
 #[macro_export]
 macro_rules! pipeline_part {
-  ($name:ident, $body:expr) => {
+  ($name:ident, $metadata:expr, $body:expr) => {
     paste::item! {
-      pub(super) fn $name () -> (String, PipelinePartFactory) {
-        Self::create_pipeline_part(Self::[<$name:upper>], &|context, infra, config| {
+      pub(super) fn $name () -> PipelinePartDefinition {
+        Self::create_pipeline_part(Self::[<$name:upper>], $metadata, &|context, infra, config| {
           $body(context, infra, config)
         })
       }
@@ -89,6 +103,7 @@ impl PipelineParts {
       Self::get_names_event_log(),
       Self::get_hashes_event_log(),
       Self::use_names_event_log(),
+      Self::use_closed_cases_event_log(),
       Self::discover_activities_for_several_levels(),
       Self::discover_activities_in_unattached_subtraces(),
       Self::discover_activities_until_no_more(),
@@ -107,6 +122,8 @@ impl PipelineParts {
       Self::discover_dfg(),
       Self::discover_petri_net_heuristic(),
       Self::discover_fuzzy_graph(),
+      Self::discover_petri_net_heuristic_stream(),
+      Self::discover_fuzzy_graph_stream(),
       Self::annotate_petri_net_count(),
       Self::annotate_petri_net_frequency(),
       Self::annotate_petri_net_trace_frequency(),
@@ -152,16 +169,25 @@ impl PipelineParts {
       Self::add_graph_to_graphs(),
       Self::clear_graphs(),
       Self::terminate_if_empty_log(),
+      Self::conditional(),
+      Self::for_each(),
+      Self::sweep(),
       Self::create_ocel_annotation_for_dag(),
+      Self::detect_concept_drift(),
     ];
 
     let mut names_to_parts = HashMap::new();
-    for part in parts {
-      let prev = names_to_parts.insert(part.0.to_owned(), part.1);
+    let mut names_to_metadata = HashMap::new();
+    for (name, factory, metadata) in parts {
+      names_to_metadata.insert(name.to_owned(), metadata);
+      let prev = names_to_parts.insert(name, factory);
       assert!(prev.is_none());
     }
 
-    Self { names_to_parts }
+    Self {
+      names_to_parts,
+      names_to_metadata,
+    }
   }
 
   pub fn len(&self) -> usize {
@@ -172,7 +198,12 @@ impl PipelineParts {
     let mut descriptors = vec![];
     for factory in self.names_to_parts.values() {
       let name = factory(Box::default()).name().to_owned();
-      descriptors.push(PipelinePartDescriptor::new(name))
+      let metadata = self
+        .names_to_metadata
+        .get(&name)
+        .expect("Every part should declare metadata")
+        .clone();
+      descriptors.push(PipelinePartDescriptor::new(name, metadata))
     }
 
     descriptors
@@ -180,8 +211,9 @@ impl PipelineParts {
 
   pub(super) fn create_pipeline_part(
     name: &'static str,
+    metadata: fn(PipelinePartMetadata) -> PipelinePartMetadata,
     executor: &'static impl Fn(&mut PipelineContext, &PipelineInfrastructure, &UserDataImpl) -> Result<(), PipelinePartExecutionError>,
-  ) -> (String, PipelinePartFactory) {
+  ) -> PipelinePartDefinition {
     (
       name.to_string(),
       Box::new(|config| {
@@ -191,6 +223,7 @@ impl PipelineParts {
           Box::new(|context, infra, config| performance_cookie(name, infra, &mut || executor(context, infra, config))),
         )
       }),
+      metadata(PipelinePartMetadata::new(name)),
     )
   }
 
@@ -1,5 +1,9 @@
 use crate::{
-  pipelines::{context::PipelineInfrastructure, keys::context_keys::COLORS_HOLDER_KEY},
+  pipelines::{
+    context::PipelineInfrastructure,
+    keys::context_keys::COLORS_HOLDER_KEY,
+    parallel::{ParallelOutputsMergePolicy, execute_parallel},
+  },
   utils::{
     colors::ColorsHolder,
     user_data::user_data::{UserData, UserDataImpl},
@@ -21,9 +25,11 @@ impl Pipeline {
 
 impl PipelinePart for Pipeline {
   fn execute(&self, context: &mut PipelineContext, infra: &PipelineInfrastructure) -> Result<(), PipelinePartExecutionError> {
-    self.put_default_concrete_keys(context);
+    Self::put_default_concrete_keys(context);
 
     for part in &self.parts {
+      infra.check_cancelled()?;
+      infra.check_budget()?;
       part.execute(context, infra)?;
     }
 
@@ -32,7 +38,7 @@ impl PipelinePart for Pipeline {
 }
 
 impl Pipeline {
-  fn put_default_concrete_keys(&self, context: &mut PipelineContext) {
+  pub(super) fn put_default_concrete_keys(context: &mut PipelineContext) {
     if context.concrete(COLORS_HOLDER_KEY.key()).is_none() {
       context.put_concrete(COLORS_HOLDER_KEY.key(), ColorsHolder::default());
     }
@@ -45,19 +51,26 @@ pub trait PipelinePart {
 
 pub struct ParallelPipelinePart {
   parallel_pipelines: Vec<Pipeline>,
+  merge_policy: ParallelOutputsMergePolicy,
 }
 
-impl PipelinePart for ParallelPipelinePart {
-  fn execute(&self, context: &mut PipelineContext, infra: &PipelineInfrastructure) -> Result<(), PipelinePartExecutionError> {
-    for pipeline in &self.parallel_pipelines[0..(self.parallel_pipelines.len() - 1)] {
-      pipeline.execute(&mut context.clone(), infra)?;
+impl ParallelPipelinePart {
+  pub fn new(parallel_pipelines: Vec<Pipeline>) -> Self {
+    Self {
+      parallel_pipelines,
+      merge_policy: ParallelOutputsMergePolicy::default(),
     }
+  }
 
-    if let Some(last_pipeline) = self.parallel_pipelines.last() {
-      last_pipeline.execute(context, infra)?;
-    }
+  pub fn with_merge_policy(mut self, merge_policy: ParallelOutputsMergePolicy) -> Self {
+    self.merge_policy = merge_policy;
+    self
+  }
+}
 
-    Ok(())
+impl PipelinePart for ParallelPipelinePart {
+  fn execute(&self, context: &mut PipelineContext, infra: &PipelineInfrastructure) -> Result<(), PipelinePartExecutionError> {
+    execute_parallel(&self.parallel_pipelines, self.merge_policy, context, infra)
   }
 }
 
@@ -86,7 +99,15 @@ impl DefaultPipelinePart {
 
 impl PipelinePart for DefaultPipelinePart {
   fn execute(&self, context: &mut PipelineContext, infra: &PipelineInfrastructure) -> Result<(), PipelinePartExecutionError> {
-    (self.executor)(context, infra, &self.config)
+    let overridden_config = context.apply_config_overrides(&self.config);
+    let config = overridden_config.as_ref().unwrap_or(&self.config);
+
+    match infra.results_cache() {
+      None => (self.executor)(context, infra, config),
+      Some(cache) => cache.execute(&self.name, config, context, infra, |context| {
+        (self.executor)(context, infra, config)
+      }),
+    }
   }
 }
 
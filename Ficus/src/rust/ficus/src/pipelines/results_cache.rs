@@ -0,0 +1,282 @@
+use crate::{
+  pipelines::{
+    context::{PipelineContext, PipelineInfrastructure},
+    errors::pipeline_errors::PipelinePartExecutionError,
+    keys::context_keys::{PATH_KEY, PATHS_KEY},
+    parts_metadata::PipelinePartMetadata,
+    sendable_values::{copy_context_value, copy_context_values, hash_context_value},
+  },
+  utils::user_data::{
+    keys::Key,
+    user_data::{UserData, UserDataImpl},
+  },
+};
+use sha2::{Digest, Sha256};
+use std::{
+  any::Any,
+  collections::{HashMap, VecDeque},
+  fs,
+  hash::{Hash, Hasher},
+  sync::{Arc, Mutex},
+};
+
+pub trait CacheHitHandler: Send + Sync {
+  fn handle(&self, part_name: &str);
+}
+
+type CacheKey = [u8; 32];
+
+struct CacheEntry {
+  outputs: UserDataImpl,
+  size: usize,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+  entries: HashMap<CacheKey, CacheEntry>,
+  order: VecDeque<CacheKey>,
+  size: usize,
+}
+
+impl CacheEntries {
+  fn remove(&mut self, cache_key: &CacheKey) {
+    if let Some(entry) = self.entries.remove(cache_key) {
+      self.size -= entry.size;
+      self.order.retain(|key| key != cache_key);
+    }
+  }
+}
+
+/// Outputs of pipeline parts keyed by part name, part config and content of the part inputs,
+/// paths in config and inputs are keyed together with the size and modification time of the file.
+/// Only parts whose config, inputs and outputs can be hashed and copied are cached, the SHA-256 digest of the hashed content
+/// is the key of the entry. Cached values do not share `Rc`s with anything (see `copy_context_value`)
+/// and are only accessed under the mutex.
+/// The least recently used entries are evicted when `max_entries` or `max_bytes` is reached,
+/// the size of an entry is the size of its key and of the hashed content of its outputs.
+pub struct PipelinePartsResultsCache {
+  max_entries: usize,
+  max_bytes: usize,
+  entries: Mutex<CacheEntries>,
+}
+
+impl PipelinePartsResultsCache {
+  pub fn new(max_entries: usize) -> Self {
+    Self {
+      max_entries,
+      max_bytes: usize::MAX,
+      entries: Mutex::new(CacheEntries::default()),
+    }
+  }
+
+  pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+    self.max_bytes = max_bytes;
+    self
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.lock().unwrap().entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn size_in_bytes(&self) -> usize {
+    self.entries.lock().unwrap().size
+  }
+
+  pub fn clear(&self) {
+    *self.entries.lock().unwrap() = CacheEntries::default();
+  }
+
+  pub(super) fn execute(
+    &self,
+    part_name: &str,
+    config: &UserDataImpl,
+    context: &mut PipelineContext,
+    infra: &PipelineInfrastructure,
+    executor: impl FnOnce(&mut PipelineContext) -> Result<(), PipelinePartExecutionError>,
+  ) -> Result<(), PipelinePartExecutionError> {
+    let Some(metadata) = context
+      .pipeline_parts()
+      .and_then(|parts| parts.find_part_metadata(part_name))
+      .cloned()
+    else {
+      return executor(context);
+    };
+
+    if metadata.output_keys().is_empty() {
+      return executor(context);
+    }
+
+    let Some(cache_key) = Self::create_cache_key(part_name, config, &metadata, context) else {
+      return executor(context);
+    };
+
+    if self.try_restore(&cache_key, context) {
+      infra.report_cache_hit(part_name);
+      return Ok(());
+    }
+
+    executor(context)?;
+
+    if let Some((outputs, outputs_size)) = Self::copy_outputs(&metadata, context) {
+      self.put(cache_key, outputs, outputs_size);
+    }
+
+    Ok(())
+  }
+
+  fn create_cache_key(
+    part_name: &str,
+    config: &UserDataImpl,
+    metadata: &PipelinePartMetadata,
+    context: &PipelineContext,
+  ) -> Option<CacheKey> {
+    let mut writer = CacheKeyWriter::default();
+    part_name.hash(&mut writer);
+
+    let mut config_items = config.items().unwrap_or_default();
+    config_items.sort_by(|first, second| first.0.name().cmp(second.0.name()));
+
+    for (key, value) in config_items {
+      key.name().hash(&mut writer);
+      if !hash_context_value(value, &mut writer) || !hash_files_state(key.name(), value, &mut writer) {
+        return None;
+      }
+    }
+
+    let context_items = context.items().unwrap_or_default();
+    for input in metadata.input_keys() {
+      input.name().hash(&mut writer);
+
+      match context_items.iter().find(|(key, _)| key.name() == input.name()) {
+        None => false.hash(&mut writer),
+        Some((key, value)) => {
+          true.hash(&mut writer);
+          if !hash_context_value(*value, &mut writer) || !hash_files_state(key.name(), *value, &mut writer) {
+            return None;
+          }
+        }
+      }
+    }
+
+    Some(writer.digest.finalize().into())
+  }
+
+  fn copy_outputs(metadata: &PipelinePartMetadata, context: &PipelineContext) -> Option<(UserDataImpl, usize)> {
+    let mut outputs = UserDataImpl::default();
+    let mut size_counter = SizeCounter::default();
+
+    for (key, value) in context.items().unwrap_or_default() {
+      if !metadata.output_keys().iter().any(|output| output.name() == key.name()) {
+        continue;
+      }
+
+      if !hash_context_value(value, &mut size_counter) || !copy_context_value(key.as_ref(), value, &mut outputs) {
+        return None;
+      }
+    }
+
+    Some((outputs, size_counter.size))
+  }
+
+  fn try_restore(&self, cache_key: &CacheKey, context: &mut PipelineContext) -> bool {
+    let mut entries = self.entries.lock().unwrap();
+    let Some(entry) = entries.entries.get(cache_key) else {
+      return false;
+    };
+
+    let Ok(outputs) = copy_context_values(&entry.outputs) else {
+      return false;
+    };
+
+    outputs.move_values_to(context.user_data_mut());
+
+    entries.order.retain(|key| key != cache_key);
+    entries.order.push_back(*cache_key);
+
+    true
+  }
+
+  fn put(&self, cache_key: CacheKey, outputs: UserDataImpl, outputs_size: usize) {
+    let size = cache_key.len() + outputs_size;
+    if self.max_entries == 0 || size > self.max_bytes {
+      return;
+    }
+
+    let mut entries = self.entries.lock().unwrap();
+    entries.remove(&cache_key);
+
+    entries.entries.insert(cache_key, CacheEntry { outputs, size });
+    entries.order.push_back(cache_key);
+    entries.size += size;
+
+    while entries.order.len() > self.max_entries || entries.size > self.max_bytes {
+      match entries.order.front().copied() {
+        Some(evicted_key) => entries.remove(&evicted_key),
+        None => break,
+      }
+    }
+  }
+}
+
+/// Feeds everything which is hashed to SHA-256, so that entries are keyed by the digest of the content
+/// and not by the content itself.
+#[derive(Default)]
+struct CacheKeyWriter {
+  digest: Sha256,
+}
+
+impl Hasher for CacheKeyWriter {
+  fn finish(&self) -> u64 {
+    let digest = self.digest.clone().finalize();
+    u64::from_le_bytes(digest[..8].try_into().unwrap())
+  }
+
+  fn write(&mut self, bytes: &[u8]) {
+    self.digest.update(bytes);
+  }
+}
+
+#[derive(Default)]
+struct SizeCounter {
+  size: usize,
+}
+
+impl Hasher for SizeCounter {
+  fn finish(&self) -> u64 {
+    self.size as u64
+  }
+
+  fn write(&mut self, bytes: &[u8]) {
+    self.size += bytes.len();
+  }
+}
+
+/// Files are read by parts outside of the context, so their size and modification time are a part of the key,
+/// returns false if the state of some file can not be obtained.
+fn hash_files_state(key_name: &str, value: &dyn Any, hasher: &mut impl Hasher) -> bool {
+  let paths: Vec<&str> = if key_name == PATH_KEY.key().name() {
+    value.downcast_ref::<Arc<str>>().map(|path| vec![path.as_ref()]).unwrap_or_default()
+  } else if key_name == PATHS_KEY.key().name() {
+    value
+      .downcast_ref::<Vec<Arc<str>>>()
+      .map(|paths| paths.iter().map(|path| path.as_ref()).collect())
+      .unwrap_or_default()
+  } else {
+    return true;
+  };
+
+  paths.into_iter().all(
+    |path| match fs::metadata(path).and_then(|metadata| Ok((metadata.len(), metadata.modified()?))) {
+      Ok((len, modified)) => {
+        len.hash(hasher);
+        modified.hash(hasher);
+        true
+      }
+      Err(_) => false,
+    },
+  )
+}
@@ -0,0 +1,143 @@
+use log::error;
+use std::{
+  panic::{self, AssertUnwindSafe},
+  sync::{Arc, Mutex},
+};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PipelineSchedulingPolicy {
+  Fifo,
+  Priority,
+}
+
+#[derive(Clone, Debug)]
+pub struct PipelineSchedulerConfig {
+  pub max_concurrent_pipelines: usize,
+  pub policy: PipelineSchedulingPolicy,
+}
+
+impl Default for PipelineSchedulerConfig {
+  fn default() -> Self {
+    Self {
+      max_concurrent_pipelines: std::thread::available_parallelism().map(|p| p.get()).unwrap_or(1),
+      policy: PipelineSchedulingPolicy::Fifo,
+    }
+  }
+}
+
+pub type QueuePositionCallback = Box<dyn Fn(usize, usize) + Send + Sync>;
+
+/// Runs a job on some thread which is allowed to block, e.g. on a thread of a blocking pool of an async runtime
+pub type JobsSpawner = Box<dyn Fn(Box<dyn FnOnce() + Send>) + Send + Sync>;
+
+pub struct PipelineJob {
+  execution_id: Uuid,
+  priority: i32,
+  job: Box<dyn FnOnce() + Send>,
+  on_queue_position_changed: QueuePositionCallback,
+}
+
+impl PipelineJob {
+  pub fn new(execution_id: Uuid, priority: i32, job: Box<dyn FnOnce() + Send>, on_queue_position_changed: QueuePositionCallback) -> Self {
+    Self {
+      execution_id,
+      priority,
+      job,
+      on_queue_position_changed,
+    }
+  }
+}
+
+struct QueuedJob {
+  sequence_number: u64,
+  job: PipelineJob,
+}
+
+struct SchedulerState {
+  running_count: usize,
+  next_sequence_number: u64,
+  queue: Vec<QueuedJob>,
+}
+
+pub struct PipelineScheduler {
+  config: PipelineSchedulerConfig,
+  spawner: JobsSpawner,
+  state: Mutex<SchedulerState>,
+}
+
+impl PipelineScheduler {
+  pub fn new(config: PipelineSchedulerConfig, spawner: JobsSpawner) -> Self {
+    Self {
+      config,
+      spawner,
+      state: Mutex::new(SchedulerState {
+        running_count: 0,
+        next_sequence_number: 0,
+        queue: vec![],
+      }),
+    }
+  }
+
+  pub fn submit(self: &Arc<Self>, job: PipelineJob) {
+    let mut state = self.state.lock().unwrap();
+
+    if state.running_count < self.config.max_concurrent_pipelines {
+      state.running_count += 1;
+      drop(state);
+
+      self.run(job);
+      return;
+    }
+
+    let sequence_number = state.next_sequence_number;
+    state.next_sequence_number += 1;
+    state.queue.push(QueuedJob { sequence_number, job });
+
+    self.sort_queue(&mut state.queue);
+    Self::notify_queue_positions(&state.queue);
+  }
+
+  fn sort_queue(&self, queue: &mut [QueuedJob]) {
+    match self.config.policy {
+      PipelineSchedulingPolicy::Fifo => queue.sort_by_key(|q| q.sequence_number),
+      PipelineSchedulingPolicy::Priority => queue.sort_by_key(|q| (-(q.job.priority as i64), q.sequence_number)),
+    }
+  }
+
+  fn notify_queue_positions(queue: &[QueuedJob]) {
+    for (index, queued) in queue.iter().enumerate() {
+      (queued.job.on_queue_position_changed)(index + 1, queue.len());
+    }
+  }
+
+  fn run(self: &Arc<Self>, job: PipelineJob) {
+    let scheduler = self.clone();
+
+    (self.spawner)(Box::new(move || {
+      let execution_id = job.execution_id;
+      if panic::catch_unwind(AssertUnwindSafe(job.job)).is_err() {
+        error!("Pipeline execution {} panicked", execution_id);
+      }
+
+      scheduler.on_job_finished();
+    }));
+  }
+
+  fn on_job_finished(self: &Arc<Self>) {
+    let mut state = self.state.lock().unwrap();
+    state.running_count -= 1;
+
+    if state.queue.is_empty() {
+      return;
+    }
+
+    let next = state.queue.remove(0);
+    state.running_count += 1;
+    Self::notify_queue_positions(&state.queue);
+
+    drop(state);
+
+    self.run(next.job);
+  }
+}
@@ -0,0 +1,325 @@
+use crate::{
+  event_log::{
+    core::{event::event::Event, event_log::EventLog, trace::trace::Trace},
+    xes::{threading::detached_event_clone, xes_event::XesEventImpl, xes_event_log::XesEventLogImpl},
+  },
+  features::{
+    analysis::{
+      drift::{
+        detection::ConceptDriftChangePoint, distributions::DriftFeature, online::OnlineDriftChangePoint, statistics::DriftStatisticalTest,
+      },
+      patterns::{
+        activity_instances::{ActivityInTraceFilterKind, ActivityNarrowingKind, AdjustingMode, UnderlyingEventsInfo},
+        contexts::PatternsDiscoveryStrategy,
+        pattern_info::{UnderlyingPatternGraphInfo, UnderlyingPatternInfo, UnderlyingPatternKind},
+        repeat_sets::SubArrayWithTraceIndex,
+        tandem_arrays::SubArrayInTraceInfo,
+      },
+    },
+    cases::CaseName,
+    clustering::{activities::activities_params::ActivityRepresentationSource, traces::traces_params::TracesRepresentationSource},
+    discovery::{
+      ecfg::models::{
+        ActivityStartEndTimeData, CorrespondingTraceData, EdgeTraceExecutionInfo, NodeAdditionalDataContainer, RootSequenceKind,
+      },
+      fuzzy::proximity_correlation::PrecomputedProximityCorrelation,
+      petri_net::{annotations::TimeAnnotationKind, petri_net::DefaultPetriNet},
+      relations::triangle_relation::PrecomputedTriangleRelation,
+      timeline::{
+        discovery::LogTimelineDiagram,
+        software_data::{extraction_config::SoftwareDataExtractionConfig, models::SoftwareData},
+      },
+    },
+    streaming::conformance::core::ConformanceReport,
+  },
+  pipelines::{
+    activities_parts::{ActivitiesLogsSourceDto, UndefActivityHandlingStrategyDto},
+    control_flow_parts::{ComparisonOperator, ConditionMetric},
+    multithreading::FeatureCountKindDto,
+    patterns_parts::PatternsKindDto,
+  },
+  utils::{
+    colors::{ColorsEventLog, ColorsHolder},
+    dataset::dataset::{FicusDataset, LabeledDataset},
+    distance::distance::FicusDistance,
+    graph::graph::DefaultGraph,
+    log_serialization_format::LogSerializationFormat,
+    user_data::{
+      keys::Key,
+      user_data::{UserData, UserDataImpl, UserDataOwner},
+    },
+  },
+};
+use bxes::models::system_models::SystemMetadata;
+use std::{
+  any::Any,
+  cell::RefCell,
+  collections::HashMap,
+  hash::{Hash, Hasher},
+  mem::discriminant,
+  rc::Rc,
+  sync::Arc,
+};
+use uuid::Uuid;
+
+/// Puts into `target` a copy of `value` which does not share any `Rc` with the original one, so the copy can be mutated
+/// independently of the original value or moved to another thread. Event logs, graphs and Petri nets are copied together
+/// with their user data, other supported values do not contain `Rc` and are cloned.
+/// Returns false if the type of the value is not supported.
+pub(crate) fn copy_context_value(key: &dyn Key, value: &dyn Any, target: &mut UserDataImpl) -> bool {
+  copy_with::<XesEventLogImpl>(key, value, target, XesEventLogImpl::detached_clone)
+    || copy_with::<HashMap<Arc<str>, XesEventLogImpl>>(key, value, target, |logs| {
+      logs.iter().map(|(name, log)| Ok((name.clone(), log.detached_clone()?))).collect()
+    })
+    || copy_with::<DefaultGraph>(key, value, target, copy_graph)
+    || copy_with::<Vec<DefaultGraph>>(key, value, target, |graphs| graphs.iter().map(copy_graph).collect())
+    || copy_with::<DefaultPetriNet>(key, value, target, |net| net.try_clone_with(&copy_context_values))
+    || copy_with::<UnderlyingEventsInfo<XesEventImpl>>(key, value, target, |info| {
+      Ok(UnderlyingEventsInfo::new(
+        info.base_pattern().map(|events| copy_events(events)).transpose()?,
+        copy_events(info.underlying_events())?,
+      ))
+    })
+    || copy_with::<Vec<NodeAdditionalDataContainer<UnderlyingPatternInfo>>>(key, value, target, |infos| {
+      copy_containers(infos, |info| {
+        Ok(UnderlyingPatternInfo::new(
+          *info.pattern_kind(),
+          copy_events(info.underlying_sequence())?,
+          info.base_pattern().map(|events| copy_events(events)).transpose()?,
+        ))
+      })
+    })
+    || copy_with::<Vec<NodeAdditionalDataContainer<UnderlyingPatternGraphInfo>>>(key, value, target, |infos| {
+      copy_containers(infos, |info| {
+        Ok(UnderlyingPatternGraphInfo::new(
+          info.pattern_kind(),
+          info.base_pattern().cloned(),
+          copy_graph(info.graph())?,
+        ))
+      })
+    })
+    || copy_with::<Vec<NodeAdditionalDataContainer<XesEventLogImpl>>>(key, value, target, |logs| {
+      copy_containers(logs, XesEventLogImpl::detached_clone)
+    })
+    || copy_cloned::<Arc<str>>(key, value, target)
+    || copy_cloned::<f64>(key, value, target)
+    || copy_cloned::<u32>(key, value, target)
+    || copy_cloned::<u64>(key, value, target)
+    || copy_cloned::<usize>(key, value, target)
+    || copy_cloned::<bool>(key, value, target)
+    || copy_cloned::<Uuid>(key, value, target)
+    || copy_cloned::<Vec<u8>>(key, value, target)
+    || copy_cloned::<Vec<u64>>(key, value, target)
+    || copy_cloned::<Vec<f64>>(key, value, target)
+    || copy_cloned::<Vec<Arc<str>>>(key, value, target)
+    || copy_cloned::<Vec<Vec<u64>>>(key, value, target)
+    || copy_cloned::<Vec<Vec<Arc<str>>>>(key, value, target)
+    || copy_cloned::<Vec<(Arc<str>, Arc<str>)>>(key, value, target)
+    || copy_cloned::<Vec<Vec<SubArrayInTraceInfo>>>(key, value, target)
+    || copy_cloned::<Vec<SubArrayWithTraceIndex>>(key, value, target)
+    || copy_cloned::<HashMap<u64, f64>>(key, value, target)
+    || copy_cloned::<HashMap<u64, usize>>(key, value, target)
+    || copy_cloned::<ColorsHolder>(key, value, target)
+    || copy_cloned::<ColorsEventLog>(key, value, target)
+    || copy_cloned::<UnderlyingPatternKind>(key, value, target)
+    || copy_cloned::<PatternsDiscoveryStrategy>(key, value, target)
+    || copy_cloned::<PatternsKindDto>(key, value, target)
+    || copy_cloned::<AdjustingMode>(key, value, target)
+    || copy_cloned::<ActivityNarrowingKind>(key, value, target)
+    || copy_cloned::<ActivityInTraceFilterKind>(key, value, target)
+    || copy_cloned::<ActivityRepresentationSource>(key, value, target)
+    || copy_cloned::<TracesRepresentationSource>(key, value, target)
+    || copy_cloned::<UndefActivityHandlingStrategyDto>(key, value, target)
+    || copy_cloned::<ActivitiesLogsSourceDto>(key, value, target)
+    || copy_cloned::<FicusDistance>(key, value, target)
+    || copy_cloned::<FeatureCountKindDto>(key, value, target)
+    || copy_cloned::<TimeAnnotationKind>(key, value, target)
+    || copy_cloned::<RootSequenceKind>(key, value, target)
+    || copy_cloned::<LogSerializationFormat>(key, value, target)
+    || copy_cloned::<ComparisonOperator>(key, value, target)
+    || copy_cloned::<ConditionMetric>(key, value, target)
+    || copy_cloned::<DriftFeature>(key, value, target)
+    || copy_cloned::<DriftStatisticalTest>(key, value, target)
+    || copy_cloned::<Vec<ConceptDriftChangePoint>>(key, value, target)
+    || copy_cloned::<Vec<OnlineDriftChangePoint>>(key, value, target)
+    || copy_cloned::<ConformanceReport>(key, value, target)
+    || copy_cloned::<FicusDataset>(key, value, target)
+    || copy_cloned::<LabeledDataset>(key, value, target)
+    || copy_cloned::<SystemMetadata>(key, value, target)
+    || copy_cloned::<CaseName>(key, value, target)
+    || copy_cloned::<LogTimelineDiagram>(key, value, target)
+    || copy_cloned::<SoftwareDataExtractionConfig>(key, value, target)
+    || copy_cloned::<PrecomputedTriangleRelation>(key, value, target)
+    || copy_cloned::<PrecomputedProximityCorrelation>(key, value, target)
+    || copy_cloned::<Vec<NodeAdditionalDataContainer<SoftwareData>>>(key, value, target)
+    || copy_cloned::<Vec<NodeAdditionalDataContainer<CorrespondingTraceData>>>(key, value, target)
+    || copy_cloned::<NodeAdditionalDataContainer<ActivityStartEndTimeData>>(key, value, target)
+    || copy_cloned::<Vec<NodeAdditionalDataContainer<ActivityStartEndTimeData>>>(key, value, target)
+    || copy_cloned::<Vec<SoftwareData>>(key, value, target)
+    || copy_cloned::<Vec<ActivityStartEndTimeData>>(key, value, target)
+    || copy_cloned::<Vec<EdgeTraceExecutionInfo>>(key, value, target)
+}
+
+/// Copies all values with `copy_context_value`, returns the name of the key of the first value which can not be copied.
+pub(crate) fn copy_context_values(user_data: &UserDataImpl) -> Result<UserDataImpl, String> {
+  user_data.try_deep_copy(copy_context_value)
+}
+
+fn copy_with<T: 'static>(key: &dyn Key, value: &dyn Any, target: &mut UserDataImpl, copy: impl Fn(&T) -> Result<T, String>) -> bool {
+  match value.downcast_ref::<T>().map(copy) {
+    Some(Ok(copy)) => {
+      target.put_any::<T>(key, copy);
+      true
+    }
+    Some(Err(_)) | None => false,
+  }
+}
+
+fn copy_cloned<T: Clone + 'static>(key: &dyn Key, value: &dyn Any, target: &mut UserDataImpl) -> bool {
+  copy_with::<T>(key, value, target, |value| Ok(value.clone()))
+}
+
+fn copy_graph(graph: &DefaultGraph) -> Result<DefaultGraph, String> {
+  graph.try_clone_with(&copy_context_values)
+}
+
+fn copy_events(events: &[Rc<RefCell<XesEventImpl>>]) -> Result<Vec<Rc<RefCell<XesEventImpl>>>, String> {
+  events.iter().map(detached_event_clone).collect()
+}
+
+fn copy_containers<T: Clone>(
+  containers: &[NodeAdditionalDataContainer<T>],
+  copy: impl Fn(&T) -> Result<T, String>,
+) -> Result<Vec<NodeAdditionalDataContainer<T>>, String> {
+  containers
+    .iter()
+    .map(|container| {
+      Ok(NodeAdditionalDataContainer::new(
+        copy(container.value())?,
+        *container.original_event_coordinates(),
+      ))
+    })
+    .collect()
+}
+
+/// Feeds the content of `value` to `hasher`, returns false if the type of the value is not supported.
+pub(crate) fn hash_context_value(value: &dyn Any, hasher: &mut impl Hasher) -> bool {
+  if let Some(log) = value.downcast_ref::<XesEventLogImpl>() {
+    return hash_event_log(log, hasher);
+  }
+
+  if let Some(info) = value.downcast_ref::<UnderlyingEventsInfo<XesEventImpl>>() {
+    info.base_pattern().is_some().hash(hasher);
+    let mut events = info.base_pattern().into_iter().flatten().chain(info.underlying_events().iter());
+
+    return events.all(|event| hash_event(&event.borrow(), hasher));
+  }
+
+  if let Some(value) = value.downcast_ref::<f64>() {
+    value.to_bits().hash(hasher);
+    return true;
+  }
+
+  if let Some(values) = value.downcast_ref::<Vec<f64>>() {
+    values.iter().for_each(|v| v.to_bits().hash(hasher));
+    return true;
+  }
+
+  if let Some(patterns) = value.downcast_ref::<Vec<Vec<SubArrayInTraceInfo>>>() {
+    for trace_patterns in patterns {
+      trace_patterns.len().hash(hasher);
+      trace_patterns.iter().for_each(|p| (p.start_index, p.length).hash(hasher));
+    }
+
+    return true;
+  }
+
+  hash::<Arc<str>>(value, hasher)
+    || hash::<u32>(value, hasher)
+    || hash::<u64>(value, hasher)
+    || hash::<usize>(value, hasher)
+    || hash::<bool>(value, hasher)
+    || hash::<Uuid>(value, hasher)
+    || hash::<Vec<u8>>(value, hasher)
+    || hash::<Vec<u64>>(value, hasher)
+    || hash::<Vec<Arc<str>>>(value, hasher)
+    || hash::<Vec<Vec<u64>>>(value, hasher)
+    || hash::<Vec<Vec<Arc<str>>>>(value, hasher)
+    || hash::<Vec<(Arc<str>, Arc<str>)>>(value, hasher)
+    || hash_discriminant::<UnderlyingPatternKind>(value, hasher)
+    || hash_discriminant::<PatternsDiscoveryStrategy>(value, hasher)
+    || hash_discriminant::<PatternsKindDto>(value, hasher)
+    || hash_discriminant::<AdjustingMode>(value, hasher)
+    || hash_discriminant::<ActivityNarrowingKind>(value, hasher)
+    || hash_discriminant::<ActivityInTraceFilterKind>(value, hasher)
+    || hash_discriminant::<ActivityRepresentationSource>(value, hasher)
+    || hash_discriminant::<TracesRepresentationSource>(value, hasher)
+    || hash_discriminant::<FicusDistance>(value, hasher)
+    || hash_discriminant::<FeatureCountKindDto>(value, hasher)
+    || hash_discriminant::<TimeAnnotationKind>(value, hasher)
+}
+
+fn hash<T: Hash + 'static>(value: &dyn Any, hasher: &mut impl Hasher) -> bool {
+  match value.downcast_ref::<T>() {
+    Some(value) => {
+      value.hash(hasher);
+      true
+    }
+    None => false,
+  }
+}
+
+fn hash_discriminant<T: 'static>(value: &dyn Any, hasher: &mut impl Hasher) -> bool {
+  match value.downcast_ref::<T>() {
+    Some(value) => {
+      discriminant(value).hash(hasher);
+      true
+    }
+    None => false,
+  }
+}
+
+fn hash_event_log(log: &XesEventLogImpl, hasher: &mut impl Hasher) -> bool {
+  if !hash_user_data(log.user_data(), hasher) {
+    return false;
+  }
+
+  log.traces().len().hash(hasher);
+
+  for trace in log.traces() {
+    let trace = trace.borrow();
+    trace.events().len().hash(hasher);
+
+    for event in trace.events() {
+      if !hash_event(&event.borrow(), hasher) {
+        return false;
+      }
+    }
+  }
+
+  true
+}
+
+fn hash_event(event: &XesEventImpl, hasher: &mut impl Hasher) -> bool {
+  event.name().hash(hasher);
+  event.timestamp().hash(hasher);
+
+  for (key, value) in event.ordered_payload() {
+    key.hash(hasher);
+    format!("{:?}", value).hash(hasher);
+  }
+
+  hash_user_data(event.user_data(), hasher)
+}
+
+/// Values are hashed in the order of their keys names, returns false if some value can not be hashed.
+fn hash_user_data(user_data: &UserDataImpl, hasher: &mut impl Hasher) -> bool {
+  let mut items = user_data.items().unwrap_or_default();
+  items.sort_by(|first, second| first.0.name().cmp(second.0.name()));
+
+  items.len().hash(hasher);
+  items.into_iter().all(|(key, value)| {
+    key.name().hash(hasher);
+    hash_context_value(value, hasher)
+  })
+}
@@ -2,7 +2,6 @@ use std::{cell::RefCell, rc::Rc};
 
 use chrono::{DateTime, Duration, Utc};
 
-use super::pipelines::PipelinePartFactory;
 use crate::{
   event_log::{
     core::{
@@ -21,10 +20,10 @@ use crate::{
     context::{PipelineContext, PipelineInfrastructure},
     errors::pipeline_errors::PipelinePartExecutionError,
     keys::context_keys::{
-      EVENT_CLASS_REGEX_KEY, EVENT_LOG_INFO_KEY, EVENT_LOG_KEY, GRAPH_KEY, GRAPHS_KEY, HASHES_EVENT_LOG_KEY, NAMES_EVENT_LOG_KEY,
-      PIPELINE_KEY,
+      CLOSED_CASES_EVENT_LOG_KEY, EVENT_CLASS_REGEX_KEY, EVENT_LOG_INFO_KEY, EVENT_LOG_KEY, GRAPH_KEY, GRAPHS_KEY, HASHES_EVENT_LOG_KEY,
+      NAMES_EVENT_LOG_KEY, PIPELINE_KEY,
     },
-    pipeline_parts::PipelineParts,
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
     pipelines::PipelinePart,
   },
   utils::{
@@ -44,64 +43,95 @@ impl PipelineParts {
     }
   }
 
-  pipeline_part!(get_event_log_info, |context: &mut PipelineContext, _, _| {
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    let log_info = OfflineEventLogInfo::create_from(EventLogInfoCreationDto::default(log));
-    context.put_concrete(EVENT_LOG_INFO_KEY.key(), log_info);
-
-    Ok(())
-  });
-
-  pipeline_part!(get_hashes_event_log, |context: &mut PipelineContext, _, config: &UserDataImpl| {
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    let hashes_event_log = Self::create_hashed_event_log(config, log);
-
-    context.put_concrete(HASHES_EVENT_LOG_KEY.key(), hashes_event_log);
+  pipeline_part!(
+    get_event_log_info,
+    |metadata| metadata.input(&EVENT_LOG_KEY).output(&EVENT_LOG_INFO_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      let log_info = OfflineEventLogInfo::create_from(EventLogInfoCreationDto::default(log));
+      context.put_concrete(EVENT_LOG_INFO_KEY.key(), log_info);
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 
-  pipeline_part!(get_names_event_log, |context: &mut PipelineContext, _, _| {
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+  pipeline_part!(
+    get_hashes_event_log,
+    |metadata| metadata
+      .optional_config(&EVENT_CLASS_REGEX_KEY)
+      .input(&EVENT_LOG_KEY)
+      .output(&HASHES_EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, config: &UserDataImpl| {
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      let hashes_event_log = Self::create_hashed_event_log(config, log);
 
-    let mut result = vec![];
-    for trace in log.traces() {
-      let mut vec = vec![];
-      for event in trace.borrow().events() {
-        vec.push(event.borrow().name_pointer().clone());
-      }
+      context.put_concrete(HASHES_EVENT_LOG_KEY.key(), hashes_event_log);
 
-      result.push(vec);
+      Ok(())
     }
+  );
 
-    context.put_concrete(NAMES_EVENT_LOG_KEY.key(), result);
+  pipeline_part!(
+    get_names_event_log,
+    |metadata| metadata.input(&EVENT_LOG_KEY).output(&NAMES_EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+
+      let mut result = vec![];
+      for trace in log.traces() {
+        let mut vec = vec![];
+        for event in trace.borrow().events() {
+          vec.push(event.borrow().name_pointer().clone());
+        }
+
+        result.push(vec);
+      }
 
-    Ok(())
-  });
+      context.put_concrete(NAMES_EVENT_LOG_KEY.key(), result);
 
-  pipeline_part!(use_names_event_log, |context: &mut PipelineContext, _, _| {
-    let names_log = Self::get_user_data(context, &NAMES_EVENT_LOG_KEY)?;
-    let mut log = XesEventLogImpl::default();
-    for names_trace in names_log {
-      let mut trace = XesTraceImpl::default();
-      let mut date = DateTime::<Utc>::MIN_UTC;
+      Ok(())
+    }
+  );
 
-      for name in names_trace {
-        let event = XesEventImpl::new(name.clone(), date);
-        trace.push(Rc::new(RefCell::new(event)));
-        date += Duration::seconds(1);
+  pipeline_part!(
+    use_names_event_log,
+    |metadata| metadata.input(&NAMES_EVENT_LOG_KEY).output(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let names_log = Self::get_user_data(context, &NAMES_EVENT_LOG_KEY)?;
+      let mut log = XesEventLogImpl::default();
+      for names_trace in names_log {
+        let mut trace = XesTraceImpl::default();
+        let mut date = DateTime::<Utc>::MIN_UTC;
+
+        for name in names_trace {
+          let event = XesEventImpl::new(name.clone(), date);
+          trace.push(Rc::new(RefCell::new(event)));
+          date += Duration::seconds(1);
+        }
+
+        log.push(Rc::new(RefCell::new(trace)));
       }
 
-      log.push(Rc::new(RefCell::new(trace)));
+      context.put_concrete::<XesEventLogImpl>(EVENT_LOG_KEY.key(), log);
+
+      Ok(())
     }
+  );
 
-    context.put_concrete::<XesEventLogImpl>(EVENT_LOG_KEY.key(), log);
+  pipeline_part!(
+    use_closed_cases_event_log,
+    |metadata| metadata.input(&CLOSED_CASES_EVENT_LOG_KEY).output(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let closed_cases_log = Self::get_user_data(context, &CLOSED_CASES_EVENT_LOG_KEY)?.clone();
+      context.put_concrete(EVENT_LOG_KEY.key(), closed_cases_log);
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 
   pipeline_part!(
     execute_frontend_pipeline,
+    |metadata| metadata.config(&PIPELINE_KEY),
     |context: &mut PipelineContext, infra: &PipelineInfrastructure, config: &UserDataImpl| {
       let pipeline = Self::get_user_data(config, &PIPELINE_KEY)?;
       pipeline.execute(context, infra)?;
@@ -110,40 +140,56 @@ impl PipelineParts {
     }
   );
 
-  pipeline_part!(merge_graphs, |context: &mut PipelineContext, _, _| {
-    let graphs = Self::get_user_data(context, &GRAPHS_KEY)?;
+  pipeline_part!(
+    merge_graphs,
+    |metadata| metadata.input(&GRAPHS_KEY).output(&GRAPH_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let graphs = Self::get_user_data(context, &GRAPHS_KEY)?;
 
-    let graph = merge_graphs(graphs).map_err(|e| PipelinePartExecutionError::new_raw(e.to_string()))?;
-    context.put_concrete(GRAPH_KEY.key(), graph);
+      let graph = merge_graphs(graphs).map_err(|e| PipelinePartExecutionError::new_raw(e.to_string()))?;
+      context.put_concrete(GRAPH_KEY.key(), graph);
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 
-  pipeline_part!(add_graph_to_graphs, |context: &mut PipelineContext, _, _| {
-    let graph = Self::get_user_data(context, &GRAPH_KEY)?.clone();
+  pipeline_part!(
+    add_graph_to_graphs,
+    |metadata| metadata.input(&GRAPH_KEY).optional_input(&GRAPHS_KEY).output(&GRAPHS_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let graph = Self::get_user_data(context, &GRAPH_KEY)?.clone();
+
+      match Self::get_user_data_mut(context, &GRAPHS_KEY).ok() {
+        None => context.put_concrete(GRAPHS_KEY.key(), vec![graph]),
+        Some(graphs) => graphs.push(graph),
+      }
 
-    match Self::get_user_data_mut(context, &GRAPHS_KEY).ok() {
-      None => context.put_concrete(GRAPHS_KEY.key(), vec![graph]),
-      Some(graphs) => graphs.push(graph),
+      Ok(())
     }
+  );
 
-    Ok(())
-  });
+  pipeline_part!(
+    clear_graphs,
+    |metadata| metadata.optional_input(&GRAPHS_KEY),
+    |context: &mut PipelineContext, _, _| {
+      if let Ok(graphs) = Self::get_user_data_mut(context, &GRAPHS_KEY) {
+        graphs.clear();
+      }
 
-  pipeline_part!(clear_graphs, |context: &mut PipelineContext, _, _| {
-    if let Ok(graphs) = Self::get_user_data_mut(context, &GRAPHS_KEY) {
-      graphs.clear();
+      Ok(())
     }
+  );
 
-    Ok(())
-  });
+  pipeline_part!(
+    terminate_if_empty_log,
+    |metadata| metadata.input(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      if log.traces().iter().map(|t| t.borrow().events().len()).sum::<usize>() == 0 {
+        return Err(PipelinePartExecutionError::new_raw("Empty log".to_string()));
+      }
 
-  pipeline_part!(terminate_if_empty_log, |context: &mut PipelineContext, _, _| {
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    if log.traces().iter().map(|t| t.borrow().events().len()).sum::<usize>() == 0 {
-      return Err(PipelinePartExecutionError::new_raw("Empty log".to_string()));
+      Ok(())
     }
-
-    Ok(())
-  });
+  );
 }
@@ -0,0 +1,245 @@
+use crate::pipelines::{keys::context_keys::find_context_key, parts_metadata::PipelinePartMetadata, pipeline_parts::PipelineParts};
+use std::{
+  collections::HashSet,
+  fmt::{Display, Formatter},
+};
+
+#[derive(Clone, Debug)]
+pub struct PipelineValidationConfigValue {
+  key_name: String,
+  type_name: Option<String>,
+}
+
+impl PipelineValidationConfigValue {
+  pub fn new(key_name: String, type_name: Option<String>) -> Self {
+    Self { key_name, type_name }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct PipelineValidationPart {
+  name: String,
+  config: Vec<PipelineValidationConfigValue>,
+}
+
+impl PipelineValidationPart {
+  pub fn new(name: String, config: Vec<PipelineValidationConfigValue>) -> Self {
+    Self { name, config }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub enum PipelineValidationStep {
+  Part(PipelineValidationPart),
+  Parallel(Vec<Vec<PipelineValidationStep>>),
+  ContextRequest {
+    keys: Vec<String>,
+    before_part: Option<PipelineValidationPart>,
+  },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PipelineValidationIssueKind {
+  UnknownPart(String),
+  UnknownContextKey(String),
+  MissingConfigKey {
+    part_name: String,
+    key_name: String,
+  },
+  WrongConfigType {
+    part_name: String,
+    key_name: String,
+    expected: String,
+    actual: String,
+  },
+  MissingInputKey {
+    part_name: String,
+    key_name: String,
+  },
+  UnreachableContextRequest(String),
+}
+
+impl Display for PipelineValidationIssueKind {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      PipelineValidationIssueKind::UnknownPart(name) => write!(f, "Unknown pipeline part {}", name),
+      PipelineValidationIssueKind::UnknownContextKey(name) => write!(f, "Unknown context key {}", name),
+      PipelineValidationIssueKind::MissingConfigKey { part_name, key_name } => {
+        write!(f, "Pipeline part {} requires config key {}", part_name, key_name)
+      }
+      PipelineValidationIssueKind::WrongConfigType {
+        part_name,
+        key_name,
+        expected,
+        actual,
+      } => write!(
+        f,
+        "Config key {} of pipeline part {} has type {}, expected {}",
+        key_name, part_name, actual, expected
+      ),
+      PipelineValidationIssueKind::MissingInputKey { part_name, key_name } => {
+        write!(
+          f,
+          "Pipeline part {} requires {} which is not in the context at this point",
+          part_name, key_name
+        )
+      }
+      PipelineValidationIssueKind::UnreachableContextRequest(key_name) => {
+        write!(f, "Requested context value {} is not in the context at this point", key_name)
+      }
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PipelineValidationIssue {
+  location: Vec<usize>,
+  kind: PipelineValidationIssueKind,
+}
+
+impl PipelineValidationIssue {
+  pub fn location(&self) -> &Vec<usize> {
+    &self.location
+  }
+
+  pub fn kind(&self) -> &PipelineValidationIssueKind {
+    &self.kind
+  }
+}
+
+impl Display for PipelineValidationIssue {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let location = self.location.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(".");
+    write!(f, "[{}] {}", location, self.kind)
+  }
+}
+
+/// Walks the pipeline without executing it, tracking which context keys are produced by each part.
+/// Parallel branches start from the same keys, outputs of all branches are available afterwards.
+pub fn validate_pipeline(parts: &PipelineParts, steps: &[PipelineValidationStep], initial_keys: &[String]) -> Vec<PipelineValidationIssue> {
+  let mut validator = PipelineValidator { parts, issues: vec![] };
+  let mut available_keys = initial_keys.iter().cloned().collect::<HashSet<String>>();
+
+  validator.validate_steps(steps, &mut available_keys, &mut vec![]);
+
+  validator.issues
+}
+
+struct PipelineValidator<'a> {
+  parts: &'a PipelineParts,
+  issues: Vec<PipelineValidationIssue>,
+}
+
+impl PipelineValidator<'_> {
+  fn validate_steps(&mut self, steps: &[PipelineValidationStep], available_keys: &mut HashSet<String>, location: &mut Vec<usize>) {
+    for (index, step) in steps.iter().enumerate() {
+      location.push(index);
+      self.validate_step(step, available_keys, location);
+      location.pop();
+    }
+  }
+
+  fn validate_step(&mut self, step: &PipelineValidationStep, available_keys: &mut HashSet<String>, location: &mut Vec<usize>) {
+    match step {
+      PipelineValidationStep::Part(part) => self.validate_part(part, available_keys, location),
+      PipelineValidationStep::Parallel(branches) => {
+        let mut merged_keys = available_keys.clone();
+        for (index, branch) in branches.iter().enumerate() {
+          let mut branch_keys = available_keys.clone();
+
+          location.push(index);
+          self.validate_steps(branch, &mut branch_keys, location);
+          location.pop();
+
+          merged_keys.extend(branch_keys);
+        }
+
+        *available_keys = merged_keys;
+      }
+      PipelineValidationStep::ContextRequest { keys, before_part } => {
+        if let Some(before_part) = before_part {
+          self.validate_part(before_part, available_keys, location);
+        }
+
+        for key_name in keys {
+          if find_context_key(key_name).is_none() {
+            self.report(location, PipelineValidationIssueKind::UnknownContextKey(key_name.to_owned()));
+          } else if !available_keys.contains(key_name) {
+            self.report(
+              location,
+              PipelineValidationIssueKind::UnreachableContextRequest(key_name.to_owned()),
+            );
+          }
+        }
+      }
+    }
+  }
+
+  fn validate_part(&mut self, part: &PipelineValidationPart, available_keys: &mut HashSet<String>, location: &[usize]) {
+    let Some(metadata) = self.parts.find_part_metadata(&part.name) else {
+      self.report(location, PipelineValidationIssueKind::UnknownPart(part.name.to_owned()));
+      return;
+    };
+
+    self.validate_config(part, metadata, location);
+
+    for input in metadata.input_keys() {
+      if input.required() && !available_keys.contains(input.name()) {
+        self.report(
+          location,
+          PipelineValidationIssueKind::MissingInputKey {
+            part_name: part.name.to_owned(),
+            key_name: input.name().to_owned(),
+          },
+        );
+      }
+    }
+
+    for output in metadata.output_keys() {
+      available_keys.insert(output.name().to_owned());
+    }
+  }
+
+  fn validate_config(&mut self, part: &PipelineValidationPart, metadata: &PipelinePartMetadata, location: &[usize]) {
+    for value in &part.config {
+      let Some(key) = find_context_key(&value.key_name) else {
+        self.report(location, PipelineValidationIssueKind::UnknownContextKey(value.key_name.to_owned()));
+        continue;
+      };
+
+      if let Some(actual) = value.type_name.as_ref() {
+        let expected = key.type_name();
+        if *actual != expected {
+          self.report(
+            location,
+            PipelineValidationIssueKind::WrongConfigType {
+              part_name: part.name.to_owned(),
+              key_name: key.key().name().to_owned(),
+              expected,
+              actual: actual.to_owned(),
+            },
+          );
+        }
+      }
+    }
+
+    for config_key in metadata.config_keys() {
+      if config_key.required() && !part.config.iter().any(|v| v.key_name == config_key.name()) {
+        self.report(
+          location,
+          PipelineValidationIssueKind::MissingConfigKey {
+            part_name: part.name.to_owned(),
+            key_name: config_key.name().to_owned(),
+          },
+        );
+      }
+    }
+  }
+
+  fn report(&mut self, location: &[usize], kind: PipelineValidationIssueKind) {
+    self.issues.push(PipelineValidationIssue {
+      location: location.to_vec(),
+      kind,
+    });
+  }
+}
@@ -1,7 +1,4 @@
-use super::{
-  errors::pipeline_errors::{PipelinePartExecutionError, RawPartExecutionError},
-  pipelines::PipelinePartFactory,
-};
+use super::errors::pipeline_errors::{PipelinePartExecutionError, RawPartExecutionError};
 use crate::{
   event_log::{
     bxes::{
@@ -18,122 +15,164 @@ use crate::{
   pipelines::{
     context::PipelineContext,
     keys::context_keys::{BYTES_KEY, EVENT_LOG_KEY, PATH_KEY, PATHS_KEY, SYSTEM_METADATA_KEY},
-    pipeline_parts::PipelineParts,
+    pipeline_parts::{PipelinePartDefinition, PipelineParts},
   },
   utils::user_data::user_data::{UserData, UserDataImpl},
 };
 
 impl PipelineParts {
-  pipeline_part!(write_log_to_xes, |context: &mut PipelineContext, _, config: &UserDataImpl| {
-    let path = Self::get_user_data(config, &PATH_KEY)?;
-    match write_xes_log(context.concrete(EVENT_LOG_KEY.key()).unwrap(), path) {
-      Ok(()) => Ok(()),
-      Err(err) => Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(err.to_string()))),
+  pipeline_part!(
+    write_log_to_xes,
+    |metadata| metadata.config(&PATH_KEY).input(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, config: &UserDataImpl| {
+      let path = Self::get_user_data(config, &PATH_KEY)?;
+      match write_xes_log(context.concrete(EVENT_LOG_KEY.key()).unwrap(), path) {
+        Ok(()) => Ok(()),
+        Err(err) => Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(err.to_string()))),
+      }
     }
-  });
-
-  pipeline_part!(read_log_from_xes, |context: &mut PipelineContext, _, _| {
-    let path = Self::get_user_data(context, &PATH_KEY)?;
+  );
+
+  pipeline_part!(
+    read_log_from_xes,
+    |metadata| metadata.input(&PATH_KEY).output(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let path = Self::get_user_data(context, &PATH_KEY)?;
+
+      let log = read_event_log(path);
+      if log.is_none() {
+        let message = format!("Failed to read event log from {}", path.as_ref());
+        return Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(message)));
+      }
 
-    let log = read_event_log(path);
-    if log.is_none() {
-      let message = format!("Failed to read event log from {}", path.as_ref());
-      return Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(message)));
+      context.put_concrete(EVENT_LOG_KEY.key(), log.unwrap());
+      Ok(())
     }
-
-    context.put_concrete(EVENT_LOG_KEY.key(), log.unwrap());
-    Ok(())
-  });
-
-  pipeline_part!(read_log_from_bxes, |context: &mut PipelineContext, _, _| {
-    let path = Self::get_user_data(context, &PATH_KEY)?;
-
-    match read_bxes_into_xes_log(path) {
-      Ok(result) => {
-        Self::put_read_result_to_context(context, result);
-        Ok(())
-      }
-      Err(err) => {
-        let message = format!("Failed to read event log from {}, error: {}", path.as_ref(), err);
-        Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(message)))
+  );
+
+  pipeline_part!(
+    read_log_from_bxes,
+    |metadata| metadata.input(&PATH_KEY).output(&EVENT_LOG_KEY).output(&SYSTEM_METADATA_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let path = Self::get_user_data(context, &PATH_KEY)?;
+
+      match read_bxes_into_xes_log(path) {
+        Ok(result) => {
+          Self::put_read_result_to_context(context, result);
+          Ok(())
+        }
+        Err(err) => {
+          let message = format!("Failed to read event log from {}, error: {}", path.as_ref(), err);
+          Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(message)))
+        }
       }
     }
-  });
+  );
 
   fn put_read_result_to_context(context: &mut PipelineContext, result: BxesToXesConversionResult) {
     context.put_concrete(EVENT_LOG_KEY.key(), result.xes_log);
     context.put_concrete(SYSTEM_METADATA_KEY.key(), result.system_metadata);
   }
 
-  pipeline_part!(write_log_to_bxes, |context: &mut PipelineContext, _, config: &UserDataImpl| {
-    let path = Self::get_user_data(config, &PATH_KEY)?;
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    let system_metadata = Self::get_user_data(context, &SYSTEM_METADATA_KEY).ok();
-
-    match write_event_log_to_bxes(log, system_metadata, path) {
-      Ok(_) => Ok(()),
-      Err(err) => Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(err.to_string()))),
-    }
-  });
-
-  pipeline_part!(read_xes_log_from_bytes, |context: &mut PipelineContext, _, _| {
-    let bytes = Self::get_user_data(context, &BYTES_KEY)?;
-    match read_event_log_from_bytes(bytes) {
-      Some(log) => {
-        context.put_concrete(EVENT_LOG_KEY.key(), log);
-        Ok(())
-      }
-      None => {
-        let message = "Failed to read event log from bytes array".to_string();
-        Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(message)))
+  pipeline_part!(
+    write_log_to_bxes,
+    |metadata| metadata
+      .config(&PATH_KEY)
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&SYSTEM_METADATA_KEY),
+    |context: &mut PipelineContext, _, config: &UserDataImpl| {
+      let path = Self::get_user_data(config, &PATH_KEY)?;
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      let system_metadata = Self::get_user_data(context, &SYSTEM_METADATA_KEY).ok();
+
+      match write_event_log_to_bxes(log, system_metadata, path) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(err.to_string()))),
       }
     }
-  });
-
-  pipeline_part!(read_bxes_log_from_bytes, |context: &mut PipelineContext, _, _| {
-    let bytes = Self::get_user_data(context, &BYTES_KEY)?;
-    match read_bxes_into_xes_log_from_bytes(bytes) {
-      Ok(read_result) => {
-        Self::put_read_result_to_context(context, read_result);
-        Ok(())
+  );
+
+  pipeline_part!(
+    read_xes_log_from_bytes,
+    |metadata| metadata.input(&BYTES_KEY).output(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let bytes = Self::get_user_data(context, &BYTES_KEY)?;
+      match read_event_log_from_bytes(bytes) {
+        Some(log) => {
+          context.put_concrete(EVENT_LOG_KEY.key(), log);
+          Ok(())
+        }
+        None => {
+          let message = "Failed to read event log from bytes array".to_string();
+          Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(message)))
+        }
       }
-      Err(err) => {
-        let message = format!("Failed to read event log from bytes: {}", err);
-        Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(message)))
+    }
+  );
+
+  pipeline_part!(
+    read_bxes_log_from_bytes,
+    |metadata| metadata.input(&BYTES_KEY).output(&EVENT_LOG_KEY).output(&SYSTEM_METADATA_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let bytes = Self::get_user_data(context, &BYTES_KEY)?;
+      match read_bxes_into_xes_log_from_bytes(bytes) {
+        Ok(read_result) => {
+          Self::put_read_result_to_context(context, read_result);
+          Ok(())
+        }
+        Err(err) => {
+          let message = format!("Failed to read event log from bytes: {}", err);
+          Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(message)))
+        }
       }
     }
-  });
-
-  pipeline_part!(write_bxes_log_to_bytes, |context: &mut PipelineContext, _, _| {
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    let system_metadata = Self::get_user_data(context, &SYSTEM_METADATA_KEY).ok();
-
-    match write_event_log_to_bxes_bytes(log, system_metadata) {
-      Ok(bytes) => {
-        context.put_concrete::<Vec<u8>>(BYTES_KEY.key(), bytes);
-        Ok(())
+  );
+
+  pipeline_part!(
+    write_bxes_log_to_bytes,
+    |metadata| metadata
+      .input(&EVENT_LOG_KEY)
+      .optional_input(&SYSTEM_METADATA_KEY)
+      .output(&BYTES_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      let system_metadata = Self::get_user_data(context, &SYSTEM_METADATA_KEY).ok();
+
+      match write_event_log_to_bxes_bytes(log, system_metadata) {
+        Ok(bytes) => {
+          context.put_concrete::<Vec<u8>>(BYTES_KEY.key(), bytes);
+          Ok(())
+        }
+        Err(err) => Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(err.to_string()))),
       }
-      Err(err) => Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(err.to_string()))),
     }
-  });
-
-  pipeline_part!(write_xes_log_to_bytes, |context: &mut PipelineContext, _, _| {
-    let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
-    match write_xes_log_to_bytes(log) {
-      Ok(bytes) => {
-        context.put_concrete::<Vec<u8>>(BYTES_KEY.key(), bytes);
-        Ok(())
+  );
+
+  pipeline_part!(
+    write_xes_log_to_bytes,
+    |metadata| metadata.input(&EVENT_LOG_KEY).output(&BYTES_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let log = Self::get_user_data(context, &EVENT_LOG_KEY)?;
+      match write_xes_log_to_bytes(log) {
+        Ok(bytes) => {
+          context.put_concrete::<Vec<u8>>(BYTES_KEY.key(), bytes);
+          Ok(())
+        }
+        Err(err) => Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(err.to_string()))),
       }
-      Err(err) => Err(PipelinePartExecutionError::Raw(RawPartExecutionError::new(err.to_string()))),
     }
-  });
+  );
 
-  pipeline_part!(merge_xes_logs_from_paths, |context: &mut PipelineContext, _, _| {
-    let paths = Self::get_user_data(context, &PATHS_KEY)?;
-    let log = merge_xes_logs(paths);
+  pipeline_part!(
+    merge_xes_logs_from_paths,
+    |metadata| metadata.input(&PATHS_KEY).output(&EVENT_LOG_KEY),
+    |context: &mut PipelineContext, _, _| {
+      let paths = Self::get_user_data(context, &PATHS_KEY)?;
+      let log = merge_xes_logs(paths);
 
-    context.put_concrete(EVENT_LOG_KEY.key(), log);
+      context.put_concrete(EVENT_LOG_KEY.key(), log);
 
-    Ok(())
-  });
+      Ok(())
+    }
+  );
 }
@@ -0,0 +1,77 @@
+use std::{
+  cell::Cell,
+  ptr,
+  sync::{
+    Arc,
+    atomic::{AtomicIsize, Ordering},
+  },
+};
+
+thread_local! {
+  static CURRENT_COUNTER: Cell<*const AtomicIsize> = const { Cell::new(ptr::null()) };
+}
+
+/// Counts bytes allocated by all threads which entered it, e.g. by all threads of one pipeline execution.
+/// Allocations are reported by the global allocator of the binary through `record_allocation`.
+#[derive(Clone, Debug, Default)]
+pub struct AllocationsCounter {
+  allocated_bytes: Arc<AtomicIsize>,
+}
+
+impl AllocationsCounter {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Counter entered by the current thread
+  pub fn current() -> Option<Self> {
+    let counter = CURRENT_COUNTER.try_with(|counter| counter.get()).unwrap_or(ptr::null());
+    if counter.is_null() {
+      return None;
+    }
+
+    // The counter is kept alive by the scope which is entered on this thread
+    unsafe {
+      Arc::increment_strong_count(counter);
+      Some(Self {
+        allocated_bytes: Arc::from_raw(counter),
+      })
+    }
+  }
+
+  pub fn allocated_bytes(&self) -> usize {
+    self.allocated_bytes.load(Ordering::Relaxed).max(0) as usize
+  }
+
+  /// Allocations of the current thread are counted until the returned scope is dropped
+  pub fn enter(&self) -> AllocationsCounterScope {
+    let counter = Arc::as_ptr(&self.allocated_bytes);
+    let previous = CURRENT_COUNTER.with(|current| current.replace(counter));
+
+    AllocationsCounterScope {
+      _counter: self.clone(),
+      previous,
+    }
+  }
+}
+
+pub struct AllocationsCounterScope {
+  _counter: AllocationsCounter,
+  previous: *const AtomicIsize,
+}
+
+impl Drop for AllocationsCounterScope {
+  fn drop(&mut self) {
+    CURRENT_COUNTER.with(|current| current.set(self.previous));
+  }
+}
+
+/// Must not allocate, as it is called from the global allocator
+pub fn record_allocation(delta: isize) {
+  let _ = CURRENT_COUNTER.try_with(|counter| {
+    let counter = counter.get();
+    if !counter.is_null() {
+      unsafe { (*counter).fetch_add(delta, Ordering::Relaxed) };
+    }
+  });
+}
@@ -50,7 +50,7 @@ impl Color {
   }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct ColorsHolder {
   names_to_colors: HashMap<Arc<str>, Color>,
   used_colors: HashSet<Color>,
@@ -69,11 +69,13 @@ impl ColorsHolder {
   }
 }
 
+#[derive(Clone)]
 pub struct ColorsEventLog {
   pub mapping: HashMap<Arc<str>, Color>,
   pub traces: Vec<Vec<ColoredRectangle>>,
 }
 
+#[derive(Clone)]
 pub struct ColoredRectangle {
   name: Arc<str>,
   start_x: f64,
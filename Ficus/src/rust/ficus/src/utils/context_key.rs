@@ -3,6 +3,7 @@ use std::hash::{Hash, Hasher};
 
 pub trait ContextKey {
   fn key(&self) -> &dyn Key;
+  fn type_name(&self) -> String;
 }
 
 pub struct DefaultContextKey<T>
@@ -16,6 +17,36 @@ impl<T> ContextKey for DefaultContextKey<T> {
   fn key(&self) -> &dyn Key {
     &self.key
   }
+
+  fn type_name(&self) -> String {
+    context_type_name::<T>()
+  }
+}
+
+pub fn context_type_name<T>() -> String {
+  short_type_name(std::any::type_name::<T>())
+}
+
+fn short_type_name(full_type_name: &str) -> String {
+  let mut result = String::new();
+  let mut segment_start = 0;
+
+  for (index, c) in full_type_name.char_indices() {
+    if c.is_alphanumeric() || c == '_' || c == ':' {
+      continue;
+    }
+
+    result.push_str(last_path_segment(&full_type_name[segment_start..index]));
+    result.push(c);
+    segment_start = index + c.len_utf8();
+  }
+
+  result.push_str(last_path_segment(&full_type_name[segment_start..]));
+  result
+}
+
+fn last_path_segment(path: &str) -> &str {
+  path.rsplit("::").next().unwrap_or(path)
 }
 
 impl<T> Clone for DefaultContextKey<T> {
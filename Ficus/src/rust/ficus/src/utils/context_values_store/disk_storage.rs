@@ -0,0 +1,158 @@
+use crate::utils::context_values_store::storage::{ExecutionValues, PersistedEntry, StoredValue, ValuesStorage};
+use log::error;
+use std::{
+  collections::HashMap,
+  fs, io,
+  marker::PhantomData,
+  path::{Path, PathBuf},
+  str::FromStr,
+  time::SystemTime,
+};
+use uuid::Uuid;
+
+const VALUES_DIR: &str = "values";
+const EXECUTIONS_DIR: &str = "executions";
+
+// Stores every context value as a file with its bytes and every execution result as a json map
+// from context key names to context value ids, so that results survive a restart.
+pub struct DiskValuesStorage<T: StoredValue> {
+  values_dir: PathBuf,
+  executions_dir: PathBuf,
+  _phantom: PhantomData<T>,
+}
+
+impl<T: StoredValue> DiskValuesStorage<T> {
+  pub fn open(root: &Path) -> io::Result<Self> {
+    let values_dir = root.join(VALUES_DIR);
+    let executions_dir = root.join(EXECUTIONS_DIR);
+
+    fs::create_dir_all(&values_dir)?;
+    fs::create_dir_all(&executions_dir)?;
+
+    Ok(Self {
+      values_dir,
+      executions_dir,
+      _phantom: PhantomData,
+    })
+  }
+
+  fn file_path(dir: &Path, id: &str) -> Option<PathBuf> {
+    match Uuid::from_str(id) {
+      Ok(uuid) => Some(dir.join(uuid.to_string())),
+      Err(_) => {
+        error!("Context value store id {} is not a valid uuid", id);
+        None
+      }
+    }
+  }
+
+  fn read_value(path: &Path) -> Option<T> {
+    let bytes = fs::read(path).ok()?;
+    match T::from_bytes(bytes.as_slice()) {
+      Ok(value) => Some(value),
+      Err(err) => {
+        error!("Failed to decode context value from {}, error: {}", path.display(), err);
+        None
+      }
+    }
+  }
+
+  fn read_execution(path: &Path) -> Option<ExecutionValues> {
+    let bytes = fs::read(path).ok()?;
+    match serde_json::from_slice::<HashMap<String, String>>(bytes.as_slice()) {
+      Ok(keys_to_ids) => keys_to_ids
+        .into_iter()
+        .map(|(key, id)| Uuid::from_str(&id).ok().map(|id| (key, id)))
+        .collect(),
+      Err(err) => {
+        error!("Failed to decode execution result from {}, error: {}", path.display(), err);
+        None
+      }
+    }
+  }
+
+  fn write_file(path: &Path, bytes: &[u8]) {
+    let temp_path = path.with_extension("tmp");
+    let result = fs::write(&temp_path, bytes).and_then(|_| fs::rename(&temp_path, path));
+
+    if let Err(err) = result {
+      error!("Failed to write {}, error: {}", path.display(), err);
+    }
+  }
+
+  fn persisted_entries<TEntry>(dir: &Path, read: impl Fn(&Path, &fs::Metadata) -> Option<TEntry>) -> Vec<PersistedEntry<TEntry>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+      return vec![];
+    };
+
+    let mut persisted = vec![];
+    for entry in entries.flatten() {
+      let path = entry.path();
+      let Some(id) = path.file_name().and_then(|name| name.to_str()) else {
+        continue;
+      };
+
+      if Uuid::from_str(id).is_err() {
+        // Leftover of an interrupted write
+        fs::remove_file(&path).ok();
+        continue;
+      }
+
+      let Ok(metadata) = entry.metadata() else { continue };
+      if let Some(value) = read(&path, &metadata) {
+        persisted.push(PersistedEntry {
+          id: id.to_owned(),
+          value,
+          modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+        });
+      }
+    }
+
+    persisted
+  }
+}
+
+impl<T: StoredValue> ValuesStorage<T> for DiskValuesStorage<T> {
+  fn persisted_values(&self) -> Vec<PersistedEntry<usize>> {
+    Self::persisted_entries(&self.values_dir, |_, metadata| Some(metadata.len() as usize))
+  }
+
+  fn persisted_executions(&self) -> Vec<PersistedEntry<ExecutionValues>> {
+    Self::persisted_entries(&self.executions_dir, |path, _| Self::read_execution(path))
+  }
+
+  fn put_value(&mut self, id: &str, value: T) {
+    if let Some(path) = Self::file_path(&self.values_dir, id) {
+      Self::write_file(&path, value.to_bytes().as_slice());
+    }
+  }
+
+  fn get_value(&self, id: &str) -> Option<T> {
+    Self::read_value(&Self::file_path(&self.values_dir, id)?)
+  }
+
+  fn remove_value(&mut self, id: &str) -> Option<T> {
+    let path = Self::file_path(&self.values_dir, id)?;
+    let value = Self::read_value(&path);
+    fs::remove_file(&path).ok();
+
+    value
+  }
+
+  fn put_execution(&mut self, execution_id: &str, values: &ExecutionValues) {
+    let Some(path) = Self::file_path(&self.executions_dir, execution_id) else {
+      return;
+    };
+
+    let keys_to_ids: HashMap<&String, String> = values.iter().map(|(key, id)| (key, id.to_string())).collect();
+    let bytes = serde_json::to_vec(&keys_to_ids).expect("Should serialize map of strings");
+
+    Self::write_file(&path, bytes.as_slice());
+  }
+
+  fn remove_execution(&mut self, execution_id: &str) {
+    if let Some(path) = Self::file_path(&self.executions_dir, execution_id) {
+      fs::remove_file(&path).ok();
+    }
+  }
+}
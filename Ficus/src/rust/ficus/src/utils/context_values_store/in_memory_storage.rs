@@ -0,0 +1,38 @@
+use crate::utils::context_values_store::storage::{ExecutionValues, PersistedEntry, StoredValue, ValuesStorage};
+use std::collections::HashMap;
+
+pub struct InMemoryValuesStorage<T: StoredValue> {
+  values: HashMap<String, T>,
+}
+
+impl<T: StoredValue> Default for InMemoryValuesStorage<T> {
+  fn default() -> Self {
+    Self { values: HashMap::new() }
+  }
+}
+
+impl<T: StoredValue> ValuesStorage<T> for InMemoryValuesStorage<T> {
+  fn persisted_values(&self) -> Vec<PersistedEntry<usize>> {
+    vec![]
+  }
+
+  fn persisted_executions(&self) -> Vec<PersistedEntry<ExecutionValues>> {
+    vec![]
+  }
+
+  fn put_value(&mut self, id: &str, value: T) {
+    self.values.insert(id.to_owned(), value);
+  }
+
+  fn get_value(&self, id: &str) -> Option<T> {
+    self.values.get(id).cloned()
+  }
+
+  fn remove_value(&mut self, id: &str) -> Option<T> {
+    self.values.remove(id)
+  }
+
+  fn put_execution(&mut self, _: &str, _: &ExecutionValues) {}
+
+  fn remove_execution(&mut self, _: &str) {}
+}
@@ -0,0 +1,4 @@
+pub mod disk_storage;
+pub mod in_memory_storage;
+pub mod storage;
+pub mod store;
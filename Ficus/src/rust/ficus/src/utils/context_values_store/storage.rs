@@ -0,0 +1,32 @@
+use std::{collections::HashMap, time::SystemTime};
+use uuid::Uuid;
+
+/// Map from context key names to ids of context values produced by one execution
+pub type ExecutionValues = HashMap<String, Uuid>;
+
+pub trait StoredValue: Clone + Send + 'static {
+  fn size_in_bytes(&self) -> usize;
+  fn to_bytes(&self) -> Vec<u8>;
+  fn from_bytes(bytes: &[u8]) -> Result<Self, String>;
+}
+
+/// Entry which was stored before the storage was opened
+pub struct PersistedEntry<T> {
+  pub id: String,
+  pub value: T,
+  pub modified: SystemTime,
+}
+
+/// Keeps values and executions, limits and access order are maintained by the `ContextValueStore`
+pub trait ValuesStorage<T: StoredValue>: Send {
+  /// Sizes of values which were stored before the storage was opened
+  fn persisted_values(&self) -> Vec<PersistedEntry<usize>>;
+  fn persisted_executions(&self) -> Vec<PersistedEntry<ExecutionValues>>;
+
+  fn put_value(&mut self, id: &str, value: T);
+  fn get_value(&self, id: &str) -> Option<T>;
+  fn remove_value(&mut self, id: &str) -> Option<T>;
+
+  fn put_execution(&mut self, execution_id: &str, values: &ExecutionValues);
+  fn remove_execution(&mut self, execution_id: &str);
+}
@@ -0,0 +1,309 @@
+use crate::utils::context_values_store::storage::{ExecutionValues, StoredValue, ValuesStorage};
+use log::{info, warn};
+use std::{
+  collections::HashMap,
+  mem::size_of,
+  sync::Mutex,
+  time::{Duration, Instant, SystemTime},
+};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Default)]
+pub struct ContextValueStoreLimits {
+  pub ttl: Option<Duration>,
+  pub max_size_bytes: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum EntryId {
+  Value(String),
+  Execution(String),
+}
+
+struct EntryUsage {
+  size: usize,
+  last_access_time: Instant,
+  last_access_stamp: u64,
+}
+
+struct StoreState<T: StoredValue> {
+  storage: Box<dyn ValuesStorage<T>>,
+  executions: HashMap<String, ExecutionValues>,
+  usages: HashMap<EntryId, EntryUsage>,
+  total_size: usize,
+  next_access_stamp: u64,
+}
+
+/// Keeps context values and execution results in a storage, both are counted towards the size limit
+/// and are evicted when expired or least recently used. Executions never reference removed values.
+pub struct ContextValueStore<T: StoredValue> {
+  limits: ContextValueStoreLimits,
+  state: Mutex<StoreState<T>>,
+}
+
+impl<T: StoredValue> ContextValueStore<T> {
+  pub fn new(storage: Box<dyn ValuesStorage<T>>, limits: ContextValueStoreLimits) -> Self {
+    let mut state = StoreState {
+      storage,
+      executions: HashMap::new(),
+      usages: HashMap::new(),
+      total_size: 0,
+      next_access_stamp: 0,
+    };
+
+    Self::load_persisted_entries(&mut state);
+
+    let store = Self {
+      limits,
+      state: Mutex::new(state),
+    };
+
+    store.enforce_limits(&mut store.state.lock().unwrap());
+    store
+  }
+
+  fn load_persisted_entries(state: &mut StoreState<T>) {
+    let mut entries = vec![];
+    for value in state.storage.persisted_values() {
+      entries.push((value.modified, EntryId::Value(value.id), value.value, None));
+    }
+
+    for execution in state.storage.persisted_executions() {
+      let size = Self::execution_size(&execution.value);
+      entries.push((execution.modified, EntryId::Execution(execution.id), size, Some(execution.value)));
+    }
+
+    entries.sort_by_key(|(modified, _, _, _)| *modified);
+
+    let (now, system_now) = (Instant::now(), SystemTime::now());
+    for (modified, id, size, execution) in entries {
+      let age = system_now.duration_since(modified).unwrap_or_default();
+      let last_access_time = now.checked_sub(age).unwrap_or(now);
+
+      if let (EntryId::Execution(execution_id), Some(execution)) = (&id, execution) {
+        state.executions.insert(execution_id.clone(), execution);
+      }
+
+      Self::insert_usage(state, id, size, last_access_time);
+    }
+
+    let executions_ids: Vec<String> = state.executions.keys().cloned().collect();
+    for execution_id in executions_ids {
+      let references_removed_value = state.executions[&execution_id]
+        .values()
+        .any(|id| !state.usages.contains_key(&EntryId::Value(id.to_string())));
+
+      if references_removed_value {
+        Self::retain_stored_values(state, &execution_id);
+      }
+    }
+  }
+
+  fn retain_stored_values(state: &mut StoreState<T>, execution_id: &str) {
+    let usages = &state.usages;
+    let execution = state.executions.get_mut(execution_id).unwrap();
+    execution.retain(|_, id| usages.contains_key(&EntryId::Value(id.to_string())));
+
+    Self::update_execution(state, execution_id);
+  }
+
+  fn execution_size(values: &ExecutionValues) -> usize {
+    values.keys().map(|key| key.len() + size_of::<Uuid>()).sum()
+  }
+
+  fn next_stamp(state: &mut StoreState<T>) -> u64 {
+    state.next_access_stamp += 1;
+    state.next_access_stamp
+  }
+
+  fn insert_usage(state: &mut StoreState<T>, id: EntryId, size: usize, last_access_time: Instant) {
+    let usage = EntryUsage {
+      size,
+      last_access_time,
+      last_access_stamp: Self::next_stamp(state),
+    };
+
+    if let Some(previous) = state.usages.insert(id, usage) {
+      state.total_size -= previous.size;
+    }
+
+    state.total_size += size;
+  }
+
+  fn touch(state: &mut StoreState<T>, id: &EntryId) -> bool {
+    let stamp = Self::next_stamp(state);
+    match state.usages.get_mut(id) {
+      Some(usage) => {
+        usage.last_access_time = Instant::now();
+        usage.last_access_stamp = stamp;
+        true
+      }
+      None => false,
+    }
+  }
+
+  fn remove_entry(state: &mut StoreState<T>, id: &EntryId) -> Option<T> {
+    let usage = state.usages.remove(id)?;
+    state.total_size -= usage.size;
+
+    match id {
+      EntryId::Value(value_id) => {
+        let value = state.storage.remove_value(value_id);
+        Self::forget_value_in_executions(state, value_id);
+
+        value
+      }
+      EntryId::Execution(execution_id) => {
+        state.executions.remove(execution_id);
+        state.storage.remove_execution(execution_id);
+
+        None
+      }
+    }
+  }
+
+  fn forget_value_in_executions(state: &mut StoreState<T>, value_id: &str) {
+    let referencing_executions: Vec<String> = state
+      .executions
+      .iter()
+      .filter(|(_, values)| values.values().any(|id| id.to_string() == value_id))
+      .map(|(execution_id, _)| execution_id.clone())
+      .collect();
+
+    for execution_id in referencing_executions {
+      Self::retain_stored_values(state, &execution_id);
+    }
+  }
+
+  fn update_execution(state: &mut StoreState<T>, execution_id: &str) {
+    // Execution which lost all of its values is useless
+    let execution = &state.executions[execution_id];
+    if execution.is_empty() {
+      Self::remove_entry(state, &EntryId::Execution(execution_id.to_owned()));
+      return;
+    }
+
+    let size = Self::execution_size(execution);
+    state.storage.put_execution(execution_id, execution);
+
+    let id = EntryId::Execution(execution_id.to_owned());
+    if let Some(usage) = state.usages.get_mut(&id) {
+      state.total_size = state.total_size - usage.size + size;
+      usage.size = size;
+    }
+  }
+
+  fn enforce_limits(&self, state: &mut StoreState<T>) {
+    if let Some(ttl) = self.limits.ttl {
+      let now = Instant::now();
+      let expired: Vec<EntryId> = state
+        .usages
+        .iter()
+        .filter(|(_, usage)| now.duration_since(usage.last_access_time) > ttl)
+        .map(|(id, _)| id.clone())
+        .collect();
+
+      for id in expired {
+        Self::remove_entry(state, &id);
+      }
+    }
+
+    let Some(max_size) = self.limits.max_size_bytes else { return };
+
+    while state.total_size > max_size {
+      let lru_id = state
+        .usages
+        .iter()
+        .min_by_key(|(_, usage)| usage.last_access_stamp)
+        .map(|(id, _)| id.clone());
+
+      let Some(lru_id) = lru_id else { break };
+
+      Self::remove_entry(state, &lru_id);
+      info!("Evicted {:?}, store size is {} bytes", lru_id, state.total_size);
+    }
+  }
+
+  /// Values larger than the size limit are not stored
+  pub fn put_value(&self, id: &str, value: T) {
+    let mut state = self.state.lock().unwrap();
+
+    let size = value.size_in_bytes();
+    if self.limits.max_size_bytes.is_some_and(|max_size| size > max_size) {
+      warn!("Context value {} of size {} bytes exceeds the store size limit", id, size);
+      Self::remove_entry(&mut state, &EntryId::Value(id.to_owned()));
+      return;
+    }
+
+    Self::insert_usage(&mut state, EntryId::Value(id.to_owned()), size, Instant::now());
+    state.storage.put_value(id, value);
+
+    self.enforce_limits(&mut state);
+  }
+
+  pub fn get_value(&self, id: &str) -> Option<T> {
+    let mut state = self.state.lock().unwrap();
+    self.enforce_limits(&mut state);
+
+    match Self::touch(&mut state, &EntryId::Value(id.to_owned())) {
+      true => state.storage.get_value(id),
+      false => None,
+    }
+  }
+
+  pub fn remove_value(&self, id: &str) -> Option<T> {
+    let mut state = self.state.lock().unwrap();
+    self.enforce_limits(&mut state);
+
+    Self::remove_entry(&mut state, &EntryId::Value(id.to_owned()))
+  }
+
+  pub fn values_count(&self) -> usize {
+    let mut state = self.state.lock().unwrap();
+    self.enforce_limits(&mut state);
+
+    state.usages.len() - state.executions.len()
+  }
+
+  pub fn size_in_bytes(&self) -> usize {
+    let mut state = self.state.lock().unwrap();
+    self.enforce_limits(&mut state);
+
+    state.total_size
+  }
+
+  /// Ids of values which are not stored are not added to the execution
+  pub fn put_execution(&self, execution_id: &str, mut values: ExecutionValues) {
+    let mut state = self.state.lock().unwrap();
+    self.enforce_limits(&mut state);
+
+    values.retain(|_, id| state.usages.contains_key(&EntryId::Value(id.to_string())));
+
+    let size = Self::execution_size(&values);
+    Self::insert_usage(&mut state, EntryId::Execution(execution_id.to_owned()), size, Instant::now());
+    state.storage.put_execution(execution_id, &values);
+    state.executions.insert(execution_id.to_owned(), values);
+
+    self.enforce_limits(&mut state);
+  }
+
+  pub fn get_execution(&self, execution_id: &str) -> Option<ExecutionValues> {
+    let mut state = self.state.lock().unwrap();
+    self.enforce_limits(&mut state);
+
+    Self::touch(&mut state, &EntryId::Execution(execution_id.to_owned()));
+    state.executions.get(execution_id).cloned()
+  }
+
+  pub fn remove_execution(&self, execution_id: &str) -> bool {
+    let mut state = self.state.lock().unwrap();
+    self.enforce_limits(&mut state);
+
+    if !state.executions.contains_key(execution_id) {
+      return false;
+    }
+
+    Self::remove_entry(&mut state, &EntryId::Execution(execution_id.to_owned()));
+    true
+  }
+}
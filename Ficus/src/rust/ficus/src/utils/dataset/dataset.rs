@@ -1,5 +1,6 @@
 use crate::utils::colors::Color;
 
+#[derive(Clone)]
 pub struct FicusDataset {
   values: Vec<Vec<f64>>,
   columns_names: Vec<String>,
@@ -28,6 +29,7 @@ impl FicusDataset {
   }
 }
 
+#[derive(Clone)]
 pub struct LabeledDataset {
   dataset: FicusDataset,
   labels: Vec<usize>,
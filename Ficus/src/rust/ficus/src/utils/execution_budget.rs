@@ -0,0 +1,148 @@
+use crate::utils::cancellation::CancellationToken;
+use std::{
+  fmt::{Debug, Display, Formatter},
+  sync::{
+    Arc,
+    mpsc::{self, RecvTimeoutError, Sender},
+  },
+  thread::{self, JoinHandle},
+  time::{Duration, Instant},
+};
+
+const WATCH_INTERVAL: Duration = Duration::from_millis(10);
+
+pub type MemoryUsageProbe = Arc<dyn Fn() -> usize + Send + Sync>;
+
+#[derive(Clone)]
+pub struct ExecutionBudget {
+  started_at: Instant,
+  time_limit: Option<Duration>,
+  memory_limit: Option<(usize, MemoryUsageProbe)>,
+}
+
+impl Default for ExecutionBudget {
+  fn default() -> Self {
+    Self::unlimited()
+  }
+}
+
+impl ExecutionBudget {
+  pub fn unlimited() -> Self {
+    Self {
+      started_at: Instant::now(),
+      time_limit: None,
+      memory_limit: None,
+    }
+  }
+
+  pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+    self.time_limit = Some(time_limit);
+    self
+  }
+
+  pub fn with_memory_limit(mut self, memory_limit_bytes: usize, probe: MemoryUsageProbe) -> Self {
+    self.memory_limit = Some((memory_limit_bytes, probe));
+    self
+  }
+
+  pub fn check(&self) -> Result<(), ExecutionBudgetViolation> {
+    if let Some(limit) = self.time_limit {
+      let elapsed = self.started_at.elapsed();
+      if elapsed > limit {
+        return Err(ExecutionBudgetViolation::TimeLimitExceeded { limit, elapsed });
+      }
+    }
+
+    if let Some((limit, probe)) = self.memory_limit.as_ref() {
+      let used = probe();
+      if used > *limit {
+        return Err(ExecutionBudgetViolation::MemoryLimitExceeded { limit: *limit, used });
+      }
+    }
+
+    Ok(())
+  }
+
+  pub fn is_unlimited(&self) -> bool {
+    self.time_limit.is_none() && self.memory_limit.is_none()
+  }
+
+  /// Checks the budget in background until the watcher is stopped and cancels `token` when the budget is exceeded,
+  /// so that the budget is also enforced inside long computations which check for cancellation.
+  pub fn watch(&self, token: CancellationToken) -> ExecutionBudgetWatcher {
+    if self.is_unlimited() {
+      return ExecutionBudgetWatcher {
+        stop_sender: None,
+        handle: None,
+      };
+    }
+
+    let (stop_sender, stop_receiver) = mpsc::channel::<()>();
+    let budget = self.clone();
+
+    let handle = thread::spawn(move || {
+      loop {
+        if let Err(violation) = budget.check() {
+          token.cancel();
+          return Some(violation);
+        }
+
+        if !matches!(stop_receiver.recv_timeout(WATCH_INTERVAL), Err(RecvTimeoutError::Timeout)) {
+          return None;
+        }
+      }
+    });
+
+    ExecutionBudgetWatcher {
+      stop_sender: Some(stop_sender),
+      handle: Some(handle),
+    }
+  }
+}
+
+pub struct ExecutionBudgetWatcher {
+  stop_sender: Option<Sender<()>>,
+  handle: Option<JoinHandle<Option<ExecutionBudgetViolation>>>,
+}
+
+impl ExecutionBudgetWatcher {
+  /// Returns the violation if the budget was exceeded while it was watched
+  pub fn stop(mut self) -> Option<ExecutionBudgetViolation> {
+    self.stop_watching()
+  }
+
+  fn stop_watching(&mut self) -> Option<ExecutionBudgetViolation> {
+    drop(self.stop_sender.take());
+    self.handle.take().and_then(|handle| handle.join().ok().flatten())
+  }
+}
+
+impl Drop for ExecutionBudgetWatcher {
+  fn drop(&mut self) {
+    self.stop_watching();
+  }
+}
+
+#[derive(Clone, Debug)]
+pub enum ExecutionBudgetViolation {
+  TimeLimitExceeded { limit: Duration, elapsed: Duration },
+  MemoryLimitExceeded { limit: usize, used: usize },
+}
+
+impl Display for ExecutionBudgetViolation {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ExecutionBudgetViolation::TimeLimitExceeded { limit, elapsed } => f.write_str(
+        format!(
+          "Time budget of {}ms was exceeded, pipeline was running for {}ms",
+          limit.as_millis(),
+          elapsed.as_millis()
+        )
+        .as_str(),
+      ),
+      ExecutionBudgetViolation::MemoryLimitExceeded { limit, used } => {
+        f.write_str(format!("Memory budget of {} bytes was exceeded, {} bytes are used", limit, used).as_str())
+      }
+    }
+  }
+}
@@ -269,3 +269,25 @@ where
     self.add_node_internal(GraphNode::new_with_user_data(other_node.data.clone(), other_node.user_data.clone()))
   }
 }
+
+impl<TNodeData, TEdgeData> Graph<TNodeData, TEdgeData>
+where
+  TNodeData: ToString + Clone,
+  TEdgeData: ToString + Clone,
+{
+  /// Clones the graph with the same ids, user data of the graph, its nodes and edges is copied with `copy_user_data`.
+  pub(crate) fn try_clone_with(&self, copy_user_data: &impl Fn(&UserDataImpl) -> Result<UserDataImpl, String>) -> Result<Self, String> {
+    let mut graph = self.clone();
+    graph.user_data = copy_user_data(&self.user_data)?;
+
+    for node in graph.nodes.values_mut() {
+      node.user_data = copy_user_data(&node.user_data)?;
+    }
+
+    for edge in graph.connections.values_mut().flat_map(|connections| connections.values_mut()) {
+      edge.user_data = copy_user_data(&edge.user_data)?;
+    }
+
+    Ok(graph)
+  }
+}
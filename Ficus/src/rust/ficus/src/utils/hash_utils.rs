@@ -33,3 +33,59 @@ pub fn compare_based_on_hashes<T: Hash>(first: &T, second: &T) -> bool {
 
   first_hasher.finish() == second_hasher.finish()
 }
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Seeded FNV-1a hasher. Unlike `DefaultHasher` its output does not depend on the Rust release or platform,
+/// so it can be used for hashes which are persisted.
+pub struct StableHasher {
+  state: u64,
+}
+
+impl StableHasher {
+  pub fn with_seed(seed: u64) -> Self {
+    let mut hasher = Self { state: FNV_OFFSET_BASIS };
+    hasher.write_u64(seed);
+
+    hasher
+  }
+}
+
+impl Hasher for StableHasher {
+  fn finish(&self) -> u64 {
+    // splitmix64 finalizer, FNV alone distributes low bits poorly
+    let mut hash = self.state;
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+    hash ^ (hash >> 31)
+  }
+
+  fn write(&mut self, bytes: &[u8]) {
+    for byte in bytes {
+      self.state ^= *byte as u64;
+      self.state = self.state.wrapping_mul(FNV_PRIME);
+    }
+  }
+
+  fn write_u16(&mut self, i: u16) {
+    self.write(&i.to_le_bytes());
+  }
+
+  fn write_u32(&mut self, i: u32) {
+    self.write(&i.to_le_bytes());
+  }
+
+  fn write_u64(&mut self, i: u64) {
+    self.write(&i.to_le_bytes());
+  }
+
+  fn write_u128(&mut self, i: u128) {
+    self.write(&i.to_le_bytes());
+  }
+
+  fn write_usize(&mut self, i: usize) {
+    self.write_u64(i as u64);
+  }
+}
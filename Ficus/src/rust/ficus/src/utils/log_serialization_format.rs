@@ -1,5 +1,6 @@
 use std::str::FromStr;
 
+#[derive(Clone)]
 pub enum LogSerializationFormat {
   Xes,
   Bxes,
@@ -1,8 +1,12 @@
+pub mod allocations_tracking;
+pub mod cancellation;
 pub mod colors;
 pub mod context_key;
+pub mod context_values_store;
 pub mod dataset;
 pub mod display_name;
 pub mod distance;
+pub mod execution_budget;
 pub mod graph;
 pub mod hash_map_utils;
 pub mod hash_utils;
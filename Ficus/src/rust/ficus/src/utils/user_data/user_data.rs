@@ -1,7 +1,12 @@
 use super::keys::{DefaultKey, Key};
 use derive_new::new;
 use getset::Getters;
-use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+  any::Any,
+  cell::{Cell, RefCell},
+  collections::HashMap,
+  rc::Rc,
+};
 
 pub trait UserDataOwner {
   fn user_data(&self) -> &UserDataImpl;
@@ -13,12 +18,22 @@ pub trait ExecuteWithUserData {
   fn execute_with_user_data_mut(&mut self, func: &mut dyn FnMut(&mut UserDataImpl));
 }
 
-#[derive(new, Getters, Debug, Clone)]
+#[derive(new, Getters, Debug)]
 struct UserDataValue {
   #[getset(get = "pub")]
   value: Rc<RefCell<dyn Any>>,
   #[getset(get = "pub")]
   key_name: String,
+  /// Set when the value is accessed mutably through this user data, the value may have been changed in place
+  #[new(default)]
+  accessed_mutably: Cell<bool>,
+}
+
+impl Clone for UserDataValue {
+  /// Mutable accesses made through the original user data are not carried over to the clone
+  fn clone(&self) -> Self {
+    Self::new(self.value.clone(), self.key_name.clone())
+  }
 }
 
 #[derive(Debug, Default)]
@@ -118,6 +133,44 @@ impl UserDataImpl {
     self.values_map = Some(HashMap::new());
   }
 
+  pub(crate) fn values_addresses(&self) -> HashMap<u64, usize> {
+    match self.values_map.as_ref() {
+      None => HashMap::new(),
+      Some(map) => map
+        .iter()
+        .map(|(id, v)| (*id, Rc::as_ptr(&v.value) as *const () as usize))
+        .collect(),
+    }
+  }
+
+  /// Removes all values which are present in `addresses`, were not replaced since `addresses` were taken
+  /// and were not accessed mutably, as such values could not have been changed.
+  pub(crate) fn retain_changed_values(&mut self, addresses: &HashMap<u64, usize>) {
+    if let Some(map) = self.values_map.as_mut() {
+      map.retain(|id, v| v.accessed_mutably.get() || addresses.get(id) != Some(&(Rc::as_ptr(&v.value) as *const () as usize)));
+    }
+  }
+
+  /// Creates a copy which does not share any value with the original one, `copy_value` puts a copy of the value
+  /// into the target and returns false if the value can not be copied, in this case the name of its key is returned.
+  pub(crate) fn try_deep_copy(&self, copy_value: impl Fn(&dyn Key, &dyn Any, &mut UserDataImpl) -> bool) -> Result<UserDataImpl, String> {
+    let mut copy = UserDataImpl::default();
+    for (key, value) in self.items().unwrap_or_default() {
+      if !copy_value(key.as_ref(), value, &mut copy) {
+        return Err(key.name().to_owned());
+      }
+    }
+
+    Ok(copy)
+  }
+
+  pub(crate) fn move_values_to(self, target: &mut UserDataImpl) {
+    let Some(map) = self.values_map else { return };
+
+    target.initialize_values_map();
+    target.values_map.as_mut().unwrap().extend(map);
+  }
+
   pub fn remove(&mut self, key: &impl Key) {
     if self.values_map.is_none() {
       return;
@@ -139,6 +192,7 @@ impl UserDataImpl {
 
     let values_map = self.values_map.as_ref().unwrap();
     if let Some(value) = values_map.get(&key.id()) {
+      value.accessed_mutably.set(true);
       unsafe { Some(value.value.as_ptr().as_mut().unwrap().downcast_mut::<T>().unwrap()) }
     } else {
       None
@@ -0,0 +1,252 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ficus::{
+  event_log::{
+    core::{event::event::Event, event_log::EventLog, trace::trace::Trace},
+    xes::{simple::create_simple_event_log, xes_event_log::XesEventLogImpl},
+  },
+  features::{
+    analysis::drift::{
+      detection::{ConceptDriftDetectionParams, detect_concept_drift},
+      distributions::{DriftDistribution, DriftFeature, DriftItem, relations_counter_distribution},
+      online::OnlineDriftDetector,
+      statistics::{DriftStatisticalTest, chi_square_survival},
+    },
+    streaming::counters::{
+      core::{StreamingCounter, ValueUpdateKind},
+      space_saving::SpaceSaving,
+    },
+  },
+  pipelines::{
+    context::{PipelineContext, PipelineInfrastructure},
+    keys::context_keys::{CONCEPT_DRIFT_CHANGE_POINTS_KEY, DRIFT_WINDOW_SIZE_KEY, EVENT_LOG_KEY},
+    pipeline_parts::PipelineParts,
+    pipelines::PipelinePart,
+  },
+  utils::user_data::user_data::{UserData, UserDataImpl},
+};
+use std::sync::Arc;
+
+fn start_time() -> DateTime<Utc> {
+  Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+fn create_log(variants: &[(&[&'static str], usize)]) -> XesEventLogImpl {
+  let raw_log: Vec<Vec<&str>> = variants
+    .iter()
+    .flat_map(|(variant, count)| std::iter::repeat_n(variant.to_vec(), *count))
+    .collect();
+
+  let log = create_simple_event_log(&raw_log);
+  for (index, trace) in log.traces().iter().enumerate() {
+    for event in trace.borrow().events() {
+      event.borrow_mut().set_timestamp(start_time() + Duration::minutes(index as i64));
+    }
+  }
+
+  log
+}
+
+fn distribution(items: &[(&str, f64)]) -> DriftDistribution {
+  items
+    .iter()
+    .map(|(name, count)| (DriftItem::Activity(Arc::from(*name)), *count))
+    .collect()
+}
+
+fn relation(from: &str, to: &str) -> DriftItem {
+  DriftItem::Relation(Arc::from(from), Arc::from(to))
+}
+
+fn nodes_names(graph: &ficus::utils::graph::graph::DefaultGraph) -> Vec<String> {
+  let mut names: Vec<String> = graph.all_nodes().iter().map(|node| node.data().unwrap().to_string()).collect();
+  names.sort();
+  names
+}
+
+#[test]
+fn test_chi_square_survival() {
+  assert!((chi_square_survival(3.841_458_820_694_124, 1) - 0.05).abs() < 1e-6);
+  assert!((chi_square_survival(5.991_464_547_107_979, 2) - 0.05).abs() < 1e-6);
+  assert!((chi_square_survival(23.209_251_158_954_356, 10) - 0.01).abs() < 1e-6);
+  assert_eq!(chi_square_survival(0., 3), 1.);
+}
+
+#[test]
+fn test_same_distributions() {
+  let first = distribution(&[("A", 10.), ("B", 20.)]);
+  let second = distribution(&[("A", 20.), ("B", 40.)]);
+
+  for test in [DriftStatisticalTest::ChiSquare, DriftStatisticalTest::GTest] {
+    let result = test.test(&first, &second).unwrap();
+    assert!(result.statistic.abs() < 1e-9);
+    assert!((result.p_value - 1.).abs() < 1e-9);
+    assert_eq!(result.degrees_of_freedom, 1);
+  }
+}
+
+#[test]
+fn test_different_distributions() {
+  let first = distribution(&[("A", 50.), ("B", 50.), ("C", 50.)]);
+  let second = distribution(&[("A", 50.), ("B", 50.), ("D", 50.)]);
+
+  for test in [DriftStatisticalTest::ChiSquare, DriftStatisticalTest::GTest] {
+    let result = test.test(&first, &second).unwrap();
+    assert!(result.p_value < 1e-6);
+    assert_eq!(result.degrees_of_freedom, 3);
+
+    let responsible: Vec<String> = result.contributions.iter().take(2).map(|(item, _)| item.to_string()).collect();
+    assert_eq!(responsible, vec!["C", "D"]);
+  }
+}
+
+#[test]
+fn test_chi_square_statistic() {
+  let first = distribution(&[("A", 30.), ("B", 10.)]);
+  let second = distribution(&[("A", 10.), ("B", 30.)]);
+
+  let result = DriftStatisticalTest::ChiSquare.test(&first, &second).unwrap();
+  assert!((result.statistic - 20.).abs() < 1e-9);
+}
+
+#[test]
+fn test_not_enough_data() {
+  let test = DriftStatisticalTest::ChiSquare;
+
+  assert!(test.test(&distribution(&[("A", 1.)]), &distribution(&[("A", 2.)])).is_none());
+  assert!(test.test(&distribution(&[]), &distribution(&[("A", 2.), ("B", 1.)])).is_none());
+}
+
+#[test]
+fn test_feature_distributions() {
+  let log = create_log(&[(&["A", "B", "A", "B"], 2), (&["A", "C"], 1)]);
+  let traces: Vec<_> = log.traces().iter().map(|trace| trace.borrow()).collect();
+
+  let relations = DriftFeature::DfgRelations.distribution(traces.iter().map(|trace| &**trace));
+  assert_eq!(relations.get(&relation("A", "B")), Some(&4.));
+  assert_eq!(relations.get(&relation("B", "A")), Some(&2.));
+  assert_eq!(relations.get(&relation("A", "C")), Some(&1.));
+
+  let activities = DriftFeature::ActivityFrequencies.distribution(traces.iter().map(|trace| &**trace));
+  assert_eq!(activities, distribution(&[("A", 5.), ("B", 4.), ("C", 1.)]));
+
+  let variants = DriftFeature::TraceVariants.distribution(traces.iter().map(|trace| &**trace));
+  assert_eq!(variants.len(), 2);
+  assert_eq!(variants.get(&DriftItem::Variant(vec!["A".into(), "C".into()])), Some(&1.));
+}
+
+#[test]
+fn test_offline_drift_detection() {
+  let log = create_log(&[(&["A", "B", "C"], 60), (&["A", "D", "C"], 60)]);
+  let params = ConceptDriftDetectionParams::new(DriftFeature::DfgRelations, DriftStatisticalTest::ChiSquare, 20, 0.01);
+
+  let change_points = detect_concept_drift(&log, &params);
+  assert_eq!(change_points.len(), 1);
+
+  let change_point = &change_points[0];
+  assert_eq!(change_point.trace_index, 60);
+  assert_eq!(change_point.timestamp, Some(start_time() + Duration::minutes(60)));
+  assert!(change_point.p_value < 0.01);
+
+  let mut responsible: Vec<DriftItem> = change_point
+    .responsible_items
+    .iter()
+    .take(4)
+    .map(|(item, _)| item.clone())
+    .collect();
+  responsible.sort();
+  assert_eq!(
+    responsible,
+    vec![relation("A", "B"), relation("A", "D"), relation("B", "C"), relation("D", "C")]
+  );
+
+  assert_eq!(nodes_names(&change_point.before_dfg), vec!["A", "B", "C"]);
+  assert_eq!(nodes_names(&change_point.after_dfg), vec!["A", "C", "D"]);
+}
+
+#[test]
+fn test_offline_drift_detection_uses_time_order() {
+  let log = create_log(&[(&["A", "B", "C"], 30), (&["A", "D", "C"], 30)]);
+  for (index, trace) in log.traces().iter().enumerate() {
+    let minutes = if index < 30 { 100 + index } else { index - 30 };
+    for event in trace.borrow().events() {
+      event.borrow_mut().set_timestamp(start_time() + Duration::minutes(minutes as i64));
+    }
+  }
+
+  let params = ConceptDriftDetectionParams::new(DriftFeature::ActivityFrequencies, DriftStatisticalTest::GTest, 10, 0.01);
+  let change_points = detect_concept_drift(&log, &params);
+
+  assert_eq!(change_points.len(), 1);
+  assert_eq!(change_points[0].trace_index, 0);
+  assert_eq!(nodes_names(&change_points[0].before_dfg), vec!["A", "C", "D"]);
+}
+
+#[test]
+fn test_no_drift() {
+  let log = create_log(&[(&["A", "B", "C"], 100)]);
+  let params = ConceptDriftDetectionParams::new(DriftFeature::TraceVariants, DriftStatisticalTest::ChiSquare, 20, 0.05);
+
+  assert!(detect_concept_drift(&log, &params).is_empty());
+}
+
+#[test]
+fn test_log_shorter_than_two_windows() {
+  let log = create_log(&[(&["A", "B"], 10), (&["C", "D"], 10)]);
+  let params = ConceptDriftDetectionParams::new(DriftFeature::DfgRelations, DriftStatisticalTest::ChiSquare, 15, 0.05);
+
+  assert!(detect_concept_drift(&log, &params).is_empty());
+}
+
+#[test]
+fn test_online_drift_detection() {
+  let mut detector = OnlineDriftDetector::new(DriftStatisticalTest::ChiSquare, 0.01, 10.).with_max_responsible_items(1);
+
+  assert!(detector.observe(distribution(&[("A", 5.)]), start_time()).is_none());
+  assert!(detector.observe(distribution(&[("A", 50.), ("B", 50.)]), start_time()).is_none());
+  assert!(detector.observe(distribution(&[("A", 40.), ("B", 40.)]), start_time()).is_none());
+
+  let stamp = start_time() + Duration::hours(1);
+  let change_point = detector.observe(distribution(&[("A", 50.), ("C", 50.)]), stamp).unwrap().clone();
+  assert_eq!(change_point.stamp, stamp);
+  assert_eq!(change_point.responsible_items.len(), 1);
+
+  assert!(detector.observe(distribution(&[("A", 50.), ("C", 50.)]), stamp).is_none());
+  assert_eq!(detector.change_points().len(), 1);
+
+  let snapshot = serde_json::to_string(&detector).unwrap();
+  let restored: OnlineDriftDetector = serde_json::from_str(&snapshot).unwrap();
+  assert_eq!(restored.change_points().len(), 1);
+  assert_eq!(restored.change_points()[0].stamp, stamp);
+  assert_eq!(restored.change_points()[0].responsible_items, change_point.responsible_items);
+}
+
+#[test]
+fn test_relations_counter_distribution() {
+  let mut counter = SpaceSaving::<(Arc<str>, Arc<str>), ()>::new(10);
+  for _ in 0..3 {
+    counter.observe(("A".into(), "B".into()), ValueUpdateKind::DoNothing);
+  }
+
+  counter.observe(("B".into(), "C".into()), ValueUpdateKind::DoNothing);
+
+  let distribution = relations_counter_distribution(&counter);
+  assert_eq!(distribution.get(&relation("A", "B")), Some(&3.));
+  assert_eq!(distribution.get(&relation("B", "C")), Some(&1.));
+}
+
+#[test]
+fn test_detect_concept_drift_pipeline_part() {
+  let mut context = PipelineContext::default();
+  context.put_concrete(EVENT_LOG_KEY.key(), create_log(&[(&["A", "B"], 40), (&["A", "C"], 40)]));
+
+  let mut config = UserDataImpl::default();
+  config.put_concrete(DRIFT_WINDOW_SIZE_KEY.key(), 20);
+
+  let parts = PipelineParts::new();
+  let part = parts.find_part(PipelineParts::DETECT_CONCEPT_DRIFT).unwrap()(Box::new(config));
+  part.execute(&mut context, &PipelineInfrastructure::new(None)).unwrap();
+
+  let change_points = context.concrete(CONCEPT_DRIFT_CHANGE_POINTS_KEY.key()).unwrap();
+  assert_eq!(change_points.len(), 1);
+  assert_eq!(change_points[0].trace_index, 40);
+}
@@ -0,0 +1,61 @@
+use bxes::models::{
+  domain::{bxes_value::BxesValue, type_ids::TypeIds},
+  system_models::{EventAttributeSchema, EventAttributesSchema, SystemMetadata},
+};
+use ficus::event_log::{
+  bxes::{bxes_to_xes_converter::read_bxes_into_xes_log_from_bytes, xes_to_bxes_converter::write_event_log_to_bxes_bytes},
+  core::{
+    event::event::{Event, EventPayloadValue},
+    event_log::EventLog,
+    trace::trace::Trace,
+  },
+};
+use std::sync::Arc;
+
+use crate::test_core::simple_events_logs_provider::create_simple_event_log;
+
+#[test]
+fn test_bxes_schema_typed_globals() {
+  let log = create_simple_event_log();
+  for trace in log.traces() {
+    for event in trace.borrow().events() {
+      event
+        .borrow_mut()
+        .add_or_update_payload(Arc::from("org:resource"), EventPayloadValue::String(Arc::from("bob")));
+    }
+  }
+
+  let schema = EventAttributesSchema::new(
+    vec![
+      EventAttributeSchema::new("org:resource", TypeIds::String, true)
+        .with_allowed_values(vec![BxesValue::String(Arc::from("alice")), BxesValue::String(Arc::from("bob"))]),
+      EventAttributeSchema::new("cost", TypeIds::F64, true),
+      EventAttributeSchema::new("comment", TypeIds::String, false),
+    ],
+    false,
+  );
+
+  let metadata = SystemMetadata::new(None).with_event_attributes_schema(schema);
+  assert!(write_event_log_to_bxes_bytes(&log, Some(&metadata)).is_err());
+
+  for trace in log.traces() {
+    for event in trace.borrow().events() {
+      event
+        .borrow_mut()
+        .add_or_update_payload(Arc::from("cost"), EventPayloadValue::Float64(1.0));
+    }
+  }
+
+  let bytes = write_event_log_to_bxes_bytes(&log, Some(&metadata)).ok().unwrap();
+  let result = read_bxes_into_xes_log_from_bytes(&bytes).ok().unwrap();
+
+  assert_eq!(result.system_metadata, metadata);
+
+  let event_globals = result.xes_log.globals_map().get("event").unwrap();
+  assert_eq!(event_globals.len(), 2);
+  assert_eq!(
+    event_globals.get("org:resource"),
+    Some(&EventPayloadValue::String(Arc::from("alice")))
+  );
+  assert_eq!(event_globals.get("cost"), Some(&EventPayloadValue::Float64(0.0)));
+}
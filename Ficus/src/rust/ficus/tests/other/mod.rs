@@ -1,3 +1,4 @@
+pub mod bxes_schema_tests;
 pub mod lifecycle_tests;
 pub mod split_tests;
 pub mod test_simple_event_log;
@@ -1,2 +1,9 @@
+pub mod pipeline_budget_tests;
+pub mod pipeline_cancellation_tests;
+pub mod pipeline_control_flow_tests;
 pub mod pipeline_keys_tests;
+pub mod pipeline_parallel_tests;
 pub mod pipeline_parts_tests;
+pub mod pipeline_results_cache_tests;
+pub mod pipeline_scheduler_tests;
+pub mod pipeline_validation_tests;
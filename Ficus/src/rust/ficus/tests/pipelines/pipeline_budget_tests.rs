@@ -0,0 +1,163 @@
+use ficus::{
+  pipelines::{
+    context::{PipelineContext, PipelineInfrastructure},
+    errors::pipeline_errors::PipelinePartExecutionError,
+    pipelines::{DefaultPipelinePart, ParallelPipelinePart, Pipeline, PipelinePart},
+  },
+  utils::{
+    allocations_tracking::{AllocationsCounter, record_allocation},
+    execution_budget::{ExecutionBudget, ExecutionBudgetViolation},
+  },
+};
+use std::{
+  cell::Cell,
+  rc::Rc,
+  sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+  },
+  time::{Duration, Instant},
+};
+
+#[test]
+fn test_time_budget_exceeded() {
+  let executed_parts = Rc::new(Cell::new(0usize));
+
+  let mut pipeline = Pipeline::default();
+  for _ in 0..3 {
+    let executed_parts = executed_parts.clone();
+    pipeline.push(Box::new(DefaultPipelinePart::new(
+      "SleepingPart".to_string(),
+      Box::default(),
+      Box::new(move |_, _, _| {
+        executed_parts.set(executed_parts.get() + 1);
+        std::thread::sleep(Duration::from_millis(20));
+        Ok(())
+      }),
+    )));
+  }
+
+  let budget = ExecutionBudget::unlimited().with_time_limit(Duration::from_millis(10));
+  let infra = PipelineInfrastructure::new(None).with_execution_budget(budget);
+  let result = pipeline.execute(&mut PipelineContext::default(), &infra);
+
+  assert!(matches!(
+    result,
+    Err(PipelinePartExecutionError::BudgetExceeded(
+      ExecutionBudgetViolation::TimeLimitExceeded { .. }
+    ))
+  ));
+  assert_eq!(executed_parts.get(), 1);
+}
+
+#[test]
+fn test_memory_budget_exceeded() {
+  let used_memory = Arc::new(AtomicUsize::new(0));
+
+  let mut pipeline = Pipeline::default();
+  for _ in 0..3 {
+    let used_memory = used_memory.clone();
+    pipeline.push(Box::new(DefaultPipelinePart::new(
+      "AllocatingPart".to_string(),
+      Box::default(),
+      Box::new(move |_, _, _| {
+        used_memory.fetch_add(1024, Ordering::SeqCst);
+        Ok(())
+      }),
+    )));
+  }
+
+  let probe_memory = used_memory.clone();
+  let budget = ExecutionBudget::unlimited().with_memory_limit(1500, Arc::new(move || probe_memory.load(Ordering::SeqCst)));
+  let infra = PipelineInfrastructure::new(None).with_execution_budget(budget);
+  let result = pipeline.execute(&mut PipelineContext::default(), &infra);
+
+  assert!(matches!(
+    result,
+    Err(PipelinePartExecutionError::BudgetExceeded(
+      ExecutionBudgetViolation::MemoryLimitExceeded { limit: 1500, used: 2048 }
+    ))
+  ));
+  assert_eq!(used_memory.load(Ordering::SeqCst), 2048);
+}
+
+#[test]
+fn test_time_budget_exceeded_inside_part() {
+  let mut pipeline = Pipeline::default();
+  pipeline.push(Box::new(DefaultPipelinePart::new(
+    "LongPart".to_string(),
+    Box::default(),
+    Box::new(|_, infra, _| {
+      let start = Instant::now();
+      while start.elapsed() < Duration::from_secs(10) {
+        infra.check_cancelled()?;
+        std::thread::sleep(Duration::from_millis(1));
+      }
+
+      Ok(())
+    }),
+  )));
+
+  let budget = ExecutionBudget::unlimited().with_time_limit(Duration::from_millis(50));
+  let infra = PipelineInfrastructure::new(None).with_execution_budget(budget);
+
+  let start = Instant::now();
+  let result = infra.execute_watched(&pipeline, &mut PipelineContext::default());
+
+  assert!(matches!(
+    result,
+    Err(PipelinePartExecutionError::BudgetExceeded(
+      ExecutionBudgetViolation::TimeLimitExceeded { .. }
+    ))
+  ));
+  assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_watched_execution_within_budget() {
+  let mut pipeline = Pipeline::default();
+  pipeline.push(Box::new(DefaultPipelinePart::new(
+    "Part".to_string(),
+    Box::default(),
+    Box::new(|_, _, _| Ok(())),
+  )));
+
+  let budget = ExecutionBudget::unlimited().with_time_limit(Duration::from_secs(10));
+  let infra = PipelineInfrastructure::new(None).with_execution_budget(budget);
+
+  assert!(infra.execute_watched(&pipeline, &mut PipelineContext::default()).is_ok());
+  assert!(!infra.cancellation_token().is_cancelled());
+}
+
+#[test]
+fn test_allocations_of_parallel_branches_are_counted() {
+  let allocating_branch = || {
+    let mut pipeline = Pipeline::default();
+    pipeline.push(Box::new(DefaultPipelinePart::new(
+      "AllocatingPart".to_string(),
+      Box::default(),
+      Box::new(|_, _, _| {
+        record_allocation(1024);
+        Ok(())
+      }),
+    )));
+
+    pipeline
+  };
+
+  let counter = AllocationsCounter::new();
+  let part = ParallelPipelinePart::new(vec![allocating_branch(), allocating_branch()]);
+
+  {
+    let _scope = counter.enter();
+    record_allocation(512);
+    part
+      .execute(&mut PipelineContext::default(), &PipelineInfrastructure::new(None))
+      .unwrap();
+  }
+
+  record_allocation(4096);
+
+  assert_eq!(counter.allocated_bytes(), 2560);
+  assert!(AllocationsCounter::current().is_none());
+}
@@ -0,0 +1,87 @@
+use crate::test_core::simple_events_logs_provider::create_simple_event_log;
+use ficus::{
+  pipelines::{
+    context::{PipelineContext, PipelineInfrastructure},
+    errors::pipeline_errors::PipelinePartExecutionError,
+    keys::context_keys::{ACTIVITY_LEVEL_KEY, EVENT_LOG_KEY, EXECUTE_ONLY_ON_LAST_EXTRACTION_KEY},
+    pipeline_parts::PipelineParts,
+    pipelines::{DefaultPipelinePart, Pipeline, PipelinePart},
+  },
+  utils::{
+    cancellation::CancellationToken,
+    user_data::user_data::{UserData, UserDataImpl},
+  },
+};
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn test_pipeline_cancellation() {
+  let token = CancellationToken::new();
+  let executed_parts = Rc::new(Cell::new(0usize));
+
+  let mut pipeline = Pipeline::default();
+  for _ in 0..3 {
+    let token = token.clone();
+    let executed_parts = executed_parts.clone();
+
+    pipeline.push(Box::new(DefaultPipelinePart::new(
+      "CancellingPart".to_string(),
+      Box::default(),
+      Box::new(move |_, _, _| {
+        executed_parts.set(executed_parts.get() + 1);
+        token.cancel();
+        Ok(())
+      }),
+    )));
+  }
+
+  let infra = PipelineInfrastructure::new(None).with_cancellation_token(token);
+  let result = pipeline.execute(&mut PipelineContext::default(), &infra);
+
+  assert!(matches!(result, Err(PipelinePartExecutionError::Cancelled)));
+  assert_eq!(executed_parts.get(), 1);
+}
+
+#[test]
+fn test_pipeline_not_cancelled() {
+  let executed_parts = Rc::new(Cell::new(0usize));
+
+  let mut pipeline = Pipeline::default();
+  for _ in 0..3 {
+    let executed_parts = executed_parts.clone();
+    pipeline.push(Box::new(DefaultPipelinePart::new(
+      "Part".to_string(),
+      Box::default(),
+      Box::new(move |_, _, _| {
+        executed_parts.set(executed_parts.get() + 1);
+        Ok(())
+      }),
+    )));
+  }
+
+  let result = pipeline.execute(&mut PipelineContext::default(), &PipelineInfrastructure::new(None));
+
+  assert!(result.is_ok());
+  assert_eq!(executed_parts.get(), 3);
+}
+
+#[test]
+fn test_discover_activities_until_no_more_cancellation() {
+  let mut config = UserDataImpl::default();
+  config.put_concrete(ACTIVITY_LEVEL_KEY.key(), 0);
+  config.put_concrete(EXECUTE_ONLY_ON_LAST_EXTRACTION_KEY.key(), false);
+
+  let parts = PipelineParts::new();
+  let part = parts.find_part(PipelineParts::DISCOVER_ACTIVITIES_UNTIL_NO_MORE).unwrap()(Box::new(config));
+
+  let mut context = PipelineContext::default();
+  context.put_concrete(EVENT_LOG_KEY.key(), create_simple_event_log());
+
+  let token = CancellationToken::new();
+  token.cancel();
+
+  let infra = PipelineInfrastructure::new(None).with_cancellation_token(token);
+  let result = part.execute(&mut context, &infra);
+
+  assert!(matches!(result, Err(PipelinePartExecutionError::Cancelled)));
+}
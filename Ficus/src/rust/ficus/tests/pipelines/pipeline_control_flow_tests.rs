@@ -0,0 +1,271 @@
+use crate::test_core::simple_events_logs_provider::create_simple_event_log;
+use ficus::{
+  event_log::{
+    core::{event_log::EventLog, trace::trace::Trace},
+    xes::xes_event_log::XesEventLogImpl,
+  },
+  pipelines::{
+    context::{PipelineContext, PipelineInfrastructure},
+    control_flow_parts::{ComparisonOperator, ConditionMetric, SweepRunResult},
+    errors::pipeline_errors::PipelinePartExecutionError,
+    keys::context_keys::{
+      CONDITION_METRIC_KEY, CONDITION_OPERATOR_KEY, CONDITION_THRESHOLD_KEY, DEPENDENCY_THRESHOLD_KEY, ELSE_PIPELINE_KEY, EVENT_LOG_KEY,
+      FOR_EACH_COLLECTION_KEY, PATH_KEY, PATHS_KEY, PIPELINE_KEY, POSITIVE_OBSERVATIONS_THRESHOLD_KEY, SWEEP_GRID_KEY, SWEEP_RESULTS_KEY,
+    },
+    pipeline_parts::PipelineParts,
+    pipelines::{DefaultPipelinePart, Pipeline, PipelinePart},
+  },
+  utils::{
+    context_key::DefaultContextKey,
+    user_data::user_data::{UserData, UserDataImpl},
+  },
+};
+use lazy_static::lazy_static;
+use std::sync::Arc;
+
+lazy_static! {
+  static ref VISITED_KEY: DefaultContextKey<Vec<Arc<str>>> = DefaultContextKey::new("control_flow_tests_visited");
+  static ref THRESHOLD_KEY: DefaultContextKey<f64> = DefaultContextKey::new("control_flow_tests_threshold");
+}
+
+fn pipeline(executor: impl Fn(&mut PipelineContext, &UserDataImpl) -> Result<(), PipelinePartExecutionError> + 'static) -> Pipeline {
+  let mut pipeline = Pipeline::default();
+  pipeline.push(Box::new(DefaultPipelinePart::new(
+    "ControlFlowTestPart".to_string(),
+    Box::default(),
+    Box::new(move |context, _, config| executor(context, config)),
+  )));
+
+  pipeline
+}
+
+fn visit(name: &'static str) -> Pipeline {
+  pipeline(move |context, _| {
+    let mut visited = context.concrete(VISITED_KEY.key()).cloned().unwrap_or_default();
+    visited.push(Arc::from(name));
+    context.put_concrete(VISITED_KEY.key(), visited);
+    Ok(())
+  })
+}
+
+fn execute(part_name: &str, config: UserDataImpl, context: &mut PipelineContext) -> Result<(), PipelinePartExecutionError> {
+  let parts = PipelineParts::new();
+  let part = parts.find_part(part_name).unwrap()(Box::new(config));
+
+  part.execute(context, &PipelineInfrastructure::new(None))
+}
+
+fn visited(context: &PipelineContext) -> Vec<String> {
+  match context.concrete(VISITED_KEY.key()) {
+    None => vec![],
+    Some(visited) => visited.iter().map(|v| v.to_string()).collect(),
+  }
+}
+
+fn create_if_config(operator: ComparisonOperator, threshold: f64) -> UserDataImpl {
+  let mut config = UserDataImpl::default();
+  config.put_concrete(CONDITION_METRIC_KEY.key(), ConditionMetric::TracesCount);
+  config.put_concrete(CONDITION_OPERATOR_KEY.key(), operator);
+  config.put_concrete(CONDITION_THRESHOLD_KEY.key(), threshold);
+  config.put_concrete(PIPELINE_KEY.key(), visit("then"));
+  config.put_concrete(ELSE_PIPELINE_KEY.key(), visit("else"));
+
+  config
+}
+
+#[test]
+fn test_if_branches() {
+  let log = create_simple_event_log();
+  let traces_count = log.traces().len() as f64;
+
+  let cases = [
+    (ComparisonOperator::Greater, traces_count - 1.0, "then"),
+    (ComparisonOperator::Greater, traces_count, "else"),
+    (ComparisonOperator::Equal, traces_count, "then"),
+    (ComparisonOperator::Less, traces_count, "else"),
+  ];
+
+  for (operator, threshold, expected_branch) in cases {
+    let mut context = PipelineContext::default();
+    context.put_concrete(EVENT_LOG_KEY.key(), create_simple_event_log());
+
+    execute("If", create_if_config(operator, threshold), &mut context).unwrap();
+
+    assert_eq!(visited(&context), vec![expected_branch.to_string()]);
+  }
+}
+
+#[test]
+fn test_if_without_else_pipeline() {
+  let mut config = create_if_config(ComparisonOperator::Less, 0.0);
+  config.remove(ELSE_PIPELINE_KEY.key());
+
+  let mut context = PipelineContext::default();
+  context.put_concrete(EVENT_LOG_KEY.key(), create_simple_event_log());
+
+  execute("If", config, &mut context).unwrap();
+
+  assert!(visited(&context).is_empty());
+}
+
+#[test]
+fn test_for_each_over_paths() {
+  let mut config = UserDataImpl::default();
+  config.put_concrete(FOR_EACH_COLLECTION_KEY.key(), Arc::from("paths"));
+  config.put_concrete(
+    PIPELINE_KEY.key(),
+    pipeline(|context, _| {
+      let path = context.concrete(PATH_KEY.key()).unwrap().clone();
+      let mut visited = context.concrete(VISITED_KEY.key()).cloned().unwrap_or_default();
+      visited.push(path);
+      context.put_concrete(VISITED_KEY.key(), visited);
+      Ok(())
+    }),
+  );
+
+  let mut context = PipelineContext::default();
+  context.put_concrete(PATHS_KEY.key(), vec![Arc::from("first"), Arc::from("second"), Arc::from("third")]);
+
+  execute("ForEach", config, &mut context).unwrap();
+
+  assert_eq!(visited(&context), vec!["first", "second", "third"]);
+  assert!(context.concrete(PATH_KEY.key()).is_none());
+}
+
+#[test]
+fn test_for_each_changing_value_in_place() {
+  let mut config = UserDataImpl::default();
+  config.put_concrete(FOR_EACH_COLLECTION_KEY.key(), Arc::from("paths"));
+  config.put_concrete(
+    PIPELINE_KEY.key(),
+    pipeline(|context, _| {
+      let path = context.concrete(PATH_KEY.key()).unwrap().clone();
+      context.concrete_mut(VISITED_KEY.key()).unwrap().push(path);
+      Ok(())
+    }),
+  );
+
+  let mut context = PipelineContext::default();
+  context.put_concrete(VISITED_KEY.key(), vec![]);
+  context.put_concrete(PATHS_KEY.key(), vec![Arc::from("first"), Arc::from("second"), Arc::from("third")]);
+
+  execute("ForEach", config, &mut context).unwrap();
+
+  assert_eq!(visited(&context), vec!["first", "second", "third"]);
+}
+
+#[test]
+fn test_for_each_unsupported_collection() {
+  let mut config = UserDataImpl::default();
+  config.put_concrete(FOR_EACH_COLLECTION_KEY.key(), Arc::from("event_log"));
+  config.put_concrete(PIPELINE_KEY.key(), visit("body"));
+
+  let result = execute("ForEach", config, &mut PipelineContext::default());
+
+  assert!(matches!(result, Err(PipelinePartExecutionError::Raw(_))));
+}
+
+fn to_tuples(result: &SweepRunResult) -> (usize, Vec<(String, String)>, Option<f64>) {
+  (
+    result.index(),
+    result.parameters().iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+    result.outputs().concrete(THRESHOLD_KEY.key()).cloned(),
+  )
+}
+
+#[test]
+fn test_sweep_over_grid() {
+  let mut config = UserDataImpl::default();
+  config.put_concrete(
+    SWEEP_GRID_KEY.key(),
+    vec![
+      Arc::from("dependency_threshold=0.5,0.9"),
+      Arc::from("positive_observations_threshold=1,2"),
+    ],
+  );
+
+  config.put_concrete(
+    PIPELINE_KEY.key(),
+    pipeline(|context, config| {
+      let dependency_threshold = *config.concrete(DEPENDENCY_THRESHOLD_KEY.key()).unwrap();
+      let observations_threshold = *config.concrete(POSITIVE_OBSERVATIONS_THRESHOLD_KEY.key()).unwrap();
+      context.put_concrete(THRESHOLD_KEY.key(), dependency_threshold * observations_threshold as f64);
+      Ok(())
+    }),
+  );
+
+  let mut context = PipelineContext::default();
+  execute("Sweep", config, &mut context).unwrap();
+
+  let parameters = |dependency: &str, observations: &str| {
+    vec![
+      ("dependency_threshold".to_string(), dependency.to_string()),
+      ("positive_observations_threshold".to_string(), observations.to_string()),
+    ]
+  };
+
+  let results = context.concrete(SWEEP_RESULTS_KEY.key()).unwrap();
+  assert_eq!(
+    results.iter().map(to_tuples).collect::<Vec<_>>(),
+    vec![
+      (0, parameters("0.5", "1"), Some(0.5)),
+      (1, parameters("0.5", "2"), Some(1.0)),
+      (2, parameters("0.9", "1"), Some(0.9)),
+      (3, parameters("0.9", "2"), Some(1.8)),
+    ]
+  );
+
+  assert!(context.concrete(THRESHOLD_KEY.key()).is_none());
+}
+
+#[test]
+fn test_sweep_invalid_grid() {
+  for grid in ["dependency_threshold", "dependency_threshold=abc", "unknown_key=1", "event_log=1"] {
+    let mut config = UserDataImpl::default();
+    config.put_concrete(SWEEP_GRID_KEY.key(), vec![Arc::from(grid)]);
+    config.put_concrete(PIPELINE_KEY.key(), visit("body"));
+
+    let result = execute("Sweep", config, &mut PipelineContext::default());
+
+    assert!(matches!(result, Err(PipelinePartExecutionError::Raw(_))));
+  }
+}
+
+#[test]
+fn test_sweep_runs_do_not_share_values() {
+  let parts = PipelineParts::new();
+
+  let mut sweep_pipeline = Pipeline::default();
+  sweep_pipeline.push(Box::new(parts.find_part("FilterEventsByName").unwrap()(Box::default())));
+  sweep_pipeline.push(Box::new(DefaultPipelinePart::new(
+    "CountEvents".to_string(),
+    Box::default(),
+    Box::new(|context, _, _| {
+      let log = context.concrete(EVENT_LOG_KEY.key()).unwrap();
+      context.put_concrete(THRESHOLD_KEY.key(), events_count(log) as f64);
+      Ok(())
+    }),
+  )));
+
+  let mut config = UserDataImpl::default();
+  config.put_concrete(SWEEP_GRID_KEY.key(), vec![Arc::from("event_name=A,B")]);
+  config.put_concrete(PIPELINE_KEY.key(), sweep_pipeline);
+
+  let mut context = PipelineContext::default();
+  context.put_concrete(EVENT_LOG_KEY.key(), create_simple_event_log());
+
+  execute("Sweep", config, &mut context).unwrap();
+
+  let results = context.concrete(SWEEP_RESULTS_KEY.key()).unwrap();
+  let events_counts = results
+    .iter()
+    .map(|result| result.outputs().concrete(THRESHOLD_KEY.key()).cloned())
+    .collect::<Vec<_>>();
+
+  assert_eq!(events_counts, vec![Some(4.0), Some(4.0)]);
+  assert_eq!(events_count(context.concrete(EVENT_LOG_KEY.key()).unwrap()), 6);
+}
+
+fn events_count(log: &XesEventLogImpl) -> usize {
+  log.traces().iter().map(|trace| trace.borrow().events().len()).sum()
+}
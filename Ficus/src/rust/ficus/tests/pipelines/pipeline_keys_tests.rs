@@ -6,6 +6,9 @@ use ficus::{
   event_log::xes::xes_event_log::XesEventLogImpl,
   features::{
     analysis::{
+      drift::{
+        detection::ConceptDriftChangePoint, distributions::DriftFeature, online::OnlineDriftChangePoint, statistics::DriftStatisticalTest,
+      },
       log_info::event_log_info::OfflineEventLogInfo,
       patterns::{
         activity_instances::{ActivityInTraceFilterKind, ActivityNarrowingKind, AdjustingMode},
@@ -18,14 +21,18 @@ use ficus::{
     },
     discovery::{
       ecfg::models::RootSequenceKind,
+      fuzzy::proximity_correlation::PrecomputedProximityCorrelation,
       ocel::graph_annotation::OcelAnnotationCreationError,
       petri_net::{annotations::TimeAnnotationKind, petri_net::DefaultPetriNet},
+      relations::triangle_relation::PrecomputedTriangleRelation,
       timeline::discovery::LogTimelineDiagram,
     },
+    streaming::conformance::core::ConformanceReport,
   },
   pipelines::{
     activities_parts::{ActivitiesLogsSourceDto, UndefActivityHandlingStrategyDto},
     aliases::{Activities, ActivitiesToLogs, Patterns, RepeatSets, TracesActivities},
+    control_flow_parts::{ComparisonOperator, ConditionMetric, SweepRunResult},
     keys::context_keys::*,
     patterns_parts::PatternsKindDto,
     pipelines::Pipeline,
@@ -136,6 +143,23 @@ fn test_event_log_all_concrete_keys() {
     assert_existence::<bool>(&DISCOVER_ACTIVITY_INSTANCES_STRICT, &mut used);
     assert_existence::<bool>(&MERGE_SEQUENCES_OF_EVENTS, &mut used);
     assert_existence::<OcelAnnotationCreationError>(&OCEL_ANNOTATION, &mut used);
+    assert_existence::<ConditionMetric>(&CONDITION_METRIC, &mut used);
+    assert_existence::<ComparisonOperator>(&CONDITION_OPERATOR, &mut used);
+    assert_existence::<f64>(&CONDITION_THRESHOLD, &mut used);
+    assert_existence::<Pipeline>(&ELSE_PIPELINE, &mut used);
+    assert_existence::<String>(&FOR_EACH_COLLECTION, &mut used);
+    assert_existence::<Vec<String>>(&SWEEP_GRID, &mut used);
+    assert_existence::<Vec<SweepRunResult>>(&SWEEP_RESULTS, &mut used);
+    assert_existence::<ConformanceReport>(&CONFORMANCE_REPORT, &mut used);
+    assert_existence::<DriftFeature>(&DRIFT_FEATURE, &mut used);
+    assert_existence::<DriftStatisticalTest>(&DRIFT_STATISTICAL_TEST, &mut used);
+    assert_existence::<u32>(&DRIFT_WINDOW_SIZE, &mut used);
+    assert_existence::<f64>(&SIGNIFICANCE_LEVEL, &mut used);
+    assert_existence::<Vec<ConceptDriftChangePoint>>(&CONCEPT_DRIFT_CHANGE_POINTS, &mut used);
+    assert_existence::<Vec<OnlineDriftChangePoint>>(&ONLINE_CONCEPT_DRIFT_CHANGE_POINTS, &mut used);
+    assert_existence::<PrecomputedTriangleRelation>(&TRIANGLE_RELATION, &mut used);
+    assert_existence::<PrecomputedProximityCorrelation>(&PROXIMITY_CORRELATION, &mut used);
+    assert_existence::<XesEventLogImpl>(&CLOSED_CASES_EVENT_LOG, &mut used);
 
     assert_eq!(used.len(), get_all_keys_names().len())
 }
@@ -241,7 +265,24 @@ fn get_all_keys_names() -> Vec<String> {
         "software_data_extraction_config",
         "discover_activity_instances_strict",
         "merge_sequences_of_events",
-        "ocel_annotation"
+        "ocel_annotation",
+        "condition_metric",
+        "condition_operator",
+        "condition_threshold",
+        "else_pipeline",
+        "for_each_collection",
+        "sweep_grid",
+        "sweep_results",
+        "conformance_report",
+        "drift_feature",
+        "drift_statistical_test",
+        "drift_window_size",
+        "significance_level",
+        "concept_drift_change_points",
+        "online_concept_drift_change_points",
+        "triangle_relation",
+        "proximity_correlation",
+        "closed_cases_event_log"
     ]
 }
 
@@ -348,6 +389,23 @@ fn test_equivalence_of_keys() {
     assert_keys_equivalence::<bool>(&DISCOVER_ACTIVITY_INSTANCES_STRICT, &mut used);
     assert_keys_equivalence::<bool>(&MERGE_SEQUENCES_OF_EVENTS, &mut used);
     assert_keys_equivalence::<bool>(&OCEL_ANNOTATION, &mut used);
+    assert_keys_equivalence::<ConditionMetric>(&CONDITION_METRIC, &mut used);
+    assert_keys_equivalence::<ComparisonOperator>(&CONDITION_OPERATOR, &mut used);
+    assert_keys_equivalence::<f64>(&CONDITION_THRESHOLD, &mut used);
+    assert_keys_equivalence::<Pipeline>(&ELSE_PIPELINE, &mut used);
+    assert_keys_equivalence::<String>(&FOR_EACH_COLLECTION, &mut used);
+    assert_keys_equivalence::<Vec<String>>(&SWEEP_GRID, &mut used);
+    assert_keys_equivalence::<Vec<SweepRunResult>>(&SWEEP_RESULTS, &mut used);
+    assert_keys_equivalence::<ConformanceReport>(&CONFORMANCE_REPORT, &mut used);
+    assert_keys_equivalence::<DriftFeature>(&DRIFT_FEATURE, &mut used);
+    assert_keys_equivalence::<DriftStatisticalTest>(&DRIFT_STATISTICAL_TEST, &mut used);
+    assert_keys_equivalence::<u32>(&DRIFT_WINDOW_SIZE, &mut used);
+    assert_keys_equivalence::<f64>(&SIGNIFICANCE_LEVEL, &mut used);
+    assert_keys_equivalence::<Vec<ConceptDriftChangePoint>>(&CONCEPT_DRIFT_CHANGE_POINTS, &mut used);
+    assert_keys_equivalence::<Vec<OnlineDriftChangePoint>>(&ONLINE_CONCEPT_DRIFT_CHANGE_POINTS, &mut used);
+    assert_keys_equivalence::<PrecomputedTriangleRelation>(&TRIANGLE_RELATION, &mut used);
+    assert_keys_equivalence::<PrecomputedProximityCorrelation>(&PROXIMITY_CORRELATION, &mut used);
+    assert_keys_equivalence::<XesEventLogImpl>(&CLOSED_CASES_EVENT_LOG, &mut used);
 
     assert_eq!(used.len(), get_all_keys_names().len())
 }
@@ -0,0 +1,238 @@
+use crate::test_core::simple_events_logs_provider::create_simple_event_log;
+use ficus::{
+  event_log::{
+    core::{event_log::EventLog, trace::trace::Trace},
+    xes::{xes_event_log::XesEventLogImpl, xes_trace::XesTraceImpl},
+  },
+  features::discovery::petri_net::{petri_net::DefaultPetriNet, place::Place},
+  pipelines::{
+    context::{PipelineContext, PipelineInfrastructure},
+    errors::pipeline_errors::PipelinePartExecutionError,
+    keys::context_keys::{EVENT_LOG_KEY, EVENT_NAME_KEY, PETRI_NET_KEY},
+    parallel::ParallelOutputsMergePolicy,
+    pipeline_parts::PipelineParts,
+    pipelines::{DefaultPipelinePart, ParallelPipelinePart, Pipeline, PipelinePart},
+  },
+  utils::{
+    context_key::DefaultContextKey,
+    display_name::DISPLAY_NAME_KEY,
+    user_data::user_data::{UserData, UserDataImpl, UserDataOwner},
+  },
+};
+use lazy_static::lazy_static;
+use std::{
+  cell::RefCell,
+  rc::Rc,
+  sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+  },
+  time::{Duration, Instant},
+};
+
+lazy_static! {
+  static ref FIRST_KEY: DefaultContextKey<u32> = DefaultContextKey::new("parallel_tests_first");
+  static ref SECOND_KEY: DefaultContextKey<u32> = DefaultContextKey::new("parallel_tests_second");
+  static ref NOT_COPYABLE_KEY: DefaultContextKey<Rc<u32>> = DefaultContextKey::new("parallel_tests_not_copyable");
+}
+
+fn branch(executor: impl Fn(&mut PipelineContext) -> Result<(), PipelinePartExecutionError> + 'static) -> Pipeline {
+  let mut pipeline = Pipeline::default();
+  pipeline.push(Box::new(DefaultPipelinePart::new(
+    "ParallelTestPart".to_string(),
+    Box::default(),
+    Box::new(move |context, _, _| executor(context)),
+  )));
+
+  pipeline
+}
+
+fn put_value(key: &'static DefaultContextKey<u32>, value: u32) -> Pipeline {
+  branch(move |context| {
+    context.put_concrete(key.key(), value);
+    Ok(())
+  })
+}
+
+#[test]
+fn test_branches_run_concurrently() {
+  let started_branches = Arc::new(AtomicUsize::new(0));
+  let saw_other_branch = Arc::new(AtomicUsize::new(0));
+
+  let create_branch = || {
+    let started_branches = started_branches.clone();
+    let saw_other_branch = saw_other_branch.clone();
+
+    branch(move |_| {
+      started_branches.fetch_add(1, Ordering::SeqCst);
+
+      let start = Instant::now();
+      while start.elapsed() < Duration::from_secs(5) {
+        if started_branches.load(Ordering::SeqCst) == 2 {
+          saw_other_branch.fetch_add(1, Ordering::SeqCst);
+          break;
+        }
+
+        std::thread::yield_now();
+      }
+
+      Ok(())
+    })
+  };
+
+  let part = ParallelPipelinePart::new(vec![create_branch(), create_branch()]);
+  part
+    .execute(&mut PipelineContext::default(), &PipelineInfrastructure::new(None))
+    .unwrap();
+
+  assert_eq!(saw_other_branch.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_branches_outputs_are_merged() {
+  let part = ParallelPipelinePart::new(vec![put_value(&FIRST_KEY, 1), put_value(&SECOND_KEY, 2)]);
+
+  let mut context = PipelineContext::default();
+  part.execute(&mut context, &PipelineInfrastructure::new(None)).unwrap();
+
+  assert_eq!(context.concrete(FIRST_KEY.key()), Some(&1));
+  assert_eq!(context.concrete(SECOND_KEY.key()), Some(&2));
+}
+
+#[test]
+fn test_branches_see_event_log_copy() {
+  let traces_counts = Arc::new(AtomicUsize::new(0));
+
+  let create_branch = || {
+    let traces_counts = traces_counts.clone();
+    branch(move |context| {
+      let log = context.concrete(EVENT_LOG_KEY.key()).unwrap();
+      traces_counts.fetch_add(log.traces().len(), Ordering::SeqCst);
+      Ok(())
+    })
+  };
+
+  let mut log = XesEventLogImpl::default();
+  log.push(Rc::new(RefCell::new(XesTraceImpl::default())));
+
+  let mut context = PipelineContext::default();
+  context.put_concrete(EVENT_LOG_KEY.key(), log);
+
+  let part = ParallelPipelinePart::new(vec![create_branch(), create_branch()]);
+  part.execute(&mut context, &PipelineInfrastructure::new(None)).unwrap();
+
+  assert_eq!(traces_counts.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_last_branch_wins() {
+  let part = ParallelPipelinePart::new(vec![put_value(&FIRST_KEY, 1), put_value(&FIRST_KEY, 2)]);
+
+  let mut context = PipelineContext::default();
+  context.put_concrete(FIRST_KEY.key(), 0);
+  part.execute(&mut context, &PipelineInfrastructure::new(None)).unwrap();
+
+  assert_eq!(context.concrete(FIRST_KEY.key()), Some(&2));
+}
+
+#[test]
+fn test_fail_on_conflict() {
+  let part = ParallelPipelinePart::new(vec![put_value(&FIRST_KEY, 1), put_value(&FIRST_KEY, 2)])
+    .with_merge_policy(ParallelOutputsMergePolicy::FailOnConflict);
+
+  let mut context = PipelineContext::default();
+  let result = part.execute(&mut context, &PipelineInfrastructure::new(None));
+
+  assert!(matches!(result, Err(PipelinePartExecutionError::Raw(_))));
+  assert_eq!(context.concrete(FIRST_KEY.key()), None);
+}
+
+#[test]
+fn test_branch_error_is_propagated() {
+  let failing_branch = branch(|_| Err(PipelinePartExecutionError::new_raw("failed".to_string())));
+  let part = ParallelPipelinePart::new(vec![put_value(&FIRST_KEY, 1), failing_branch]);
+
+  let mut context = PipelineContext::default();
+  let result = part.execute(&mut context, &PipelineInfrastructure::new(None));
+
+  assert!(matches!(result, Err(PipelinePartExecutionError::Raw(_))));
+  assert_eq!(context.concrete(FIRST_KEY.key()), None);
+}
+
+#[test]
+fn test_branches_see_petri_net_and_events_user_data() {
+  let places_counts = Arc::new(AtomicUsize::new(0));
+  let display_names_counts = Arc::new(AtomicUsize::new(0));
+
+  let create_branch = || {
+    let places_counts = places_counts.clone();
+    let display_names_counts = display_names_counts.clone();
+
+    branch(move |context| {
+      places_counts.fetch_add(context.concrete(PETRI_NET_KEY.key()).unwrap().all_places().len(), Ordering::SeqCst);
+
+      let log = context.concrete(EVENT_LOG_KEY.key()).unwrap();
+      for trace in log.traces() {
+        for event in trace.borrow().events() {
+          if event.borrow().user_data().concrete(DISPLAY_NAME_KEY.key()).is_some() {
+            display_names_counts.fetch_add(1, Ordering::SeqCst);
+          }
+        }
+      }
+
+      Ok(())
+    })
+  };
+
+  let mut net = DefaultPetriNet::default();
+  net.add_place(Place::with_name("p".to_string()));
+
+  let mut log = create_simple_event_log();
+  log.mutate_events(|event| event.user_data_mut().put_concrete(DISPLAY_NAME_KEY.key(), Arc::from("display")));
+
+  let mut context = PipelineContext::default();
+  context.put_concrete(PETRI_NET_KEY.key(), net);
+  context.put_concrete(EVENT_LOG_KEY.key(), log);
+
+  let part = ParallelPipelinePart::new(vec![create_branch(), create_branch()]);
+  part.execute(&mut context, &PipelineInfrastructure::new(None)).unwrap();
+
+  assert_eq!(places_counts.load(Ordering::SeqCst), 2);
+  assert_eq!(display_names_counts.load(Ordering::SeqCst), 12);
+}
+
+#[test]
+fn test_not_copyable_value_fails_execution() {
+  let mut context = PipelineContext::default();
+  context.put_concrete(NOT_COPYABLE_KEY.key(), Rc::new(1));
+
+  let part = ParallelPipelinePart::new(vec![put_value(&FIRST_KEY, 1)]);
+  let result = part.execute(&mut context, &PipelineInfrastructure::new(None));
+
+  assert!(matches!(result, Err(PipelinePartExecutionError::Raw(_))));
+  assert!(context.concrete(FIRST_KEY.key()).is_none());
+}
+
+#[test]
+fn test_branch_changing_value_in_place_is_merged() {
+  let mut config = UserDataImpl::default();
+  config.put_concrete(EVENT_NAME_KEY.key(), Arc::from("A"));
+
+  let mut filter_branch = Pipeline::default();
+  filter_branch.push(Box::new(PipelineParts::new().find_part("FilterEventsByName").unwrap()(Box::new(
+    config,
+  ))));
+
+  let mut context = PipelineContext::default();
+  context.put_concrete(EVENT_LOG_KEY.key(), create_simple_event_log());
+
+  let part =
+    ParallelPipelinePart::new(vec![filter_branch, put_value(&FIRST_KEY, 1)]).with_merge_policy(ParallelOutputsMergePolicy::FailOnConflict);
+  part.execute(&mut context, &PipelineInfrastructure::new(None)).unwrap();
+
+  let log = context.concrete(EVENT_LOG_KEY.key()).unwrap();
+  let events_count = log.traces().iter().map(|trace| trace.borrow().events().len()).sum::<usize>();
+
+  assert_eq!(events_count, 4);
+  assert_eq!(context.concrete(FIRST_KEY.key()), Some(&1));
+}
@@ -1,4 +1,56 @@
-use ficus::{pipelines::pipeline_parts::PipelineParts, vecs};
+use crate::test_core::simple_events_logs_provider::create_simple_event_log;
+use bxes::models::system_models::SystemMetadata;
+use ficus::{
+  event_log::xes::xes_event_log::XesEventLogImpl,
+  features::{
+    analysis::{
+      drift::{distributions::DriftFeature, statistics::DriftStatisticalTest},
+      log_info::{event_log_info::OfflineEventLogInfo, log_info_creation_dto::EventLogInfoCreationDto},
+      patterns::{
+        activity_instances::{ActivityInTraceFilterKind, ActivityInTraceInfo, ActivityNarrowingKind, AdjustingMode},
+        contexts::PatternsDiscoveryStrategy,
+        pattern_info::{UNDERLYING_PATTERN_KIND_KEY, UnderlyingPatternKind},
+        repeat_sets::ActivityNode,
+        tandem_arrays::SubArrayInTraceInfo,
+      },
+    },
+    clustering::{activities::activities_params::ActivityRepresentationSource, traces::traces_params::TracesRepresentationSource},
+    discovery::{
+      ecfg::models::RootSequenceKind,
+      fuzzy::proximity_correlation::PrecomputedProximityCorrelation,
+      petri_net::{annotations::TimeAnnotationKind, petri_net::DefaultPetriNet},
+      relations::triangle_relation::PrecomputedTriangleRelation,
+      timeline::{
+        discovery::{LogTimelineDiagram, discover_timeline_diagram},
+        software_data::extraction_config::SoftwareDataExtractionConfig,
+      },
+    },
+  },
+  pipelines::{
+    activities_parts::{ActivitiesLogsSourceDto, UndefActivityHandlingStrategyDto},
+    context::{PipelineContext, PipelineInfrastructure},
+    control_flow_parts::{ComparisonOperator, ConditionMetric},
+    errors::pipeline_errors::PipelinePartExecutionError,
+    keys::context_keys::find_context_key,
+    multithreading::FeatureCountKindDto,
+    parts_metadata::{PipelinePartKeyDescriptor, PipelinePartMetadata},
+    patterns_parts::PatternsKindDto,
+    pipeline_parts::PipelineParts,
+    pipelines::{Pipeline, PipelinePart},
+  },
+  utils::{
+    colors::ColorsHolder,
+    distance::distance::FicusDistance,
+    graph::graph::DefaultGraph,
+    log_serialization_format::LogSerializationFormat,
+    user_data::{
+      keys::Key,
+      user_data::{UserData, UserDataImpl},
+    },
+  },
+  vecs,
+};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
 
 fn get_test_parts_names() -> Vec<String> {
   vecs![
@@ -28,6 +80,7 @@ fn get_test_parts_names() -> Vec<String> {
     "GetHashesEventLog",
     "GetNamesEventLog",
     "UseNamesEventLog",
+    "UseClosedCasesEventLog",
     "DiscoverActivitiesForSeveralLevels",
     "DiscoverActivitiesInUnattachedSubTraces",
     "DiscoverActivitiesUntilNoMore",
@@ -46,6 +99,8 @@ fn get_test_parts_names() -> Vec<String> {
     "DiscoverDirectlyFollowsGraph",
     "DiscoverPetriNetHeuristic",
     "DiscoverFuzzyGraph",
+    "DiscoverPetriNetHeuristicStream",
+    "DiscoverFuzzyGraphStream",
     "AnnotatePetriNetWithCount",
     "AnnotatePetriNetWithFrequency",
     "AnnotatePetriNetWithTraceFrequency",
@@ -91,7 +146,11 @@ fn get_test_parts_names() -> Vec<String> {
     "AddGraphToGraphs",
     "ClearGraphs",
     "TerminateIfEmptyLog",
-    "CreateOcelAnnotationForDag"
+    "CreateOcelAnnotationForDag",
+    "If",
+    "ForEach",
+    "Sweep",
+    "DetectConceptDrift"
   ]
 }
 
@@ -112,3 +171,183 @@ fn test_pipeline_parts_count() {
 
   assert_eq!(parts.len(), names.len());
 }
+
+#[test]
+fn test_pipeline_parts_metadata() {
+  let parts = PipelineParts::new();
+
+  for name in get_test_parts_names() {
+    assert_eq!(parts.find_part_metadata(name.as_str()).unwrap().name(), name);
+  }
+}
+
+#[test]
+fn test_read_log_from_xes_metadata() {
+  let parts = PipelineParts::new();
+  let metadata = parts.find_part_metadata("ReadLogFromXes").unwrap();
+
+  let to_tuples = |keys: &Vec<PipelinePartKeyDescriptor>| {
+    keys
+      .iter()
+      .map(|k| (k.name().to_owned(), k.type_name().to_owned(), k.required()))
+      .collect::<Vec<(String, String, bool)>>()
+  };
+
+  assert!(metadata.config_keys().is_empty());
+  assert_eq!(
+    to_tuples(metadata.input_keys()),
+    vec![("path".to_string(), "Arc<str>".to_string(), true)]
+  );
+  assert_eq!(
+    to_tuples(metadata.output_keys()),
+    vec![("event_log".to_string(), "XesEventLogImpl".to_string(), true)]
+  );
+}
+
+#[test]
+fn test_discover_dfg_metadata() {
+  let parts = PipelineParts::new();
+  let metadata = parts.find_part_metadata("DiscoverDirectlyFollowsGraph").unwrap();
+
+  let thread_attribute = &metadata.config_keys()[0];
+  assert_eq!(thread_attribute.name(), "thread_attribute");
+  assert!(!thread_attribute.required());
+  assert_eq!(thread_attribute.default_value(), None);
+}
+
+fn find_key(name: &str) -> &dyn Key {
+  match find_context_key(name) {
+    Some(context_key) => context_key.key(),
+    None if name == UNDERLYING_PATTERN_KIND_KEY.key().name() => UNDERLYING_PATTERN_KIND_KEY.key(),
+    None => panic!("Unknown key {}", name),
+  }
+}
+
+/// Values are not meaningful, they only have to be of the declared type so that the part can read them.
+fn put_declared_value(user_data: &mut impl UserData, descriptor: &PipelinePartKeyDescriptor) {
+  let missing_path = std::env::temp_dir().join("ficus_parts_metadata_tests_missing_dir").join("log");
+  let missing_path: Arc<str> = Arc::from(missing_path.to_str().unwrap());
+
+  let key = find_key(descriptor.name());
+  match (descriptor.name(), descriptor.type_name()) {
+    ("path", _) => user_data.put_any::<Arc<str>>(key, missing_path),
+    ("paths", _) => user_data.put_any::<Vec<Arc<str>>>(key, vec![missing_path]),
+    ("for_each_collection", _) => user_data.put_any::<Arc<str>>(key, Arc::from("paths")),
+    ("sweep_grid", _) => user_data.put_any::<Vec<Arc<str>>>(key, vec![Arc::from("event_name=A")]),
+    (_, "Arc<str>") => user_data.put_any::<Arc<str>>(key, Arc::from("A")),
+    (_, "bool") => user_data.put_any::<bool>(key, false),
+    (_, "f64") => user_data.put_any::<f64>(key, 0.5),
+    (_, "u32") => user_data.put_any::<u32>(key, 2),
+    (_, "Vec<Arc<str>>") => user_data.put_any::<Vec<Arc<str>>>(key, vec![Arc::from("A")]),
+    (_, "Vec<f64>") => user_data.put_any::<Vec<f64>>(key, vec![0.5]),
+    (_, "Vec<u64>") => user_data.put_any::<Vec<u64>>(key, vec![1]),
+    (_, "Vec<u8>") => user_data.put_any::<Vec<u8>>(key, vec![]),
+    (_, "Vec<Vec<u64>>") => user_data.put_any::<Vec<Vec<u64>>>(key, vec![]),
+    (_, "Vec<Vec<Arc<str>>>") => user_data.put_any::<Vec<Vec<Arc<str>>>>(key, vec![]),
+    (_, "Vec<Vec<SubArrayInTraceInfo>>") => user_data.put_any::<Vec<Vec<SubArrayInTraceInfo>>>(key, vec![]),
+    (_, "Vec<Vec<ActivityInTraceInfo>>") => user_data.put_any::<Vec<Vec<ActivityInTraceInfo>>>(key, vec![]),
+    (_, "Vec<Rc<RefCell<ActivityNode>>>") => user_data.put_any::<Vec<Rc<RefCell<ActivityNode>>>>(key, vec![]),
+    (_, "XesEventLogImpl") => user_data.put_any::<XesEventLogImpl>(key, create_simple_event_log()),
+    (_, "HashMap<Arc<str>, XesEventLogImpl>") => user_data.put_any::<HashMap<Arc<str>, XesEventLogImpl>>(key, HashMap::new()),
+    (_, "Graph<Arc<str>, Arc<str>>") => user_data.put_any::<DefaultGraph>(key, DefaultGraph::default()),
+    (_, "Vec<Graph<Arc<str>, Arc<str>>>") => user_data.put_any::<Vec<DefaultGraph>>(key, vec![]),
+    (_, "PetriNet<Arc<str>, ()>") => user_data.put_any::<DefaultPetriNet>(key, DefaultPetriNet::default()),
+    (_, "Pipeline") => user_data.put_any::<Pipeline>(key, Pipeline::default()),
+    (_, "OfflineEventLogInfo") => {
+      let info = OfflineEventLogInfo::create_from(EventLogInfoCreationDto::default(&create_simple_event_log()));
+      user_data.put_any::<OfflineEventLogInfo>(key, info)
+    }
+    (_, "LogTimelineDiagram") => {
+      let diagram = discover_timeline_diagram(&create_simple_event_log(), &Arc::from("thread"), None, None, None).unwrap();
+      user_data.put_any::<LogTimelineDiagram>(key, diagram)
+    }
+    (_, "PrecomputedTriangleRelation") => user_data.put_any::<PrecomputedTriangleRelation>(key, Default::default()),
+    (_, "PrecomputedProximityCorrelation") => user_data.put_any::<PrecomputedProximityCorrelation>(key, Default::default()),
+    (_, "SoftwareDataExtractionConfig") => user_data.put_any::<SoftwareDataExtractionConfig>(key, Default::default()),
+    (_, "SystemMetadata") => user_data.put_any::<SystemMetadata>(key, SystemMetadata::new(None)),
+    (_, "ColorsHolder") => user_data.put_any::<ColorsHolder>(key, Default::default()),
+    (_, "UnderlyingPatternKind") => user_data.put_any::<UnderlyingPatternKind>(key, UnderlyingPatternKind::Unknown),
+    (_, "ActivityInTraceFilterKind") => user_data.put_any::<ActivityInTraceFilterKind>(key, ActivityInTraceFilterKind::NoFilter),
+    (_, "ActivitiesLogsSourceDto") => user_data.put_any::<ActivitiesLogsSourceDto>(key, ActivitiesLogsSourceDto::Log),
+    (_, "ActivityNarrowingKind") => user_data.put_any::<ActivityNarrowingKind>(key, ActivityNarrowingKind::DontNarrow),
+    (_, "ActivityRepresentationSource") => {
+      user_data.put_any::<ActivityRepresentationSource>(key, ActivityRepresentationSource::EventClasses)
+    }
+    (_, "AdjustingMode") => user_data.put_any::<AdjustingMode>(key, AdjustingMode::FromAllLog),
+    (_, "ComparisonOperator") => user_data.put_any::<ComparisonOperator>(key, ComparisonOperator::Less),
+    (_, "ConditionMetric") => user_data.put_any::<ConditionMetric>(key, ConditionMetric::EventsCount),
+    (_, "DriftFeature") => user_data.put_any::<DriftFeature>(key, DriftFeature::DfgRelations),
+    (_, "DriftStatisticalTest") => user_data.put_any::<DriftStatisticalTest>(key, DriftStatisticalTest::ChiSquare),
+    (_, "FeatureCountKindDto") => user_data.put_any::<FeatureCountKindDto>(key, FeatureCountKindDto::One),
+    (_, "FicusDistance") => user_data.put_any::<FicusDistance>(key, FicusDistance::Cosine),
+    (_, "LogSerializationFormat") => user_data.put_any::<LogSerializationFormat>(key, LogSerializationFormat::Xes),
+    (_, "PatternsDiscoveryStrategy") => user_data.put_any::<PatternsDiscoveryStrategy>(key, PatternsDiscoveryStrategy::FromAllTraces),
+    (_, "PatternsKindDto") => user_data.put_any::<PatternsKindDto>(key, PatternsKindDto::PrimitiveTandemArrays),
+    (_, "RootSequenceKind") => user_data.put_any::<RootSequenceKind>(key, RootSequenceKind::FindBest),
+    (_, "TimeAnnotationKind") => user_data.put_any::<TimeAnnotationKind>(key, TimeAnnotationKind::SummedTime),
+    (_, "TracesRepresentationSource") => user_data.put_any::<TracesRepresentationSource>(key, TracesRepresentationSource::Events),
+    (_, "UndefActivityHandlingStrategyDto") => {
+      user_data.put_any::<UndefActivityHandlingStrategyDto>(key, UndefActivityHandlingStrategyDto::DontInsert)
+    }
+    (name, type_name) => panic!("No test value for key {} of type {}", name, type_name),
+  }
+}
+
+/// Executes the part with the declared config and input keys only, keys which are not required are put only if `with_optional` is set.
+fn execute_with_declared_keys(
+  parts: &PipelineParts,
+  metadata: &PipelinePartMetadata,
+  with_optional: bool,
+) -> Result<(), PipelinePartExecutionError> {
+  let declared = |keys: &Vec<PipelinePartKeyDescriptor>| keys.iter().filter(|k| with_optional || k.required()).cloned().collect::<Vec<_>>();
+
+  let mut config = UserDataImpl::default();
+  for descriptor in declared(metadata.config_keys()) {
+    put_declared_value(&mut config, &descriptor);
+  }
+
+  let mut context = PipelineContext::new_with_logging(parts);
+  for descriptor in declared(metadata.input_keys()) {
+    put_declared_value(&mut context, &descriptor);
+  }
+
+  let mut pipeline = Pipeline::default();
+  pipeline.push(Box::new(parts.find_part(metadata.name()).unwrap()(Box::new(config))));
+  pipeline.execute(&mut context, &PipelineInfrastructure::new(None))
+}
+
+/// K-means initialization panics on the dataset created from placeholder activities
+const PARTS_NOT_EXECUTABLE_WITH_TEST_VALUES: [&str; 1] = ["ClusterizeActivitiesFromTracesKMeans"];
+
+#[test]
+fn test_parts_read_only_declared_keys() {
+  let parts = PipelineParts::new();
+
+  for descriptor in parts.pipeline_parts_descriptors() {
+    let metadata = descriptor.metadata();
+    if PARTS_NOT_EXECUTABLE_WITH_TEST_VALUES.contains(&metadata.name()) {
+      continue;
+    }
+
+    if let Err(PipelinePartExecutionError::MissingContext(key)) = execute_with_declared_keys(&parts, metadata, true) {
+      panic!("{} reads undeclared key {}", metadata.name(), key);
+    }
+
+    // Optional keys may be required for some values of other keys, e.g. ForEach requires the collection it iterates over
+    if let Err(PipelinePartExecutionError::MissingContext(key)) = execute_with_declared_keys(&parts, metadata, false) {
+      let key_name = key.to_string();
+      let is_optional = metadata
+        .config_keys()
+        .iter()
+        .chain(metadata.input_keys())
+        .any(|k| !k.required() && k.name() == key_name);
+
+      assert!(
+        is_optional,
+        "{} reads undeclared key {} when optional keys are not set",
+        metadata.name(),
+        key_name
+      );
+    }
+  }
+}
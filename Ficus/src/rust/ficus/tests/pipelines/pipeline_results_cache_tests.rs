@@ -0,0 +1,257 @@
+use crate::test_core::simple_events_logs_provider::{create_simple_event_log, create_simple_event_log2};
+use ficus::{
+  event_log::{
+    core::{event_log::EventLog, trace::trace::Trace},
+    xes::xes_event_log::XesEventLogImpl,
+  },
+  pipelines::{
+    context::{PipelineContext, PipelineInfrastructure},
+    keys::context_keys::{EVENT_LOG_KEY, HASHES_EVENT_LOG_KEY, PATH_KEY, TANDEM_ARRAY_LENGTH_KEY},
+    pipeline_parts::PipelineParts,
+    pipelines::{DefaultPipelinePart, PipelinePart},
+    results_cache::{CacheHitHandler, PipelinePartsResultsCache},
+  },
+  utils::{
+    display_name::DISPLAY_NAME_KEY,
+    user_data::user_data::{UserData, UserDataImpl, UserDataOwner},
+  },
+};
+use std::{
+  fs,
+  sync::{
+    Arc, Mutex,
+    atomic::{AtomicUsize, Ordering},
+  },
+};
+
+struct TestCacheHitHandler {
+  hits: Mutex<Vec<String>>,
+}
+
+impl CacheHitHandler for TestCacheHitHandler {
+  fn handle(&self, part_name: &str) {
+    self.hits.lock().unwrap().push(part_name.to_owned());
+  }
+}
+
+fn create_part(tandem_array_length: u32, executions_count: Arc<AtomicUsize>) -> DefaultPipelinePart {
+  let mut config = UserDataImpl::default();
+  config.put_concrete(TANDEM_ARRAY_LENGTH_KEY.key(), tandem_array_length);
+
+  DefaultPipelinePart::new(
+    "FindPrimitiveTandemArrays".to_string(),
+    Box::new(config),
+    Box::new(move |context, _, config| {
+      executions_count.fetch_add(1, Ordering::SeqCst);
+      let length = *config.concrete(TANDEM_ARRAY_LENGTH_KEY.key()).unwrap() as u64;
+      context.put_concrete(HASHES_EVENT_LOG_KEY.key(), vec![vec![length]]);
+      Ok(())
+    }),
+  )
+}
+
+fn execute(part: &DefaultPipelinePart, log: &XesEventLogImpl, infra: &PipelineInfrastructure) -> Option<Vec<Vec<u64>>> {
+  let parts = PipelineParts::new();
+  let mut context = PipelineContext::new_with_logging(&parts);
+  context.put_concrete(EVENT_LOG_KEY.key(), log.detached_clone().unwrap());
+
+  part.execute(&mut context, infra).unwrap();
+
+  context.concrete(HASHES_EVENT_LOG_KEY.key()).cloned()
+}
+
+#[test]
+fn test_cached_results_are_reused() {
+  let cache = Arc::new(PipelinePartsResultsCache::new(8));
+  let handler = Arc::new(TestCacheHitHandler { hits: Mutex::new(vec![]) });
+  let infra = PipelineInfrastructure::new(None)
+    .with_results_cache(cache.clone())
+    .with_cache_hit_handler(handler.clone());
+
+  let executions_count = Arc::new(AtomicUsize::new(0));
+  let part = create_part(2, executions_count.clone());
+  let log = create_simple_event_log();
+
+  assert_eq!(execute(&part, &log, &infra), Some(vec![vec![2]]));
+  assert_eq!(execute(&part, &log, &infra), Some(vec![vec![2]]));
+
+  assert_eq!(executions_count.load(Ordering::SeqCst), 1);
+  assert_eq!(cache.len(), 1);
+  assert_eq!(*handler.hits.lock().unwrap(), vec!["FindPrimitiveTandemArrays".to_string()]);
+}
+
+#[test]
+fn test_different_config_or_inputs_are_not_reused() {
+  let cache = Arc::new(PipelinePartsResultsCache::new(8));
+  let infra = PipelineInfrastructure::new(None).with_results_cache(cache.clone());
+
+  let executions_count = Arc::new(AtomicUsize::new(0));
+  let log = create_simple_event_log();
+
+  execute(&create_part(2, executions_count.clone()), &log, &infra);
+  execute(&create_part(3, executions_count.clone()), &log, &infra);
+  execute(&create_part(2, executions_count.clone()), &create_simple_event_log2(), &infra);
+
+  assert_eq!(executions_count.load(Ordering::SeqCst), 3);
+  assert_eq!(cache.len(), 3);
+}
+
+#[test]
+fn test_no_cache_without_infrastructure_cache() {
+  let infra = PipelineInfrastructure::new(None);
+  let executions_count = Arc::new(AtomicUsize::new(0));
+  let part = create_part(2, executions_count.clone());
+  let log = create_simple_event_log();
+
+  execute(&part, &log, &infra);
+  execute(&part, &log, &infra);
+
+  assert_eq!(executions_count.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_least_recently_used_entries_are_evicted() {
+  let cache = Arc::new(PipelinePartsResultsCache::new(2));
+  let infra = PipelineInfrastructure::new(None).with_results_cache(cache.clone());
+
+  let executions_count = Arc::new(AtomicUsize::new(0));
+  let log = create_simple_event_log();
+  let run = |length| execute(&create_part(length, executions_count.clone()), &log, &infra);
+
+  run(1);
+  run(2);
+  run(1);
+  run(3);
+  assert_eq!(executions_count.load(Ordering::SeqCst), 3);
+
+  run(1);
+  assert_eq!(executions_count.load(Ordering::SeqCst), 3);
+
+  run(2);
+  assert_eq!(executions_count.load(Ordering::SeqCst), 4);
+  assert_eq!(cache.len(), 2);
+}
+
+fn set_display_name(log: &mut XesEventLogImpl, display_name: &str) {
+  log.mutate_events(|event| event.user_data_mut().put_concrete(DISPLAY_NAME_KEY.key(), Arc::from(display_name)));
+}
+
+fn first_event_display_name(log: &XesEventLogImpl) -> Option<Arc<str>> {
+  let trace = log.traces().first()?.borrow();
+  let event = trace.events().first()?.borrow();
+
+  event.user_data().concrete(DISPLAY_NAME_KEY.key()).cloned()
+}
+
+#[test]
+fn test_events_user_data_is_keyed_and_restored() {
+  let cache = Arc::new(PipelinePartsResultsCache::new(8));
+  let infra = PipelineInfrastructure::new(None).with_results_cache(cache.clone());
+  let executions_count = Arc::new(AtomicUsize::new(0));
+
+  let part = {
+    let executions_count = executions_count.clone();
+    DefaultPipelinePart::new(
+      "SubstituteUnderlyingEvents".to_string(),
+      Box::default(),
+      Box::new(move |context, _, _| {
+        executions_count.fetch_add(1, Ordering::SeqCst);
+        let mut log = create_simple_event_log();
+        set_display_name(&mut log, "substituted");
+        context.put_concrete(EVENT_LOG_KEY.key(), log);
+        Ok(())
+      }),
+    )
+  };
+
+  let log = create_simple_event_log();
+  let run = |display_name: &str| {
+    let parts = PipelineParts::new();
+    let mut context = PipelineContext::new_with_logging(&parts);
+    let mut log = log.detached_clone().unwrap();
+    set_display_name(&mut log, display_name);
+    context.put_concrete(EVENT_LOG_KEY.key(), log);
+
+    part.execute(&mut context, &infra).unwrap();
+
+    first_event_display_name(context.concrete(EVENT_LOG_KEY.key()).unwrap())
+  };
+
+  assert_eq!(run("first"), Some(Arc::from("substituted")));
+  assert_eq!(cache.len(), 1);
+  assert_eq!(run("second"), Some(Arc::from("substituted")));
+  assert_eq!(executions_count.load(Ordering::SeqCst), 2);
+
+  assert_eq!(run("first"), Some(Arc::from("substituted")));
+  assert_eq!(executions_count.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_changed_file_is_not_reused() {
+  let cache = Arc::new(PipelinePartsResultsCache::new(8));
+  let infra = PipelineInfrastructure::new(None).with_results_cache(cache.clone());
+  let executions_count = Arc::new(AtomicUsize::new(0));
+
+  let part = {
+    let executions_count = executions_count.clone();
+    DefaultPipelinePart::new(
+      "ReadLogFromXes".to_string(),
+      Box::default(),
+      Box::new(move |context, _, _| {
+        executions_count.fetch_add(1, Ordering::SeqCst);
+        context.put_concrete(EVENT_LOG_KEY.key(), create_simple_event_log());
+        Ok(())
+      }),
+    )
+  };
+
+  let path = std::env::temp_dir().join(format!("ficus_results_cache_{}.xes", uuid::Uuid::new_v4()));
+  let run = || {
+    let parts = PipelineParts::new();
+    let mut context = PipelineContext::new_with_logging(&parts);
+    context.put_concrete(PATH_KEY.key(), Arc::from(path.to_str().unwrap()));
+
+    part.execute(&mut context, &infra).unwrap();
+  };
+
+  fs::write(&path, "first").unwrap();
+  run();
+  run();
+  assert_eq!(executions_count.load(Ordering::SeqCst), 1);
+
+  fs::write(&path, "second version").unwrap();
+  run();
+  assert_eq!(executions_count.load(Ordering::SeqCst), 2);
+
+  fs::remove_file(&path).unwrap();
+  run();
+  assert_eq!(executions_count.load(Ordering::SeqCst), 3);
+  assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn test_entries_are_evicted_when_bytes_budget_is_reached() {
+  let log = create_simple_event_log();
+  let executions_count = Arc::new(AtomicUsize::new(0));
+
+  let unbounded_cache = Arc::new(PipelinePartsResultsCache::new(8));
+  let infra = PipelineInfrastructure::new(None).with_results_cache(unbounded_cache.clone());
+  execute(&create_part(1, executions_count.clone()), &log, &infra);
+  let entry_size = unbounded_cache.size_in_bytes();
+  assert!(entry_size > 0);
+
+  let cache = Arc::new(PipelinePartsResultsCache::new(8).with_max_bytes(2 * entry_size));
+  let infra = PipelineInfrastructure::new(None).with_results_cache(cache.clone());
+  for length in 1..=3 {
+    execute(&create_part(length, executions_count.clone()), &log, &infra);
+  }
+
+  assert_eq!(cache.len(), 2);
+  assert!(cache.size_in_bytes() <= 2 * entry_size);
+
+  let small_cache = Arc::new(PipelinePartsResultsCache::new(8).with_max_bytes(entry_size - 1));
+  let infra = PipelineInfrastructure::new(None).with_results_cache(small_cache.clone());
+  execute(&create_part(1, executions_count.clone()), &log, &infra);
+
+  assert!(small_cache.is_empty());
+}
@@ -0,0 +1,140 @@
+use ficus::pipelines::scheduler::{PipelineJob, PipelineScheduler, PipelineSchedulerConfig, PipelineSchedulingPolicy};
+use std::{
+  sync::{
+    Arc, Mutex,
+    atomic::{AtomicUsize, Ordering},
+    mpsc::{self, Receiver, Sender},
+  },
+  time::Duration,
+};
+use uuid::Uuid;
+
+const WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn create_scheduler(max_concurrent_pipelines: usize, policy: PipelineSchedulingPolicy) -> Arc<PipelineScheduler> {
+  let config = PipelineSchedulerConfig {
+    max_concurrent_pipelines,
+    policy,
+  };
+
+  Arc::new(PipelineScheduler::new(
+    config,
+    Box::new(|job| {
+      std::thread::spawn(job);
+    }),
+  ))
+}
+
+fn create_job(priority: i32, job: impl FnOnce() + Send + 'static) -> PipelineJob {
+  PipelineJob::new(Uuid::new_v4(), priority, Box::new(job), Box::new(|_, _| {}))
+}
+
+/// Submits a job which runs until the returned sender is used or dropped
+fn submit_blocking_job(scheduler: &Arc<PipelineScheduler>, finished: Sender<i32>) -> Sender<()> {
+  let (release_sender, release_receiver) = mpsc::channel::<()>();
+  scheduler.submit(create_job(0, move || {
+    let _ = release_receiver.recv_timeout(WAIT_TIMEOUT);
+    finished.send(0).unwrap();
+  }));
+
+  release_sender
+}
+
+fn receive_all(receiver: &Receiver<i32>, count: usize) -> Vec<i32> {
+  (0..count).map(|_| receiver.recv_timeout(WAIT_TIMEOUT).unwrap()).collect()
+}
+
+fn execute_queued_jobs(policy: PipelineSchedulingPolicy, priorities: &[i32]) -> Vec<i32> {
+  let scheduler = create_scheduler(1, policy);
+  let (finished_sender, finished_receiver) = mpsc::channel();
+
+  let release_sender = submit_blocking_job(&scheduler, finished_sender.clone());
+  for priority in priorities {
+    let finished_sender = finished_sender.clone();
+    let priority = *priority;
+    scheduler.submit(create_job(priority, move || finished_sender.send(priority).unwrap()));
+  }
+
+  release_sender.send(()).unwrap();
+
+  receive_all(&finished_receiver, priorities.len() + 1)[1..].to_vec()
+}
+
+#[test]
+fn test_fifo_policy() {
+  assert_eq!(execute_queued_jobs(PipelineSchedulingPolicy::Fifo, &[1, 3, 2]), vec![1, 3, 2]);
+}
+
+#[test]
+fn test_priority_policy() {
+  assert_eq!(
+    execute_queued_jobs(PipelineSchedulingPolicy::Priority, &[1, 3, 2, 3]),
+    vec![3, 3, 2, 1]
+  );
+}
+
+#[test]
+fn test_concurrent_jobs_are_bounded() {
+  let scheduler = create_scheduler(2, PipelineSchedulingPolicy::Fifo);
+  let (finished_sender, finished_receiver) = mpsc::channel();
+
+  let running = Arc::new(AtomicUsize::new(0));
+  let max_running = Arc::new(AtomicUsize::new(0));
+
+  for _ in 0..6 {
+    let finished_sender = finished_sender.clone();
+    let running = running.clone();
+    let max_running = max_running.clone();
+
+    scheduler.submit(create_job(0, move || {
+      let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+      max_running.fetch_max(now_running, Ordering::SeqCst);
+
+      std::thread::sleep(Duration::from_millis(20));
+
+      running.fetch_sub(1, Ordering::SeqCst);
+      finished_sender.send(0).unwrap();
+    }));
+  }
+
+  receive_all(&finished_receiver, 6);
+
+  assert_eq!(max_running.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_queue_positions_are_reported() {
+  let scheduler = create_scheduler(1, PipelineSchedulingPolicy::Priority);
+  let (finished_sender, finished_receiver) = mpsc::channel();
+  let positions = Arc::new(Mutex::new(vec![]));
+
+  let release_sender = submit_blocking_job(&scheduler, finished_sender.clone());
+  for priority in [1, 2] {
+    let finished_sender = finished_sender.clone();
+    let positions = positions.clone();
+
+    scheduler.submit(PipelineJob::new(
+      Uuid::new_v4(),
+      priority,
+      Box::new(move || finished_sender.send(priority).unwrap()),
+      Box::new(move |position, queue_length| positions.lock().unwrap().push((priority, position, queue_length))),
+    ));
+  }
+
+  release_sender.send(()).unwrap();
+  receive_all(&finished_receiver, 3);
+
+  let positions = positions.lock().unwrap().clone();
+  assert_eq!(positions, vec![(1, 1, 1), (2, 1, 2), (1, 2, 2), (1, 1, 1)]);
+}
+
+#[test]
+fn test_panicking_job_releases_slot() {
+  let scheduler = create_scheduler(1, PipelineSchedulingPolicy::Fifo);
+  let (finished_sender, finished_receiver) = mpsc::channel();
+
+  scheduler.submit(create_job(0, || panic!("Job failed")));
+  scheduler.submit(create_job(0, move || finished_sender.send(1).unwrap()));
+
+  assert_eq!(receive_all(&finished_receiver, 1), vec![1]);
+}
@@ -0,0 +1,133 @@
+use ficus::pipelines::{
+  pipeline_parts::PipelineParts,
+  validation::{
+    PipelineValidationConfigValue, PipelineValidationIssueKind, PipelineValidationPart, PipelineValidationStep, validate_pipeline,
+  },
+};
+
+fn part(name: &str, config: Vec<(&str, &str)>) -> PipelineValidationStep {
+  let config = config
+    .into_iter()
+    .map(|(key, type_name)| PipelineValidationConfigValue::new(key.to_owned(), Some(type_name.to_owned())))
+    .collect();
+
+  PipelineValidationStep::Part(PipelineValidationPart::new(name.to_owned(), config))
+}
+
+fn validate(steps: Vec<PipelineValidationStep>, initial_keys: Vec<&str>) -> Vec<(Vec<usize>, PipelineValidationIssueKind)> {
+  let initial_keys = initial_keys.into_iter().map(|k| k.to_owned()).collect::<Vec<String>>();
+
+  validate_pipeline(&PipelineParts::new(), &steps, &initial_keys)
+    .into_iter()
+    .map(|issue| (issue.location().clone(), issue.kind().clone()))
+    .collect()
+}
+
+#[test]
+fn test_valid_pipeline() {
+  let steps = vec![
+    part("ReadLogFromXes", vec![]),
+    part("FindPrimitiveTandemArrays", vec![("tandem_array_length", "u32")]),
+    part("DiscoverDirectlyFollowsGraph", vec![]),
+  ];
+
+  assert_eq!(validate(steps, vec!["path"]), vec![]);
+}
+
+#[test]
+fn test_missing_input_key() {
+  let steps = vec![part("DiscoverDirectlyFollowsGraph", vec![]), part("ReadLogFromXes", vec![])];
+
+  assert_eq!(
+    validate(steps, vec!["path"]),
+    vec![(
+      vec![0],
+      PipelineValidationIssueKind::MissingInputKey {
+        part_name: "DiscoverDirectlyFollowsGraph".to_owned(),
+        key_name: "event_log".to_owned(),
+      }
+    )]
+  );
+}
+
+#[test]
+fn test_unknown_part() {
+  let steps = vec![part("ReadLogFromXes", vec![]), part("SomeUnknownPart", vec![])];
+
+  assert_eq!(
+    validate(steps, vec!["path"]),
+    vec![(vec![1], PipelineValidationIssueKind::UnknownPart("SomeUnknownPart".to_owned()))]
+  );
+}
+
+#[test]
+fn test_config_issues() {
+  let steps = vec![
+    part("ReadLogFromXes", vec![]),
+    part("FindPrimitiveTandemArrays", vec![("tandem_array_length", "Arc<str>")]),
+    part("FindMaximalTandemArrays", vec![("some_unknown_key", "u32")]),
+  ];
+
+  assert_eq!(
+    validate(steps, vec!["path"]),
+    vec![
+      (
+        vec![1],
+        PipelineValidationIssueKind::WrongConfigType {
+          part_name: "FindPrimitiveTandemArrays".to_owned(),
+          key_name: "tandem_array_length".to_owned(),
+          expected: "u32".to_owned(),
+          actual: "Arc<str>".to_owned(),
+        }
+      ),
+      (
+        vec![2],
+        PipelineValidationIssueKind::UnknownContextKey("some_unknown_key".to_owned())
+      ),
+      (
+        vec![2],
+        PipelineValidationIssueKind::MissingConfigKey {
+          part_name: "FindMaximalTandemArrays".to_owned(),
+          key_name: "tandem_array_length".to_owned(),
+        }
+      ),
+    ]
+  );
+}
+
+#[test]
+fn test_unreachable_context_request() {
+  let steps = vec![
+    part("ReadLogFromXes", vec![]),
+    PipelineValidationStep::ContextRequest {
+      keys: vec!["event_log".to_owned(), "graph".to_owned()],
+      before_part: None,
+    },
+    PipelineValidationStep::ContextRequest {
+      keys: vec!["graph".to_owned()],
+      before_part: Some(PipelineValidationPart::new("DiscoverDirectlyFollowsGraph".to_owned(), vec![])),
+    },
+  ];
+
+  assert_eq!(
+    validate(steps, vec!["path"]),
+    vec![(vec![1], PipelineValidationIssueKind::UnreachableContextRequest("graph".to_owned()))]
+  );
+}
+
+#[test]
+fn test_parallel_branches_outputs_are_merged() {
+  let steps = vec![
+    part("ReadLogFromXes", vec![]),
+    PipelineValidationStep::Parallel(vec![
+      vec![part("DiscoverDirectlyFollowsGraph", vec![])],
+      vec![part("DiscoverPetriNetAlpha", vec![])],
+    ]),
+    PipelineValidationStep::ContextRequest {
+      keys: vec!["petri_net".to_owned(), "graph".to_owned()],
+      before_part: None,
+    },
+  ];
+
+  assert_eq!(validate(steps, vec!["path"]), vec![]);
+}
@@ -0,0 +1,252 @@
+use chrono::{Duration, TimeZone, Utc};
+use ficus::{
+  features::{
+    discovery::petri_net::{
+      marking::{Marking, SingleMarking},
+      petri_net::DefaultPetriNet,
+      place::Place,
+      transition::Transition,
+    },
+    streaming::conformance::{
+      core::{CaseConformance, OnlineConformanceChecker, OnlineConformanceModel},
+      dfg::DfgConformanceModel,
+      token_replay::TokenReplayConformanceModel,
+    },
+  },
+  utils::graph::graph::{DefaultGraph, NodesConnectionData},
+};
+use std::{collections::HashSet, sync::Arc};
+
+// start -> A -> p1 -> (silent) -> p2 -> B -> p3 -> C -> end
+fn create_sequence_net() -> DefaultPetriNet {
+  create_sequence_net_with(|i| format!("p{}", i), false)
+}
+
+fn create_sequence_net_with(place_name: impl Fn(usize) -> String, reverse_places_order: bool) -> DefaultPetriNet {
+  let mut net = DefaultPetriNet::default();
+  let mut places: Vec<(usize, u64)> = vec![];
+  for i in 0..5 {
+    let i = if reverse_places_order { 4 - i } else { i };
+    places.push((i, net.add_place(Place::with_name(place_name(i)))));
+  }
+
+  places.sort();
+  let places: Vec<u64> = places.into_iter().map(|(_, id)| id).collect();
+
+  let transitions = [("A", false, 0, 1), ("tau", true, 1, 2), ("B", false, 2, 3), ("C", false, 3, 4)];
+  for (name, silent, from, to) in transitions {
+    let id = net.add_transition(Transition::empty(name.into(), silent, Some(name.into())));
+    net.connect_place_to_transition(&places[from], &id, None);
+    net.connect_transition_to_place(&id, &places[to], None);
+  }
+
+  net.set_initial_marking(Marking::new(vec![SingleMarking::new(places[0], 1)]));
+  net
+}
+
+fn create_token_replay_checker(threshold: f64) -> OnlineConformanceChecker<u64, TokenReplayConformanceModel> {
+  OnlineConformanceChecker::new(TokenReplayConformanceModel::new(&create_sequence_net()).unwrap(), threshold)
+}
+
+fn observe_all<TModel: OnlineConformanceModel>(
+  checker: &mut OnlineConformanceChecker<u64, TModel>,
+  case: u64,
+  activities: &[&str],
+) -> Vec<f64> {
+  activities
+    .iter()
+    .map(|activity| checker.observe(case, activity).move_cost)
+    .collect()
+}
+
+#[test]
+fn test_token_replay_fitting_case() {
+  let mut checker = create_token_replay_checker(1.);
+
+  assert_eq!(observe_all(&mut checker, 1, &["A", "B", "C"]), vec![0., 0., 0.]);
+  assert_eq!(
+    checker.case(&1),
+    Some(&CaseConformance {
+      events_count: 3,
+      deviating_events_count: 0,
+      cost: 0.,
+      alerted: false,
+    })
+  );
+}
+
+#[test]
+fn test_token_replay_missing_tokens() {
+  let mut checker = create_token_replay_checker(10.);
+  assert_eq!(observe_all(&mut checker, 1, &["A", "C", "C"]), vec![0., 1., 1.]);
+  assert_eq!(observe_all(&mut checker, 2, &["A", "C", "B"]), vec![0., 1., 0.]);
+}
+
+#[test]
+fn test_token_replay_unknown_activity() {
+  let mut checker = create_token_replay_checker(10.);
+  assert_eq!(observe_all(&mut checker, 1, &["A", "X", "B"]), vec![0., 1., 0.]);
+}
+
+#[test]
+fn test_model_without_initial_marking() {
+  assert!(TokenReplayConformanceModel::new(&DefaultPetriNet::default()).is_none());
+}
+
+#[test]
+fn test_threshold_alert_is_raised_once() {
+  let mut checker = create_token_replay_checker(1.);
+
+  let alerts: Vec<bool> = ["C", "B", "C", "C"]
+    .iter()
+    .map(|activity| checker.observe(1, activity).threshold_exceeded)
+    .collect();
+
+  assert_eq!(alerts, vec![false, true, false, false]);
+  assert!(checker.case(&1).unwrap().alerted);
+}
+
+#[test]
+fn test_cases_are_independent() {
+  let mut checker = create_token_replay_checker(10.);
+
+  checker.observe(1, "A");
+  checker.observe(2, "B");
+  checker.observe(1, "B");
+
+  assert_eq!(checker.case(&1).unwrap().cost, 0.);
+  assert_eq!(checker.case(&2).unwrap().cost, 1.);
+}
+
+#[test]
+fn test_statistics() {
+  let mut checker = create_token_replay_checker(1.);
+
+  observe_all(&mut checker, 1, &["A", "B", "C"]);
+  observe_all(&mut checker, 2, &["C", "B"]);
+  observe_all(&mut checker, 3, &["A", "X"]);
+
+  let statistics = checker.statistics();
+  assert_eq!(statistics.cases_count, 3);
+  assert_eq!(statistics.deviating_cases_count, 2);
+  assert_eq!(statistics.alerted_cases_count, 1);
+  assert_eq!(statistics.events_count, 7);
+  assert_eq!(statistics.deviating_events_count, 3);
+  assert_eq!(statistics.total_cost, 3.);
+  assert_eq!(statistics.max_case_cost, 2.);
+  assert_eq!(statistics.mean_case_cost(), 1.);
+
+  assert_eq!(checker.remove_case(&2).unwrap().cost, 2.);
+  assert_eq!(checker.statistics().cases_count, 2);
+}
+
+#[test]
+fn test_snapshot_restore() {
+  let mut checker = create_token_replay_checker(10.);
+  checker.observe(1, "A");
+
+  let snapshot = serde_json::to_string(&checker.snapshot()).unwrap();
+
+  let mut restored = create_token_replay_checker(10.);
+  restored.restore(serde_json::from_str(&snapshot).unwrap());
+
+  assert_eq!(observe_all(&mut restored, 1, &["B", "C"]), vec![0., 0.]);
+  assert_eq!(restored.case(&1).unwrap().events_count, 3);
+}
+
+#[test]
+fn test_report() {
+  let mut checker = create_token_replay_checker(0.5);
+  observe_all(&mut checker, 2, &["B"]);
+  observe_all(&mut checker, 1, &["A"]);
+
+  let report = checker.report();
+  assert_eq!(report.cost_threshold, 0.5);
+  assert_eq!(
+    report.cases.iter().map(|(case, _)| case.as_str()).collect::<Vec<&str>>(),
+    vec!["1", "2"]
+  );
+  assert_eq!(report.statistics.alerted_cases_count, 1);
+}
+
+fn create_dfg() -> DefaultGraph {
+  let mut graph = DefaultGraph::default();
+  let a = graph.add_node(Some("A".into()));
+  let b = graph.add_node(Some("B".into()));
+  let c = graph.add_node(Some("C".into()));
+
+  graph.connect_nodes(&a, &b, NodesConnectionData::zero_weight(None));
+  graph.connect_nodes(&b, &c, NodesConnectionData::zero_weight(None));
+
+  graph
+}
+
+#[test]
+fn test_dfg_conformance() {
+  let mut checker = OnlineConformanceChecker::new(DfgConformanceModel::from_graph(&create_dfg()), 10.);
+
+  assert_eq!(observe_all(&mut checker, 1, &["A", "B", "C"]), vec![0., 0., 0.]);
+  assert_eq!(observe_all(&mut checker, 2, &["A", "C", "X", "B"]), vec![0., 1., 1., 0.]);
+}
+
+#[test]
+fn test_dfg_conformance_from_relations() {
+  let activities: HashSet<Arc<str>> = HashSet::from(["A".into(), "B".into()]);
+  let relations = HashSet::from([("A".into(), "B".into())]);
+  let mut checker = OnlineConformanceChecker::new(DfgConformanceModel::new(activities, relations), 10.);
+
+  assert_eq!(observe_all(&mut checker, 1, &["A", "B", "A"]), vec![0., 0., 1.]);
+}
+
+#[test]
+fn test_least_recent_cases_eviction() {
+  let mut checker = create_token_replay_checker(10.).with_max_cases(10);
+
+  for case in 0..10 {
+    checker.observe(case, "A");
+  }
+
+  checker.observe(0, "B");
+  checker.observe(10, "A");
+
+  let mut cases: Vec<u64> = checker.cases().map(|(case, _)| *case).collect();
+  cases.sort();
+
+  assert_eq!(cases, vec![0, 3, 4, 5, 6, 7, 8, 9, 10]);
+}
+
+#[test]
+fn test_inactive_cases_eviction() {
+  let stamp = |seconds: i64| Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::seconds(seconds);
+  let mut checker = create_token_replay_checker(10.).with_inactivity_timeout(Duration::seconds(60));
+
+  checker.observe_at(1, "A", stamp(0));
+  checker.observe_at(2, "A", stamp(30));
+  checker.observe_at(2, "B", stamp(100));
+  checker.observe(3, "A");
+
+  let evicted = checker.evict_inactive_cases(stamp(120));
+
+  assert_eq!(evicted.iter().map(|(case, _)| *case).collect::<Vec<u64>>(), vec![1]);
+  assert_eq!(checker.statistics().cases_count, 2);
+}
+
+#[test]
+fn test_state_is_valid_for_net_with_same_place_names() {
+  let mut checker = OnlineConformanceChecker::new(
+    TokenReplayConformanceModel::new(&create_sequence_net_with(|_| "p".to_string(), false)).unwrap(),
+    10.,
+  );
+
+  checker.observe(1, "A");
+  let snapshot = serde_json::to_string(&checker.snapshot()).unwrap();
+
+  let mut restored = OnlineConformanceChecker::new(
+    TokenReplayConformanceModel::new(&create_sequence_net_with(|_| "p".to_string(), true)).unwrap(),
+    10.,
+  );
+
+  restored.restore(serde_json::from_str(&snapshot).unwrap());
+
+  assert_eq!(observe_all(&mut restored, 1, &["B", "C"]), vec![0., 0.]);
+}
@@ -0,0 +1,111 @@
+use ficus::{
+  features::streaming::counters::{
+    core::{StreamingCounter, ValueUpdateKind},
+    count_min::{CountMinSketch, CountMinSketchCreationError},
+  },
+  utils::hash_utils::StableHasher,
+};
+use std::hash::{Hash, Hasher};
+
+#[test]
+pub fn count_min_test_frequent_keys() {
+  let sequence = vec![
+    1, 1, 2, 3, 1, 2, 1, 2, 2, 1, 1, 2, 2, 1, 1, 2, 1, 2, 2, 2, 2, 1, 1, 1, 1, 1, 2, 2, 1, 2, 1,
+  ];
+
+  let mut counter = CountMinSketch::<i32, ()>::new(0.01, 0.01, 0.1).unwrap();
+  for key in sequence {
+    counter.observe(key, ValueUpdateKind::DoNothing);
+  }
+
+  assert_eq!(counts(&counter), vec![(1, 16), (2, 14), (3, 1)]);
+
+  counter.invalidate();
+
+  assert_eq!(counts(&counter), vec![(1, 16), (2, 14)]);
+  assert!(counter.estimate(&3) >= 1);
+  assert!(counter.get(&3).is_none());
+}
+
+#[test]
+pub fn count_min_test_huge_key_space() {
+  let mut counter = CountMinSketch::<i32, ()>::new(0.01, 0.01, 0.1).unwrap();
+  for key in 0..1000 {
+    counter.observe(key, ValueUpdateKind::DoNothing);
+  }
+
+  for _ in 0..1000 {
+    counter.observe(5000, ValueUpdateKind::DoNothing);
+  }
+
+  counter.invalidate();
+
+  let counts = counts(&counter);
+  assert_eq!(counts.len(), 1);
+  assert_eq!(counts[0].0, 5000);
+  assert!(counts[0].1 >= 1000);
+  assert!(counts[0].1 <= 1000 + (0.01 * 2000.0) as u64);
+}
+
+#[test]
+pub fn count_min_test_values() {
+  let mut counter = CountMinSketch::<i32, &str>::new(0.01, 0.01, 0.0).unwrap();
+  counter.observe(1, ValueUpdateKind::Replace("a"));
+  counter.observe(1, ValueUpdateKind::DoNothing);
+  counter.observe(2, ValueUpdateKind::Replace("b"));
+  counter.observe(2, ValueUpdateKind::Replace("c"));
+
+  assert_eq!(counter.get(&1).unwrap().value(), Some(&"a"));
+  assert_eq!(counter.get(&2).unwrap().value(), Some(&"c"));
+}
+
+fn counts(counter: &CountMinSketch<i32, ()>) -> Vec<(i32, u64)> {
+  let mut counts: Vec<(i32, u64)> = counter
+    .all_frequencies()
+    .iter()
+    .map(|entry| (*entry.key(), entry.absolute_count()))
+    .collect();
+
+  counts.sort();
+  counts
+}
+
+#[test]
+pub fn count_min_test_invalid_parameters() {
+  let create = |error, failure_probability, support| CountMinSketch::<i32, ()>::new(error, failure_probability, support).err();
+
+  assert_eq!(create(0.0, 0.01, 0.1), Some(CountMinSketchCreationError::InvalidError(0.0)));
+  assert_eq!(create(-0.1, 0.01, 0.1), Some(CountMinSketchCreationError::InvalidError(-0.1)));
+  assert_eq!(create(1.0, 0.01, 0.1), Some(CountMinSketchCreationError::InvalidError(1.0)));
+  assert!(matches!(
+    create(f64::NAN, 0.01, 0.1),
+    Some(CountMinSketchCreationError::InvalidError(_))
+  ));
+
+  assert_eq!(
+    create(0.01, 0.0, 0.1),
+    Some(CountMinSketchCreationError::InvalidFailureProbability(0.0))
+  );
+  assert_eq!(
+    create(0.01, 1.5, 0.1),
+    Some(CountMinSketchCreationError::InvalidFailureProbability(1.5))
+  );
+
+  assert_eq!(create(0.01, 0.01, -1.0), Some(CountMinSketchCreationError::InvalidSupport(-1.0)));
+  assert_eq!(create(0.01, 0.01, 0.1), None);
+}
+
+#[test]
+pub fn count_min_test_stable_hash() {
+  let hash = |seed: u64, key: &str| {
+    let mut hasher = StableHasher::with_seed(seed);
+    key.hash(&mut hasher);
+    hasher.finish()
+  };
+
+  assert_eq!(hash(0, "A"), hash(0, "A"));
+  assert_ne!(hash(0, "A"), hash(1, "A"));
+
+  // Sketches are persisted in checkpoints, so the hash of the same key must never change
+  assert_eq!(hash(0, "A"), 4276908950701181395);
+}
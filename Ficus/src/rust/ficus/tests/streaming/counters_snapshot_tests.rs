@@ -0,0 +1,133 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ficus::features::streaming::{
+  counters::{
+    core::{StreamingCounter, StreamingCounterRestoreError, StreamingCounterSnapshot, ValueUpdateKind},
+    count_min::CountMinSketch,
+    exponential_decay::ExponentialDecay,
+    lossy_count::LossyCount,
+    sliding_window::SlidingWindow,
+    space_saving::SpaceSaving,
+  },
+  time::StreamingTime,
+};
+
+const SEQUENCE: [i32; 12] = [1, 1, 2, 3, 1, 2, 1, 2, 2, 4, 1, 1];
+
+#[test]
+pub fn lossy_count_snapshot_test() {
+  execute_snapshot_test(|| LossyCount::new(0.01));
+}
+
+#[test]
+pub fn sliding_window_snapshot_test() {
+  let time = StreamingTime::event_time(Duration::zero());
+  time.accept(stamp(0));
+
+  execute_snapshot_test(|| SlidingWindow::new_time_with(std::time::Duration::from_secs(60), time.clone()));
+}
+
+#[test]
+pub fn space_saving_snapshot_test() {
+  execute_snapshot_test(|| SpaceSaving::new(3));
+}
+
+#[test]
+pub fn count_min_sketch_snapshot_test() {
+  execute_snapshot_test(|| CountMinSketch::new(0.01, 0.01, 0.1).unwrap());
+}
+
+#[test]
+pub fn exponential_decay_snapshot_test() {
+  let time = StreamingTime::event_time(Duration::zero());
+  time.accept(stamp(0));
+
+  execute_snapshot_test(|| ExponentialDecay::new_with(std::time::Duration::from_secs(10), time.clone()));
+}
+
+#[test]
+pub fn snapshot_kind_mismatch_test() {
+  let mut counter = SpaceSaving::<i32, String>::new(3);
+  let snapshot = observe_all(LossyCount::new(0.01)).snapshot();
+
+  assert!(matches!(
+    counter.restore(snapshot),
+    Err(StreamingCounterRestoreError::SnapshotKindMismatch)
+  ));
+}
+
+#[test]
+pub fn space_saving_snapshot_capacity_test() {
+  let snapshot = observe_all(SpaceSaving::new(3)).snapshot();
+  let mut counter = SpaceSaving::<i32, String>::new(2);
+
+  assert!(matches!(
+    counter.restore(snapshot),
+    Err(StreamingCounterRestoreError::CapacityExceeded(3, 2))
+  ));
+}
+
+#[test]
+pub fn count_min_sketch_snapshot_dimensions_test() {
+  let snapshot = observe_all(CountMinSketch::new(0.01, 0.01, 0.1).unwrap()).snapshot();
+  let mut counter = CountMinSketch::<i32, String>::new(0.1, 0.01, 0.1).unwrap();
+
+  assert!(matches!(
+    counter.restore(snapshot),
+    Err(StreamingCounterRestoreError::SketchDimensionsMismatch)
+  ));
+}
+
+#[test]
+pub fn streaming_time_restore_test() {
+  let time = StreamingTime::event_time(Duration::seconds(5));
+  time.accept(stamp(10));
+  time.accept(stamp(7));
+
+  let restored_time = StreamingTime::event_time(Duration::seconds(5));
+  restored_time.restore_max_event_time(time.max_event_time());
+
+  assert_eq!(restored_time.max_event_time(), Some(stamp(10)));
+  assert_eq!(restored_time.watermark(), time.watermark());
+  assert!(!restored_time.accept(stamp(4)));
+}
+
+fn execute_snapshot_test<TCounter: StreamingCounter<i32, String>>(factory: impl Fn() -> TCounter) {
+  let counter = observe_all(factory());
+
+  let serialized = serde_json::to_string(&counter.snapshot()).unwrap();
+  let snapshot: StreamingCounterSnapshot<i32, String> = serde_json::from_str(&serialized).unwrap();
+
+  let mut restored = factory();
+  restored.restore(snapshot).unwrap();
+
+  assert_eq!(counts(&restored), counts(&counter));
+
+  let mut counter = counter;
+  counter.observe(5, ValueUpdateKind::Replace("x".to_string()));
+  restored.observe(5, ValueUpdateKind::Replace("x".to_string()));
+
+  assert_eq!(counts(&restored), counts(&counter));
+}
+
+fn observe_all<TCounter: StreamingCounter<i32, String>>(mut counter: TCounter) -> TCounter {
+  for key in SEQUENCE {
+    counter.observe(key, ValueUpdateKind::Replace(if key % 2 == 0 { "even" } else { "odd" }.to_string()));
+  }
+
+  counter
+}
+
+fn counts(counter: &impl StreamingCounter<i32, String>) -> Vec<(i32, Option<String>, u64)> {
+  let mut counts: Vec<_> = counter
+    .above_threshold(0.0)
+    .iter()
+    .map(|entry| (*entry.key(), entry.value().cloned(), entry.absolute_count()))
+    .collect();
+
+  counts.sort_by_key(|(key, _, _)| *key);
+  counts
+}
+
+fn stamp(seconds: i64) -> DateTime<Utc> {
+  Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::seconds(seconds)
+}
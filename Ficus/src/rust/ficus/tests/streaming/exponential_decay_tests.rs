@@ -0,0 +1,67 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ficus::features::streaming::{
+  counters::{
+    core::{StreamingCounter, ValueUpdateKind},
+    exponential_decay::ExponentialDecay,
+  },
+  time::{ManualClock, StreamingTime},
+};
+use std::sync::Arc;
+
+fn stamp(seconds: i64) -> DateTime<Utc> {
+  Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::seconds(seconds)
+}
+
+#[test]
+pub fn exponential_decay_test_processing_time() {
+  let clock = Arc::new(ManualClock::new(stamp(0)));
+  let time = StreamingTime::processing_time(clock.clone());
+  let mut counter = ExponentialDecay::<&str, ()>::new_with(std::time::Duration::from_secs(10), time);
+
+  counter.observe("A", ValueUpdateKind::DoNothing);
+  counter.observe("A", ValueUpdateKind::DoNothing);
+  assert_weight(&counter, "A", 2.0);
+
+  clock.advance(Duration::seconds(10));
+  assert_weight(&counter, "A", 1.0);
+
+  counter.observe("B", ValueUpdateKind::DoNothing);
+  let entry = counter.get(&"A").unwrap();
+  assert_eq!(entry.absolute_count(), 1);
+  assert!((entry.approx_frequency() - 0.5).abs() < 1e-9);
+
+  clock.advance(Duration::seconds(20));
+  assert_weight(&counter, "A", 0.25);
+  assert_weight(&counter, "B", 0.25);
+
+  counter.observe("B", ValueUpdateKind::DoNothing);
+  counter.invalidate();
+
+  assert!(counter.get(&"A").is_none());
+  assert_weight(&counter, "B", 1.25);
+  assert_eq!(counter.above_threshold(0.5).len(), 1);
+}
+
+#[test]
+pub fn exponential_decay_test_event_time() {
+  let time = StreamingTime::event_time(Duration::zero());
+  let mut counter = ExponentialDecay::<&str, ()>::new_with(std::time::Duration::from_secs(60), time.clone());
+
+  assert!(time.accept(stamp(0)));
+  counter.observe("A", ValueUpdateKind::DoNothing);
+
+  assert!(time.accept(stamp(120)));
+  counter.observe("B", ValueUpdateKind::DoNothing);
+
+  assert_weight(&counter, "A", 0.25);
+  assert_weight(&counter, "B", 1.0);
+
+  counter.invalidate();
+  assert!(counter.get(&"A").is_none());
+  assert_eq!(counter.get(&"B").unwrap().absolute_count(), 1);
+}
+
+fn assert_weight(counter: &ExponentialDecay<&str, ()>, key: &str, expected: f64) {
+  let weight = counter.weight(&key).unwrap();
+  assert!((weight - expected).abs() < 1e-9, "expected {}, got {}", expected, weight);
+}
@@ -1,3 +1,13 @@
+pub mod conformance_tests;
+pub mod count_min_tests;
+pub mod counters_snapshot_tests;
+pub mod exponential_decay_tests;
 pub mod lossy_count_tests;
+pub mod replay_tests;
 pub mod sliding_window_test;
+pub mod space_saving_tests;
+pub mod streaming_time_tests;
+pub mod t1_completion_tests;
 pub mod t1_filterers_test;
+pub mod t2_relations_tests;
+pub mod triggers_tests;
@@ -0,0 +1,128 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ficus::{
+  event_log::{
+    bxes::{bxes_to_xes_converter::read_bxes_events, xes_to_bxes_converter::xes_event_to_bxes_event},
+    core::{
+      event::event::{Event, EventPayloadValue},
+      event_log::EventLog,
+      trace::trace::Trace,
+    },
+    xes::{simple::create_simple_event_log, xes_event_log::XesEventLogImpl},
+  },
+  features::streaming::replay::{ReplayClockMode, StreamReplay, StreamReplayError},
+  vecs,
+};
+
+fn start_time() -> DateTime<Utc> {
+  Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+fn create_interleaved_log() -> XesEventLogImpl {
+  let log = create_simple_event_log(&vec![vec!["A", "B", "C"], vec!["D", "E"], vec!["F"]]);
+  let offsets = [vec![0, 20, 40], vec![10, 20], vec![5]];
+
+  for (trace, offsets) in log.traces().iter().zip(offsets.iter()) {
+    for (event, offset) in trace.borrow().events().iter().zip(offsets.iter()) {
+      event.borrow_mut().set_timestamp(start_time() + Duration::seconds(*offset));
+    }
+  }
+
+  log
+}
+
+fn replayed_names(replay: &StreamReplay) -> Vec<String> {
+  let mut names = vec![];
+  replay.replay(|event| names.push(event.event.name().to_owned()));
+  names
+}
+
+#[test]
+fn test_events_are_ordered_by_timestamp_across_traces() {
+  let replay = StreamReplay::new(&create_interleaved_log(), ReplayClockMode::Simulated).unwrap();
+  assert_eq!(replayed_names(&replay), vecs!["A", "F", "D", "B", "E", "C"]);
+}
+
+#[test]
+fn test_replayed_events_positions() {
+  let replay = StreamReplay::new(&create_interleaved_log(), ReplayClockMode::Simulated).unwrap();
+
+  let positions: Vec<(usize, usize, bool)> = replay
+    .events()
+    .iter()
+    .map(|event| (event.trace_index, event.event_index, event.is_last_in_trace))
+    .collect();
+
+  assert_eq!(
+    positions,
+    vec![
+      (0, 0, false),
+      (2, 0, true),
+      (1, 0, false),
+      (0, 1, false),
+      (1, 1, true),
+      (0, 2, true)
+    ]
+  );
+}
+
+#[test]
+fn test_simulated_clock_follows_events() {
+  let replay = StreamReplay::new(&create_interleaved_log(), ReplayClockMode::Simulated).unwrap();
+  let time = replay.processing_time();
+
+  let mut observed = vec![];
+  replay.replay(|event| observed.push((event.timestamp(), time.now().unwrap())));
+
+  for (stamp, now) in observed {
+    assert_eq!(stamp, now);
+  }
+}
+
+#[test]
+fn test_accelerated_replay() {
+  let replay = StreamReplay::new(&create_interleaved_log(), ReplayClockMode::Accelerated(1_000.)).unwrap();
+  let time = replay.processing_time();
+
+  let mut observed = vec![];
+  replay.replay(|event| observed.push((event.timestamp(), time.now().unwrap())));
+
+  assert_eq!(observed.len(), 6);
+  for (stamp, now) in observed {
+    assert!(now >= stamp);
+  }
+}
+
+#[test]
+fn test_invalid_speedup() {
+  for speedup in [0., -1., f64::NAN, f64::INFINITY] {
+    let result = StreamReplay::new(&create_interleaved_log(), ReplayClockMode::Accelerated(speedup));
+    assert!(matches!(result, Err(StreamReplayError::InvalidSpeedup(_))));
+  }
+}
+
+#[test]
+fn test_unsupported_log_format() {
+  let result = StreamReplay::read("log.csv", ReplayClockMode::Simulated);
+  assert!(matches!(result, Err(StreamReplayError::UnsupportedLogFormat(_))));
+}
+
+#[test]
+fn test_xes_event_to_bxes_event() {
+  let log = create_interleaved_log();
+  let trace = log.traces().first().unwrap().borrow();
+  let event = trace.events().first().unwrap();
+  event
+    .borrow_mut()
+    .add_or_update_payload("resource".into(), EventPayloadValue::String("bob".to_string().into()));
+
+  let bxes_event = xes_event_to_bxes_event(&event.borrow());
+  let xes_trace = read_bxes_events(&vec![bxes_event]).ok().unwrap();
+  let read_event = xes_trace.events().first().unwrap().borrow();
+
+  assert_eq!(read_event.name(), "A");
+  assert_eq!(*read_event.timestamp(), start_time());
+  assert_eq!(
+    read_event.payload_map().unwrap().get("resource"),
+    Some(&EventPayloadValue::String("bob".to_string().into()))
+  );
+}
@@ -0,0 +1,84 @@
+use ficus::features::streaming::counters::{
+  core::{StreamingCounter, ValueUpdateKind},
+  space_saving::SpaceSaving,
+};
+
+#[test]
+pub fn space_saving_test_exact() {
+  let sequence = vec![
+    1, 1, 2, 3, 1, 2, 1, 2, 2, 1, 1, 2, 2, 1, 1, 2, 1, 2, 2, 2, 2, 1, 1, 1, 1, 1, 2, 2, 1, 2, 1,
+  ];
+
+  let counter = observe_all(sequence, SpaceSaving::new(10));
+
+  assert_eq!(counts(&counter, 0.0), vec![(1, 16), (2, 14), (3, 1)]);
+  assert_eq!(counter.error_bound(), 3);
+}
+
+#[test]
+pub fn space_saving_test_replacement() {
+  let counter = observe_all(vec![1, 1, 2, 3, 1], SpaceSaving::new(2));
+
+  assert_eq!(counts(&counter, 0.0), vec![(1, 3), (3, 2)]);
+  assert_eq!(counter.guaranteed_count(&1), Some(3));
+  assert_eq!(counter.guaranteed_count(&3), Some(1));
+  assert_eq!(counter.guaranteed_count(&2), None);
+  assert_eq!(counter.error_bound(), 2);
+}
+
+#[test]
+pub fn space_saving_test_threshold() {
+  let counter = observe_all(vec![1, 1, 1, 1, 2, 2, 3, 4, 5, 6], SpaceSaving::new(3));
+
+  assert_eq!(counts(&counter, 0.4), vec![(1, 4)]);
+}
+
+#[test]
+pub fn space_saving_test_values() {
+  let mut counter = SpaceSaving::<i32, &str>::new(2);
+  counter.observe(1, ValueUpdateKind::Replace("a"));
+  counter.observe(1, ValueUpdateKind::DoNothing);
+  counter.observe(2, ValueUpdateKind::Replace("b"));
+  counter.observe(2, ValueUpdateKind::Replace("c"));
+
+  assert_eq!(counter.get(&1).unwrap().value(), Some(&"a"));
+  assert_eq!(counter.get(&2).unwrap().value(), Some(&"c"));
+}
+
+#[test]
+pub fn space_saving_test_evicts_least_recently_updated_minimum() {
+  let counter = observe_all(vec![1, 2, 3, 2, 1, 4], SpaceSaving::new(3));
+
+  assert_eq!(counts(&counter, 0.0), vec![(1, 2), (2, 2), (4, 2)]);
+  assert_eq!(counter.guaranteed_count(&4), Some(1));
+}
+
+#[test]
+pub fn space_saving_test_restored_counter_evicts_minimum() {
+  let counter = observe_all(vec![1, 1, 1, 2, 2, 3], SpaceSaving::new(3));
+
+  let mut restored = SpaceSaving::new(3);
+  restored.restore(counter.snapshot()).unwrap();
+
+  let restored = observe_all(vec![4, 4], restored);
+  assert_eq!(counts(&restored, 0.0), vec![(1, 3), (2, 2), (4, 3)]);
+}
+
+fn observe_all(sequence: Vec<i32>, mut counter: SpaceSaving<i32, ()>) -> SpaceSaving<i32, ()> {
+  for key in sequence {
+    counter.observe(key, ValueUpdateKind::DoNothing);
+  }
+
+  counter
+}
+
+fn counts(counter: &SpaceSaving<i32, ()>, threshold: f64) -> Vec<(i32, u64)> {
+  let mut counts: Vec<(i32, u64)> = counter
+    .above_threshold(threshold)
+    .iter()
+    .map(|entry| (*entry.key(), entry.absolute_count()))
+    .collect();
+
+  counts.sort();
+  counts
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ficus::features::streaming::{
+  counters::{
+    core::{StreamingCounter, ValueUpdateKind},
+    sliding_window::SlidingWindow,
+  },
+  time::{ManualClock, StreamingTime, StreamingTimeMode},
+};
+use std::sync::Arc;
+
+fn stamp(seconds: i64) -> DateTime<Utc> {
+  Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::seconds(seconds)
+}
+
+#[test]
+pub fn test_event_time_watermark() {
+  let time = StreamingTime::event_time(Duration::seconds(10));
+  assert_eq!(time.mode(), StreamingTimeMode::EventTime);
+  assert_eq!(time.now(), None);
+  assert_eq!(time.watermark(), None);
+
+  assert!(time.accept(stamp(100)));
+  assert!(time.accept(stamp(95)));
+  assert!(time.accept(stamp(120)));
+
+  assert_eq!(time.now(), Some(stamp(120)));
+  assert_eq!(time.watermark(), Some(stamp(110)));
+
+  assert!(!time.accept(stamp(105)));
+  assert!(time.accept(stamp(110)));
+  assert_eq!(time.now(), Some(stamp(120)));
+}
+
+#[test]
+pub fn test_forked_time_has_own_watermark() {
+  let time = StreamingTime::event_time(Duration::zero());
+  time.accept(stamp(100));
+
+  let forked = time.fork();
+  assert_eq!(forked.now(), None);
+  assert!(forked.accept(stamp(50)));
+
+  assert_eq!(time.now(), Some(stamp(100)));
+  assert_eq!(forked.now(), Some(stamp(50)));
+}
+
+#[test]
+pub fn test_processing_time_uses_clock() {
+  let clock = Arc::new(ManualClock::new(stamp(0)));
+  let time = StreamingTime::processing_time(clock.clone());
+
+  assert!(time.accept(stamp(-1000)));
+  assert_eq!(time.now(), Some(stamp(0)));
+
+  clock.advance(Duration::seconds(30));
+  assert_eq!(time.watermark(), Some(stamp(30)));
+}
+
+#[test]
+pub fn test_event_time_sliding_window() {
+  let time = StreamingTime::event_time(Duration::zero());
+  let mut window = SlidingWindow::<i32, ()>::new_time_with(std::time::Duration::from_secs(60), time.clone());
+
+  for (key, seconds) in [(1, 0), (2, 30), (3, 90)] {
+    time.accept(stamp(seconds));
+    window.observe(key, ValueUpdateKind::DoNothing);
+  }
+
+  window.invalidate();
+
+  let mut keys = window.all_frequencies().iter().map(|e| *e.key()).collect::<Vec<i32>>();
+  keys.sort();
+
+  assert_eq!(keys, vec![2, 3]);
+}
+
+#[test]
+pub fn test_event_time_sliding_window_uses_event_stamps() {
+  let time = StreamingTime::event_time(Duration::seconds(100));
+  let mut window = SlidingWindow::<i32, ()>::new_time_with(std::time::Duration::from_secs(60), time.clone());
+
+  for (key, seconds) in [(1, 200), (2, 120), (3, 190)] {
+    assert!(time.accept(stamp(seconds)));
+    window.observe_at(key, ValueUpdateKind::DoNothing, stamp(seconds));
+  }
+
+  time.accept(stamp(290));
+  window.invalidate();
+
+  let mut keys = window.all_frequencies().iter().map(|e| *e.key()).collect::<Vec<i32>>();
+  keys.sort();
+
+  assert_eq!(keys, vec![1, 3]);
+}
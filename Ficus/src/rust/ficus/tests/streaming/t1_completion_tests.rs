@@ -0,0 +1,127 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use fancy_regex::Regex;
+use ficus::{
+  event_log::{
+    core::{
+      event::event::{Event, EventPayloadValue},
+      event_log::EventLog,
+      trace::trace::Trace,
+    },
+    xes::{simple::create_simple_event_log, xes_event_log::XesEventLogImpl},
+  },
+  features::streaming::{
+    t1::completion::{CASE_COMPLETED_MARKER, CaseCompletionDetector, CaseCompletionRule},
+    time::StreamingTime,
+  },
+  pipelines::{
+    context::{PipelineContext, PipelineInfrastructure},
+    keys::context_keys::{CLOSED_CASES_EVENT_LOG_KEY, EVENT_LOG_KEY},
+    pipeline_parts::PipelineParts,
+    pipelines::PipelinePart,
+  },
+  utils::user_data::user_data::UserData,
+};
+
+fn start_time() -> DateTime<Utc> {
+  Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+// Events of the i-th trace start at i * 100 seconds and follow each other with 10 seconds delta
+fn create_timed_log(traces: &Vec<Vec<&str>>) -> XesEventLogImpl {
+  let log = create_simple_event_log(traces);
+  for (trace_index, trace) in log.traces().iter().enumerate() {
+    for (index, event) in trace.borrow().events().iter().enumerate() {
+      let offset = (trace_index * 100 + index * 10) as i64;
+      event.borrow_mut().set_timestamp(start_time() + Duration::seconds(offset));
+    }
+  }
+
+  log
+}
+
+fn observe_all_events(log: &XesEventLogImpl, time: &StreamingTime) {
+  for trace in log.traces() {
+    for event in trace.borrow().events() {
+      time.accept(*event.borrow().timestamp());
+    }
+  }
+}
+
+#[test]
+fn test_end_activity_completion() {
+  let mut running = create_timed_log(&vec![vec!["A", "B", "End"], vec!["A", "End", "C"], vec!["B", "FinalEnd"]]);
+  let mut closed = XesEventLogImpl::default();
+
+  let detector = CaseCompletionDetector::new(vec![CaseCompletionRule::EndActivity(Regex::new("End$").unwrap())]);
+  let completed = detector.close_completed_cases(&mut running, &mut closed, &StreamingTime::default());
+
+  assert_eq!(completed.len(), 2);
+  assert_eq!(running.to_raw_vector(), vec![vec!["A", "End", "C"]]);
+  assert_eq!(closed.to_raw_vector(), vec![vec!["A", "B", "End"], vec!["B", "FinalEnd"]]);
+}
+
+#[test]
+fn test_inactivity_gap_completion() {
+  let mut running = create_timed_log(&vec![vec!["A", "B"], vec!["A", "C"], vec!["B", "D"]]);
+  let mut closed = XesEventLogImpl::default();
+
+  let time = StreamingTime::event_time(Duration::zero());
+  observe_all_events(&running, &time);
+  assert_eq!(time.now(), Some(start_time() + Duration::seconds(210)));
+
+  let detector = CaseCompletionDetector::new(vec![CaseCompletionRule::InactivityGap(150 * 1000)]);
+  let completed = detector.close_completed_cases(&mut running, &mut closed, &time);
+
+  assert_eq!(completed.len(), 1);
+  assert_eq!(completed[0].rule.to_string(), "InactivityGap(150000ms)");
+  assert_eq!(running.to_raw_vector(), vec![vec!["A", "C"], vec!["B", "D"]]);
+  assert_eq!(closed.to_raw_vector(), vec![vec!["A", "B"]]);
+}
+
+#[test]
+fn test_end_marker_completion() {
+  let mut running = create_timed_log(&vec![vec!["A", "B"], vec!["A", "C"]]);
+  let mut closed = XesEventLogImpl::default();
+
+  running.traces()[1]
+    .borrow_mut()
+    .metadata_mut()
+    .insert(CASE_COMPLETED_MARKER.to_string(), EventPayloadValue::Boolean(true));
+
+  let detector = CaseCompletionDetector::new(vec![
+    CaseCompletionRule::EndActivity(Regex::new("^End$").unwrap()),
+    CaseCompletionRule::EndMarker,
+  ]);
+
+  let completed = detector.close_completed_cases(&mut running, &mut closed, &StreamingTime::default());
+
+  assert_eq!(completed.len(), 1);
+  assert_eq!(completed[0].rule.to_string(), "EndMarker");
+  assert_eq!(running.to_raw_vector(), vec![vec!["A", "B"]]);
+  assert_eq!(closed.to_raw_vector(), vec![vec!["A", "C"]]);
+}
+
+#[test]
+fn test_no_completion_rules() {
+  let mut running = create_timed_log(&vec![vec!["A", "End"]]);
+  let mut closed = XesEventLogImpl::default();
+
+  let completed = CaseCompletionDetector::default().close_completed_cases(&mut running, &mut closed, &StreamingTime::default());
+
+  assert!(completed.is_empty());
+  assert_eq!(running.to_raw_vector(), vec![vec!["A", "End"]]);
+  assert!(closed.traces().is_empty());
+}
+
+#[test]
+fn test_use_closed_cases_event_log_part() {
+  let mut context = PipelineContext::default();
+  context.put_concrete(EVENT_LOG_KEY.key(), create_simple_event_log(&vec![vec!["A", "B"]]));
+  context.put_concrete(CLOSED_CASES_EVENT_LOG_KEY.key(), create_simple_event_log(&vec![vec!["C", "D"]]));
+
+  let parts = PipelineParts::new();
+  let part = parts.find_part(PipelineParts::USE_CLOSED_CASES_EVENT_LOG).unwrap()(Box::default());
+  part.execute(&mut context, &PipelineInfrastructure::new(None)).unwrap();
+
+  assert_eq!(context.concrete(EVENT_LOG_KEY.key()).unwrap().to_raw_vector(), vec![vec!["C", "D"]]);
+}
@@ -1,21 +1,48 @@
 use crate::test_core::simple_events_logs_provider::{annotate_log_with_real_time, create_simple_event_log2};
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, TimeZone, Utc};
 use ficus::{
-  event_log::{core::event_log::EventLog, xes::xes_event_log::XesEventLogImpl},
-  features::streaming::t1::{
-    configs::{EventsTimeoutConfiguration, TracesQueueConfiguration, TracesTimeoutConfiguration},
-    filterers::{EventsTimeoutFiltererImpl, TracesQueueFiltererImpl, TracesTimeoutFiltererImpl},
+  event_log::{
+    core::{event::event::Event, event_log::EventLog, trace::trace::Trace},
+    xes::xes_event_log::XesEventLogImpl,
+  },
+  features::streaming::{
+    t1::{
+      configs::{EventsTimeoutConfiguration, TracesQueueConfiguration, TracesTimeoutConfiguration},
+      filterers::{EventsTimeoutFiltererImpl, TracesQueueFiltererImpl, TracesTimeoutFiltererImpl},
+    },
+    time::{ManualClock, StreamingTime},
   },
   vecs,
 };
-use std::ops::Sub;
+use std::sync::Arc;
+
+fn start_time() -> DateTime<Utc> {
+  Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+fn create_timed_log() -> XesEventLogImpl {
+  let mut log = create_simple_event_log2();
+  annotate_log_with_real_time(&mut log, start_time(), Duration::seconds(100));
+  log
+}
+
+fn processing_time() -> StreamingTime {
+  StreamingTime::processing_time(Arc::new(ManualClock::new(start_time() + Duration::seconds(1000))))
+}
+
+fn observe_all_events(log: &XesEventLogImpl, time: &StreamingTime) {
+  for trace in log.traces() {
+    for event in trace.borrow().events() {
+      time.accept(*event.borrow().timestamp());
+    }
+  }
+}
 
 #[test]
 pub fn events_filterer_test() {
   let filterer = EventsTimeoutFiltererImpl::new(EventsTimeoutConfiguration::new(500 * 1000));
-  let mut log = create_simple_event_log2();
-  annotate_log_with_real_time(&mut log, Utc::now().sub(Duration::seconds(1000)), Duration::seconds(100));
-  filterer.filter(&mut log);
+  let mut log = create_timed_log();
+  filterer.filter(&mut log, &processing_time());
 
   assert_eq!(log.to_raw_vector(), vec![vec!["A"], vec!["B", "E", "A"], vec!["A"]]);
 }
@@ -23,9 +50,8 @@ pub fn events_filterer_test() {
 #[test]
 pub fn traces_filterer_test() {
   let filterer = TracesTimeoutFiltererImpl::new(TracesTimeoutConfiguration::new(500 * 1000));
-  let mut log = create_simple_event_log2();
-  annotate_log_with_real_time(&mut log, Utc::now().sub(Duration::seconds(1000)), Duration::seconds(100));
-  filterer.filter(&mut log);
+  let mut log = create_timed_log();
+  filterer.filter(&mut log, &processing_time());
 
   assert_eq!(
     log.to_raw_vector(),
@@ -37,6 +63,52 @@ pub fn traces_filterer_test() {
   );
 }
 
+#[test]
+pub fn events_filterer_event_time_test() {
+  let filterer = EventsTimeoutFiltererImpl::new(EventsTimeoutConfiguration::new(300 * 1000));
+  let mut log = create_timed_log();
+
+  let time = StreamingTime::event_time(Duration::seconds(100));
+  observe_all_events(&log, &time);
+  assert_eq!(time.now(), Some(start_time() + Duration::seconds(700)));
+
+  filterer.filter(&mut log, &time);
+
+  assert_eq!(
+    log.to_raw_vector(),
+    vec![
+      vec!["D", "E"],
+      vec!["A", "A", "A"],
+      vec!["B", "B", "B", "E", "A"],
+      vec!["C", "A"],
+      vec!["A", "A", "A"]
+    ]
+  );
+}
+
+#[test]
+pub fn traces_filterer_event_time_test() {
+  let filterer = TracesTimeoutFiltererImpl::new(TracesTimeoutConfiguration::new(100 * 1000));
+  let mut log = create_timed_log();
+
+  let time = StreamingTime::event_time(Duration::zero());
+  observe_all_events(&log, &time);
+
+  filterer.filter(&mut log, &time);
+
+  assert_eq!(log.to_raw_vector(), vec![vec!["A", "E", "C", "B", "B", "B", "E", "A"]]);
+}
+
+#[test]
+pub fn traces_filterer_without_observed_events_test() {
+  let filterer = TracesTimeoutFiltererImpl::new(TracesTimeoutConfiguration::new(0));
+  let mut log = create_timed_log();
+
+  filterer.filter(&mut log, &StreamingTime::event_time(Duration::zero()));
+
+  assert_eq!(log.traces().len(), 5);
+}
+
 #[test]
 pub fn traces_queue_test() {
   execute_traces_queue_test(
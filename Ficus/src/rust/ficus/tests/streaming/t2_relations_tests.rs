@@ -0,0 +1,282 @@
+use crate::test_core::simple_events_logs_provider::{create_alpha_plus_plus_nfc_test_log2, create_alpha_plus_plus_nfc_test_log5};
+use chrono::{Duration, TimeZone, Utc};
+use ficus::{
+  event_log::{
+    core::{event::event::Event, event_log::EventLog, trace::trace::Trace},
+    xes::{simple::create_simple_event_log, xes_event_log::XesEventLogImpl},
+  },
+  features::{
+    analysis::log_info::{
+      event_log_info::{EventLogInfo, OfflineEventLogInfo},
+      log_info_creation_dto::EventLogInfoCreationDto,
+    },
+    discovery::{
+      fuzzy::{
+        fuzzy_miner::{FuzzyGraph, FuzzyMinerParams, discover_graph_fuzzy, discover_graph_fuzzy_from_info},
+        proximity_correlation::{OfflineProximityCorrelation, PrecomputedProximityCorrelation, ProximityCorrelation},
+      },
+      heuristic::heuristic_miner::discover_petri_net_heuristic,
+      petri_net::pnml_serialization::serialize_to_pnml,
+      relations::triangle_relation::{OfflineTriangleRelation, PrecomputedTriangleRelation, TriangleRelation},
+    },
+    streaming::{
+      counters::{
+        core::{StreamingCounter, ValueUpdateKind},
+        space_saving::SpaceSaving,
+      },
+      t2::relations::{CaseHistory, ClassesPair, observe_proximity, proximity_correlation_from_counter, triangle_relation_from_counter},
+    },
+  },
+  pipelines::{
+    context::{PipelineContext, PipelineInfrastructure},
+    keys::context_keys::{
+      AND_THRESHOLD_KEY, DEPENDENCY_THRESHOLD_KEY, EVENT_LOG_INFO_KEY, LOOP_LENGTH_TWO_THRESHOLD_KEY, PETRI_NET_KEY,
+      POSITIVE_OBSERVATIONS_THRESHOLD_KEY, RELATIVE_TO_BEST_THRESHOLD_KEY, TRIANGLE_RELATION_KEY,
+    },
+    pipeline_parts::PipelineParts,
+    pipelines::PipelinePart,
+  },
+  utils::user_data::user_data::{UserData, UserDataImpl},
+};
+use std::{collections::HashMap, sync::Arc};
+
+const CAPACITY: usize = 1_000;
+
+struct StreamingRelations {
+  relations: SpaceSaving<ClassesPair, ()>,
+  classes: SpaceSaving<Arc<str>, ()>,
+  triangles: SpaceSaving<ClassesPair, ()>,
+  proximities: SpaceSaving<ClassesPair, f64>,
+}
+
+impl StreamingRelations {
+  fn observe(log: &XesEventLogImpl) -> Self {
+    let mut streaming = Self {
+      relations: SpaceSaving::new(CAPACITY),
+      classes: SpaceSaving::new(CAPACITY),
+      triangles: SpaceSaving::new(CAPACITY),
+      proximities: SpaceSaving::new(CAPACITY),
+    };
+
+    for trace in log.traces() {
+      let mut history = CaseHistory::default();
+      let mut previous_class: Option<Arc<str>> = None;
+
+      for event in trace.borrow().events() {
+        let event = event.borrow();
+        let class = event.name_pointer().clone();
+
+        if let Some(previous_class) = previous_class.take() {
+          streaming
+            .relations
+            .observe((previous_class, class.clone()), ValueUpdateKind::DoNothing);
+        }
+
+        streaming.classes.observe(class.clone(), ValueUpdateKind::DoNothing);
+
+        let relations = history.observe(class.clone(), *event.timestamp());
+        if let Some(triangle) = relations.triangle {
+          streaming.triangles.observe(triangle, ValueUpdateKind::DoNothing);
+        }
+
+        for (pair, proximity) in relations.proximities {
+          observe_proximity(&mut streaming.proximities, pair, proximity);
+        }
+
+        previous_class = Some(class);
+      }
+    }
+
+    streaming
+  }
+
+  fn log_info(&self) -> OfflineEventLogInfo {
+    let relations = self.relations.to_freq_count_map().into_iter().map(|(k, v)| (k, v.1)).collect();
+    let classes = self.classes.to_freq_count_map().into_iter().map(|(k, v)| (k, v.1)).collect();
+
+    OfflineEventLogInfo::create_from_relations(&relations, &classes)
+  }
+}
+
+fn create_timed_log() -> XesEventLogImpl {
+  let log = create_simple_event_log(&vec![
+    vec!["A", "B", "A", "C", "B"],
+    vec!["A", "C", "B", "B", "D"],
+    vec!["B", "A", "B", "A", "D"],
+    vec!["A", "D", "C", "A", "C"],
+  ]);
+
+  let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+  for (trace_index, trace) in log.traces().iter().enumerate() {
+    for (index, event) in trace.borrow().events().iter().enumerate() {
+      let offset = (index * index * 7 + trace_index * 3) as i64;
+      event.borrow_mut().set_timestamp(start + Duration::seconds(offset));
+    }
+  }
+
+  log
+}
+
+fn all_classes(log: &XesEventLogImpl) -> Vec<Arc<str>> {
+  let info = OfflineEventLogInfo::create_from(EventLogInfoCreationDto::default(log));
+  let mut classes: Vec<Arc<str>> = info.all_event_classes().into_iter().cloned().collect();
+  classes.sort();
+  classes
+}
+
+#[test]
+fn test_case_history_triangles() {
+  let mut history = CaseHistory::default();
+  let stamp = Utc::now();
+
+  let triangles: Vec<Option<(String, String)>> = ["A", "B", "A", "B", "C"]
+    .iter()
+    .map(|class| {
+      history
+        .observe(Arc::from(*class), stamp)
+        .triangle
+        .map(|(first, second)| (first.to_string(), second.to_string()))
+    })
+    .collect();
+
+  assert_eq!(
+    triangles,
+    vec![
+      None,
+      None,
+      Some(("A".to_string(), "B".to_string())),
+      Some(("B".to_string(), "A".to_string())),
+      None
+    ]
+  );
+}
+
+#[test]
+fn test_streaming_triangle_relation() {
+  let log = create_timed_log();
+  let streaming = StreamingRelations::observe(&log);
+  let offline = OfflineTriangleRelation::new(&log);
+  let precomputed = triangle_relation_from_counter(&streaming.triangles);
+
+  for first in all_classes(&log) {
+    for second in all_classes(&log) {
+      assert_eq!(precomputed.get(&first, &second), offline.get(&first, &second));
+    }
+  }
+}
+
+#[test]
+fn test_streaming_proximity_correlation() {
+  let log = create_timed_log();
+  let streaming = StreamingRelations::observe(&log);
+  let mut offline = OfflineProximityCorrelation::new(&log);
+  let mut precomputed = proximity_correlation_from_counter(&streaming.proximities);
+
+  for first in all_classes(&log) {
+    for second in all_classes(&log) {
+      let expected = offline.get(&first, &second);
+      assert!((precomputed.get(&first, &second) - expected).abs() < 1e-6);
+    }
+  }
+}
+
+#[test]
+fn test_precomputed_relations() {
+  let mut correlation = PrecomputedProximityCorrelation::new(vec![(("A".into(), "B".into()), 10.)]);
+  assert_eq!(correlation.get("A", "B"), 10.);
+  assert_eq!(correlation.get("B", "A"), 0.);
+
+  let triangle = PrecomputedTriangleRelation::new(vec![(("A".into(), "B".into()), 2), (("A".into(), "B".into()), 3)]);
+  assert_eq!(triangle.get("A", "B"), Some(5));
+  assert_eq!(triangle.get("B", "A"), None);
+}
+
+#[test]
+fn test_streaming_heuristic_miner() {
+  for log in [
+    create_alpha_plus_plus_nfc_test_log2(),
+    create_alpha_plus_plus_nfc_test_log5(),
+    create_timed_log(),
+  ] {
+    let offline_info = OfflineEventLogInfo::create_from(EventLogInfoCreationDto::default(&log));
+    let offline_net = discover_petri_net_heuristic(&offline_info, &OfflineTriangleRelation::new(&log), 0.2, 1, 1.0, 0.1, 0.5);
+
+    let streaming = StreamingRelations::observe(&log);
+    let triangle_relation = triangle_relation_from_counter(&streaming.triangles);
+    let streaming_net = discover_petri_net_heuristic(&streaming.log_info(), &triangle_relation, 0.2, 1, 1.0, 0.1, 0.5);
+
+    assert_eq!(
+      serialize_to_pnml(&streaming_net, true).unwrap(),
+      serialize_to_pnml(&offline_net, true).unwrap()
+    );
+  }
+}
+
+fn graph_edges(graph: &FuzzyGraph) -> Vec<(String, String)> {
+  let names: HashMap<u64, String> = graph
+    .all_nodes()
+    .iter()
+    .map(|node| (*node.id(), node.data().unwrap().to_string()))
+    .collect();
+
+  let mut edges: Vec<(String, String)> = graph
+    .all_edges()
+    .iter()
+    .map(|edge| (names[edge.from_node()].clone(), names[edge.to_node()].clone()))
+    .collect();
+
+  edges.sort();
+  edges
+}
+
+#[test]
+fn test_streaming_fuzzy_miner() {
+  let log = create_timed_log();
+  let offline_graph = discover_graph_fuzzy(&log, 0.0, 0.0, 0.4, 0.7, 0.5, 0.2, 0.0);
+
+  let streaming = StreamingRelations::observe(&log);
+  let params = FuzzyMinerParams {
+    unary_frequency_threshold: 0.0,
+    binary_frequency_significance_threshold: 0.0,
+    preserve_threshold: 0.4,
+    ratio_threshold: 0.7,
+    utility_rate: 0.5,
+    edge_cutoff_threshold: 0.2,
+    node_cutoff_threshold: 0.0,
+  };
+
+  let mut proximity_correlation = proximity_correlation_from_counter(&streaming.proximities);
+  let streaming_graph = discover_graph_fuzzy_from_info(&streaming.log_info(), &mut proximity_correlation, &params);
+
+  assert!(!graph_edges(&offline_graph).is_empty());
+  assert_eq!(graph_edges(&streaming_graph), graph_edges(&offline_graph));
+}
+
+#[test]
+fn test_discover_petri_net_heuristic_stream_part() {
+  let log = create_alpha_plus_plus_nfc_test_log2();
+  let streaming = StreamingRelations::observe(&log);
+
+  let mut context = PipelineContext::default();
+  context.put_concrete(EVENT_LOG_INFO_KEY.key(), streaming.log_info());
+  context.put_concrete(TRIANGLE_RELATION_KEY.key(), triangle_relation_from_counter(&streaming.triangles));
+
+  let mut config = UserDataImpl::default();
+  config.put_concrete(DEPENDENCY_THRESHOLD_KEY.key(), 0.2);
+  config.put_concrete(POSITIVE_OBSERVATIONS_THRESHOLD_KEY.key(), 1);
+  config.put_concrete(RELATIVE_TO_BEST_THRESHOLD_KEY.key(), 1.0);
+  config.put_concrete(AND_THRESHOLD_KEY.key(), 0.1);
+  config.put_concrete(LOOP_LENGTH_TWO_THRESHOLD_KEY.key(), 0.5);
+
+  let parts = PipelineParts::new();
+  let part = parts.find_part(PipelineParts::DISCOVER_PETRI_NET_HEURISTIC_STREAM).unwrap()(Box::new(config));
+  part.execute(&mut context, &PipelineInfrastructure::new(None)).unwrap();
+
+  let offline_info = OfflineEventLogInfo::create_from(EventLogInfoCreationDto::default(&log));
+  let offline_net = discover_petri_net_heuristic(&offline_info, &OfflineTriangleRelation::new(&log), 0.2, 1, 1.0, 0.1, 0.5);
+
+  assert_eq!(
+    serialize_to_pnml(context.concrete(PETRI_NET_KEY.key()).unwrap(), true).unwrap(),
+    serialize_to_pnml(&offline_net, true).unwrap()
+  );
+}
@@ -0,0 +1,90 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ficus::features::streaming::triggers::{PipelineTrigger, TriggerObservation, TriggerPolicy};
+
+fn start() -> DateTime<Utc> {
+  Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+fn observe_all(trigger: &mut PipelineTrigger, observations: &[TriggerObservation]) -> Vec<bool> {
+  observations.iter().map(|observation| trigger.observe(observation)).collect()
+}
+
+fn at(seconds: i64) -> TriggerObservation {
+  TriggerObservation {
+    event_time: Some(start() + Duration::seconds(seconds)),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn test_every_traces_trigger() {
+  let mut trigger = PipelineTrigger::new(TriggerPolicy::EveryTraces(3));
+  let observations = vec![TriggerObservation::default(); 7];
+
+  assert_eq!(
+    observe_all(&mut trigger, &observations),
+    vec![false, false, true, false, false, true, false]
+  );
+
+  assert_eq!(trigger.fires_count(), 2);
+}
+
+#[test]
+fn test_every_zero_traces_trigger_fires_on_each_trace() {
+  let mut trigger = PipelineTrigger::new(TriggerPolicy::EveryTraces(0));
+  let observations = vec![TriggerObservation::default(); 3];
+
+  assert_eq!(observe_all(&mut trigger, &observations), vec![true, true, true]);
+}
+
+#[test]
+fn test_event_time_interval_trigger() {
+  let mut trigger = PipelineTrigger::new(TriggerPolicy::EventTimeInterval(Duration::seconds(10)));
+  let observations = vec![at(0), at(5), at(10), at(3), at(19), at(20), at(45)];
+
+  assert_eq!(
+    observe_all(&mut trigger, &observations),
+    vec![false, false, true, false, false, true, true]
+  );
+}
+
+#[test]
+fn test_event_time_interval_trigger_without_event_time() {
+  let mut trigger = PipelineTrigger::new(TriggerPolicy::EventTimeInterval(Duration::seconds(1)));
+  let observations = vec![TriggerObservation::default(); 3];
+
+  assert_eq!(observe_all(&mut trigger, &observations), vec![false, false, false]);
+}
+
+#[test]
+fn test_case_completion_trigger() {
+  let mut trigger = PipelineTrigger::new(TriggerPolicy::CaseCompletion);
+  let completed = TriggerObservation {
+    case_completed: true,
+    ..Default::default()
+  };
+
+  let observations = vec![
+    TriggerObservation::default(),
+    completed.clone(),
+    TriggerObservation::default(),
+    completed,
+  ];
+
+  assert_eq!(observe_all(&mut trigger, &observations), vec![false, true, false, true]);
+}
+
+#[test]
+fn test_significant_change_trigger() {
+  let mut trigger = PipelineTrigger::new(TriggerPolicy::SignificantChange);
+  let changed = TriggerObservation {
+    significant_change: true,
+    case_completed: true,
+    ..Default::default()
+  };
+
+  let observations = vec![at(0), changed, at(100)];
+
+  assert_eq!(observe_all(&mut trigger, &observations), vec![false, true, false]);
+  assert_eq!(trigger.fires_count(), 1);
+}
@@ -0,0 +1,240 @@
+use ficus::utils::context_values_store::{
+  disk_storage::DiskValuesStorage,
+  in_memory_storage::InMemoryValuesStorage,
+  storage::{ExecutionValues, StoredValue},
+  store::{ContextValueStore, ContextValueStoreLimits},
+};
+use std::{collections::HashMap, path::PathBuf, str::FromStr, thread, time::Duration};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq)]
+struct TestValue(Vec<u8>);
+
+impl StoredValue for TestValue {
+  fn size_in_bytes(&self) -> usize {
+    self.0.len()
+  }
+
+  fn to_bytes(&self) -> Vec<u8> {
+    self.0.clone()
+  }
+
+  fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+    Ok(Self(bytes.to_vec()))
+  }
+}
+
+fn value(size: usize) -> TestValue {
+  TestValue(vec![1; size])
+}
+
+fn in_memory_store(limits: ContextValueStoreLimits) -> ContextValueStore<TestValue> {
+  ContextValueStore::new(Box::new(InMemoryValuesStorage::default()), limits)
+}
+
+fn max_size(max_size_bytes: usize) -> ContextValueStoreLimits {
+  ContextValueStoreLimits {
+    ttl: None,
+    max_size_bytes: Some(max_size_bytes),
+  }
+}
+
+fn put_values(store: &ContextValueStore<TestValue>, sizes: &[usize]) -> Vec<String> {
+  sizes
+    .iter()
+    .map(|size| {
+      let id = Uuid::new_v4().to_string();
+      store.put_value(&id, value(*size));
+      id
+    })
+    .collect()
+}
+
+fn execution(keys_to_ids: &[(&str, &String)]) -> ExecutionValues {
+  keys_to_ids
+    .iter()
+    .map(|(key, id)| (key.to_string(), Uuid::from_str(id).unwrap()))
+    .collect()
+}
+
+#[test]
+fn test_size_accounting() {
+  let store = in_memory_store(ContextValueStoreLimits::default());
+  let ids = put_values(&store, &[10, 20]);
+  assert_eq!(store.size_in_bytes(), 30);
+  assert_eq!(store.values_count(), 2);
+
+  store.put_value(&ids[0], value(5));
+  assert_eq!(store.size_in_bytes(), 25);
+
+  store.put_execution(&Uuid::new_v4().to_string(), execution(&[("key", &ids[1])]));
+  assert_eq!(store.size_in_bytes(), 25 + "key".len() + size_of::<Uuid>());
+  assert_eq!(store.values_count(), 2);
+
+  assert_eq!(store.remove_value(&ids[0]), Some(value(5)));
+  assert_eq!(store.size_in_bytes(), 20 + "key".len() + size_of::<Uuid>());
+}
+
+#[test]
+fn test_least_recently_used_values_are_evicted() {
+  let store = in_memory_store(max_size(30));
+  let ids = put_values(&store, &[10, 10, 10]);
+
+  assert!(store.get_value(&ids[0]).is_some());
+  put_values(&store, &[10]);
+
+  assert!(store.get_value(&ids[0]).is_some());
+  assert!(store.get_value(&ids[1]).is_none());
+  assert!(store.get_value(&ids[2]).is_some());
+  assert_eq!(store.size_in_bytes(), 30);
+}
+
+#[test]
+fn test_value_larger_than_max_size_is_not_kept() {
+  let store = in_memory_store(max_size(10));
+  let ids = put_values(&store, &[5, 20]);
+
+  assert!(store.get_value(&ids[0]).is_some());
+  assert!(store.get_value(&ids[1]).is_none());
+  assert_eq!(store.size_in_bytes(), 5);
+}
+
+#[test]
+fn test_executions_are_evicted() {
+  let store = in_memory_store(max_size(100));
+  let ids = put_values(&store, &[10]);
+
+  let execution_id = Uuid::new_v4().to_string();
+  store.put_execution(&execution_id, execution(&[("key", &ids[0])]));
+
+  put_values(&store, &[50]);
+  assert!(store.get_value(&ids[0]).is_some());
+
+  put_values(&store, &[30]);
+  assert!(store.get_execution(&execution_id).is_none());
+  assert!(store.get_value(&ids[0]).is_some());
+}
+
+#[test]
+fn test_evicted_values_are_removed_from_executions() {
+  let store = in_memory_store(max_size(80));
+  let ids = put_values(&store, &[10, 10]);
+
+  let execution_id = Uuid::new_v4().to_string();
+  store.put_execution(&execution_id, execution(&[("first", &ids[0]), ("second", &ids[1])]));
+
+  assert!(store.get_value(&ids[1]).is_some());
+  put_values(&store, &[20]);
+
+  assert_eq!(store.get_execution(&execution_id), Some(execution(&[("second", &ids[1])])));
+
+  store.remove_value(&ids[1]);
+  assert_eq!(store.get_execution(&execution_id), None);
+}
+
+#[test]
+fn test_expired_entries_are_removed() {
+  let store = in_memory_store(ContextValueStoreLimits {
+    ttl: Some(Duration::from_millis(50)),
+    max_size_bytes: None,
+  });
+
+  let ids = put_values(&store, &[10]);
+  let execution_id = Uuid::new_v4().to_string();
+  store.put_execution(&execution_id, HashMap::new());
+
+  thread::sleep(Duration::from_millis(100));
+
+  assert!(store.get_value(&ids[0]).is_none());
+  assert!(store.get_execution(&execution_id).is_none());
+  assert_eq!(store.size_in_bytes(), 0);
+}
+
+#[test]
+fn test_execution_with_unknown_values() {
+  let store = in_memory_store(ContextValueStoreLimits::default());
+  let ids = put_values(&store, &[10]);
+  let unknown_id = Uuid::new_v4().to_string();
+
+  let execution_id = Uuid::new_v4().to_string();
+  store.put_execution(&execution_id, execution(&[("known", &ids[0]), ("unknown", &unknown_id)]));
+
+  assert_eq!(store.get_execution(&execution_id), Some(execution(&[("known", &ids[0])])));
+  assert!(store.remove_execution(&execution_id));
+  assert!(!store.remove_execution(&execution_id));
+}
+
+struct TempDir(PathBuf);
+
+impl TempDir {
+  fn new() -> Self {
+    Self(std::env::temp_dir().join(format!("ficus_context_values_{}", Uuid::new_v4())))
+  }
+}
+
+impl Drop for TempDir {
+  fn drop(&mut self) {
+    std::fs::remove_dir_all(&self.0).ok();
+  }
+}
+
+fn disk_store(dir: &TempDir, limits: ContextValueStoreLimits) -> ContextValueStore<TestValue> {
+  ContextValueStore::new(Box::new(DiskValuesStorage::open(&dir.0).unwrap()), limits)
+}
+
+#[test]
+fn test_disk_store_persists_entries() {
+  let dir = TempDir::new();
+  let execution_id = Uuid::new_v4().to_string();
+
+  let ids = {
+    let store = disk_store(&dir, ContextValueStoreLimits::default());
+    let ids = put_values(&store, &[10, 20]);
+    store.put_execution(&execution_id, execution(&[("key", &ids[1])]));
+
+    ids
+  };
+
+  let store = disk_store(&dir, ContextValueStoreLimits::default());
+  assert_eq!(store.values_count(), 2);
+  assert_eq!(store.size_in_bytes(), 30 + "key".len() + size_of::<Uuid>());
+  assert_eq!(store.get_value(&ids[0]), Some(value(10)));
+  assert_eq!(store.get_execution(&execution_id), Some(execution(&[("key", &ids[1])])));
+
+  assert_eq!(store.remove_value(&ids[1]), Some(value(20)));
+  assert!(store.get_execution(&execution_id).is_none());
+
+  let store = disk_store(&dir, ContextValueStoreLimits::default());
+  assert_eq!(store.values_count(), 1);
+  assert!(store.get_execution(&execution_id).is_none());
+}
+
+#[test]
+fn test_disk_store_evicts_values() {
+  let dir = TempDir::new();
+  let store = disk_store(&dir, max_size(30));
+  let ids = put_values(&store, &[10, 10, 10]);
+
+  assert!(store.get_value(&ids[0]).is_some());
+  put_values(&store, &[10]);
+
+  assert!(store.get_value(&ids[1]).is_none());
+  assert_eq!(std::fs::read_dir(dir.0.join("values")).unwrap().count(), 3);
+}
+
+#[test]
+fn test_disk_store_enforces_limits_on_open() {
+  let dir = TempDir::new();
+  let ids = {
+    let store = disk_store(&dir, ContextValueStoreLimits::default());
+    let first = put_values(&store, &[10]);
+    thread::sleep(Duration::from_millis(20));
+
+    first.into_iter().chain(put_values(&store, &[10])).collect::<Vec<_>>()
+  };
+
+  let store = disk_store(&dir, max_size(15));
+  assert!(store.get_value(&ids[0]).is_none());
+  assert!(store.get_value(&ids[1]).is_some());
+  assert_eq!(std::fs::read_dir(dir.0.join("values")).unwrap().count(), 1);
+}
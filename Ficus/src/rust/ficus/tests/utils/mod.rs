@@ -1,2 +1,3 @@
+pub mod context_values_store_tests;
 pub mod lcs;
 pub mod user_data_tests;
@@ -0,0 +1,664 @@
+use ficus::{
+  pipelines::scheduler::{PipelineSchedulerConfig, PipelineSchedulingPolicy},
+  utils::context_values_store::store::ContextValueStoreLimits,
+};
+use log::LevelFilter;
+use serde::Deserialize;
+use std::{
+  collections::HashMap,
+  env,
+  fmt::{Display, Formatter},
+  fs, io,
+  net::SocketAddr,
+  path::{Path, PathBuf},
+  str::FromStr,
+  time::Duration,
+};
+
+const CONFIG_FILE_ENV: &str = "FICUS_BACKEND_CONFIG";
+const LISTEN_ADDRESSES_ENV: &str = "FICUS_LISTEN_ADDRESSES";
+const TLS_CERT_PATH_ENV: &str = "FICUS_TLS_CERT_PATH";
+const TLS_KEY_PATH_ENV: &str = "FICUS_TLS_KEY_PATH";
+const MAX_MESSAGE_SIZE_ENV: &str = "FICUS_MAX_MESSAGE_SIZE_BYTES";
+const CONTEXT_VALUE_CHUNK_SIZE_ENV: &str = "FICUS_CONTEXT_VALUE_CHUNK_SIZE_BYTES";
+const LOG_LEVEL_ENV: &str = "FICUS_LOG_LEVEL";
+const KAFKA_CONSUMER_SETTINGS_ENV: &str = "FICUS_KAFKA_CONSUMER_SETTINGS";
+const KAFKA_PRODUCER_SETTINGS_ENV: &str = "FICUS_KAFKA_PRODUCER_SETTINGS";
+pub(crate) const KAFKA_CHECKPOINTS_DIR_ENV: &str = "FICUS_KAFKA_CHECKPOINTS_DIR";
+const KAFKA_CHECKPOINT_INTERVAL_ENV: &str = "FICUS_KAFKA_CHECKPOINT_INTERVAL_SECS";
+const KAFKA_SUBSCRIPTIONS_FILE_ENV: &str = "FICUS_KAFKA_SUBSCRIPTIONS_FILE";
+const METRICS_ADDRESS_ENV: &str = "FICUS_METRICS_ADDRESS";
+const MAX_CONCURRENT_PIPELINES_ENV: &str = "FICUS_MAX_CONCURRENT_PIPELINES";
+const SCHEDULING_POLICY_ENV: &str = "FICUS_PIPELINES_SCHEDULING_POLICY";
+const RESULTS_CACHE_MAX_ENTRIES_ENV: &str = "FICUS_RESULTS_CACHE_MAX_ENTRIES";
+const RESULTS_CACHE_MAX_BYTES_ENV: &str = "FICUS_RESULTS_CACHE_MAX_BYTES";
+const CONTEXT_VALUES_STORE_ENV: &str = "FICUS_CONTEXT_VALUES_STORE";
+const CONTEXT_VALUES_DIR_ENV: &str = "FICUS_CONTEXT_VALUES_DIR";
+const CONTEXT_VALUES_TTL_ENV: &str = "FICUS_CONTEXT_VALUES_TTL_SECS";
+const CONTEXT_VALUES_MAX_SIZE_ENV: &str = "FICUS_CONTEXT_VALUES_MAX_SIZE_BYTES";
+
+const DEFAULT_LISTEN_ADDRESS: &str = "[::]:8080";
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+const DEFAULT_CONTEXT_VALUE_CHUNK_SIZE: usize = 1024;
+const DEFAULT_LOG_LEVEL: &str = "info";
+const DEFAULT_KAFKA_CHECKPOINT_INTERVAL_SECS: u64 = 60;
+const DEFAULT_METRICS_ADDRESS: &str = "[::]:9090";
+const DEFAULT_SCHEDULING_POLICY: &str = "fifo";
+const DEFAULT_RESULTS_CACHE_MAX_ENTRIES: usize = 32;
+const DEFAULT_RESULTS_CACHE_MAX_BYTES: usize = 256 * 1024 * 1024;
+const DEFAULT_CONTEXT_VALUES_STORE: &str = "memory";
+const DEFAULT_CONTEXT_VALUES_DIR: &str = "ficus_context_values";
+
+#[derive(Debug)]
+pub enum BackendConfigError {
+  FailedToReadFile(PathBuf, io::Error),
+  InvalidFile(PathBuf, serde_json::Error),
+  InvalidEnvValue(&'static str, String),
+  NoListenAddresses,
+  InvalidListenAddress(String),
+  IncompleteTlsConfig,
+  TlsFileNotFound(PathBuf),
+  InvalidMaxMessageSize,
+  InvalidChunkSize(usize, usize),
+  InvalidLogLevel(String),
+  InvalidMetricsAddress(String),
+  InvalidMaxConcurrentPipelines,
+  InvalidSchedulingPolicy(String),
+  InvalidContextValuesStore(String),
+  InvalidCheckpointInterval,
+}
+
+impl Display for BackendConfigError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      BackendConfigError::FailedToReadFile(path, err) => write!(f, "Failed to read config file {}: {}", path.display(), err),
+      BackendConfigError::InvalidFile(path, err) => write!(f, "Config file {} is invalid: {}", path.display(), err),
+      BackendConfigError::InvalidEnvValue(name, value) => write!(f, "Invalid value {} of environment variable {}", value, name),
+      BackendConfigError::NoListenAddresses => write!(f, "At least one listen address must be specified"),
+      BackendConfigError::InvalidListenAddress(address) => write!(f, "Listen address {} is not a valid socket address", address),
+      BackendConfigError::IncompleteTlsConfig => write!(f, "Both TLS certificate and key paths must be specified"),
+      BackendConfigError::TlsFileNotFound(path) => write!(f, "TLS file {} does not exist", path.display()),
+      BackendConfigError::InvalidMaxMessageSize => write!(f, "Max message size must be greater than zero"),
+      BackendConfigError::InvalidChunkSize(chunk_size, max_message_size) => write!(
+        f,
+        "Context value chunk size {} must be greater than zero and less than max message size {}",
+        chunk_size, max_message_size
+      ),
+      BackendConfigError::InvalidLogLevel(level) => write!(f, "Unknown log level {}", level),
+      BackendConfigError::InvalidMetricsAddress(address) => write!(f, "Metrics address {} is not a valid socket address", address),
+      BackendConfigError::InvalidMaxConcurrentPipelines => write!(f, "Max concurrent pipelines count must be greater than zero"),
+      BackendConfigError::InvalidSchedulingPolicy(policy) => write!(f, "Unknown pipelines scheduling policy {}", policy),
+      BackendConfigError::InvalidContextValuesStore(store) => write!(f, "Unknown context values store {}", store),
+      BackendConfigError::InvalidCheckpointInterval => write!(f, "Kafka checkpoint interval must be greater than zero"),
+    }
+  }
+}
+
+impl std::error::Error for BackendConfigError {}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TlsConfig {
+  pub cert_path: Option<PathBuf>,
+  pub key_path: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KafkaDefaults {
+  pub consumer: HashMap<String, String>,
+  pub producer: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KafkaPersistenceConfig {
+  pub checkpoints_dir: Option<PathBuf>,
+  pub checkpoint_interval_secs: u64,
+  pub subscriptions_file: Option<PathBuf>,
+}
+
+impl Default for KafkaPersistenceConfig {
+  fn default() -> Self {
+    Self {
+      checkpoints_dir: None,
+      checkpoint_interval_secs: DEFAULT_KAFKA_CHECKPOINT_INTERVAL_SECS,
+      subscriptions_file: None,
+    }
+  }
+}
+
+impl KafkaPersistenceConfig {
+  pub fn checkpoint_interval(&self) -> Duration {
+    Duration::from_secs(self.checkpoint_interval_secs)
+  }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SchedulerConfig {
+  /// Number of available cores if not specified
+  pub max_concurrent_pipelines: Option<usize>,
+  pub policy: String,
+}
+
+impl Default for SchedulerConfig {
+  fn default() -> Self {
+    Self {
+      max_concurrent_pipelines: None,
+      policy: DEFAULT_SCHEDULING_POLICY.to_string(),
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResultsCacheConfig {
+  pub max_entries: usize,
+  pub max_bytes: usize,
+}
+
+impl Default for ResultsCacheConfig {
+  fn default() -> Self {
+    Self {
+      max_entries: DEFAULT_RESULTS_CACHE_MAX_ENTRIES,
+      max_bytes: DEFAULT_RESULTS_CACHE_MAX_BYTES,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContextValuesConfig {
+  /// Either "memory" or "disk"
+  pub store: String,
+  pub dir: PathBuf,
+  pub ttl_secs: Option<u64>,
+  pub max_size_bytes: Option<usize>,
+}
+
+impl Default for ContextValuesConfig {
+  fn default() -> Self {
+    Self {
+      store: DEFAULT_CONTEXT_VALUES_STORE.to_string(),
+      dir: PathBuf::from(DEFAULT_CONTEXT_VALUES_DIR),
+      ttl_secs: None,
+      max_size_bytes: None,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContextValueStoreKind {
+  InMemory,
+  Disk(PathBuf),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BackendConfig {
+  pub listen_addresses: Vec<String>,
+  pub tls: TlsConfig,
+  pub max_message_size_bytes: usize,
+  pub context_value_chunk_size_bytes: usize,
+  pub log_level: String,
+  pub kafka: KafkaDefaults,
+  pub kafka_persistence: KafkaPersistenceConfig,
+  pub metrics_address: String,
+  pub scheduler: SchedulerConfig,
+  pub results_cache: ResultsCacheConfig,
+  pub context_values: ContextValuesConfig,
+}
+
+impl Default for BackendConfig {
+  fn default() -> Self {
+    Self {
+      listen_addresses: vec![DEFAULT_LISTEN_ADDRESS.to_string()],
+      tls: TlsConfig::default(),
+      max_message_size_bytes: DEFAULT_MAX_MESSAGE_SIZE,
+      context_value_chunk_size_bytes: DEFAULT_CONTEXT_VALUE_CHUNK_SIZE,
+      log_level: DEFAULT_LOG_LEVEL.to_string(),
+      kafka: KafkaDefaults::default(),
+      kafka_persistence: KafkaPersistenceConfig::default(),
+      metrics_address: DEFAULT_METRICS_ADDRESS.to_string(),
+      scheduler: SchedulerConfig::default(),
+      results_cache: ResultsCacheConfig::default(),
+      context_values: ContextValuesConfig::default(),
+    }
+  }
+}
+
+impl BackendConfig {
+  pub fn load() -> Result<Self, BackendConfigError> {
+    Self::load_with_env(&|name| env::var(name).ok())
+  }
+
+  pub fn load_with_env(env: EnvReader) -> Result<Self, BackendConfigError> {
+    let mut config = match env(CONFIG_FILE_ENV) {
+      Some(path) => Self::from_file(Path::new(&path))?,
+      None => Self::default(),
+    };
+
+    config.apply_env_overrides(env)?;
+    config.validate()?;
+
+    Ok(config)
+  }
+
+  pub fn from_file(path: &Path) -> Result<Self, BackendConfigError> {
+    let content = fs::read_to_string(path).map_err(|err| BackendConfigError::FailedToReadFile(path.to_owned(), err))?;
+    serde_json::from_str(&content).map_err(|err| BackendConfigError::InvalidFile(path.to_owned(), err))
+  }
+
+  fn apply_env_overrides(&mut self, env: EnvReader) -> Result<(), BackendConfigError> {
+    if let Some(value) = env(LISTEN_ADDRESSES_ENV) {
+      self.listen_addresses = value.split(',').map(|address| address.trim().to_string()).collect();
+    }
+
+    if let Some(value) = env(TLS_CERT_PATH_ENV) {
+      self.tls.cert_path = Some(value.into());
+    }
+
+    if let Some(value) = env(TLS_KEY_PATH_ENV) {
+      self.tls.key_path = Some(value.into());
+    }
+
+    if let Some(size) = parse_env(env, MAX_MESSAGE_SIZE_ENV)? {
+      self.max_message_size_bytes = size;
+    }
+
+    if let Some(size) = parse_env(env, CONTEXT_VALUE_CHUNK_SIZE_ENV)? {
+      self.context_value_chunk_size_bytes = size;
+    }
+
+    if let Some(value) = env(LOG_LEVEL_ENV) {
+      self.log_level = value;
+    }
+
+    self.kafka.consumer.extend(parse_env_settings(env, KAFKA_CONSUMER_SETTINGS_ENV)?);
+    self.kafka.producer.extend(parse_env_settings(env, KAFKA_PRODUCER_SETTINGS_ENV)?);
+
+    if let Some(value) = env(KAFKA_CHECKPOINTS_DIR_ENV) {
+      self.kafka_persistence.checkpoints_dir = Some(value.into());
+    }
+
+    if let Some(secs) = parse_env(env, KAFKA_CHECKPOINT_INTERVAL_ENV)? {
+      self.kafka_persistence.checkpoint_interval_secs = secs;
+    }
+
+    if let Some(value) = env(KAFKA_SUBSCRIPTIONS_FILE_ENV) {
+      self.kafka_persistence.subscriptions_file = Some(value.into());
+    }
+
+    if let Some(value) = env(METRICS_ADDRESS_ENV) {
+      self.metrics_address = value;
+    }
+
+    if let Some(count) = parse_env(env, MAX_CONCURRENT_PIPELINES_ENV)? {
+      self.scheduler.max_concurrent_pipelines = Some(count);
+    }
+
+    if let Some(value) = env(SCHEDULING_POLICY_ENV) {
+      self.scheduler.policy = value;
+    }
+
+    if let Some(count) = parse_env(env, RESULTS_CACHE_MAX_ENTRIES_ENV)? {
+      self.results_cache.max_entries = count;
+    }
+
+    if let Some(size) = parse_env(env, RESULTS_CACHE_MAX_BYTES_ENV)? {
+      self.results_cache.max_bytes = size;
+    }
+
+    if let Some(value) = env(CONTEXT_VALUES_STORE_ENV) {
+      self.context_values.store = value;
+    }
+
+    if let Some(value) = env(CONTEXT_VALUES_DIR_ENV) {
+      self.context_values.dir = value.into();
+    }
+
+    if let Some(secs) = parse_env(env, CONTEXT_VALUES_TTL_ENV)? {
+      self.context_values.ttl_secs = Some(secs);
+    }
+
+    if let Some(size) = parse_env(env, CONTEXT_VALUES_MAX_SIZE_ENV)? {
+      self.context_values.max_size_bytes = Some(size);
+    }
+
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<(), BackendConfigError> {
+    self.socket_addresses()?;
+
+    if let Some((cert_path, key_path)) = self.tls_paths()? {
+      for path in [cert_path, key_path] {
+        if !path.is_file() {
+          return Err(BackendConfigError::TlsFileNotFound(path.to_owned()));
+        }
+      }
+    }
+
+    if self.max_message_size_bytes == 0 {
+      return Err(BackendConfigError::InvalidMaxMessageSize);
+    }
+
+    if self.context_value_chunk_size_bytes == 0 || self.context_value_chunk_size_bytes >= self.max_message_size_bytes {
+      return Err(BackendConfigError::InvalidChunkSize(
+        self.context_value_chunk_size_bytes,
+        self.max_message_size_bytes,
+      ));
+    }
+
+    self.log_level_filter()?;
+    self.metrics_socket_address()?;
+    self.scheduler_config()?;
+    self.context_value_store_kind()?;
+
+    if self.kafka_persistence.checkpoint_interval_secs == 0 {
+      return Err(BackendConfigError::InvalidCheckpointInterval);
+    }
+
+    Ok(())
+  }
+
+  pub fn socket_addresses(&self) -> Result<Vec<SocketAddr>, BackendConfigError> {
+    if self.listen_addresses.is_empty() {
+      return Err(BackendConfigError::NoListenAddresses);
+    }
+
+    self
+      .listen_addresses
+      .iter()
+      .map(|address| SocketAddr::from_str(address).map_err(|_| BackendConfigError::InvalidListenAddress(address.to_owned())))
+      .collect()
+  }
+
+  pub fn tls_paths(&self) -> Result<Option<(&Path, &Path)>, BackendConfigError> {
+    match (self.tls.cert_path.as_ref(), self.tls.key_path.as_ref()) {
+      (None, None) => Ok(None),
+      (Some(cert_path), Some(key_path)) => Ok(Some((cert_path.as_path(), key_path.as_path()))),
+      _ => Err(BackendConfigError::IncompleteTlsConfig),
+    }
+  }
+
+  pub fn log_level_filter(&self) -> Result<LevelFilter, BackendConfigError> {
+    LevelFilter::from_str(&self.log_level).map_err(|_| BackendConfigError::InvalidLogLevel(self.log_level.to_owned()))
+  }
+
+  pub fn metrics_socket_address(&self) -> Result<SocketAddr, BackendConfigError> {
+    SocketAddr::from_str(&self.metrics_address).map_err(|_| BackendConfigError::InvalidMetricsAddress(self.metrics_address.to_owned()))
+  }
+
+  pub fn scheduler_config(&self) -> Result<PipelineSchedulerConfig, BackendConfigError> {
+    let mut config = PipelineSchedulerConfig::default();
+
+    match self.scheduler.max_concurrent_pipelines {
+      Some(0) => return Err(BackendConfigError::InvalidMaxConcurrentPipelines),
+      Some(count) => config.max_concurrent_pipelines = count,
+      None => {}
+    }
+
+    config.policy = match self.scheduler.policy.to_lowercase().as_str() {
+      "fifo" => PipelineSchedulingPolicy::Fifo,
+      "priority" => PipelineSchedulingPolicy::Priority,
+      _ => return Err(BackendConfigError::InvalidSchedulingPolicy(self.scheduler.policy.to_owned())),
+    };
+
+    Ok(config)
+  }
+
+  pub fn context_value_store_kind(&self) -> Result<ContextValueStoreKind, BackendConfigError> {
+    match self.context_values.store.to_lowercase().as_str() {
+      "memory" => Ok(ContextValueStoreKind::InMemory),
+      "disk" => Ok(ContextValueStoreKind::Disk(self.context_values.dir.to_owned())),
+      _ => Err(BackendConfigError::InvalidContextValuesStore(self.context_values.store.to_owned())),
+    }
+  }
+
+  pub fn context_value_store_limits(&self) -> ContextValueStoreLimits {
+    ContextValueStoreLimits {
+      ttl: self.context_values.ttl_secs.map(Duration::from_secs),
+      max_size_bytes: self.context_values.max_size_bytes,
+    }
+  }
+}
+
+pub type EnvReader<'a> = &'a dyn Fn(&str) -> Option<String>;
+
+fn parse_env<T: FromStr>(env: EnvReader, name: &'static str) -> Result<Option<T>, BackendConfigError> {
+  match env(name) {
+    Some(value) => match value.parse::<T>() {
+      Ok(parsed) => Ok(Some(parsed)),
+      Err(_) => Err(BackendConfigError::InvalidEnvValue(name, value)),
+    },
+    None => Ok(None),
+  }
+}
+
+fn parse_env_settings(env: EnvReader, name: &'static str) -> Result<HashMap<String, String>, BackendConfigError> {
+  let Some(value) = env(name) else {
+    return Ok(HashMap::new());
+  };
+
+  let mut settings = HashMap::new();
+  for pair in value.split(';').filter(|pair| !pair.trim().is_empty()) {
+    match pair.split_once('=') {
+      Some((key, value)) if !key.trim().is_empty() => {
+        settings.insert(key.trim().to_string(), value.trim().to_string());
+      }
+      _ => return Err(BackendConfigError::InvalidEnvValue(name, value.clone())),
+    }
+  }
+
+  Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn load(vars: &[(&str, &str)]) -> Result<BackendConfig, BackendConfigError> {
+    let vars: HashMap<String, String> = vars.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+    BackendConfig::load_with_env(&|name| vars.get(name).cloned())
+  }
+
+  struct TempFile(PathBuf);
+
+  impl TempFile {
+    fn new(content: &str) -> Self {
+      let path = env::temp_dir().join(format!("ficus_backend_config_{}.json", uuid::Uuid::new_v4()));
+      fs::write(&path, content).unwrap();
+      Self(path)
+    }
+  }
+
+  impl Drop for TempFile {
+    fn drop(&mut self) {
+      fs::remove_file(&self.0).ok();
+    }
+  }
+
+  #[test]
+  fn test_defaults() {
+    let config = load(&[]).unwrap();
+
+    assert_eq!(
+      config.socket_addresses().unwrap(),
+      vec![SocketAddr::from_str(DEFAULT_LISTEN_ADDRESS).unwrap()]
+    );
+    assert_eq!(
+      config.metrics_socket_address().unwrap(),
+      SocketAddr::from_str(DEFAULT_METRICS_ADDRESS).unwrap()
+    );
+    assert_eq!(config.log_level_filter().unwrap(), LevelFilter::Info);
+    assert_eq!(config.scheduler_config().unwrap().policy, PipelineSchedulingPolicy::Fifo);
+    assert_eq!(config.context_value_store_kind().unwrap(), ContextValueStoreKind::InMemory);
+    assert_eq!(config.results_cache.max_entries, DEFAULT_RESULTS_CACHE_MAX_ENTRIES);
+    assert_eq!(config.kafka_persistence.checkpoint_interval(), Duration::from_secs(60));
+    assert!(config.tls_paths().unwrap().is_none());
+  }
+
+  #[test]
+  fn test_env_overrides() {
+    let config = load(&[
+      (LISTEN_ADDRESSES_ENV, "127.0.0.1:1000, 127.0.0.1:1001"),
+      (MAX_MESSAGE_SIZE_ENV, "2048"),
+      (CONTEXT_VALUE_CHUNK_SIZE_ENV, "512"),
+      (LOG_LEVEL_ENV, "debug"),
+      (KAFKA_CONSUMER_SETTINGS_ENV, "group.id=ficus; auto.offset.reset = earliest"),
+      (KAFKA_CHECKPOINTS_DIR_ENV, "checkpoints"),
+      (KAFKA_CHECKPOINT_INTERVAL_ENV, "5"),
+      (KAFKA_SUBSCRIPTIONS_FILE_ENV, "subscriptions.bin"),
+      (METRICS_ADDRESS_ENV, "127.0.0.1:9999"),
+      (MAX_CONCURRENT_PIPELINES_ENV, "3"),
+      (SCHEDULING_POLICY_ENV, "Priority"),
+      (RESULTS_CACHE_MAX_ENTRIES_ENV, "4"),
+      (RESULTS_CACHE_MAX_BYTES_ENV, "1024"),
+      (CONTEXT_VALUES_STORE_ENV, "disk"),
+      (CONTEXT_VALUES_DIR_ENV, "values"),
+      (CONTEXT_VALUES_TTL_ENV, "10"),
+      (CONTEXT_VALUES_MAX_SIZE_ENV, "4096"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.socket_addresses().unwrap().len(), 2);
+    assert_eq!(config.max_message_size_bytes, 2048);
+    assert_eq!(config.context_value_chunk_size_bytes, 512);
+    assert_eq!(config.log_level_filter().unwrap(), LevelFilter::Debug);
+
+    let expected_settings = HashMap::from([
+      ("group.id".to_string(), "ficus".to_string()),
+      ("auto.offset.reset".to_string(), "earliest".to_string()),
+    ]);
+    assert_eq!(config.kafka.consumer, expected_settings);
+
+    assert_eq!(config.kafka_persistence.checkpoints_dir, Some(PathBuf::from("checkpoints")));
+    assert_eq!(config.kafka_persistence.checkpoint_interval(), Duration::from_secs(5));
+    assert_eq!(
+      config.kafka_persistence.subscriptions_file,
+      Some(PathBuf::from("subscriptions.bin"))
+    );
+    assert_eq!(
+      config.metrics_socket_address().unwrap(),
+      SocketAddr::from_str("127.0.0.1:9999").unwrap()
+    );
+
+    let scheduler_config = config.scheduler_config().unwrap();
+    assert_eq!(scheduler_config.max_concurrent_pipelines, 3);
+    assert_eq!(scheduler_config.policy, PipelineSchedulingPolicy::Priority);
+
+    assert_eq!(config.results_cache.max_entries, 4);
+    assert_eq!(config.results_cache.max_bytes, 1024);
+
+    assert_eq!(
+      config.context_value_store_kind().unwrap(),
+      ContextValueStoreKind::Disk("values".into())
+    );
+    let limits = config.context_value_store_limits();
+    assert_eq!(limits.ttl, Some(Duration::from_secs(10)));
+    assert_eq!(limits.max_size_bytes, Some(4096));
+  }
+
+  type ErrorMatcher = fn(&BackendConfigError) -> bool;
+
+  #[test]
+  fn test_invalid_values() {
+    let cases: Vec<(&str, &str, ErrorMatcher)> = vec![
+      (MAX_MESSAGE_SIZE_ENV, "big", |err| {
+        matches!(err, BackendConfigError::InvalidEnvValue(..))
+      }),
+      (MAX_MESSAGE_SIZE_ENV, "0", |err| {
+        matches!(err, BackendConfigError::InvalidMaxMessageSize)
+      }),
+      (CONTEXT_VALUE_CHUNK_SIZE_ENV, "0", |err| {
+        matches!(err, BackendConfigError::InvalidChunkSize(..))
+      }),
+      (LISTEN_ADDRESSES_ENV, "localhost", |err| {
+        matches!(err, BackendConfigError::InvalidListenAddress(..))
+      }),
+      (TLS_CERT_PATH_ENV, "cert.pem", |err| {
+        matches!(err, BackendConfigError::IncompleteTlsConfig)
+      }),
+      (LOG_LEVEL_ENV, "loud", |err| matches!(err, BackendConfigError::InvalidLogLevel(..))),
+      (KAFKA_PRODUCER_SETTINGS_ENV, "acks", |err| {
+        matches!(err, BackendConfigError::InvalidEnvValue(..))
+      }),
+      (KAFKA_CHECKPOINT_INTERVAL_ENV, "0", |err| {
+        matches!(err, BackendConfigError::InvalidCheckpointInterval)
+      }),
+      (METRICS_ADDRESS_ENV, "9090", |err| {
+        matches!(err, BackendConfigError::InvalidMetricsAddress(..))
+      }),
+      (MAX_CONCURRENT_PIPELINES_ENV, "0", |err| {
+        matches!(err, BackendConfigError::InvalidMaxConcurrentPipelines)
+      }),
+      (MAX_CONCURRENT_PIPELINES_ENV, "-1", |err| {
+        matches!(err, BackendConfigError::InvalidEnvValue(..))
+      }),
+      (SCHEDULING_POLICY_ENV, "lifo", |err| {
+        matches!(err, BackendConfigError::InvalidSchedulingPolicy(..))
+      }),
+      (RESULTS_CACHE_MAX_BYTES_ENV, "many", |err| {
+        matches!(err, BackendConfigError::InvalidEnvValue(..))
+      }),
+      (CONTEXT_VALUES_STORE_ENV, "s3", |err| {
+        matches!(err, BackendConfigError::InvalidContextValuesStore(..))
+      }),
+      (CONTEXT_VALUES_TTL_ENV, "forever", |err| {
+        matches!(err, BackendConfigError::InvalidEnvValue(..))
+      }),
+    ];
+
+    for (name, value, is_expected_error) in cases {
+      match load(&[(name, value)]) {
+        Ok(_) => panic!("{}={} must be rejected", name, value),
+        Err(err) => assert!(
+          is_expected_error(&err),
+          "{}={} is rejected with unexpected error {}",
+          name,
+          value,
+          err
+        ),
+      }
+    }
+  }
+
+  #[test]
+  fn test_config_file() {
+    let file = TempFile::new(
+      r#"{
+        "listen_addresses": ["127.0.0.1:1000"],
+        "scheduler": { "max_concurrent_pipelines": 2, "policy": "priority" },
+        "context_values": { "store": "disk", "dir": "values", "max_size_bytes": 100 },
+        "kafka_persistence": { "subscriptions_file": "subscriptions.bin" }
+      }"#,
+    );
+
+    let path = file.0.to_str().unwrap();
+    let config = load(&[(CONFIG_FILE_ENV, path), (SCHEDULING_POLICY_ENV, "fifo")]).unwrap();
+
+    assert_eq!(config.listen_addresses, vec!["127.0.0.1:1000".to_string()]);
+    assert_eq!(config.scheduler_config().unwrap().max_concurrent_pipelines, 2);
+    assert_eq!(config.scheduler_config().unwrap().policy, PipelineSchedulingPolicy::Fifo);
+    assert_eq!(
+      config.context_value_store_kind().unwrap(),
+      ContextValueStoreKind::Disk("values".into())
+    );
+    assert_eq!(config.context_value_store_limits().max_size_bytes, Some(100));
+    assert_eq!(
+      config.kafka_persistence.subscriptions_file,
+      Some(PathBuf::from("subscriptions.bin"))
+    );
+    assert_eq!(config.results_cache.max_entries, DEFAULT_RESULTS_CACHE_MAX_ENTRIES);
+  }
+
+  #[test]
+  fn test_config_file_with_unknown_field() {
+    let file = TempFile::new(r#"{ "scheduler": { "threads": 2 } }"#);
+    let result = load(&[(CONFIG_FILE_ENV, file.0.to_str().unwrap())]);
+
+    assert!(matches!(result, Err(BackendConfigError::InvalidFile(..))));
+  }
+}
@@ -3,18 +3,38 @@ use super::events::{
   grpc_events_handler::GrpcPipelineEventsHandler,
 };
 use crate::{
+  config::ResultsCacheConfig,
   ficus_proto::{
-    GrpcFicusBackendInfo, GrpcGuid, GrpcPipelinePartDescriptor, GrpcPipelinePartExecutionResult, GrpcProxyPipelineExecutionRequest,
-    grpc_backend_service_server::GrpcBackendService,
+    GrpcFicusBackendInfo, GrpcGuid, GrpcPipelineExecutionOptions, GrpcPipelinePartDescriptor, GrpcPipelinePartExecutionResult,
+    GrpcPipelinePartKeyDescriptor, GrpcPipelineQueuePosition, GrpcPipelineValidationResult, GrpcProxyPipelineExecutionRequest,
+    GrpcRunningPipeline, GrpcRunningPipelines, grpc_backend_service_server::GrpcBackendService,
   },
-  grpc::{context_values_service::ContextValueService, pipeline_executor::ServicePipelineExecutionContext},
+  grpc::{
+    context_values_service::ContextValueService, converters::convert_to_grpc_timestamp, execution_registry::PipelineExecutionRegistry,
+    metrics::BackendMetrics, pipeline_executor::ServicePipelineExecutionContext, pipeline_validation::validate_grpc_pipeline,
+    scheduler::create_scheduler,
+  },
+};
+use ficus::{
+  pipelines::{
+    errors::pipeline_errors::PipelinePartExecutionError,
+    parts_metadata::PipelinePartKeyDescriptor,
+    pipeline_parts::PipelineParts,
+    results_cache::PipelinePartsResultsCache,
+    scheduler::{PipelineJob, PipelineScheduler, PipelineSchedulerConfig, QueuePositionCallback},
+  },
+  utils::{allocations_tracking::AllocationsCounter, execution_budget::ExecutionBudget},
+};
+use futures::{Stream, channel::oneshot, future};
+use log::warn;
+use std::{pin::Pin, sync::Arc, time::Duration};
+use tokio::{
+  runtime::Handle,
+  sync::mpsc::{self, Sender},
 };
-use ficus::pipelines::pipeline_parts::PipelineParts;
-use futures::Stream;
-use std::{pin::Pin, sync::Arc};
-use tokio::sync::mpsc::{self, Sender};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
+use uuid::Uuid;
 
 pub(super) type GrpcResult = crate::ficus_proto::grpc_pipeline_part_execution_result::Result;
 pub(super) type GrpcSender = Sender<Result<GrpcPipelinePartExecutionResult, Status>>;
@@ -22,15 +42,76 @@ pub(super) type GrpcSender = Sender<Result<GrpcPipelinePartExecutionResult, Stat
 pub struct FicusService {
   cv_service: Arc<ContextValueService>,
   pipeline_parts: Arc<PipelineParts>,
+  execution_registry: Arc<PipelineExecutionRegistry>,
+  scheduler: Arc<PipelineScheduler>,
+  results_cache: Arc<PipelinePartsResultsCache>,
+  metrics: Arc<BackendMetrics>,
 }
 
 impl FicusService {
-  pub fn new(cv_service: Arc<ContextValueService>) -> Self {
+  pub fn new(
+    cv_service: Arc<ContextValueService>,
+    scheduler_config: PipelineSchedulerConfig,
+    results_cache_config: &ResultsCacheConfig,
+    metrics: Arc<BackendMetrics>,
+  ) -> Self {
     Self {
       cv_service,
       pipeline_parts: Arc::new(PipelineParts::new()),
+      execution_registry: Arc::new(PipelineExecutionRegistry::new()),
+      scheduler: Arc::new(create_scheduler(scheduler_config, Handle::current())),
+      results_cache: Arc::new(Self::create_results_cache(results_cache_config)),
+      metrics,
     }
   }
+
+  fn create_results_cache(config: &ResultsCacheConfig) -> PipelinePartsResultsCache {
+    PipelinePartsResultsCache::new(config.max_entries).with_max_bytes(config.max_bytes)
+  }
+
+  fn create_execution_budget(options: &GrpcPipelineExecutionOptions, allocations_counter: &AllocationsCounter) -> ExecutionBudget {
+    let mut budget = ExecutionBudget::unlimited();
+
+    if options.time_budget_ms > 0 {
+      budget = budget.with_time_limit(Duration::from_millis(options.time_budget_ms));
+    }
+
+    if options.memory_budget_bytes > 0 {
+      let allocations_counter = allocations_counter.clone();
+      let probe = Arc::new(move || allocations_counter.allocated_bytes());
+
+      budget = budget.with_memory_limit(options.memory_budget_bytes as usize, probe);
+    }
+
+    budget
+  }
+
+  fn to_grpc_key_descriptors(descriptors: &[PipelinePartKeyDescriptor]) -> Vec<GrpcPipelinePartKeyDescriptor> {
+    descriptors
+      .iter()
+      .map(|d| GrpcPipelinePartKeyDescriptor {
+        name: d.name().to_owned(),
+        type_name: d.type_name().to_owned(),
+        required: d.required(),
+        default_value: d.default_value().map(|v| v.to_owned()),
+      })
+      .collect()
+  }
+
+  fn create_queue_position_callback(sender: GrpcSender) -> QueuePositionCallback {
+    Box::new(move |position, queue_length| {
+      let result = GrpcPipelinePartExecutionResult {
+        result: Some(GrpcResult::QueuePosition(GrpcPipelineQueuePosition {
+          position: position as u32,
+          queue_length: queue_length as u32,
+        })),
+      };
+
+      if let Err(err) = sender.try_send(Ok(result)) {
+        warn!("Failed to send queue position event, error: {}", err);
+      }
+    })
+  }
 }
 
 #[tonic::async_trait]
@@ -44,6 +125,16 @@ impl GrpcBackendService for FicusService {
     let pipeline_parts = self.pipeline_parts.clone();
     let (sender, receiver) = mpsc::channel(4);
 
+    let execution_id = match request.get_ref().execution_id.as_ref() {
+      Some(execution_id) => execution_id.to_uuid()?,
+      None => Uuid::new_v4(),
+    };
+
+    let Some(registration) = self.execution_registry.register(execution_id) else {
+      let message = format!("Pipeline execution {} is already running", execution_id);
+      return Err(Status::already_exists(message));
+    };
+
     let context_values = match self.cv_service.reclaim_context_values(&request.get_ref().context_values_ids) {
       Ok(context_values) => context_values,
       Err(not_found_id) => {
@@ -53,28 +144,59 @@ impl GrpcBackendService for FicusService {
     };
 
     let cv_service = self.cv_service.clone();
+    let cancellation_token = registration.token().clone();
+
+    let (finished_sender, finished_receiver) = oneshot::channel::<()>();
+    let disconnect_sender = sender.clone();
+    let disconnect_token = cancellation_token.clone();
+
+    tokio::spawn(async move {
+      let closed = Box::pin(disconnect_sender.closed());
+      if let future::Either::Left(_) = future::select(closed, finished_receiver).await {
+        disconnect_token.cancel();
+      }
+    });
+
+    let options = request.get_ref().options.unwrap_or_default();
+    let priority = options.priority;
+    let results_cache = options.use_results_cache.then(|| self.results_cache.clone());
+    let queue_position_callback = Self::create_queue_position_callback(sender.clone());
+    let metrics = self.metrics.clone();
+
+    let job = Box::new(move || {
+      registration.mark_started();
+
+      let allocations_counter = AllocationsCounter::new();
+      let _allocations_scope = allocations_counter.enter();
 
-    tokio::task::spawn_blocking(move || {
       let grpc_pipeline = request.get_ref().pipeline.as_ref().unwrap();
 
       let sender = Arc::new(GrpcPipelineEventsHandler::new(sender));
       let sender = sender as Arc<dyn PipelineEventsHandler>;
-      let context = ServicePipelineExecutionContext::new(grpc_pipeline, &context_values, pipeline_parts, sender);
-
-      match context.execute_grpc_pipeline_and_fill_context_values(|_| Ok(()), cv_service) {
-        Ok(uuid) => {
-          context
-            .sender()
-            .handle(&PipelineEvent::FinalResult(PipelineFinalResult::Success(uuid)));
-        }
-        Err(error) => {
-          context
-            .sender()
-            .handle(&PipelineEvent::FinalResult(PipelineFinalResult::Error(error.to_string())));
-        }
+      let context = ServicePipelineExecutionContext::new(grpc_pipeline, &context_values, pipeline_parts, sender)
+        .with_cancellation_token(cancellation_token)
+        .with_execution_budget(Self::create_execution_budget(&options, &allocations_counter))
+        .with_results_cache(results_cache)
+        .with_metrics(Some(metrics));
+
+      context.sender().handle(&PipelineEvent::ExecutionStarted(execution_id));
+
+      let final_result = match context.execute_grpc_pipeline_and_fill_context_values(|_| Ok(()), cv_service) {
+        Ok(uuid) => PipelineFinalResult::Success(uuid),
+        Err(PipelinePartExecutionError::Cancelled) => PipelineFinalResult::Cancelled(execution_id),
+        Err(error) => PipelineFinalResult::Error(error.to_string()),
       };
+
+      drop(registration);
+      context.sender().handle(&PipelineEvent::FinalResult(final_result));
+
+      drop(finished_sender);
     });
 
+    self
+      .scheduler
+      .submit(PipelineJob::new(execution_id, priority, job, queue_position_callback));
+
     Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
   }
 
@@ -89,8 +211,62 @@ impl GrpcBackendService for FicusService {
         .pipeline_parts
         .pipeline_parts_descriptors()
         .into_iter()
-        .map(|d| GrpcPipelinePartDescriptor { name: d.name() })
+        .map(|d| {
+          let metadata = d.metadata();
+          GrpcPipelinePartDescriptor {
+            config_keys: Self::to_grpc_key_descriptors(metadata.config_keys()),
+            input_keys: Self::to_grpc_key_descriptors(metadata.input_keys()),
+            output_keys: Self::to_grpc_key_descriptors(metadata.output_keys()),
+            name: d.name(),
+          }
+        })
         .collect(),
     }))
   }
+
+  async fn cancel_pipeline(&self, request: Request<GrpcGuid>) -> Result<Response<()>, Status> {
+    let execution_id = request.get_ref().to_uuid()?;
+
+    match self.execution_registry.cancel(&execution_id) {
+      true => Ok(Response::new(())),
+      false => Err(Status::not_found(format!("Failed to find running pipeline {}", execution_id))),
+    }
+  }
+
+  async fn get_running_pipelines(&self, _: Request<()>) -> Result<Response<GrpcRunningPipelines>, Status> {
+    Ok(Response::new(GrpcRunningPipelines {
+      pipelines: self
+        .execution_registry
+        .running_executions()
+        .into_iter()
+        .map(|e| GrpcRunningPipeline {
+          execution_id: Some(e.execution_id.into()),
+          start_time: Some(convert_to_grpc_timestamp(&e.start_time)),
+          cancellation_requested: e.cancellation_requested,
+          queued: e.queued,
+        })
+        .collect(),
+    }))
+  }
+
+  async fn validate_pipeline(
+    &self,
+    request: Request<GrpcProxyPipelineExecutionRequest>,
+  ) -> Result<Response<GrpcPipelineValidationResult>, Status> {
+    let Some(grpc_pipeline) = request.get_ref().pipeline.as_ref() else {
+      return Err(Status::invalid_argument("Pipeline is not specified"));
+    };
+
+    let initial_keys = match self.cv_service.context_values_keys(&request.get_ref().context_values_ids) {
+      Ok(keys) => keys,
+      Err(not_found_id) => {
+        let message = format!("Failed to find context value for id {}", not_found_id);
+        return Err(Status::invalid_argument(message));
+      }
+    };
+
+    Ok(Response::new(GrpcPipelineValidationResult {
+      issues: validate_grpc_pipeline(&self.pipeline_parts, grpc_pipeline, initial_keys),
+    }))
+  }
 }
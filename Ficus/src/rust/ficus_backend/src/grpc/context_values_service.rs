@@ -3,72 +3,76 @@ use crate::{
     GrpcContextKey, GrpcContextKeyValue, GrpcContextValuePart, GrpcDropContextValuesRequest, GrpcGetAllContextValuesResult,
     GrpcGetContextValueRequest, GrpcGuid, grpc_context_values_service_server::GrpcContextValuesService,
   },
-  grpc::converters::context_value_from_bytes,
+  grpc::{converters::context_value_from_bytes, cv_store::stored_value::GrpcContextValueStore, metrics::BackendMetrics},
 };
 use ficus::pipelines::keys::context_keys::find_context_key;
 use futures::Stream;
 use prost::Message;
-use std::{
-  collections::HashMap,
-  pin::Pin,
-  sync::{Arc, Mutex},
-};
+use std::{collections::HashMap, pin::Pin, sync::Arc};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Code, Request, Response, Status, Streaming};
 use uuid::Uuid;
 
 pub struct ContextValueService {
-  context_values: Mutex<HashMap<String, GrpcContextKeyValue>>,
-  contexts: Mutex<HashMap<String, HashMap<String, Uuid>>>,
+  store: Arc<GrpcContextValueStore>,
+  metrics: Arc<BackendMetrics>,
 }
 
 impl ContextValueService {
-  pub fn new() -> Self {
-    Self {
-      context_values: Default::default(),
-      contexts: Default::default(),
-    }
+  pub fn new(store: Arc<GrpcContextValueStore>, metrics: Arc<BackendMetrics>) -> Self {
+    metrics.set_context_values_count(store.values_count());
+    Self { store, metrics }
+  }
+
+  fn update_values_count_metric(&self) {
+    self.metrics.set_context_values_count(self.store.values_count());
   }
 
   pub fn reclaim_context_values(&self, ids: &Vec<GrpcGuid>) -> Result<Vec<GrpcContextKeyValue>, String> {
     let mut values = vec![];
 
-    let mut context_values = self.context_values.lock();
-    let context_values = context_values.as_mut().expect("Must acquire lock");
-
     for id in ids {
-      if let Some((_, context_key_value)) = context_values.remove_entry(&id.guid) {
+      if let Some(context_key_value) = self.store.remove_value(&id.guid) {
         values.push(context_key_value);
       } else {
+        self.update_values_count_metric();
         return Err(id.guid.clone());
       }
     }
 
+    self.update_values_count_metric();
     Ok(values)
   }
 
-  pub fn put_context_value(&self, key: String, value: GrpcContextKeyValue) {
-    let mut context_values = self.context_values.lock();
-    let context_values = context_values.as_mut().expect("Must acquire lock");
+  pub fn context_values_keys(&self, ids: &Vec<GrpcGuid>) -> Result<Vec<String>, String> {
+    let mut keys = vec![];
+
+    for id in ids {
+      match self.store.get_value(&id.guid).and_then(|value| value.key) {
+        Some(key) => keys.push(key.name),
+        None => return Err(id.guid.clone()),
+      }
+    }
 
-    context_values.insert(key, value);
+    Ok(keys)
   }
 
-  pub fn prune_context_values(&self, ids: &Vec<GrpcGuid>) {
-    let mut context_values = self.context_values.lock();
-    let context_values = context_values.as_mut().expect("Must acquire lock");
+  pub fn put_context_value(&self, key: String, value: GrpcContextKeyValue) {
+    self.store.put_value(&key, value);
+    self.update_values_count_metric();
+  }
 
+  pub fn prune_context_values(&self, ids: &Vec<GrpcGuid>) {
     for id in ids {
-      context_values.remove(&id.guid);
+      self.store.remove_value(&id.guid);
     }
+
+    self.update_values_count_metric();
   }
 
   pub fn get_context_value_bytes(&self, key: &str) -> Option<(String, Vec<u8>)> {
-    let context_values = self.context_values.lock();
-    let context_values = context_values.as_ref().expect("Must acquire lock");
-
-    context_values.get(key).map(|value| {
+    self.store.get_value(key).map(|value| {
       (
         value.key.as_ref().unwrap().name.clone(),
         value.value.as_ref().unwrap().encode_to_vec(),
@@ -77,28 +81,25 @@ impl ContextValueService {
   }
 
   pub fn insert_cv_to_ids(&self, id: Uuid, keys_to_ids: HashMap<String, Uuid>) {
-    self.contexts.lock().as_mut().unwrap().insert(id.to_string(), keys_to_ids);
+    self.store.put_execution(&id.to_string(), keys_to_ids);
   }
 
   pub fn drop_execution_result(&self, execution_id: &str) -> Result<Response<()>, Status> {
-    let mut contexts = self.contexts.lock();
-    let contexts = contexts.as_mut().ok().unwrap();
-
-    contexts.remove(execution_id).map_or_else(
-      || Err(Status::not_found(format!("The session for {} does not exist", execution_id))),
-      |_| Ok(Response::new(())),
-    )
+    match self.store.remove_execution(execution_id) {
+      true => Ok(Response::new(())),
+      false => Err(Status::not_found(format!("The session for {} does not exist", execution_id))),
+    }
   }
 
   pub fn get_all_context_values(&self, execution_id: &str) -> Result<Vec<String>, Status> {
-    self.contexts.lock().as_ref().unwrap().get(execution_id).map_or_else(
+    self.store.get_execution(execution_id).map_or_else(
       || Err(Status::not_found("The context values for supplied execution id are not found")),
       |ids| Ok(ids.values().map(|id| id.to_string()).collect()),
     )
   }
 
   pub fn get_context_value(&self, execution_id: &str, key: &str) -> Result<Uuid, Status> {
-    match self.contexts.lock().unwrap().get_mut(execution_id) {
+    match self.store.get_execution(execution_id) {
       None => Err(Status::not_found("Failed to get context for guid".to_string())),
       Some(keys_to_cv_ids) => match keys_to_cv_ids.get(key) {
         None => Err(Status::not_found("Failed to get context for guid".to_string())),
@@ -110,11 +111,12 @@ impl ContextValueService {
 
 pub struct GrpcContextValueService {
   cv_service: Arc<ContextValueService>,
+  chunk_size: usize,
 }
 
 impl GrpcContextValueService {
-  pub fn new(cv_service: Arc<ContextValueService>) -> Self {
-    Self { cv_service }
+  pub fn new(cv_service: Arc<ContextValueService>, chunk_size: usize) -> Self {
+    Self { cv_service, chunk_size }
   }
 }
 
@@ -155,11 +157,13 @@ impl GrpcContextValuesService for GrpcContextValueService {
     let (sender, receiver) = mpsc::channel(4);
 
     let id = request.get_ref().guid.as_str();
+    let chunk_size = self.chunk_size;
+
     match self.cv_service.get_context_value_bytes(id) {
       None => Err(Status::new(Code::NotFound, format!("Context value for id {} is not found", id))),
       Some((key, bytes)) => {
         tokio::spawn(async move {
-          for chunk in bytes.chunks(1024) {
+          for chunk in bytes.chunks(chunk_size) {
             let part = GrpcContextValuePart {
               key: key.clone(),
               bytes: chunk.to_vec(),
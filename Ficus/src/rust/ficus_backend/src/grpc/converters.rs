@@ -1,17 +1,18 @@
 use crate::ficus_proto::{
   GrpcActivityDurationData, GrpcActivityStartEndData, GrpcAnnotation, GrpcBytes, GrpcColor, GrpcColoredRectangle, GrpcColorsEventLog,
-  GrpcColorsEventLogMapping, GrpcColorsTrace, GrpcContextValue, GrpcCountAnnotation, GrpcDataset, GrpcDurationKind, GrpcEdgeExecutionInfo,
-  GrpcEntityCountAnnotation, GrpcEntityFrequencyAnnotation, GrpcEntityTimeAnnotation, GrpcEvent, GrpcEventAttribute, GrpcEventCoordinates,
-  GrpcEventLogInfo, GrpcEventLogTraceSubArraysContextValue, GrpcFrequenciesAnnotation, GrpcGeneralHistogramData,
-  GrpcGenericEnhancementBase, GrpcGraph, GrpcGraphEdge, GrpcGraphEdgeAdditionalData, GrpcGraphKind, GrpcGraphNode, GrpcGuid,
-  GrpcHashesEventLog, GrpcHashesEventLogContextValue, GrpcHashesLogTrace, GrpcHistogramEntry, GrpcLabeledDataset, GrpcLogPoint,
-  GrpcLogTimelineDiagram, GrpcMatrix, GrpcMatrixRow, GrpcModelElementOcelAnnotation, GrpcMultithreadedFragment, GrpcNamesEventLog,
-  GrpcNamesEventLogContextValue, GrpcNamesTrace, GrpcNodeAdditionalData, GrpcNodeCorrespondingTraceData, GrpcOcelAllocateMerge,
-  GrpcOcelConsumeProduce, GrpcOcelData, GrpcOcelModelAnnotation, GrpcOcelObjectTypeData, GrpcOcelObjectTypeState, GrpcOcelProducedObject,
-  GrpcOcelState, GrpcOcelStateObjectRelation, GrpcPetriNet, GrpcPetriNetArc, GrpcPetriNetMarking, GrpcPetriNetPlace,
-  GrpcPetriNetSinglePlaceMarking, GrpcPetriNetTransition, GrpcSimpleCounterData, GrpcSimpleEventLog, GrpcSimpleTrace, GrpcSoftwareData,
-  GrpcSubArrayWithTraceIndex, GrpcSubArraysWithTraceIndexContextValue, GrpcThread, GrpcThreadEvent, GrpcTimePerformanceAnnotation,
-  GrpcTimeSpan, GrpcTimelineDiagramFragment, GrpcTimelineTraceEventsGroup, GrpcTraceSubArray, GrpcTraceSubArrays, GrpcTraceTimelineDiagram,
+  GrpcColorsEventLogMapping, GrpcColorsTrace, GrpcConceptDriftChangePoint, GrpcConceptDriftChangePoints, GrpcConceptDriftResponsibleItem,
+  GrpcContextValue, GrpcCountAnnotation, GrpcDataset, GrpcDurationKind, GrpcEdgeExecutionInfo, GrpcEntityCountAnnotation,
+  GrpcEntityFrequencyAnnotation, GrpcEntityTimeAnnotation, GrpcEvent, GrpcEventAttribute, GrpcEventCoordinates, GrpcEventLogInfo,
+  GrpcEventLogTraceSubArraysContextValue, GrpcFrequenciesAnnotation, GrpcGeneralHistogramData, GrpcGenericEnhancementBase, GrpcGraph,
+  GrpcGraphEdge, GrpcGraphEdgeAdditionalData, GrpcGraphKind, GrpcGraphNode, GrpcGuid, GrpcHashesEventLog, GrpcHashesEventLogContextValue,
+  GrpcHashesLogTrace, GrpcHistogramEntry, GrpcLabeledDataset, GrpcLogPoint, GrpcLogTimelineDiagram, GrpcMatrix, GrpcMatrixRow,
+  GrpcModelElementOcelAnnotation, GrpcMultithreadedFragment, GrpcNamesEventLog, GrpcNamesEventLogContextValue, GrpcNamesTrace,
+  GrpcNodeAdditionalData, GrpcNodeCorrespondingTraceData, GrpcOcelAllocateMerge, GrpcOcelConsumeProduce, GrpcOcelData,
+  GrpcOcelModelAnnotation, GrpcOcelObjectTypeData, GrpcOcelObjectTypeState, GrpcOcelProducedObject, GrpcOcelState,
+  GrpcOcelStateObjectRelation, GrpcPetriNet, GrpcPetriNetArc, GrpcPetriNetMarking, GrpcPetriNetPlace, GrpcPetriNetSinglePlaceMarking,
+  GrpcPetriNetTransition, GrpcSimpleCounterData, GrpcSimpleEventLog, GrpcSimpleTrace, GrpcSoftwareData, GrpcSubArrayWithTraceIndex,
+  GrpcSubArraysWithTraceIndexContextValue, GrpcThread, GrpcThreadEvent, GrpcTimePerformanceAnnotation, GrpcTimeSpan,
+  GrpcTimelineDiagramFragment, GrpcTimelineTraceEventsGroup, GrpcTraceSubArray, GrpcTraceSubArrays, GrpcTraceTimelineDiagram,
   GrpcUnderlyingPatternInfo, GrpcUnderlyingPatternKind,
   grpc_annotation::Annotation::{CountAnnotation, FrequencyAnnotation, TimeAnnotation},
   grpc_context_value::{ContextValue, ContextValue::Annotation},
@@ -31,6 +32,9 @@ use ficus::{
   },
   features::{
     analysis::{
+      drift::{
+        detection::ConceptDriftChangePoint, distributions::DriftFeature, online::OnlineDriftChangePoint, statistics::DriftStatisticalTest,
+      },
       log_info::event_log_info::{EventLogInfo, OfflineEventLogInfo},
       patterns::{
         activity_instances::{ActivityInTraceFilterKind, ActivityNarrowingKind, AdjustingMode},
@@ -70,15 +74,17 @@ use ficus::{
         },
       },
     },
+    streaming::conformance::core::ConformanceReport,
   },
   pipelines::{
     activities_parts::{ActivitiesLogsSourceDto, UndefActivityHandlingStrategyDto},
+    control_flow_parts::{ComparisonOperator, ConditionMetric},
     keys::context_keys::{
-      BYTES_KEY, COLORS_EVENT_LOG_KEY, EVENT_LOG_INFO_KEY, EVENT_LOG_KEY, GRAPH_KEY, GRAPH_TIME_ANNOTATION_KEY, HASHES_EVENT_LOG_KEY,
-      LABELED_LOG_TRACES_DATASET_KEY, LABELED_TRACES_ACTIVITIES_DATASET_KEY, LOG_THREADS_DIAGRAM_KEY, LOG_TRACES_DATASET_KEY,
-      NAMES_EVENT_LOG_KEY, OCEL_ANNOTATION_KEY, PATH_KEY, PATTERNS_KEY, PETRI_NET_COUNT_ANNOTATION_KEY, PETRI_NET_FREQUENCY_ANNOTATION_KEY,
-      PETRI_NET_KEY, PETRI_NET_TRACE_FREQUENCY_ANNOTATION_KEY, REPEAT_SETS_KEY, SOFTWARE_DATA_EXTRACTION_CONFIG_KEY,
-      TRACES_ACTIVITIES_DATASET_KEY,
+      BYTES_KEY, COLORS_EVENT_LOG_KEY, CONCEPT_DRIFT_CHANGE_POINTS_KEY, CONFORMANCE_REPORT_KEY, EVENT_LOG_INFO_KEY, EVENT_LOG_KEY,
+      GRAPH_KEY, GRAPH_TIME_ANNOTATION_KEY, HASHES_EVENT_LOG_KEY, LABELED_LOG_TRACES_DATASET_KEY, LABELED_TRACES_ACTIVITIES_DATASET_KEY,
+      LOG_THREADS_DIAGRAM_KEY, LOG_TRACES_DATASET_KEY, NAMES_EVENT_LOG_KEY, OCEL_ANNOTATION_KEY, ONLINE_CONCEPT_DRIFT_CHANGE_POINTS_KEY,
+      PATH_KEY, PATTERNS_KEY, PETRI_NET_COUNT_ANNOTATION_KEY, PETRI_NET_FREQUENCY_ANNOTATION_KEY, PETRI_NET_KEY,
+      PETRI_NET_TRACE_FREQUENCY_ANNOTATION_KEY, REPEAT_SETS_KEY, SOFTWARE_DATA_EXTRACTION_CONFIG_KEY, TRACES_ACTIVITIES_DATASET_KEY,
     },
     multithreading::FeatureCountKindDto,
     patterns_parts::PatternsKindDto,
@@ -86,7 +92,7 @@ use ficus::{
   },
   utils::{
     colors::{Color, ColoredRectangle, ColorsEventLog},
-    context_key::ContextKey,
+    context_key::{ContextKey, context_type_name},
     dataset::dataset::{FicusDataset, LabeledDataset},
     distance::distance::FicusDistance,
     graph::{
@@ -160,6 +166,14 @@ pub(super) fn put_into_user_data(
         parse_grpc_enum::<FeatureCountKindDto>(user_data, key, &grpc_enum.value);
       } else if enum_name == name_of_type!(RootSequenceKind) {
         parse_grpc_enum::<RootSequenceKind>(user_data, key, &grpc_enum.value);
+      } else if enum_name == name_of_type!(ConditionMetric) {
+        parse_grpc_enum::<ConditionMetric>(user_data, key, &grpc_enum.value);
+      } else if enum_name == name_of_type!(ComparisonOperator) {
+        parse_grpc_enum::<ComparisonOperator>(user_data, key, &grpc_enum.value);
+      } else if enum_name == name_of_type!(DriftFeature) {
+        parse_grpc_enum::<DriftFeature>(user_data, key, &grpc_enum.value);
+      } else if enum_name == name_of_type!(DriftStatisticalTest) {
+        parse_grpc_enum::<DriftStatisticalTest>(user_data, key, &grpc_enum.value);
       }
     }
     ContextValue::EventLogInfo(_) => todo!(),
@@ -225,6 +239,31 @@ pub(super) fn put_into_user_data(
       user_data.put_concrete(EVENT_LOG_KEY.key(), xes_log);
     }
     ContextValue::OcelAnnotation(_) => todo!(),
+    ContextValue::ConceptDriftChangePoints(_) => {
+      error!(
+        "Concept drift change points can not be passed as an input value, key: {}",
+        key.name()
+      );
+    }
+  }
+}
+
+pub(super) fn context_value_type_name(value: &ContextValue) -> Option<String> {
+  match value {
+    ContextValue::String(_) => Some(context_type_name::<Arc<str>>()),
+    ContextValue::NamesLog(_) | ContextValue::XesEventLog(_) => Some(context_type_name::<Vec<Vec<Arc<str>>>>()),
+    ContextValue::Uint32(_) => Some(context_type_name::<u32>()),
+    ContextValue::Bool(_) => Some(context_type_name::<bool>()),
+    ContextValue::Enum(grpc_enum) => Some(grpc_enum.enum_type.to_owned()),
+    ContextValue::Strings(_) => Some(context_type_name::<Vec<Arc<str>>>()),
+    ContextValue::Pipeline(_) => Some(context_type_name::<Pipeline>()),
+    ContextValue::Float(_) => Some(context_type_name::<f64>()),
+    ContextValue::Bytes(_) => Some(context_type_name::<Vec<u8>>()),
+    ContextValue::FloatArray(_) => Some(context_type_name::<Vec<f64>>()),
+    ContextValue::IntArray(_) => Some(context_type_name::<Vec<i64>>()),
+    ContextValue::UintArray(_) => Some(context_type_name::<Vec<u64>>()),
+    ContextValue::EventLog(_) => Some(context_type_name::<XesEventLogImpl>()),
+    _ => None,
   }
 }
 
@@ -309,6 +348,12 @@ pub fn convert_to_grpc_context_value(key: &dyn ContextKey, value: &dyn Any) -> O
     try_convert_to_grpc_simple_log(value)
   } else if OCEL_ANNOTATION_KEY.eq_other(key) {
     try_convert_to_grpc_ocel_annotation(value)
+  } else if CONFORMANCE_REPORT_KEY.eq_other(key) {
+    try_convert_to_grpc_conformance_report(value)
+  } else if CONCEPT_DRIFT_CHANGE_POINTS_KEY.eq_other(key) {
+    try_convert_to_grpc_concept_drift_change_points(value)
+  } else if ONLINE_CONCEPT_DRIFT_CHANGE_POINTS_KEY.eq_other(key) {
+    try_convert_to_grpc_online_concept_drift_change_points(value)
   } else {
     None
   }
@@ -366,6 +411,72 @@ fn convert_to_grpc_ocel_node_state(state: &NodeObjectsState) -> GrpcOcelState {
   }
 }
 
+fn try_convert_to_grpc_conformance_report(value: &dyn Any) -> Option<GrpcContextValue> {
+  if !value.is::<ConformanceReport>() {
+    None
+  } else {
+    let value = value.downcast_ref::<ConformanceReport>().unwrap();
+    match serde_json::to_string(value) {
+      Ok(json) => Some(GrpcContextValue {
+        context_value: Some(ContextValue::Json(json)),
+      }),
+      Err(err) => {
+        error!("Failed to serialize conformance report, error: {}", err);
+        None
+      }
+    }
+  }
+}
+
+fn try_convert_to_grpc_concept_drift_change_points(value: &dyn Any) -> Option<GrpcContextValue> {
+  if !value.is::<Vec<ConceptDriftChangePoint>>() {
+    None
+  } else {
+    let value = value.downcast_ref::<Vec<ConceptDriftChangePoint>>().unwrap();
+    Some(GrpcContextValue {
+      context_value: Some(ContextValue::ConceptDriftChangePoints(GrpcConceptDriftChangePoints {
+        change_points: value.iter().map(convert_to_grpc_concept_drift_change_point).collect(),
+      })),
+    })
+  }
+}
+
+fn convert_to_grpc_concept_drift_change_point(change_point: &ConceptDriftChangePoint) -> GrpcConceptDriftChangePoint {
+  GrpcConceptDriftChangePoint {
+    trace_index: change_point.trace_index as u64,
+    timestamp: change_point.timestamp.as_ref().map(convert_to_grpc_timestamp),
+    statistic: change_point.statistic,
+    p_value: change_point.p_value,
+    responsible_items: change_point
+      .responsible_items
+      .iter()
+      .map(|(item, contribution)| GrpcConceptDriftResponsibleItem {
+        item: item.to_string(),
+        contribution: *contribution,
+      })
+      .collect(),
+    before_dfg: Some(convert_to_grpc_graph(&change_point.before_dfg)),
+    after_dfg: Some(convert_to_grpc_graph(&change_point.after_dfg)),
+  }
+}
+
+fn try_convert_to_grpc_online_concept_drift_change_points(value: &dyn Any) -> Option<GrpcContextValue> {
+  if !value.is::<Vec<OnlineDriftChangePoint>>() {
+    None
+  } else {
+    let value = value.downcast_ref::<Vec<OnlineDriftChangePoint>>().unwrap();
+    match serde_json::to_string(value) {
+      Ok(json) => Some(GrpcContextValue {
+        context_value: Some(ContextValue::Json(json)),
+      }),
+      Err(err) => {
+        error!("Failed to serialize online concept drift change points, error: {}", err);
+        None
+      }
+    }
+  }
+}
+
 fn try_convert_to_grpc_bytes(value: &dyn Any) -> Option<GrpcContextValue> {
   if !value.is::<Vec<u8>>() {
     None
@@ -890,7 +1001,7 @@ fn convert_to_grpc_attribute_value(value: &EventPayloadValue) -> Option<grpc_eve
   }
 }
 
-fn convert_to_grpc_timestamp(stamp: &DateTime<Utc>) -> Timestamp {
+pub(super) fn convert_to_grpc_timestamp(stamp: &DateTime<Utc>) -> Timestamp {
   Timestamp::from_str(stamp.to_rfc3339().as_str()).unwrap()
 }
 
@@ -0,0 +1,22 @@
+use super::stored_value::GrpcContextValueStore;
+use crate::{config::ContextValueStoreKind, ficus_proto::GrpcContextKeyValue};
+use ficus::utils::context_values_store::{
+  disk_storage::DiskValuesStorage, in_memory_storage::InMemoryValuesStorage, storage::ValuesStorage, store::ContextValueStoreLimits,
+};
+use log::info;
+use std::{io, sync::Arc};
+
+pub fn create_context_value_store(kind: &ContextValueStoreKind, limits: ContextValueStoreLimits) -> io::Result<Arc<GrpcContextValueStore>> {
+  let storage: Box<dyn ValuesStorage<GrpcContextKeyValue>> = match kind {
+    ContextValueStoreKind::InMemory => {
+      info!("Using in-memory context values store, {:?}", limits);
+      Box::new(InMemoryValuesStorage::default())
+    }
+    ContextValueStoreKind::Disk(root) => {
+      info!("Using disk context values store at {}, {:?}", root.display(), limits);
+      Box::new(DiskValuesStorage::open(root)?)
+    }
+  };
+
+  Ok(Arc::new(GrpcContextValueStore::new(storage, limits)))
+}
@@ -0,0 +1,19 @@
+use crate::ficus_proto::GrpcContextKeyValue;
+use ficus::utils::context_values_store::{storage::StoredValue, store::ContextValueStore};
+use prost::Message;
+
+pub type GrpcContextValueStore = ContextValueStore<GrpcContextKeyValue>;
+
+impl StoredValue for GrpcContextKeyValue {
+  fn size_in_bytes(&self) -> usize {
+    self.encoded_len()
+  }
+
+  fn to_bytes(&self) -> Vec<u8> {
+    self.encode_to_vec()
+  }
+
+  fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+    Self::decode(bytes).map_err(|err| err.to_string())
+  }
+}
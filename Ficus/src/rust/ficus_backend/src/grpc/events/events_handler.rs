@@ -1,6 +1,6 @@
 use std::any::Any;
 
-use crate::grpc::events::kafka_events_handler::ProcessCaseMetadata;
+use crate::grpc::events::kafka_events_handler::{CaseCompletedEvent, ConformanceAlert, ProcessCaseMetadata};
 use ficus::utils::context_key::ContextKey;
 use uuid::Uuid;
 
@@ -36,11 +36,16 @@ pub struct GetContextValuesEvent<'a> {
 pub enum PipelineFinalResult {
   Success(Uuid),
   Error(String),
+  Cancelled(Uuid),
 }
 
 pub enum PipelineEvent<'a> {
+  ExecutionStarted(Uuid),
   GetContextValuesEvent(GetContextValuesEvent<'a>),
   LogMessage(String),
+  CacheHit(String),
   FinalResult(PipelineFinalResult),
   ProcessCaseMetadata(ProcessCaseMetadata),
+  ConformanceAlert(ConformanceAlert),
+  CaseCompleted(CaseCompletedEvent),
 }
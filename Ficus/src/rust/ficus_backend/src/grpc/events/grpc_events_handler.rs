@@ -3,8 +3,8 @@ use std::sync::Arc;
 use super::events_handler::{GetContextValuesEvent, PipelineEvent, PipelineEventsHandler, PipelineFinalResult};
 use crate::{
   ficus_proto::{
-    GrpcGuid, GrpcPipelineFinalResult, GrpcPipelinePartExecutionResult, GrpcPipelinePartLogMessage, GrpcPipelinePartResult,
-    grpc_pipeline_final_result::ExecutionResult,
+    GrpcGuid, GrpcPipelineExecutionStarted, GrpcPipelineFinalResult, GrpcPipelinePartCacheHit, GrpcPipelinePartExecutionResult,
+    GrpcPipelinePartLogMessage, GrpcPipelinePartResult, grpc_pipeline_final_result::ExecutionResult,
   },
   grpc::{
     backend_service::{GrpcResult, GrpcSender},
@@ -13,6 +13,7 @@ use crate::{
   },
 };
 use ficus::pipelines::context::LogMessageHandler;
+use uuid::Uuid;
 
 pub struct GrpcPipelineEventsHandler {
   sender: Arc<GrpcSender>,
@@ -31,13 +32,16 @@ impl GrpcPipelineEventsHandler {
 impl PipelineEventsHandler for GrpcPipelineEventsHandler {
   fn handle(&self, event: &PipelineEvent) {
     let result = match event {
+      PipelineEvent::ExecutionStarted(execution_id) => self.create_execution_started_result(execution_id),
       PipelineEvent::GetContextValuesEvent(event) => self.create_get_context_values_event(event),
       PipelineEvent::LogMessage(message) => self.create_log_message_result(message),
+      PipelineEvent::CacheHit(part_name) => self.create_cache_hit_result(part_name),
       PipelineEvent::FinalResult(result) => self.create_final_result(match result {
         PipelineFinalResult::Success(uuid) => ExecutionResult::Success(GrpcGuid { guid: uuid.to_string() }),
         PipelineFinalResult::Error(error_message) => ExecutionResult::Error(error_message.to_string()),
+        PipelineFinalResult::Cancelled(uuid) => ExecutionResult::Cancelled(GrpcGuid { guid: uuid.to_string() }),
       }),
-      PipelineEvent::ProcessCaseMetadata(_) => unreachable!(),
+      PipelineEvent::ProcessCaseMetadata(_) | PipelineEvent::ConformanceAlert(_) | PipelineEvent::CaseCompleted(_) => return,
     };
 
     if !self.is_alive() {
@@ -76,6 +80,24 @@ impl GrpcPipelineEventsHandler {
     }
   }
 
+  fn create_cache_hit_result(&self, part_name: &str) -> GrpcPipelinePartExecutionResult {
+    GrpcPipelinePartExecutionResult {
+      result: Some(GrpcResult::CacheHit(GrpcPipelinePartCacheHit {
+        pipeline_part_name: part_name.to_owned(),
+      })),
+    }
+  }
+
+  fn create_execution_started_result(&self, execution_id: &Uuid) -> GrpcPipelinePartExecutionResult {
+    GrpcPipelinePartExecutionResult {
+      result: Some(GrpcResult::ExecutionStarted(GrpcPipelineExecutionStarted {
+        execution_id: Some(GrpcGuid {
+          guid: execution_id.to_string(),
+        }),
+      })),
+    }
+  }
+
   fn create_final_result(&self, execution_result: ExecutionResult) -> GrpcPipelinePartExecutionResult {
     GrpcPipelinePartExecutionResult {
       result: Some(GrpcResult::FinalResult(GrpcPipelineFinalResult {
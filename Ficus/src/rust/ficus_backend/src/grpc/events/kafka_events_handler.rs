@@ -1,7 +1,14 @@
-use super::events_handler::{PipelineEvent, PipelineEventsHandler, PipelineFinalResult};
+use super::events_handler::{GetContextValuesEvent, PipelineEvent, PipelineEventsHandler, PipelineFinalResult};
 use crate::{
-  ficus_proto::{GrpcCaseName, GrpcGuid, GrpcKafkaConnectionMetadata, GrpcKafkaUpdate, GrpcProcessCaseMetadata, GrpcStringKeyValue},
-  grpc::logs_handler::ConsoleLogMessageHandler,
+  ficus_proto::{
+    GrpcCaseCompleted, GrpcCaseName, GrpcConformanceAlert, GrpcGuid, GrpcKafkaConnectionMetadata, GrpcKafkaPipelinePartResult,
+    GrpcKafkaUpdate, GrpcPipelinePartInfo, GrpcProcessCaseMetadata, GrpcStringKeyValue,
+  },
+  grpc::{
+    events::utils::create_grpc_context_values,
+    kafka::streaming::{conformance::processors::CaseDeviation, t1::processors::T1CompletedCase},
+    logs_handler::ConsoleLogMessageHandler,
+  },
 };
 use ficus::{features::cases::CaseName, pipelines::context::LogMessageHandler};
 use prost::Message;
@@ -10,7 +17,7 @@ use rdkafka::{
   error::KafkaError,
   producer::{BaseProducer, BaseRecord},
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use uuid::Uuid;
 
 pub struct PipelineEventsProducer {
@@ -19,9 +26,13 @@ pub struct PipelineEventsProducer {
 }
 
 impl PipelineEventsProducer {
-  pub fn create(connection_metadata: &GrpcKafkaConnectionMetadata) -> Result<Self, KafkaError> {
+  pub fn create(connection_metadata: &GrpcKafkaConnectionMetadata, default_settings: &HashMap<String, String>) -> Result<Self, KafkaError> {
     let mut config = ClientConfig::new();
 
+    for (key, value) in default_settings {
+      config.set(key.to_owned(), value.to_owned());
+    }
+
     for kv_pair in &connection_metadata.metadata {
       config.set(kv_pair.key.clone(), kv_pair.value.clone());
     }
@@ -62,32 +73,70 @@ impl KafkaEventsHandler {
       console_logs_handler: ConsoleLogMessageHandler::new(),
     }
   }
+
+  fn produce(&self, update: GrpcKafkaUpdate) {
+    let message = match self.producer.produce(update) {
+      Ok(_) => "Sent message to kafka".to_string(),
+      Err(err) => format!("Failed to produce event: {}", err),
+    };
+
+    self.console_logs_handler.handle(message.as_str()).expect("Should log message");
+  }
+
+  fn create_pipeline_part_result(event: &GetContextValuesEvent) -> GrpcKafkaPipelinePartResult {
+    GrpcKafkaPipelinePartResult {
+      process_case_metadata: Some(event.process_case_metadata.to_grpc_process_case_metadata()),
+      pipeline_part_info: Some(GrpcPipelinePartInfo {
+        name: event.pipeline_part_name.clone(),
+        id: Some(GrpcGuid::from(event.pipeline_part_id)),
+        execution_id: Some(GrpcGuid::from(event.execution_id)),
+      }),
+      context_values: create_grpc_context_values(&event.key_values),
+    }
+  }
 }
 
 impl PipelineEventsHandler for KafkaEventsHandler {
   fn handle(&self, event: &PipelineEvent) {
     match event {
-      PipelineEvent::GetContextValuesEvent(_) => {}
+      PipelineEvent::ExecutionStarted(_) => {}
+      PipelineEvent::GetContextValuesEvent(event) => self.produce(GrpcKafkaUpdate {
+        process_case_metadata: None,
+        conformance_alert: None,
+        pipeline_part_result: Some(Self::create_pipeline_part_result(event)),
+        case_completed: None,
+      }),
       PipelineEvent::LogMessage(_) => {}
+      PipelineEvent::CacheHit(_) => {}
       PipelineEvent::FinalResult(result) => match result {
         PipelineFinalResult::Success(_) => {}
         PipelineFinalResult::Error(err) => {
           let message = format!("Received error as final result: {}", err);
           self.console_logs_handler.handle(message.as_str()).expect("Should log message");
         }
+        PipelineFinalResult::Cancelled(execution_id) => {
+          let message = format!("Pipeline execution {} was cancelled", execution_id);
+          self.console_logs_handler.handle(message.as_str()).expect("Should log message");
+        }
       },
-      PipelineEvent::ProcessCaseMetadata(event) => {
-        let result = self.producer.produce(GrpcKafkaUpdate {
-          process_case_metadata: Some(event.to_grpc_process_case_metadata()),
-        });
-
-        let message = match result {
-          Ok(_) => "Sent message to kafka".to_string(),
-          Err(err) => format!("Failed to produce event: {}", err),
-        };
-
-        self.console_logs_handler.handle(message.as_str()).expect("Should log message");
-      }
+      PipelineEvent::ProcessCaseMetadata(event) => self.produce(GrpcKafkaUpdate {
+        process_case_metadata: Some(event.to_grpc_process_case_metadata()),
+        conformance_alert: None,
+        pipeline_part_result: None,
+        case_completed: None,
+      }),
+      PipelineEvent::ConformanceAlert(alert) => self.produce(GrpcKafkaUpdate {
+        process_case_metadata: None,
+        conformance_alert: Some(alert.to_grpc_conformance_alert()),
+        pipeline_part_result: None,
+        case_completed: None,
+      }),
+      PipelineEvent::CaseCompleted(event) => self.produce(GrpcKafkaUpdate {
+        process_case_metadata: None,
+        conformance_alert: None,
+        pipeline_part_result: None,
+        case_completed: Some(event.to_grpc_case_completed()),
+      }),
     };
   }
 
@@ -96,6 +145,7 @@ impl PipelineEventsHandler for KafkaEventsHandler {
   }
 }
 
+#[derive(Clone)]
 pub struct ProcessCaseMetadata {
   pub case_name: CaseName,
   pub process_name: Arc<str>,
@@ -131,3 +181,38 @@ impl ProcessCaseMetadata {
     }
   }
 }
+
+pub struct ConformanceAlert {
+  pub process_case_metadata: ProcessCaseMetadata,
+  pub deviation: CaseDeviation,
+}
+
+impl ConformanceAlert {
+  fn to_grpc_conformance_alert(&self) -> GrpcConformanceAlert {
+    GrpcConformanceAlert {
+      process_case_metadata: Some(self.process_case_metadata.to_grpc_process_case_metadata()),
+      case_id: Some(GrpcGuid::from(self.deviation.case_id)),
+      activity: self.deviation.activity.clone(),
+      cost: self.deviation.conformance.cost,
+      cost_threshold: self.deviation.cost_threshold,
+      events_count: self.deviation.conformance.events_count,
+      deviating_events_count: self.deviation.conformance.deviating_events_count,
+    }
+  }
+}
+
+pub struct CaseCompletedEvent {
+  pub process_case_metadata: ProcessCaseMetadata,
+  pub completed_case: T1CompletedCase,
+}
+
+impl CaseCompletedEvent {
+  fn to_grpc_case_completed(&self) -> GrpcCaseCompleted {
+    GrpcCaseCompleted {
+      process_case_metadata: Some(self.process_case_metadata.to_grpc_process_case_metadata()),
+      case_id: Some(GrpcGuid::from(self.completed_case.case_id)),
+      completion_rule: self.completed_case.completion_rule.clone(),
+      events_count: self.completed_case.events_count,
+    }
+  }
+}
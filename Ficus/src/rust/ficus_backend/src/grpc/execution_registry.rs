@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use ficus::utils::cancellation::CancellationToken;
+use std::{
+  collections::{HashMap, hash_map::Entry},
+  sync::{Arc, Mutex},
+};
+use uuid::Uuid;
+
+pub struct RunningPipelineExecution {
+  pub execution_id: Uuid,
+  pub start_time: DateTime<Utc>,
+  pub cancellation_requested: bool,
+  pub queued: bool,
+}
+
+struct RegisteredExecution {
+  token: CancellationToken,
+  start_time: DateTime<Utc>,
+  queued: bool,
+}
+
+pub struct PipelineExecutionRegistry {
+  executions: Mutex<HashMap<Uuid, RegisteredExecution>>,
+}
+
+impl PipelineExecutionRegistry {
+  pub fn new() -> Self {
+    Self {
+      executions: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Returns `None` if an execution with the same id is already registered, the execution
+  /// is unregistered when the returned registration is dropped, even if the job panics
+  pub fn register(self: &Arc<Self>, execution_id: Uuid) -> Option<ExecutionRegistration> {
+    let token = CancellationToken::new();
+
+    match self.executions.lock().unwrap().entry(execution_id) {
+      Entry::Occupied(_) => return None,
+      Entry::Vacant(entry) => {
+        entry.insert(RegisteredExecution {
+          token: token.clone(),
+          start_time: Utc::now(),
+          queued: true,
+        });
+      }
+    }
+
+    Some(ExecutionRegistration {
+      registry: self.clone(),
+      execution_id,
+      token,
+    })
+  }
+
+  fn mark_started(&self, execution_id: &Uuid) {
+    if let Some(execution) = self.executions.lock().unwrap().get_mut(execution_id) {
+      execution.queued = false;
+    }
+  }
+
+  fn unregister(&self, execution_id: &Uuid) {
+    self.executions.lock().unwrap().remove(execution_id);
+  }
+
+  pub fn cancel(&self, execution_id: &Uuid) -> bool {
+    match self.executions.lock().unwrap().get(execution_id) {
+      Some(execution) => {
+        execution.token.cancel();
+        true
+      }
+      None => false,
+    }
+  }
+
+  pub fn running_executions(&self) -> Vec<RunningPipelineExecution> {
+    let mut executions: Vec<RunningPipelineExecution> = self
+      .executions
+      .lock()
+      .unwrap()
+      .iter()
+      .map(|(id, execution)| RunningPipelineExecution {
+        execution_id: *id,
+        start_time: execution.start_time,
+        cancellation_requested: execution.token.is_cancelled(),
+        queued: execution.queued,
+      })
+      .collect();
+
+    executions.sort_by_key(|e| e.start_time);
+    executions
+  }
+}
+
+pub struct ExecutionRegistration {
+  registry: Arc<PipelineExecutionRegistry>,
+  execution_id: Uuid,
+  token: CancellationToken,
+}
+
+impl ExecutionRegistration {
+  pub fn token(&self) -> &CancellationToken {
+    &self.token
+  }
+
+  pub fn mark_started(&self) {
+    self.registry.mark_started(&self.execution_id);
+  }
+}
+
+impl Drop for ExecutionRegistration {
+  fn drop(&mut self) {
+    self.registry.unregister(&self.execution_id);
+  }
+}
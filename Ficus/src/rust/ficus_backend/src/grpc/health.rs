@@ -0,0 +1,47 @@
+use crate::{
+  ficus_proto::{
+    grpc_backend_service_server::GrpcBackendServiceServer, grpc_context_values_service_server::GrpcContextValuesServiceServer,
+    grpc_kafka_service_server::GrpcKafkaServiceServer,
+  },
+  grpc::{backend_service::FicusService, context_values_service::GrpcContextValueService, kafka::grpc_kafka_service::GrpcKafkaServiceImpl},
+};
+use log::info;
+use tonic::server::NamedService;
+use tonic_health::{ServingStatus, server::HealthReporter};
+
+const SERVER_HEALTH_SERVICE_NAME: &str = "";
+
+/// Services are not serving until the backend listens on all of its addresses and stop serving once the shutdown is requested
+pub struct BackendHealth {
+  reporter: HealthReporter,
+}
+
+impl BackendHealth {
+  pub async fn new(reporter: HealthReporter) -> Self {
+    let health = Self { reporter };
+    health.set_status(ServingStatus::NotServing).await;
+
+    health
+  }
+
+  pub async fn set_ready(&self) {
+    info!("Backend is ready to serve requests");
+    self.set_status(ServingStatus::Serving).await;
+  }
+
+  pub async fn set_shutting_down(&self) {
+    info!("Backend is shutting down");
+    self.set_status(ServingStatus::NotServing).await;
+  }
+
+  async fn set_status(&self, status: ServingStatus) {
+    for service_name in [
+      SERVER_HEALTH_SERVICE_NAME,
+      GrpcContextValuesServiceServer::<GrpcContextValueService>::NAME,
+      GrpcBackendServiceServer::<FicusService>::NAME,
+      GrpcKafkaServiceServer::<GrpcKafkaServiceImpl>::NAME,
+    ] {
+      self.reporter.set_service_status(service_name, status).await;
+    }
+  }
+}
@@ -0,0 +1,166 @@
+use crate::{
+  config::{KAFKA_CHECKPOINTS_DIR_ENV, KafkaPersistenceConfig},
+  grpc::kafka::streaming::processors::ProcessorSnapshot,
+};
+use bxes_kafka::consumer::bxes_kafka_consumer::{BxesKafkaError, BxesKafkaPartitionOffset};
+use chrono::{DateTime, Utc};
+use ficus::{
+  event_log::bxes::{bxes_to_xes_converter::BxesToXesReadError, xes_to_bxes_converter::XesToBxesWriterError},
+  features::streaming::counters::core::StreamingCounterRestoreError,
+};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::HashMap,
+  fmt::{Display, Formatter},
+  fs, io,
+  path::PathBuf,
+  time::Duration,
+};
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum CheckpointError {
+  Disabled,
+  Io(PathBuf, io::Error),
+  Serialization(serde_json::Error),
+  Kafka(BxesKafkaError),
+  LogWrite(XesToBxesWriterError),
+  LogRead(BxesToXesReadError),
+  CounterRestore(StreamingCounterRestoreError),
+  ProcessorKindMismatch,
+  SubscriptionNotFound(Uuid),
+  ConsumerStopped,
+  Failed(String),
+}
+
+impl Display for CheckpointError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      CheckpointError::Disabled => write!(f, "Checkpoints are disabled, set {} to enable them", KAFKA_CHECKPOINTS_DIR_ENV),
+      CheckpointError::Io(path, err) => write!(f, "Failed to access checkpoint {}: {}", path.display(), err),
+      CheckpointError::Serialization(err) => write!(f, "Failed to serialize checkpoint: {}", err),
+      CheckpointError::Kafka(err) => write!(f, "Failed to get consumer state: {:?}", err),
+      CheckpointError::LogWrite(err) => write!(f, "Failed to write event log snapshot: {}", err),
+      CheckpointError::LogRead(err) => write!(f, "Failed to read event log snapshot: {}", err),
+      CheckpointError::CounterRestore(err) => write!(f, "Failed to restore streaming counter: {}", err),
+      CheckpointError::ProcessorKindMismatch => write!(f, "Snapshot was created by the processor of another kind"),
+      CheckpointError::SubscriptionNotFound(id) => write!(f, "Failed to find subscription for id {}", id),
+      CheckpointError::ConsumerStopped => write!(f, "Consumer was stopped before the checkpoint was taken"),
+      CheckpointError::Failed(message) => write!(f, "{}", message),
+    }
+  }
+}
+
+impl From<BxesKafkaError> for CheckpointError {
+  fn from(value: BxesKafkaError) -> Self {
+    Self::Kafka(value)
+  }
+}
+
+impl From<StreamingCounterRestoreError> for CheckpointError {
+  fn from(value: StreamingCounterRestoreError) -> Self {
+    Self::CounterRestore(value)
+  }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckpointOffset {
+  pub topic: String,
+  pub partition: i32,
+  pub offset: i64,
+}
+
+impl From<BxesKafkaPartitionOffset> for CheckpointOffset {
+  fn from(value: BxesKafkaPartitionOffset) -> Self {
+    Self {
+      topic: value.topic,
+      partition: value.partition,
+      offset: value.offset,
+    }
+  }
+}
+
+impl From<&CheckpointOffset> for BxesKafkaPartitionOffset {
+  fn from(value: &CheckpointOffset) -> Self {
+    Self {
+      topic: value.topic.to_owned(),
+      partition: value.partition,
+      offset: value.offset,
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PipelineCheckpoint {
+  pub pipeline_id: Uuid,
+  pub name: String,
+  pub processor: ProcessorSnapshot,
+}
+
+// Offsets, bxes read metadata and processors state are taken between two consumed messages,
+// so that after restoring them the consumer continues exactly from the first message which is not reflected in the state.
+// Subscriptions and pipelines are identified by ids, which are preserved when persisted subscriptions are restored.
+#[derive(Serialize, Deserialize)]
+pub struct SubscriptionCheckpoint {
+  pub subscription_id: Uuid,
+  pub subscription_name: String,
+  pub created_at: DateTime<Utc>,
+  pub offsets: Vec<CheckpointOffset>,
+  pub read_metadata: HashMap<Uuid, Vec<u8>>,
+  pub pipelines: Vec<PipelineCheckpoint>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CheckpointStore {
+  dir: PathBuf,
+  interval: Duration,
+}
+
+impl CheckpointStore {
+  pub fn create(config: &KafkaPersistenceConfig) -> Option<Self> {
+    let dir = config.checkpoints_dir.as_ref()?;
+    info!(
+      "Kafka checkpoints are stored at {} every {:?}",
+      dir.display(),
+      config.checkpoint_interval()
+    );
+
+    Some(Self {
+      dir: dir.to_owned(),
+      interval: config.checkpoint_interval(),
+    })
+  }
+
+  pub fn interval(&self) -> Duration {
+    self.interval
+  }
+
+  pub fn save(&self, checkpoint: &SubscriptionCheckpoint) -> Result<PathBuf, CheckpointError> {
+    fs::create_dir_all(&self.dir).map_err(|err| CheckpointError::Io(self.dir.to_owned(), err))?;
+
+    let path = self.checkpoint_path(checkpoint.subscription_id);
+    let bytes = serde_json::to_vec(checkpoint).map_err(CheckpointError::Serialization)?;
+
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, bytes)
+      .and_then(|_| fs::rename(&temp_path, &path))
+      .map_err(|err| CheckpointError::Io(path.to_owned(), err))?;
+
+    Ok(path)
+  }
+
+  pub fn load(&self, subscription_id: Uuid) -> Result<Option<SubscriptionCheckpoint>, CheckpointError> {
+    let path = self.checkpoint_path(subscription_id);
+    if !path.is_file() {
+      return Ok(None);
+    }
+
+    let bytes = fs::read(&path).map_err(|err| CheckpointError::Io(path.to_owned(), err))?;
+    serde_json::from_slice(&bytes).map(Some).map_err(CheckpointError::Serialization)
+  }
+
+  fn checkpoint_path(&self, subscription_id: Uuid) -> PathBuf {
+    self.dir.join(format!("{}.json", subscription_id))
+  }
+}
@@ -1,9 +1,11 @@
 use crate::{
+  config::KafkaDefaults,
   ficus_proto::{
-    GrpcAddPipelineRequest, GrpcAddPipelineStreamRequest, GrpcExecutePipelineAndProduceKafkaRequest,
-    GrpcGetAllSubscriptionsAndPipelinesResponse, GrpcGetCurrentContextValuesRequest, GrpcGuid, GrpcKafkaFailedResult, GrpcKafkaResult,
-    GrpcKafkaSubscription, GrpcKafkaSubscriptionMetadata, GrpcKafkaSuccessResult, GrpcPipelineMetadata, GrpcPipelinePartExecutionResult,
-    GrpcRemoveAllPipelinesRequest, GrpcRemovePipelineRequest, GrpcSubscribeToKafkaRequest, GrpcSubscriptionPipeline,
+    GrpcAddPipelineRequest, GrpcAddPipelineStreamRequest, GrpcCheckpointKafkaSubscriptionRequest,
+    GrpcExecutePipelineAndProduceKafkaRequest, GrpcGetAllSubscriptionsAndPipelinesResponse, GrpcGetCurrentContextValuesRequest, GrpcGuid,
+    GrpcImportKafkaSubscriptionsResponse, GrpcKafkaFailedResult, GrpcKafkaResult, GrpcKafkaSubscription, GrpcKafkaSubscriptionMetadata,
+    GrpcKafkaSubscriptionsFile, GrpcKafkaSuccessResult, GrpcPipelineMetadata, GrpcPipelinePartExecutionResult,
+    GrpcRemoveAllPipelinesRequest, GrpcRemovePipelineRequest, GrpcReplayLogRequest, GrpcSubscribeToKafkaRequest, GrpcSubscriptionPipeline,
     GrpcUnsubscribeFromKafkaRequest, grpc_kafka_result, grpc_kafka_service_server::GrpcKafkaService,
   },
   grpc::{
@@ -13,7 +15,10 @@ use crate::{
       events_handler::{PipelineEvent, PipelineEventsHandler, PipelineFinalResult},
       grpc_events_handler::GrpcPipelineEventsHandler,
     },
-    kafka::{kafka_service::KafkaService, models::PipelineExecutionDto},
+    kafka::{
+      checkpoints::CheckpointStore, kafka_service::KafkaService, models::PipelineExecutionDto, subscriptions_store::KafkaSubscriptionsStore,
+    },
+    metrics::BackendMetrics,
   },
 };
 use ficus::{
@@ -25,6 +30,7 @@ use ficus::{
   utils::user_data::user_data::UserData,
 };
 use futures::Stream;
+use prost::Message;
 use std::{pin::Pin, sync::Arc};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
@@ -35,15 +41,34 @@ pub struct GrpcKafkaServiceImpl {
   cv_service: Arc<ContextValueService>,
   kafka_service: Arc<KafkaService>,
   pipeline_parts: Arc<PipelineParts>,
+  metrics: Arc<BackendMetrics>,
 }
 
 impl GrpcKafkaServiceImpl {
-  pub fn new(cv_service: Arc<ContextValueService>) -> Self {
+  pub fn new(
+    cv_service: Arc<ContextValueService>,
+    metrics: Arc<BackendMetrics>,
+    kafka_defaults: KafkaDefaults,
+    checkpoints: Option<CheckpointStore>,
+    subscriptions_store: Option<KafkaSubscriptionsStore>,
+  ) -> Self {
     let pipeline_parts = Arc::new(PipelineParts::default());
+    let kafka_service = KafkaService::new(
+      pipeline_parts.clone(),
+      cv_service.clone(),
+      metrics.clone(),
+      kafka_defaults,
+      checkpoints,
+      subscriptions_store,
+    );
+
+    kafka_service.restore_persisted_subscriptions();
+
     Self {
-      cv_service: cv_service.clone(),
-      kafka_service: Arc::new(KafkaService::new(pipeline_parts.clone(), cv_service)),
+      cv_service,
+      kafka_service: Arc::new(kafka_service),
       pipeline_parts,
+      metrics,
     }
   }
 }
@@ -79,6 +104,27 @@ impl GrpcKafkaService for GrpcKafkaServiceImpl {
     Ok(Response::new(GrpcKafkaResult { result: Some(result) }))
   }
 
+  async fn checkpoint_kafka_subscription(
+    &self,
+    request: Request<GrpcCheckpointKafkaSubscriptionRequest>,
+  ) -> Result<Response<GrpcKafkaResult>, Status> {
+    let Some(subscription_id) = request.get_ref().subscription_id.as_ref() else {
+      return Err(Status::invalid_argument("Subscription id must be provided"));
+    };
+
+    let subscription_id = subscription_id.to_uuid()?;
+    let result = match self.kafka_service.checkpoint_subscription(subscription_id).await {
+      Ok(_) => grpc_kafka_result::Result::Success(GrpcKafkaSuccessResult {
+        id: Some(GrpcGuid::from(subscription_id)),
+      }),
+      Err(err) => grpc_kafka_result::Result::Failure(GrpcKafkaFailedResult {
+        error_message: err.to_string(),
+      }),
+    };
+
+    Ok(Response::new(GrpcKafkaResult { result: Some(result) }))
+  }
+
   type GetCurrentContextValuesStream = Self::ExecutePipelineAndProduceToKafkaStream;
 
   async fn get_current_context_values(
@@ -108,12 +154,7 @@ impl GrpcKafkaService for GrpcKafkaServiceImpl {
   }
 
   async fn add_pipeline_to_subscription(&self, request: Request<GrpcAddPipelineRequest>) -> Result<Response<GrpcKafkaResult>, Status> {
-    let dto = request.get_ref().to_dto();
-    let handler = KafkaService::create_kafka_events_handler(request.get_ref().producer_kafka_metadata.as_ref())?;
-    let pipeline_id =
-      self
-        .kafka_service
-        .add_execution_request(dto.subscription_id, handler, dto.request, dto.streaming_configuration, dto.name);
+    let pipeline_id = self.kafka_service.add_kafka_pipeline(request.into_inner())?;
 
     Ok(Response::new(GrpcKafkaResult::success(pipeline_id)))
   }
@@ -131,7 +172,7 @@ impl GrpcKafkaService for GrpcKafkaServiceImpl {
 
     self
       .kafka_service
-      .add_execution_request(dto.subscription_id, handler, dto.request, dto.streaming_configuration, dto.name);
+      .add_execution_request(dto.subscription_id, handler, dto.request, dto.streaming_configuration, dto.name)?;
 
     Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
   }
@@ -199,6 +240,22 @@ impl GrpcKafkaService for GrpcKafkaServiceImpl {
     Ok(Response::new(GrpcGetAllSubscriptionsAndPipelinesResponse { subscriptions }))
   }
 
+  async fn export_kafka_subscriptions(&self, _: Request<()>) -> Result<Response<GrpcKafkaSubscriptionsFile>, Status> {
+    let content = self.kafka_service.export_subscriptions().encode_to_vec();
+
+    Ok(Response::new(GrpcKafkaSubscriptionsFile { content }))
+  }
+
+  async fn import_kafka_subscriptions(
+    &self,
+    request: Request<GrpcKafkaSubscriptionsFile>,
+  ) -> Result<Response<GrpcImportKafkaSubscriptionsResponse>, Status> {
+    match self.kafka_service.import_subscriptions(&request.get_ref().content) {
+      Ok(results) => Ok(Response::new(GrpcImportKafkaSubscriptionsResponse { results })),
+      Err(err) => Err(Status::invalid_argument(err.to_string())),
+    }
+  }
+
   type ExecutePipelineAndProduceToKafkaStream =
     Pin<Box<dyn Stream<Item = Result<GrpcPipelinePartExecutionResult, Status>> + Send + Sync + 'static>>;
 
@@ -207,7 +264,9 @@ impl GrpcKafkaService for GrpcKafkaServiceImpl {
     request: Request<GrpcExecutePipelineAndProduceKafkaRequest>,
   ) -> Result<Response<Self::ExecutePipelineAndProduceToKafkaStream>, Status> {
     let (sender, receiver) = mpsc::channel(4);
-    let kafka_handler = KafkaService::create_kafka_events_handler(request.get_ref().producer_metadata.as_ref())?;
+    let kafka_handler = self
+      .kafka_service
+      .create_kafka_events_handler(request.get_ref().producer_metadata.as_ref())?;
     let kafka_handler = Box::new(kafka_handler) as Box<dyn PipelineEventsHandler>;
     let grpc_handler = Box::new(GrpcPipelineEventsHandler::new(sender)) as Box<dyn PipelineEventsHandler>;
 
@@ -226,6 +285,8 @@ impl GrpcKafkaService for GrpcKafkaServiceImpl {
       }
     };
 
+    let metrics = self.metrics.clone();
+
     tokio::task::spawn_blocking(move || {
       let pipeline = request
         .get_ref()
@@ -245,7 +306,7 @@ impl GrpcKafkaService for GrpcKafkaServiceImpl {
       let pipeline_name = request.pipeline_name.clone();
       let subscription_name = request.subscription_name.clone();
 
-      let context = KafkaService::create_pipeline_execution_context_from_proxy(pipeline, &context_values, &dto);
+      let context = KafkaService::create_pipeline_execution_context_from_proxy(pipeline, &context_values, &dto).with_metrics(Some(metrics));
 
       let execution_result = context.execute_grpc_pipeline(move |context| {
         context.put_concrete(SUBSCRIPTION_ID_KEY.key(), subscription_id.unwrap());
@@ -281,4 +342,29 @@ impl GrpcKafkaService for GrpcKafkaServiceImpl {
 
     Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
   }
+
+  type ReplayLogThroughStreamingPipelineStream =
+    Pin<Box<dyn Stream<Item = Result<GrpcPipelinePartExecutionResult, Status>> + Send + Sync + 'static>>;
+
+  async fn replay_log_through_streaming_pipeline(
+    &self,
+    request: Request<GrpcReplayLogRequest>,
+  ) -> Result<Response<Self::ReplayLogThroughStreamingPipelineStream>, Status> {
+    let (sender, receiver) = mpsc::channel(4);
+    let grpc_handler = Arc::new(GrpcPipelineEventsHandler::new(sender));
+    let kafka_service = self.kafka_service.clone();
+
+    tokio::task::spawn_blocking(move || {
+      match kafka_service.replay_log(request.get_ref(), grpc_handler.clone()) {
+        Ok(uuid) => {
+          grpc_handler.handle(&PipelineEvent::FinalResult(PipelineFinalResult::Success(uuid)));
+        }
+        Err(err) => {
+          grpc_handler.handle(&PipelineEvent::FinalResult(PipelineFinalResult::Error(err.to_string())));
+        }
+      };
+    });
+
+    Ok(Response::new(Box::pin(ReceiverStream::new(receiver))))
+  }
 }
@@ -1,31 +1,41 @@
 use crate::{
+  config::KafkaDefaults,
   ficus_proto::{
-    GrpcContextKeyValue, GrpcGuid, GrpcKafkaConnectionMetadata, GrpcKafkaFailedResult, GrpcKafkaSuccessResult, GrpcPipeline,
-    GrpcPipelineExecutionRequest, GrpcPipelineStreamingConfiguration, GrpcSubscribeToKafkaRequest, grpc_kafka_result,
+    GrpcAddPipelineRequest, GrpcContextKeyValue, GrpcGuid, GrpcKafkaConnectionMetadata, GrpcKafkaFailedResult, GrpcKafkaResult,
+    GrpcKafkaSubscriptionsSet, GrpcKafkaSuccessResult, GrpcPersistedKafkaPipeline, GrpcPersistedKafkaSubscription, GrpcPipeline,
+    GrpcPipelineExecutionRequest, GrpcPipelineStreamingConfiguration, GrpcReplayLogRequest, GrpcSubscribeToKafkaRequest, grpc_kafka_result,
   },
   grpc::{
     context_values_service::ContextValueService,
     events::{
       events_handler::{EmptyPipelineEventsHandler, PipelineEvent, PipelineEventsHandler, PipelineFinalResult},
       grpc_events_handler::GrpcPipelineEventsHandler,
-      kafka_events_handler::{KafkaEventsHandler, PipelineEventsProducer, ProcessCaseMetadata},
+      kafka_events_handler::{CaseCompletedEvent, ConformanceAlert, KafkaEventsHandler, PipelineEventsProducer, ProcessCaseMetadata},
     },
     kafka::{
-      models::{ExtractedTraceMetadata, KafkaConsumerCreationDto, PipelineExecutionDto},
+      checkpoints::{CheckpointError, CheckpointOffset, CheckpointStore, PipelineCheckpoint, SubscriptionCheckpoint},
+      models::{ExtractedTraceMetadata, KafkaConsumerCreationDto, PipelineExecutionDto, is_case_completed},
       streaming::{
         configs::StreamingConfiguration,
         processors::{KafkaTraceProcessingContext, TracesProcessor},
+        replay::StreamReplayer,
       },
+      subscriptions_store::{KafkaSubscriptionsStore, SubscriptionsStoreError, decode_subscriptions},
     },
     logs_handler::ConsoleLogMessageHandler,
+    metrics::BackendMetrics,
     pipeline_executor::ServicePipelineExecutionContext,
   },
 };
-use bxes_kafka::consumer::bxes_kafka_consumer::{BxesKafkaConsumer, BxesKafkaError, BxesKafkaTrace};
+use bxes_kafka::consumer::bxes_kafka_consumer::{BxesKafkaConsumer, BxesKafkaError, BxesKafkaPartitionOffset, BxesKafkaTrace};
+use chrono::{DateTime, TimeZone, Utc};
 use ficus::{
-  features::cases::CaseName,
+  features::{
+    cases::CaseName,
+    streaming::triggers::{PipelineTrigger, TriggerObservation, TriggerPolicy},
+  },
   pipelines::{
-    context::LogMessageHandler,
+    context::{LogMessageHandler, PipelineContext},
     keys::context_keys::{
       PIPELINE_ID_KEY, PIPELINE_NAME_KEY, PROCESS_NAME_KEY, SUBSCRIPTION_ID_KEY, SUBSCRIPTION_NAME_KEY, UNSTRUCTURED_METADATA_KEY,
     },
@@ -33,12 +43,15 @@ use ficus::{
   },
   utils::user_data::user_data::UserData,
 };
-use log::{debug, error, warn};
+use log::{debug, error, info, warn};
 use rdkafka::{ClientConfig, error::KafkaError};
 use std::{
   collections::HashMap,
+  path::PathBuf,
   sync::{Arc, Mutex},
+  time::{Duration, Instant},
 };
+use tokio::sync::oneshot;
 use tonic::Status;
 use uuid::Uuid;
 
@@ -48,6 +61,8 @@ pub struct KafkaSubscriptionPipeline {
   execution_dto: PipelineExecutionDto,
   name: String,
   processor: TracesProcessor,
+  definition: Option<GrpcAddPipelineRequest>,
+  trigger: Option<Arc<Mutex<PipelineTrigger>>>,
 }
 
 impl KafkaSubscriptionPipeline {
@@ -57,8 +72,39 @@ impl KafkaSubscriptionPipeline {
       execution_dto,
       name,
       processor,
+      definition: None,
+      trigger: None,
     }
   }
+
+  fn with_trigger_policy(mut self, policy: Option<TriggerPolicy>) -> Self {
+    self.trigger = policy.map(|policy| Arc::new(Mutex::new(PipelineTrigger::new(policy))));
+    self
+  }
+
+  // Pipelines without trigger policy notify about each observed trace and are executed only on demand
+  fn should_fire(&self, trace: &BxesKafkaTrace, case_completed: bool) -> bool {
+    let Some(trigger) = self.trigger.as_ref() else {
+      return true;
+    };
+
+    let observation = TriggerObservation {
+      event_time: Self::trace_event_time(trace),
+      case_completed,
+      significant_change: self.processor.take_drift_detected(),
+    };
+
+    trigger.lock().expect("Must acquire lock").observe(&observation)
+  }
+
+  fn trace_event_time(trace: &BxesKafkaTrace) -> Option<DateTime<Utc>> {
+    trace
+      .events()
+      .iter()
+      .map(|event| event.timestamp)
+      .max()
+      .map(|stamp| Utc.timestamp_nanos(stamp))
+  }
 }
 
 impl KafkaSubscriptionPipeline {
@@ -67,17 +113,23 @@ impl KafkaSubscriptionPipeline {
   }
 }
 
+type CheckpointRequest = oneshot::Sender<Result<PathBuf, String>>;
+
 #[derive(Clone)]
 pub struct KafkaSubscription {
   name: Arc<str>,
+  subscribe_request: GrpcSubscribeToKafkaRequest,
   pipelines: HashMap<Uuid, KafkaSubscriptionPipeline>,
+  checkpoint_requests: Arc<Mutex<Vec<CheckpointRequest>>>,
 }
 
 impl KafkaSubscription {
-  fn new(name: Arc<str>) -> Self {
+  fn new(name: Arc<str>, subscribe_request: GrpcSubscribeToKafkaRequest) -> Self {
     Self {
       name,
+      subscribe_request,
       pipelines: HashMap::new(),
+      checkpoint_requests: Arc::new(Mutex::new(vec![])),
     }
   }
 
@@ -87,6 +139,24 @@ impl KafkaSubscription {
   pub fn pipelines(&self) -> Vec<(Uuid, KafkaSubscriptionPipeline)> {
     self.pipelines.iter().map(|p| (*p.0, p.1.clone())).collect()
   }
+
+  // Pipelines added through a grpc stream are not persisted, as their events handler can not outlive the stream
+  fn to_persisted(&self, id: Uuid) -> GrpcPersistedKafkaSubscription {
+    GrpcPersistedKafkaSubscription {
+      subscription_id: Some(GrpcGuid::from(id)),
+      subscribe_request: Some(self.subscribe_request.clone()),
+      pipelines: self
+        .pipelines
+        .iter()
+        .filter_map(|(pipeline_id, pipeline)| {
+          pipeline.definition.as_ref().map(|definition| GrpcPersistedKafkaPipeline {
+            pipeline_id: Some(GrpcGuid::from(*pipeline_id)),
+            add_pipeline_request: Some(definition.clone()),
+          })
+        })
+        .collect(),
+    }
+  }
 }
 
 pub struct KafkaService {
@@ -94,15 +164,32 @@ pub struct KafkaService {
   subscriptions_to_execution_requests: Arc<Mutex<HashMap<Uuid, KafkaSubscription>>>,
   cv_service: Arc<ContextValueService>,
   logger: ConsoleLogMessageHandler,
+  metrics: Arc<BackendMetrics>,
+  kafka_defaults: KafkaDefaults,
+  checkpoints: Option<Arc<CheckpointStore>>,
+  subscriptions_store: Option<KafkaSubscriptionsStore>,
 }
 
+const CONSUMER_LAG_UPDATE_INTERVAL: Duration = Duration::from_secs(10);
+
 impl KafkaService {
-  pub fn new(pipeline_parts: Arc<PipelineParts>, cv_service: Arc<ContextValueService>) -> Self {
+  pub fn new(
+    pipeline_parts: Arc<PipelineParts>,
+    cv_service: Arc<ContextValueService>,
+    metrics: Arc<BackendMetrics>,
+    kafka_defaults: KafkaDefaults,
+    checkpoints: Option<CheckpointStore>,
+    subscriptions_store: Option<KafkaSubscriptionsStore>,
+  ) -> Self {
     Self {
       pipeline_parts,
       subscriptions_to_execution_requests: Arc::new(Mutex::new(HashMap::new())),
       logger: ConsoleLogMessageHandler::new(),
       cv_service,
+      metrics,
+      kafka_defaults,
+      checkpoints: checkpoints.map(Arc::new),
+      subscriptions_store,
     }
   }
 }
@@ -110,13 +197,19 @@ impl KafkaService {
 impl KafkaService {
   pub(super) fn unsubscribe_from_kafka(&self, uuid: Uuid) -> grpc_kafka_result::Result {
     let mut states = self.subscriptions_to_execution_requests.lock().expect("Should take lock");
-    match states.remove(&uuid) {
+    let removed = states.remove(&uuid);
+    drop(states);
+
+    match removed {
       None => grpc_kafka_result::Result::Failure(GrpcKafkaFailedResult {
         error_message: "There is not state for the supplied consumer uuid".to_string(),
       }),
-      Some(_) => grpc_kafka_result::Result::Success(GrpcKafkaSuccessResult {
-        id: Some(GrpcGuid { guid: uuid.to_string() }),
-      }),
+      Some(_) => {
+        self.persist_subscriptions();
+        grpc_kafka_result::Result::Success(GrpcKafkaSuccessResult {
+          id: Some(GrpcGuid { guid: uuid.to_string() }),
+        })
+      }
     }
   }
 
@@ -128,18 +221,29 @@ impl KafkaService {
 
 impl KafkaService {
   pub(super) fn subscribe_to_kafka_topic(&self, request: GrpcSubscribeToKafkaRequest) -> Result<Uuid, KafkaError> {
-    let name = request.subscription_metadata.as_ref().unwrap().subscription_name.clone();
-    let creation_dto = self.create_kafka_creation_dto(name.into());
-    let id = creation_dto.uuid;
+    let id = Uuid::new_v4();
+    let (consumer, dto, _) = self.create_subscription(request, id)?;
 
-    match Self::spawn_consumer(request, creation_dto) {
-      Ok(_) => Ok(id),
-      Err(err) => Err(err),
-    }
+    Self::start_consumer(consumer, dto);
+    self.persist_subscriptions();
+
+    Ok(id)
   }
 
-  fn spawn_consumer(request: GrpcSubscribeToKafkaRequest, dto: KafkaConsumerCreationDto) -> Result<(), KafkaError> {
-    let mut consumer = match Self::create_consumer(&request) {
+  // The consumer is started separately, so that pipelines of a restored subscription are added and restored
+  // from the checkpoint before the first message is consumed
+  fn create_subscription(
+    &self,
+    request: GrpcSubscribeToKafkaRequest,
+    id: Uuid,
+  ) -> Result<(BxesKafkaConsumer, KafkaConsumerCreationDto, Vec<PipelineCheckpoint>), KafkaError> {
+    let Some(metadata) = request.subscription_metadata.as_ref() else {
+      return Err(KafkaError::Subscription("Subscription metadata was not provided".to_string()));
+    };
+
+    let dto = self.create_kafka_creation_dto(id, metadata.subscription_name.clone().into());
+
+    let mut consumer = match Self::create_consumer(&request, &self.kafka_defaults.consumer) {
       Ok(consumer) => consumer,
       Err(err) => {
         error!("Failed to create kafka consumer: {}", err);
@@ -147,38 +251,56 @@ impl KafkaService {
       }
     };
 
-    match consumer.subscribe() {
-      Ok(_) => {
-        let mut map = dto.subscriptions_to_execution_requests.lock().expect("Must acquire lock");
-        map.insert(dto.uuid, KafkaSubscription::new(dto.name.clone()));
-      }
-      Err(err) => {
-        return match err {
-          BxesKafkaError::Kafka(err) => Err(err),
-          BxesKafkaError::Bxes(_) => Err(KafkaError::Subscription("Failed to subscribe".to_string())),
-        };
-      }
+    let pipelines_checkpoints = Self::restore_consumer_from_checkpoint(&mut consumer, &dto);
+
+    if let Err(err) = consumer.subscribe() {
+      return match err {
+        BxesKafkaError::Kafka(err) => Err(err),
+        _ => Err(KafkaError::Subscription("Failed to subscribe".to_string())),
+      };
     }
 
+    let mut map = dto.subscriptions_to_execution_requests.lock().expect("Must acquire lock");
+    map.insert(dto.uuid, KafkaSubscription::new(dto.name.clone(), request));
+    drop(map);
+
+    Ok((consumer, dto, pipelines_checkpoints))
+  }
+
+  fn start_consumer(mut consumer: BxesKafkaConsumer, dto: KafkaConsumerCreationDto) {
     tokio::spawn(async move {
       let handle = tokio::task::spawn_blocking(move || {
+        let mut last_lag_update = Instant::now();
+        let mut last_checkpoint = Instant::now();
+
         loop {
           let should_stop = Self::execute_consumer_routine(&mut consumer, &dto);
 
           if should_stop {
             consumer.unsubscribe();
+            dto.metrics.remove_kafka_consumer_lag(&dto.name);
             return;
           }
+
+          if last_lag_update.elapsed() >= CONSUMER_LAG_UPDATE_INTERVAL {
+            Self::update_consumer_lag(&consumer, &dto);
+            last_lag_update = Instant::now();
+          }
+
+          if Self::execute_checkpoint_routine(&consumer, &dto, last_checkpoint) {
+            last_checkpoint = Instant::now();
+          }
         }
       });
 
       handle.await
     });
-
-    Ok(())
   }
 
-  fn create_consumer(request: &GrpcSubscribeToKafkaRequest) -> Result<BxesKafkaConsumer, KafkaError> {
+  fn create_consumer(
+    request: &GrpcSubscribeToKafkaRequest,
+    default_settings: &HashMap<String, String>,
+  ) -> Result<BxesKafkaConsumer, KafkaError> {
     let Some(metadata) = request.connection_metadata.as_ref() else {
       const ERROR_MESSAGE: &str = "Kafka connection metadata was not provided";
       return Err(KafkaError::Subscription(ERROR_MESSAGE.to_string()));
@@ -186,6 +308,10 @@ impl KafkaService {
 
     let mut config = ClientConfig::new();
 
+    for (key, value) in default_settings {
+      config.set(key.to_owned(), value.to_owned());
+    }
+
     for metadata_pair in &metadata.metadata {
       config.set(metadata_pair.key.to_owned(), metadata_pair.value.to_owned());
     }
@@ -203,10 +329,12 @@ impl KafkaService {
     match consumer.consume() {
       Ok(trace) => {
         if let Some(trace) = trace {
+          dto.metrics.kafka_trace_consumed(&dto.name);
           Self::process_kafka_trace(trace, dto)
         }
       }
       Err(err) => {
+        dto.metrics.kafka_trace_failed(&dto.name);
         print!("Failed to read messages from kafka: {:?}", err)
       }
     };
@@ -214,6 +342,132 @@ impl KafkaService {
     false
   }
 
+  fn restore_consumer_from_checkpoint(consumer: &mut BxesKafkaConsumer, dto: &KafkaConsumerCreationDto) -> Vec<PipelineCheckpoint> {
+    let Some(store) = dto.checkpoints.as_ref() else {
+      return vec![];
+    };
+
+    let checkpoint = match store.load(dto.uuid) {
+      Ok(Some(checkpoint)) => checkpoint,
+      Ok(None) => return vec![],
+      Err(err) => {
+        error!("Failed to load checkpoint for subscription {}, error: {}", dto.name, err);
+        return vec![];
+      }
+    };
+
+    let offsets: Vec<BxesKafkaPartitionOffset> = checkpoint.offsets.iter().map(BxesKafkaPartitionOffset::from).collect();
+    let restore_result = consumer
+      .commit_offsets(&offsets)
+      .and_then(|_| consumer.restore_read_metadata(&checkpoint.read_metadata));
+
+    if let Err(err) = restore_result {
+      error!(
+        "Failed to restore consumer of subscription {} from checkpoint, error: {:?}",
+        dto.name, err
+      );
+      return vec![];
+    }
+
+    info!(
+      "Restored subscription {} from checkpoint created at {}",
+      dto.name, checkpoint.created_at
+    );
+
+    checkpoint.pipelines
+  }
+
+  fn restore_pipelines(&self, subscription_id: Uuid, checkpoints: Vec<PipelineCheckpoint>) {
+    let map = self.subscriptions_to_execution_requests.lock().expect("Must acquire lock");
+    let Some(subscription) = map.get(&subscription_id) else {
+      return;
+    };
+
+    for checkpoint in checkpoints {
+      let Some(pipeline) = subscription.pipelines.get(&checkpoint.pipeline_id) else {
+        warn!(
+          "Pipeline {} ({}) of subscription {} was not restored, its state is dropped",
+          checkpoint.name, checkpoint.pipeline_id, subscription_id
+        );
+        continue;
+      };
+
+      match pipeline.processor.restore(checkpoint.processor) {
+        Ok(()) => info!("Restored state of pipeline {} from checkpoint", pipeline.name),
+        Err(err) => error!("Failed to restore state of pipeline {}, error: {}", pipeline.name, err),
+      }
+    }
+  }
+
+  fn execute_checkpoint_routine(consumer: &BxesKafkaConsumer, dto: &KafkaConsumerCreationDto, last_checkpoint: Instant) -> bool {
+    let Some(store) = dto.checkpoints.as_ref() else {
+      return false;
+    };
+
+    let map = dto.subscriptions_to_execution_requests.lock().expect("Must acquire lock");
+    let Some(subscription) = map.get(&dto.uuid).cloned() else {
+      return false;
+    };
+
+    drop(map);
+
+    let requests: Vec<CheckpointRequest> = subscription
+      .checkpoint_requests
+      .lock()
+      .expect("Must acquire lock")
+      .drain(..)
+      .collect();
+    if requests.is_empty() && last_checkpoint.elapsed() < store.interval() {
+      return false;
+    }
+
+    let result = Self::create_checkpoint(consumer, dto.uuid, &subscription, store);
+    match result.as_ref() {
+      Ok(path) => info!("Saved checkpoint of subscription {} to {}", dto.name, path.display()),
+      Err(err) => error!("Failed to checkpoint subscription {}, error: {}", dto.name, err),
+    }
+
+    for request in requests {
+      let _ = request.send(result.as_ref().map(|path| path.to_owned()).map_err(|err| err.to_string()));
+    }
+
+    true
+  }
+
+  fn create_checkpoint(
+    consumer: &BxesKafkaConsumer,
+    subscription_id: Uuid,
+    subscription: &KafkaSubscription,
+    store: &CheckpointStore,
+  ) -> Result<PathBuf, CheckpointError> {
+    let mut pipelines = vec![];
+    for (pipeline_id, pipeline) in &subscription.pipelines {
+      pipelines.push(PipelineCheckpoint {
+        pipeline_id: *pipeline_id,
+        name: pipeline.name.clone(),
+        processor: pipeline.processor.snapshot()?,
+      });
+    }
+
+    let checkpoint = SubscriptionCheckpoint {
+      subscription_id,
+      subscription_name: subscription.name.to_string(),
+      created_at: Utc::now(),
+      offsets: consumer.positions()?.into_iter().map(CheckpointOffset::from).collect(),
+      read_metadata: consumer.read_metadata_snapshot()?,
+      pipelines,
+    };
+
+    store.save(&checkpoint)
+  }
+
+  fn update_consumer_lag(consumer: &BxesKafkaConsumer, dto: &KafkaConsumerCreationDto) {
+    match consumer.lag() {
+      Ok(lag) => dto.metrics.set_kafka_consumer_lag(&dto.name, lag),
+      Err(err) => warn!("Failed to get consumer lag for subscription {}, error: {:?}", dto.name, err),
+    }
+  }
+
   fn process_kafka_trace(trace: BxesKafkaTrace, dto: &KafkaConsumerCreationDto) {
     let map = dto.subscriptions_to_execution_requests.lock().expect("Must acquire lock");
     let Some(kafka_subscription) = map.get(&dto.uuid).cloned() else {
@@ -223,17 +477,21 @@ impl KafkaService {
     drop(map);
 
     for (pipeline_id, pipeline) in &kafka_subscription.pipelines {
-      let trace = trace.clone();
       let Ok(metadata) = ExtractedTraceMetadata::create_from(trace.metadata()) else {
         continue;
       };
 
+      let case_name_parts_joined = metadata.case.case_name_parts_joined.clone();
+
       let execution_dto = PipelineExecutionDto::new(
         Arc::new(PipelineParts::new()),
         Arc::new(EmptyPipelineEventsHandler::new()) as Arc<dyn PipelineEventsHandler>,
       );
 
-      let trace_processing_context = KafkaTraceProcessingContext { execution_dto, trace };
+      let trace_processing_context = KafkaTraceProcessingContext {
+        execution_dto,
+        trace: trace.clone(),
+      };
 
       match pipeline.processor.observe(trace_processing_context) {
         Ok(..) => {
@@ -250,12 +508,45 @@ impl KafkaService {
             },
           };
 
+          for deviation in pipeline.processor.take_deviations() {
+            let alert = ConformanceAlert {
+              process_case_metadata: metadata.clone(),
+              deviation,
+            };
+
+            pipeline
+              .execution_dto
+              .events_handler
+              .handle(&PipelineEvent::ConformanceAlert(alert));
+          }
+
+          let completed_cases = pipeline.processor.take_completed_cases();
+          let case_completed = is_case_completed(trace.metadata()) || !completed_cases.is_empty();
+
+          for completed_case in completed_cases {
+            let event = CaseCompletedEvent {
+              process_case_metadata: metadata.clone(),
+              completed_case,
+            };
+
+            pipeline.execution_dto.events_handler.handle(&PipelineEvent::CaseCompleted(event));
+          }
+
+          if !pipeline.should_fire(&trace, case_completed) {
+            continue;
+          }
+
           pipeline
             .execution_dto
             .events_handler
             .handle(&PipelineEvent::ProcessCaseMetadata(metadata));
+
+          if pipeline.trigger.is_some() {
+            Self::execute_triggered_pipeline(&kafka_subscription, *pipeline_id, pipeline, case_name_parts_joined.as_ref(), dto);
+          }
         }
         Err(err) => {
+          dto.metrics.kafka_trace_failed(&dto.name);
           let message = format!("Failed to get update result, err: {}", err);
           dto.logger.handle(&message).expect("Must log message");
         }
@@ -263,6 +554,53 @@ impl KafkaService {
     }
   }
 
+  fn execute_triggered_pipeline(
+    subscription: &KafkaSubscription,
+    pipeline_id: Uuid,
+    pipeline: &KafkaSubscriptionPipeline,
+    case_name: &str,
+    dto: &KafkaConsumerCreationDto,
+  ) {
+    let context =
+      Self::create_pipeline_execution_context(&pipeline.request, &pipeline.execution_dto).with_metrics(Some(dto.metrics.clone()));
+
+    let result = context.execute_grpc_pipeline(|context| {
+      Self::fill_kafka_pipeline_context(context, dto.uuid, subscription, pipeline_id, pipeline, case_name);
+      Ok(())
+    });
+
+    let final_result = match result {
+      Ok((uuid, _)) => PipelineFinalResult::Success(uuid),
+      Err(err) => {
+        let message = format!("Failed to execute triggered pipeline {}, err: {}", pipeline.name, err);
+        dto.logger.handle(&message).expect("Must log message");
+
+        PipelineFinalResult::Error(err.to_string())
+      }
+    };
+
+    pipeline
+      .execution_dto
+      .events_handler
+      .handle(&PipelineEvent::FinalResult(final_result));
+  }
+
+  fn fill_kafka_pipeline_context(
+    context: &mut PipelineContext,
+    subscription_id: Uuid,
+    subscription: &KafkaSubscription,
+    pipeline_id: Uuid,
+    pipeline: &KafkaSubscriptionPipeline,
+    case_name: &str,
+  ) {
+    pipeline.processor.fill_pipeline_context(context, case_name);
+
+    context.put_concrete(SUBSCRIPTION_ID_KEY.key(), subscription_id);
+    context.put_concrete(PIPELINE_ID_KEY.key(), pipeline_id);
+    context.put_concrete(SUBSCRIPTION_NAME_KEY.key(), subscription.name.as_ref().into());
+    context.put_concrete(PIPELINE_NAME_KEY.key(), pipeline.name.clone().into());
+  }
+
   pub(super) fn get_context_values(
     &self,
     sub_id: Uuid,
@@ -285,17 +623,11 @@ impl KafkaService {
 
     let handler = handler as Arc<dyn PipelineEventsHandler>;
     let execution_dto = PipelineExecutionDto::new(Arc::new(PipelineParts::new()), handler);
-    let context = Self::create_pipeline_execution_context(&pipeline.request, &execution_dto);
+    let context = Self::create_pipeline_execution_context(&pipeline.request, &execution_dto).with_metrics(Some(self.metrics.clone()));
 
     let result = context.execute_grpc_pipeline_and_fill_context_values(
       |context| {
-        pipeline.processor.fill_pipeline_context(context, case_name);
-
-        context.put_concrete(SUBSCRIPTION_ID_KEY.key(), sub_id);
-        context.put_concrete(PIPELINE_ID_KEY.key(), pipeline_id);
-        context.put_concrete(SUBSCRIPTION_NAME_KEY.key(), kafka_subscription.name.as_ref().into());
-        context.put_concrete(PIPELINE_NAME_KEY.key(), pipeline.name.clone().into());
-
+        Self::fill_kafka_pipeline_context(context, sub_id, &kafka_subscription, pipeline_id, pipeline, case_name);
         Ok(())
       },
       self.cv_service.clone(),
@@ -305,21 +637,88 @@ impl KafkaService {
 
     result.map_err(|err| Status::internal(format!("Failed to execute pipeline, err: {}", err)))
   }
+
+  pub(super) fn replay_log(&self, request: &GrpcReplayLogRequest, handler: Arc<GrpcPipelineEventsHandler>) -> Result<Uuid, Status> {
+    let Some(pipeline_request) = request.pipeline_request.as_ref() else {
+      return Err(Status::invalid_argument("Pipeline request must be provided"));
+    };
+
+    let streaming_config = request.streaming_configuration.clone().unwrap_or_default();
+    let replayer = StreamReplayer::new(&request.log_path, &streaming_config, request.clock_configuration.as_ref())
+      .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+    let handler = handler as Arc<dyn PipelineEventsHandler>;
+    let execution_dto = PipelineExecutionDto::new(self.pipeline_parts.clone(), handler);
+
+    let statistics = replayer.replay(&execution_dto);
+    if statistics.failed_events > 0 {
+      warn!(
+        "{} of {} replayed events failed to be observed",
+        statistics.failed_events, statistics.observed_events
+      );
+    }
+
+    let context = Self::create_pipeline_execution_context(pipeline_request, &execution_dto).with_metrics(Some(self.metrics.clone()));
+    let result = context.execute_grpc_pipeline_and_fill_context_values(
+      |context| {
+        replayer.fill_pipeline_context(context);
+        Ok(())
+      },
+      self.cv_service.clone(),
+    );
+
+    result.map_err(|err| Status::internal(format!("Failed to execute pipeline, err: {}", err)))
+  }
 }
 
 impl KafkaService {
+  pub fn add_kafka_pipeline(&self, request: GrpcAddPipelineRequest) -> Result<Uuid, Status> {
+    let subscription_id = request.to_dto().subscription_id;
+    let pipeline_id = self.add_kafka_pipeline_with_id(subscription_id, Uuid::new_v4(), request)?;
+    self.persist_subscriptions();
+
+    Ok(pipeline_id)
+  }
+
+  fn add_kafka_pipeline_with_id(
+    &self,
+    subscription_id: Uuid,
+    pipeline_id: Uuid,
+    mut request: GrpcAddPipelineRequest,
+  ) -> Result<Uuid, Status> {
+    let handler = self.create_kafka_events_handler(request.producer_kafka_metadata.as_ref())?;
+
+    if let Some(pipeline_request) = request.pipeline_request.as_mut() {
+      pipeline_request.subscription_id = Some(GrpcGuid::from(subscription_id));
+    }
+
+    let dto = request.to_dto();
+    let mut kafka_pipeline = self.create_kafka_pipeline(dto.request, handler, dto.name, &dto.streaming_configuration)?;
+    kafka_pipeline.definition = Some(request);
+
+    self.insert_kafka_pipeline(subscription_id, pipeline_id, kafka_pipeline);
+
+    Ok(pipeline_id)
+  }
+
   pub fn add_execution_request<T: PipelineEventsHandler + 'static>(
     &self,
     subscription_id: Uuid,
     handler: T,
     request: GrpcPipelineExecutionRequest,
-    streaming_config: GrpcPipelineStreamingConfiguration,
+    grpc_streaming_config: GrpcPipelineStreamingConfiguration,
     pipeline_name: String,
-  ) -> Uuid {
-    let mut map = self.subscriptions_to_execution_requests.lock().expect("Must acquire lock");
+  ) -> Result<Uuid, Status> {
     let pipeline_id = Uuid::new_v4();
-    let streaming_config = StreamingConfiguration::new(&streaming_config).unwrap_or(StreamingConfiguration::NotSpecified);
-    let kafka_pipeline = self.create_kafka_pipeline(request, handler, pipeline_name, streaming_config);
+    let kafka_pipeline = self.create_kafka_pipeline(request, handler, pipeline_name, &grpc_streaming_config)?;
+
+    self.insert_kafka_pipeline(subscription_id, pipeline_id, kafka_pipeline);
+
+    Ok(pipeline_id)
+  }
+
+  fn insert_kafka_pipeline(&self, subscription_id: Uuid, pipeline_id: Uuid, kafka_pipeline: KafkaSubscriptionPipeline) {
+    let mut map = self.subscriptions_to_execution_requests.lock().expect("Must acquire lock");
 
     match map.get_mut(&subscription_id) {
       None => {
@@ -329,8 +728,6 @@ impl KafkaService {
         subscription.pipelines.insert(pipeline_id, kafka_pipeline);
       }
     }
-
-    pipeline_id
   }
 
   fn create_kafka_pipeline<T: PipelineEventsHandler + 'static>(
@@ -338,11 +735,21 @@ impl KafkaService {
     request: GrpcPipelineExecutionRequest,
     handler: T,
     pipeline_name: String,
-    streaming_config: StreamingConfiguration,
-  ) -> KafkaSubscriptionPipeline {
+    grpc_streaming_config: &GrpcPipelineStreamingConfiguration,
+  ) -> Result<KafkaSubscriptionPipeline, Status> {
+    let time = StreamingConfiguration::create_streaming_time(grpc_streaming_config);
+    let streaming_config = StreamingConfiguration::new(grpc_streaming_config).map_err(|err| Status::invalid_argument(err.to_string()))?;
+    let trigger_policy = StreamingConfiguration::create_trigger_policy(grpc_streaming_config);
+    if let Some(policy) = trigger_policy.as_ref() {
+      info!("Pipeline {} will be executed with trigger policy {}", pipeline_name, policy);
+    }
+
     let handler = Arc::new(handler) as Arc<dyn PipelineEventsHandler>;
     let dto = PipelineExecutionDto::new(self.pipeline_parts.clone(), handler);
-    KafkaSubscriptionPipeline::new(request, dto, pipeline_name, streaming_config.create_processor())
+    Ok(
+      KafkaSubscriptionPipeline::new(request, dto, pipeline_name, streaming_config.create_processor(time))
+        .with_trigger_policy(trigger_policy),
+    )
   }
 
   pub fn remove_execution_request(&self, subscription_id: &Uuid, pipeline_id: &Uuid) {
@@ -350,6 +757,9 @@ impl KafkaService {
     if let Some(map) = map.get_mut(subscription_id) {
       map.pipelines.remove(pipeline_id);
     }
+
+    drop(map);
+    self.persist_subscriptions();
   }
 
   pub fn remove_all_execution_requests(&self, subscription_id: &Uuid) {
@@ -357,6 +767,31 @@ impl KafkaService {
     if let Some(map) = map.get_mut(subscription_id) {
       map.pipelines.clear();
     }
+
+    drop(map);
+    self.persist_subscriptions();
+  }
+
+  pub async fn checkpoint_subscription(&self, subscription_id: Uuid) -> Result<PathBuf, CheckpointError> {
+    if self.checkpoints.is_none() {
+      return Err(CheckpointError::Disabled);
+    }
+
+    let (sender, receiver) = oneshot::channel();
+
+    {
+      let map = self.subscriptions_to_execution_requests.lock().expect("Must acquire lock");
+      let Some(subscription) = map.get(&subscription_id) else {
+        return Err(CheckpointError::SubscriptionNotFound(subscription_id));
+      };
+
+      subscription.checkpoint_requests.lock().expect("Must acquire lock").push(sender);
+    }
+
+    match receiver.await {
+      Ok(result) => result.map_err(CheckpointError::Failed),
+      Err(_) => Err(CheckpointError::ConsumerStopped),
+    }
   }
 
   pub fn get_all_subscriptions(&self) -> Vec<(Uuid, KafkaSubscription)> {
@@ -366,8 +801,117 @@ impl KafkaService {
 }
 
 impl KafkaService {
-  fn create_kafka_creation_dto(&self, name: Arc<str>) -> KafkaConsumerCreationDto {
-    KafkaConsumerCreationDto::new(name, self.subscriptions_to_execution_requests.clone())
+  pub fn export_subscriptions(&self) -> GrpcKafkaSubscriptionsSet {
+    let map = self.subscriptions_to_execution_requests.lock().expect("Must acquire lock");
+
+    GrpcKafkaSubscriptionsSet {
+      subscriptions: map.iter().map(|(id, subscription)| subscription.to_persisted(*id)).collect(),
+    }
+  }
+
+  /// Re-creates subscriptions from the exported set, imported subscriptions and pipelines get new ids.
+  pub fn import_subscriptions(&self, bytes: &[u8]) -> Result<Vec<GrpcKafkaResult>, SubscriptionsStoreError> {
+    let subscriptions = decode_subscriptions(bytes)?;
+
+    let results = subscriptions
+      .subscriptions
+      .into_iter()
+      .map(|subscription| match self.create_persisted_subscription(subscription, false) {
+        Ok(id) => GrpcKafkaResult::success(id),
+        Err(error_message) => GrpcKafkaResult {
+          result: Some(grpc_kafka_result::Result::Failure(GrpcKafkaFailedResult { error_message })),
+        },
+      })
+      .collect();
+
+    self.persist_subscriptions();
+
+    Ok(results)
+  }
+
+  pub fn restore_persisted_subscriptions(&self) {
+    let Some(store) = self.subscriptions_store.as_ref() else {
+      return;
+    };
+
+    let subscriptions = match store.load() {
+      Ok(subscriptions) => subscriptions,
+      Err(err) => {
+        error!("Failed to load persisted kafka subscriptions, error: {}", err);
+        return;
+      }
+    };
+
+    for subscription in subscriptions.subscriptions {
+      match self.create_persisted_subscription(subscription, true) {
+        Ok(id) => info!("Restored kafka subscription {}", id),
+        Err(err) => error!("Failed to restore kafka subscription, error: {}", err),
+      }
+    }
+  }
+
+  fn create_persisted_subscription(&self, subscription: GrpcPersistedKafkaSubscription, preserve_ids: bool) -> Result<Uuid, String> {
+    let Some(subscribe_request) = subscription.subscribe_request else {
+      return Err("Subscribe request was not provided".to_string());
+    };
+
+    let subscription_id = Self::persisted_id(subscription.subscription_id.as_ref(), preserve_ids);
+    let (consumer, dto, pipelines_checkpoints) = self
+      .create_subscription(subscribe_request, subscription_id)
+      .map_err(|err| err.to_string())?;
+
+    for pipeline in subscription.pipelines {
+      let Some(add_pipeline_request) = pipeline.add_pipeline_request else {
+        warn!(
+          "Add pipeline request was not provided for pipeline of subscription {}",
+          subscription_id
+        );
+        continue;
+      };
+
+      let pipeline_id = Self::persisted_id(pipeline.pipeline_id.as_ref(), preserve_ids);
+      if let Err(err) = self.add_kafka_pipeline_with_id(subscription_id, pipeline_id, add_pipeline_request) {
+        error!(
+          "Failed to add pipeline to subscription {}, error: {}",
+          subscription_id,
+          err.message()
+        );
+      }
+    }
+
+    self.restore_pipelines(subscription_id, pipelines_checkpoints);
+    Self::start_consumer(consumer, dto);
+
+    Ok(subscription_id)
+  }
+
+  fn persisted_id(id: Option<&GrpcGuid>, preserve_ids: bool) -> Uuid {
+    match id {
+      Some(id) if preserve_ids => id.to_uuid().unwrap_or_else(|_| Uuid::new_v4()),
+      _ => Uuid::new_v4(),
+    }
+  }
+
+  fn persist_subscriptions(&self) {
+    let Some(store) = self.subscriptions_store.as_ref() else {
+      return;
+    };
+
+    if let Err(err) = store.save(|| self.export_subscriptions()) {
+      error!("Failed to persist kafka subscriptions, error: {}", err);
+    }
+  }
+}
+
+impl KafkaService {
+  fn create_kafka_creation_dto(&self, id: Uuid, name: Arc<str>) -> KafkaConsumerCreationDto {
+    KafkaConsumerCreationDto::new(
+      id,
+      name,
+      self.subscriptions_to_execution_requests.clone(),
+      self.metrics.clone(),
+      self.checkpoints.clone(),
+    )
   }
 
   pub(super) fn create_pipeline_execution_context_from_proxy<'a>(
@@ -392,15 +936,20 @@ impl KafkaService {
     )
   }
 
-  pub(super) fn create_kafka_events_handler(producer_metadata: Option<&GrpcKafkaConnectionMetadata>) -> Result<KafkaEventsHandler, Status> {
+  pub(super) fn create_kafka_events_handler(
+    &self,
+    producer_metadata: Option<&GrpcKafkaConnectionMetadata>,
+  ) -> Result<KafkaEventsHandler, Status> {
     producer_metadata
       .ok_or_else(|| Status::invalid_argument("Producer metadata must be provided"))
-      .and_then(|metadata| match PipelineEventsProducer::create(metadata) {
-        Ok(producer) => Ok(KafkaEventsHandler::new(producer)),
-        Err(err) => {
-          let message = format!("Failed to create producer: {}", err);
-          Err(Status::invalid_argument(message))
-        }
-      })
+      .and_then(
+        |metadata| match PipelineEventsProducer::create(metadata, &self.kafka_defaults.producer) {
+          Ok(producer) => Ok(KafkaEventsHandler::new(producer)),
+          Err(err) => {
+            let message = format!("Failed to create producer: {}", err);
+            Err(Status::invalid_argument(message))
+          }
+        },
+      )
   }
 }
@@ -1,4 +1,6 @@
+pub mod checkpoints;
 pub mod grpc_kafka_service;
 pub mod kafka_service;
 pub mod models;
 pub mod streaming;
+pub mod subscriptions_store;
@@ -1,5 +1,8 @@
 use crate::grpc::{
-  events::events_handler::PipelineEventsHandler, kafka::kafka_service::KafkaSubscription, logs_handler::ConsoleLogMessageHandler,
+  events::events_handler::PipelineEventsHandler,
+  kafka::{checkpoints::CheckpointStore, kafka_service::KafkaSubscription},
+  logs_handler::ConsoleLogMessageHandler,
+  metrics::BackendMetrics,
 };
 use bxes::models::domain::bxes_value::BxesValue;
 use ficus::{
@@ -13,6 +16,7 @@ use ficus::{
   },
   utils::user_data::user_data::UserData,
 };
+use serde::{Deserialize, Serialize};
 use std::{
   collections::HashMap,
   fmt::{Debug, Display, Formatter},
@@ -26,11 +30,13 @@ pub(super) const KAFKA_CASE_ID: &str = "case_id";
 pub(super) const KAFKA_CASE_NAME_PARTS_SEPARATOR: &str = ";";
 pub(super) const KAFKA_PROCESS_NAME: &str = "process_name";
 pub(super) const KAFKA_TRACE_ID: &str = "trace_id";
+pub(super) const KAFKA_CASE_COMPLETED: &str = "case_completed";
 
 #[derive(Debug)]
 pub enum KafkaTraceProcessingError {
   XesFromBxesTraceCreationError(XesFromBxesKafkaTraceCreatingError),
   FailedToPreprocessTrace(PipelinePartExecutionError),
+  FailedToCreateReferenceModel(String),
 }
 
 impl Display for KafkaTraceProcessingError {
@@ -38,6 +44,7 @@ impl Display for KafkaTraceProcessingError {
     match self {
       KafkaTraceProcessingError::XesFromBxesTraceCreationError(e) => Display::fmt(e, f),
       KafkaTraceProcessingError::FailedToPreprocessTrace(e) => Display::fmt(e, f),
+      KafkaTraceProcessingError::FailedToCreateReferenceModel(message) => write!(f, "Failed to create reference model: {}", message),
     }
   }
 }
@@ -88,19 +95,30 @@ pub struct KafkaConsumerCreationDto {
   pub name: Arc<str>,
   pub subscriptions_to_execution_requests: Arc<Mutex<HashMap<Uuid, KafkaSubscription>>>,
   pub logger: ConsoleLogMessageHandler,
+  pub metrics: Arc<BackendMetrics>,
+  pub checkpoints: Option<Arc<CheckpointStore>>,
 }
 
 impl KafkaConsumerCreationDto {
-  pub fn new(name: Arc<str>, subscriptions_to_execution_requests: Arc<Mutex<HashMap<Uuid, KafkaSubscription>>>) -> Self {
+  pub fn new(
+    uuid: Uuid,
+    name: Arc<str>,
+    subscriptions_to_execution_requests: Arc<Mutex<HashMap<Uuid, KafkaSubscription>>>,
+    metrics: Arc<BackendMetrics>,
+    checkpoints: Option<Arc<CheckpointStore>>,
+  ) -> Self {
     Self {
-      uuid: Uuid::new_v4(),
+      uuid,
       name,
       subscriptions_to_execution_requests,
       logger: ConsoleLogMessageHandler::new(),
+      metrics,
+      checkpoints,
     }
   }
 }
 
+#[derive(Serialize, Deserialize)]
 pub(super) struct ExtractedTraceMetadata {
   pub process: ProcessMetadata,
   pub case: CaseMetadata,
@@ -129,6 +147,7 @@ impl ExtractedTraceMetadata {
   }
 }
 
+#[derive(Serialize, Deserialize)]
 pub(super) struct ProcessMetadata {
   pub process_name: Arc<str>,
 }
@@ -141,6 +160,7 @@ impl ProcessMetadata {
   }
 }
 
+#[derive(Serialize, Deserialize)]
 pub(super) struct CaseMetadata {
   pub case_id: Uuid,
   pub case_display_name: Arc<str>,
@@ -197,6 +217,12 @@ pub(super) fn uuid_or_err(metadata: &HashMap<Arc<str>, Arc<BxesValue>>, key: &st
   }
 }
 
+pub(super) fn is_case_completed(metadata: &HashMap<Arc<str>, Arc<BxesValue>>) -> bool {
+  metadata
+    .get(KAFKA_CASE_COMPLETED)
+    .is_some_and(|value| matches!(value.as_ref(), BxesValue::Bool(true)))
+}
+
 fn metadata_to_string_string_pairs(metadata: &HashMap<Arc<str>, Arc<BxesValue>>) -> Vec<(Arc<str>, Arc<str>)> {
   metadata
     .iter()
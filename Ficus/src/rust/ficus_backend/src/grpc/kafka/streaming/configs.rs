@@ -1,46 +1,105 @@
 use crate::{
-  ficus_proto::GrpcPipelineStreamingConfiguration,
+  ficus_proto::{GrpcPipelineStreamingConfiguration, GrpcPipelineTriggerPolicy, GrpcStreamingTimeMode, grpc_pipeline_trigger_policy},
   grpc::kafka::streaming::{
+    conformance::configs::ConformanceStreamingConfiguration,
     processors::TracesProcessor,
     t1::{configs::T1StreamingConfiguration, processors::T1StreamingProcessor},
     t2::configs::T2StreamingConfiguration,
   },
 };
-use ficus::features::streaming::t1::{
-  configs::TracesQueueConfiguration,
-  filterers::{T1LogFilterer, TracesQueueFiltererImpl},
+use chrono::Duration;
+use ficus::features::streaming::{
+  counters::count_min::CountMinSketchCreationError,
+  t1::{
+    configs::TracesQueueConfiguration,
+    filterers::{T1LogFilterer, TracesQueueFiltererImpl},
+  },
+  time::{StreamingClock, StreamingTime, SystemClock},
+  triggers::TriggerPolicy,
+};
+use std::{
+  fmt::{Display, Formatter},
+  sync::Arc,
 };
 
 type StreamingConfigurationEnum = crate::ficus_proto::grpc_pipeline_streaming_configuration::Configuration;
 
+#[derive(Debug)]
+pub enum StreamingConfigurationError {
+  InvalidCountMinSketch(CountMinSketchCreationError),
+}
+
+impl Display for StreamingConfigurationError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      StreamingConfigurationError::InvalidCountMinSketch(err) => write!(f, "Invalid count-min sketch configuration: {}", err),
+    }
+  }
+}
+
 pub enum StreamingConfiguration {
   NotSpecified,
   T1(T1StreamingConfiguration),
   T2(T2StreamingConfiguration),
+  Conformance(ConformanceStreamingConfiguration),
 }
 
 impl StreamingConfiguration {
-  pub fn new(grpc_config: &GrpcPipelineStreamingConfiguration) -> Option<Self> {
-    match grpc_config.configuration.as_ref() {
-      None => None,
-      Some(c) => match c {
-        StreamingConfigurationEnum::NotSpecified(_) => Some(StreamingConfiguration::NotSpecified),
-        StreamingConfigurationEnum::T1Configuration(t1) => T1StreamingConfiguration::new(t1).map(StreamingConfiguration::T1),
-        StreamingConfigurationEnum::T2Configuration(t2) => T2StreamingConfiguration::new(t2).map(StreamingConfiguration::T2),
-      },
+  pub fn new(grpc_config: &GrpcPipelineStreamingConfiguration) -> Result<Self, StreamingConfigurationError> {
+    let config = match grpc_config.configuration.as_ref() {
+      None | Some(StreamingConfigurationEnum::NotSpecified(_)) => None,
+      Some(StreamingConfigurationEnum::T1Configuration(t1)) => T1StreamingConfiguration::new(t1).map(StreamingConfiguration::T1),
+      Some(StreamingConfigurationEnum::T2Configuration(t2)) => T2StreamingConfiguration::new(t2)?.map(StreamingConfiguration::T2),
+      Some(StreamingConfigurationEnum::ConformanceConfiguration(c)) => {
+        ConformanceStreamingConfiguration::new(c).map(StreamingConfiguration::Conformance)
+      }
+    };
+
+    Ok(config.unwrap_or(StreamingConfiguration::NotSpecified))
+  }
+
+  pub fn create_streaming_time(grpc_config: &GrpcPipelineStreamingConfiguration) -> StreamingTime {
+    Self::create_streaming_time_with_clock(grpc_config, Arc::new(SystemClock))
+  }
+
+  pub fn create_streaming_time_with_clock(
+    grpc_config: &GrpcPipelineStreamingConfiguration,
+    clock: Arc<dyn StreamingClock>,
+  ) -> StreamingTime {
+    match grpc_config.time_configuration.as_ref() {
+      Some(time_config) if time_config.mode() == GrpcStreamingTimeMode::EventTime => {
+        StreamingTime::event_time(Duration::milliseconds(time_config.allowed_lateness_ms.max(0)))
+      }
+      _ => StreamingTime::processing_time(clock),
+    }
+  }
+
+  pub fn create_trigger_policy(grpc_config: &GrpcPipelineStreamingConfiguration) -> Option<TriggerPolicy> {
+    grpc_config.trigger_policy.as_ref().and_then(Self::convert_trigger_policy)
+  }
+
+  fn convert_trigger_policy(grpc_policy: &GrpcPipelineTriggerPolicy) -> Option<TriggerPolicy> {
+    match grpc_policy.policy.as_ref()? {
+      grpc_pipeline_trigger_policy::Policy::EveryTraces(count) => Some(TriggerPolicy::EveryTraces(*count)),
+      grpc_pipeline_trigger_policy::Policy::EventTimeIntervalMs(interval_ms) => {
+        Some(TriggerPolicy::EventTimeInterval(Duration::milliseconds((*interval_ms).max(0))))
+      }
+      grpc_pipeline_trigger_policy::Policy::CaseCompletion(_) => Some(TriggerPolicy::CaseCompletion),
+      grpc_pipeline_trigger_policy::Policy::SignificantChange(_) => Some(TriggerPolicy::SignificantChange),
     }
   }
 
-  pub fn create_processor(&self) -> TracesProcessor {
+  pub fn create_processor(&self, time: StreamingTime) -> TracesProcessor {
     match self {
-      StreamingConfiguration::NotSpecified => Self::create_default_processor(),
-      StreamingConfiguration::T1(c) => TracesProcessor::T1(c.create_processor()),
-      StreamingConfiguration::T2(c) => TracesProcessor::T2(c.create_processor()),
+      StreamingConfiguration::NotSpecified => Self::create_default_processor(time),
+      StreamingConfiguration::T1(c) => TracesProcessor::T1(c.create_processor(time)),
+      StreamingConfiguration::T2(c) => TracesProcessor::T2(c.create_processor(time)),
+      StreamingConfiguration::Conformance(c) => TracesProcessor::Conformance(c.create_processor(time)),
     }
   }
 
-  fn create_default_processor() -> TracesProcessor {
+  fn create_default_processor(time: StreamingTime) -> TracesProcessor {
     let filterer = TracesQueueFiltererImpl::new(TracesQueueConfiguration::new(1_000));
-    TracesProcessor::T1(T1StreamingProcessor::new(T1LogFilterer::TracesQueueFilterer(filterer)))
+    TracesProcessor::T1(T1StreamingProcessor::new(T1LogFilterer::TracesQueueFilterer(filterer), time))
   }
 }
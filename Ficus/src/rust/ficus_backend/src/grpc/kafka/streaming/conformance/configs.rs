@@ -0,0 +1,48 @@
+use crate::{
+  ficus_proto::{GrpcConformanceReferenceModelKind, GrpcConformanceStreamingConfiguration, GrpcPipeline},
+  grpc::kafka::streaming::conformance::processors::{ConformanceStreamingProcessor, ReferenceModelKind},
+};
+use chrono::Duration;
+use ficus::features::streaming::time::StreamingTime;
+
+const DEFAULT_MAX_CASES: usize = 100_000;
+
+pub struct ConformanceStreamingConfiguration {
+  reference_model_pipeline: GrpcPipeline,
+  reference_model_kind: ReferenceModelKind,
+  cost_threshold: f64,
+  case_inactivity_timeout: Option<Duration>,
+  max_cases: usize,
+}
+
+impl ConformanceStreamingConfiguration {
+  pub fn new(grpc_config: &GrpcConformanceStreamingConfiguration) -> Option<Self> {
+    let reference_model_kind = match grpc_config.reference_model_kind() {
+      GrpcConformanceReferenceModelKind::PetriNet => ReferenceModelKind::PetriNet,
+      GrpcConformanceReferenceModelKind::Dfg => ReferenceModelKind::Dfg,
+    };
+
+    grpc_config.reference_model_pipeline.as_ref().map(|pipeline| Self {
+      reference_model_pipeline: pipeline.clone(),
+      reference_model_kind,
+      cost_threshold: grpc_config.cost_threshold.max(0.),
+      case_inactivity_timeout: grpc_config
+        .case_inactivity_timeout_ms
+        .map(|timeout_ms| Duration::milliseconds(timeout_ms.max(0))),
+      max_cases: grpc_config
+        .max_cases
+        .map_or(DEFAULT_MAX_CASES, |max_cases| max_cases.max(1) as usize),
+    })
+  }
+
+  pub fn create_processor(&self, time: StreamingTime) -> ConformanceStreamingProcessor {
+    ConformanceStreamingProcessor::new(
+      self.reference_model_pipeline.clone(),
+      self.reference_model_kind,
+      self.cost_threshold,
+      time,
+    )
+    .with_max_cases(self.max_cases)
+    .with_case_inactivity_timeout(self.case_inactivity_timeout)
+  }
+}
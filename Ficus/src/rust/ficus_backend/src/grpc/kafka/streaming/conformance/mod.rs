@@ -0,0 +1,2 @@
+pub mod configs;
+pub mod processors;
@@ -0,0 +1,343 @@
+use crate::{
+  ficus_proto::GrpcPipeline,
+  grpc::{
+    kafka::{
+      checkpoints::CheckpointError,
+      models::{CaseMetadata, KafkaTraceProcessingError, XesFromBxesKafkaTraceCreatingError, is_case_completed},
+      streaming::processors::KafkaTraceProcessingContext,
+    },
+    pipeline_executor::ServicePipelineExecutionContext,
+  },
+};
+use chrono::{DateTime, Duration, Utc};
+use ficus::{
+  event_log::{
+    bxes::bxes_to_xes_converter::read_bxes_events,
+    core::{event::event::Event, trace::trace::Trace},
+  },
+  features::streaming::{
+    conformance::{
+      core::{CaseConformance, ConformanceCheckerSnapshot, ConformanceReport, OnlineConformanceChecker, OnlineConformanceModel},
+      dfg::{DfgConformanceModel, DfgReplayState},
+      token_replay::{TokenReplayConformanceModel, TokenReplayState},
+    },
+    time::{StreamingTime, StreamingTimeMode},
+  },
+  pipelines::{
+    context::{PipelineContext, PipelineInfrastructure},
+    keys::context_keys::{CONFORMANCE_REPORT_KEY, GRAPH_KEY, PETRI_NET_KEY},
+    pipelines::PipelinePart,
+  },
+  utils::user_data::{keys::Key, user_data::UserData},
+};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug)]
+pub enum ReferenceModelKind {
+  PetriNet,
+  Dfg,
+}
+
+/// Produced once for the event which made the cost of the case exceed the threshold.
+#[derive(Clone, Debug)]
+pub struct CaseDeviation {
+  pub case_id: Uuid,
+  pub activity: String,
+  pub conformance: CaseConformance,
+  pub cost_threshold: f64,
+}
+
+enum ConformanceChecker {
+  PetriNet(OnlineConformanceChecker<Uuid, TokenReplayConformanceModel>),
+  Dfg(OnlineConformanceChecker<Uuid, DfgConformanceModel>),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+enum ConformanceCasesSnapshot {
+  PetriNet(ConformanceCheckerSnapshot<Uuid, TokenReplayState>),
+  Dfg(ConformanceCheckerSnapshot<Uuid, DfgReplayState>),
+}
+
+impl ConformanceChecker {
+  fn observe(&mut self, case_id: Uuid, activity: &str, stamp: DateTime<Utc>, deviations: &mut Vec<CaseDeviation>) {
+    let (observation, cost_threshold) = match self {
+      ConformanceChecker::PetriNet(checker) => (checker.observe_at(case_id, activity, stamp), checker.cost_threshold()),
+      ConformanceChecker::Dfg(checker) => (checker.observe_at(case_id, activity, stamp), checker.cost_threshold()),
+    };
+
+    if observation.threshold_exceeded {
+      deviations.push(CaseDeviation {
+        case_id,
+        activity: activity.to_owned(),
+        conformance: observation.case,
+        cost_threshold,
+      });
+    }
+  }
+
+  fn remove_case(&mut self, case_id: &Uuid) {
+    match self {
+      ConformanceChecker::PetriNet(checker) => checker.remove_case(case_id).map(|_| ()),
+      ConformanceChecker::Dfg(checker) => checker.remove_case(case_id).map(|_| ()),
+    };
+  }
+
+  fn evict_inactive_cases(&mut self, now: DateTime<Utc>) -> usize {
+    match self {
+      ConformanceChecker::PetriNet(checker) => checker.evict_inactive_cases(now).len(),
+      ConformanceChecker::Dfg(checker) => checker.evict_inactive_cases(now).len(),
+    }
+  }
+
+  fn report(&self) -> ConformanceReport {
+    match self {
+      ConformanceChecker::PetriNet(checker) => checker.report(),
+      ConformanceChecker::Dfg(checker) => checker.report(),
+    }
+  }
+
+  fn snapshot(&self) -> ConformanceCasesSnapshot {
+    match self {
+      ConformanceChecker::PetriNet(checker) => ConformanceCasesSnapshot::PetriNet(checker.snapshot()),
+      ConformanceChecker::Dfg(checker) => ConformanceCasesSnapshot::Dfg(checker.snapshot()),
+    }
+  }
+
+  fn restore(&mut self, snapshot: ConformanceCasesSnapshot) -> Result<(), CheckpointError> {
+    match (self, snapshot) {
+      (ConformanceChecker::PetriNet(checker), ConformanceCasesSnapshot::PetriNet(snapshot)) => checker.restore(snapshot),
+      (ConformanceChecker::Dfg(checker), ConformanceCasesSnapshot::Dfg(snapshot)) => checker.restore(snapshot),
+      _ => return Err(CheckpointError::ProcessorKindMismatch),
+    }
+
+    Ok(())
+  }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ConformanceProcessorSnapshot {
+  cases: Option<ConformanceCasesSnapshot>,
+  max_event_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+struct ConformanceProcessorState {
+  checker: Option<ConformanceChecker>,
+  // The reference model is discovered on the first trace, so restored cases wait for it here.
+  restored_cases: Option<ConformanceCasesSnapshot>,
+  deviations: Vec<CaseDeviation>,
+}
+
+#[derive(Clone)]
+pub struct ConformanceStreamingProcessor {
+  state: Arc<Mutex<ConformanceProcessorState>>,
+  reference_model_pipeline: GrpcPipeline,
+  reference_model_kind: ReferenceModelKind,
+  cost_threshold: f64,
+  max_cases: Option<usize>,
+  case_inactivity_timeout: Option<Duration>,
+  time: StreamingTime,
+}
+
+impl ConformanceStreamingProcessor {
+  pub fn new(
+    reference_model_pipeline: GrpcPipeline,
+    reference_model_kind: ReferenceModelKind,
+    cost_threshold: f64,
+    time: StreamingTime,
+  ) -> Self {
+    Self {
+      state: Arc::new(Mutex::new(ConformanceProcessorState::default())),
+      reference_model_pipeline,
+      reference_model_kind,
+      cost_threshold,
+      max_cases: None,
+      case_inactivity_timeout: None,
+      time,
+    }
+  }
+
+  pub fn with_max_cases(mut self, max_cases: usize) -> Self {
+    self.max_cases = Some(max_cases);
+    self
+  }
+
+  pub fn with_case_inactivity_timeout(mut self, case_inactivity_timeout: Option<Duration>) -> Self {
+    self.case_inactivity_timeout = case_inactivity_timeout;
+    self
+  }
+
+  pub fn observe(&self, context: &KafkaTraceProcessingContext) -> Result<(), KafkaTraceProcessingError> {
+    let case = CaseMetadata::create_from(context.trace.metadata()).map_err(KafkaTraceProcessingError::XesFromBxesTraceCreationError)?;
+
+    let mut xes_trace = match read_bxes_events(context.trace.events()) {
+      Ok(xes_trace) => xes_trace,
+      Err(err) => {
+        let err = XesFromBxesKafkaTraceCreatingError::BxesToXexConversionError(err);
+        return Err(KafkaTraceProcessingError::XesFromBxesTraceCreationError(err));
+      }
+    };
+
+    xes_trace.remove_events_by(|event| !self.time.accept(*event.timestamp()));
+
+    let mut state = self.state.lock().expect("Must acquire lock");
+    if state.checker.is_none() {
+      let mut checker = self.create_checker(context)?;
+      if let Some(restored_cases) = state.restored_cases.take() {
+        checker
+          .restore(restored_cases)
+          .map_err(|err| KafkaTraceProcessingError::FailedToCreateReferenceModel(err.to_string()))?;
+      }
+
+      state.checker = Some(checker);
+    }
+
+    let ConformanceProcessorState { checker, deviations, .. } = &mut *state;
+    let checker = checker.as_mut().expect("Must be initialized");
+
+    for event in xes_trace.events() {
+      let event = event.borrow();
+      let stamp = match self.time.mode() {
+        StreamingTimeMode::EventTime => *event.timestamp(),
+        StreamingTimeMode::ProcessingTime => self.time.now().unwrap_or_else(Utc::now),
+      };
+
+      checker.observe(case.case_id, event.name(), stamp, deviations);
+    }
+
+    if is_case_completed(context.trace.metadata()) {
+      checker.remove_case(&case.case_id);
+    }
+
+    if let Some(watermark) = self.time.watermark() {
+      let evicted = checker.evict_inactive_cases(watermark);
+      if evicted > 0 {
+        info!("Evicted {} inactive cases from online conformance checking", evicted);
+      }
+    }
+
+    Ok(())
+  }
+
+  fn create_checker(&self, context: &KafkaTraceProcessingContext) -> Result<ConformanceChecker, KafkaTraceProcessingError> {
+    let mut pipeline_context = PipelineContext::default();
+    let initial_context_values = vec![];
+    let reference_model_pipeline = ServicePipelineExecutionContext::new(
+      &self.reference_model_pipeline,
+      &initial_context_values,
+      context.execution_dto.pipeline_parts.clone(),
+      context.execution_dto.events_handler.clone(),
+    );
+
+    if let Err(err) = reference_model_pipeline
+      .to_pipeline()
+      .execute(&mut pipeline_context, &PipelineInfrastructure::new(None))
+    {
+      return Err(KafkaTraceProcessingError::FailedToCreateReferenceModel(err.to_string()));
+    }
+
+    let checker = match self.reference_model_kind {
+      ReferenceModelKind::PetriNet => {
+        let Some(net) = pipeline_context.concrete(PETRI_NET_KEY.key()) else {
+          let message = format!(
+            "The key {} was not produced by the reference model pipeline",
+            PETRI_NET_KEY.key().name()
+          );
+          return Err(KafkaTraceProcessingError::FailedToCreateReferenceModel(message));
+        };
+
+        let Some(model) = TokenReplayConformanceModel::new(net) else {
+          let message = "The reference Petri net does not have an initial marking".to_string();
+          return Err(KafkaTraceProcessingError::FailedToCreateReferenceModel(message));
+        };
+
+        ConformanceChecker::PetriNet(self.configure_checker(OnlineConformanceChecker::new(model, self.cost_threshold)))
+      }
+      ReferenceModelKind::Dfg => {
+        let Some(graph) = pipeline_context.concrete(GRAPH_KEY.key()) else {
+          let message = format!(
+            "The key {} was not produced by the reference model pipeline",
+            GRAPH_KEY.key().name()
+          );
+          return Err(KafkaTraceProcessingError::FailedToCreateReferenceModel(message));
+        };
+
+        ConformanceChecker::Dfg(self.configure_checker(OnlineConformanceChecker::new(
+          DfgConformanceModel::from_graph(graph),
+          self.cost_threshold,
+        )))
+      }
+    };
+
+    info!(
+      "Created reference model {:?} for online conformance checking",
+      self.reference_model_kind
+    );
+
+    Ok(checker)
+  }
+
+  fn configure_checker<TModel: OnlineConformanceModel>(
+    &self,
+    mut checker: OnlineConformanceChecker<Uuid, TModel>,
+  ) -> OnlineConformanceChecker<Uuid, TModel> {
+    if let Some(max_cases) = self.max_cases {
+      checker = checker.with_max_cases(max_cases);
+    }
+
+    if let Some(case_inactivity_timeout) = self.case_inactivity_timeout {
+      checker = checker.with_inactivity_timeout(case_inactivity_timeout);
+    }
+
+    checker
+  }
+
+  pub fn take_deviations(&self) -> Vec<CaseDeviation> {
+    std::mem::take(&mut self.state.lock().expect("Must acquire lock").deviations)
+  }
+
+  pub fn fill_pipeline_context(&self, context: &mut PipelineContext) {
+    let state = self.state.lock().expect("Must acquire lock");
+    let report = match state.checker.as_ref() {
+      Some(checker) => checker.report(),
+      None => ConformanceReport {
+        cost_threshold: self.cost_threshold,
+        ..ConformanceReport::default()
+      },
+    };
+
+    context.put_concrete(CONFORMANCE_REPORT_KEY.key(), report);
+  }
+
+  pub fn snapshot(&self) -> ConformanceProcessorSnapshot {
+    let state = self.state.lock().expect("Must acquire lock");
+
+    ConformanceProcessorSnapshot {
+      cases: match state.checker.as_ref() {
+        Some(checker) => Some(checker.snapshot()),
+        None => state.restored_cases.clone(),
+      },
+      max_event_time: self.time.max_event_time(),
+    }
+  }
+
+  pub fn restore(&self, snapshot: ConformanceProcessorSnapshot) -> Result<(), CheckpointError> {
+    self.time.restore_max_event_time(snapshot.max_event_time);
+
+    let Some(cases) = snapshot.cases else {
+      return Ok(());
+    };
+
+    let mut state = self.state.lock().expect("Must acquire lock");
+    match state.checker.as_mut() {
+      Some(checker) => checker.restore(cases),
+      None => {
+        state.restored_cases = Some(cases);
+        Ok(())
+      }
+    }
+  }
+}
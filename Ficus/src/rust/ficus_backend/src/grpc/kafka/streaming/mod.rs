@@ -1,4 +1,6 @@
 pub mod configs;
+pub mod conformance;
 pub mod processors;
+pub mod replay;
 pub mod t1;
 pub mod t2;
@@ -1,21 +1,36 @@
 use crate::grpc::kafka::{
+  checkpoints::CheckpointError,
   models::{ExtractedTraceMetadata, KafkaTraceProcessingError, PipelineExecutionDto},
-  streaming::{t1::processors::T1StreamingProcessor, t2::processors::T2StreamingProcessor},
+  streaming::{
+    conformance::processors::{CaseDeviation, ConformanceProcessorSnapshot, ConformanceStreamingProcessor},
+    t1::processors::{T1CompletedCase, T1ProcessorSnapshot, T1StreamingProcessor},
+    t2::processors::{T2ProcessorSnapshot, T2StreamingProcessor},
+  },
 };
 use bxes_kafka::consumer::bxes_kafka_consumer::BxesKafkaTrace;
-use ficus::pipelines::context::PipelineContext;
+use ficus::{features::streaming::time::StreamingTime, pipelines::context::PipelineContext};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub enum TracesProcessor {
   T1(T1StreamingProcessor),
   T2(T2StreamingProcessor),
+  Conformance(ConformanceStreamingProcessor),
 }
 
 #[derive(Clone)]
 pub(super) struct ProcessorState<TData> {
   pub data: TData,
   pub metadata: Arc<ExtractedTraceMetadata>,
+  pub time: StreamingTime,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum ProcessorSnapshot {
+  T1(T1ProcessorSnapshot),
+  T2(Box<T2ProcessorSnapshot>),
+  Conformance(ConformanceProcessorSnapshot),
 }
 
 pub struct KafkaTraceProcessingContext {
@@ -28,6 +43,28 @@ impl TracesProcessor {
     match self {
       TracesProcessor::T1(processor) => processor.observe(&context.trace),
       TracesProcessor::T2(processor) => processor.observe(&mut context),
+      TracesProcessor::Conformance(processor) => processor.observe(&context),
+    }
+  }
+
+  pub fn take_deviations(&self) -> Vec<CaseDeviation> {
+    match self {
+      TracesProcessor::Conformance(processor) => processor.take_deviations(),
+      TracesProcessor::T1(_) | TracesProcessor::T2(_) => vec![],
+    }
+  }
+
+  pub fn take_completed_cases(&self) -> Vec<T1CompletedCase> {
+    match self {
+      TracesProcessor::T1(processor) => processor.take_completed_cases(),
+      TracesProcessor::T2(_) | TracesProcessor::Conformance(_) => vec![],
+    }
+  }
+
+  pub fn take_drift_detected(&self) -> bool {
+    match self {
+      TracesProcessor::T2(processor) => processor.take_drift_detected(),
+      TracesProcessor::T1(_) | TracesProcessor::Conformance(_) => false,
     }
   }
 
@@ -35,6 +72,24 @@ impl TracesProcessor {
     match self {
       TracesProcessor::T1(processor) => processor.fill_pipeline_context(context, case_name),
       TracesProcessor::T2(processor) => processor.fill_pipeline_context(context, case_name),
+      TracesProcessor::Conformance(processor) => processor.fill_pipeline_context(context),
+    }
+  }
+
+  pub fn snapshot(&self) -> Result<ProcessorSnapshot, CheckpointError> {
+    Ok(match self {
+      TracesProcessor::T1(processor) => ProcessorSnapshot::T1(processor.snapshot()?),
+      TracesProcessor::T2(processor) => ProcessorSnapshot::T2(Box::new(processor.snapshot())),
+      TracesProcessor::Conformance(processor) => ProcessorSnapshot::Conformance(processor.snapshot()),
+    })
+  }
+
+  pub fn restore(&self, snapshot: ProcessorSnapshot) -> Result<(), CheckpointError> {
+    match (self, snapshot) {
+      (TracesProcessor::T1(processor), ProcessorSnapshot::T1(snapshot)) => processor.restore(snapshot),
+      (TracesProcessor::T2(processor), ProcessorSnapshot::T2(snapshot)) => processor.restore(*snapshot),
+      (TracesProcessor::Conformance(processor), ProcessorSnapshot::Conformance(snapshot)) => processor.restore(snapshot),
+      _ => Err(CheckpointError::ProcessorKindMismatch),
     }
   }
 }
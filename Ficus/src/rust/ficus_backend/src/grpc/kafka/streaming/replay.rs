@@ -0,0 +1,143 @@
+use crate::{
+  ficus_proto::{GrpcPipelineStreamingConfiguration, GrpcReplayClockConfiguration, grpc_replay_clock_configuration::Clock},
+  grpc::kafka::{
+    models::{
+      KAFKA_CASE_DISPLAY_NAME, KAFKA_CASE_ID, KAFKA_CASE_NAME_PARTS, KAFKA_PROCESS_NAME, KafkaTraceProcessingError, PipelineExecutionDto,
+    },
+    streaming::{
+      configs::{StreamingConfiguration, StreamingConfigurationError},
+      processors::{KafkaTraceProcessingContext, TracesProcessor},
+    },
+  },
+};
+use bxes::models::domain::bxes_value::BxesValue;
+use bxes_kafka::consumer::bxes_kafka_consumer::BxesKafkaTrace;
+use ficus::{
+  event_log::bxes::xes_to_bxes_converter::xes_event_to_bxes_event,
+  features::streaming::replay::{ReplayClockMode, ReplayedEvent, StreamReplay, StreamReplayError},
+  pipelines::context::PipelineContext,
+};
+use log::{info, warn};
+use std::{
+  collections::HashMap,
+  fmt::{Display, Formatter},
+  path::Path,
+  sync::Arc,
+};
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum StreamReplayerCreationError {
+  Replay(StreamReplayError),
+  Configuration(StreamingConfigurationError),
+}
+
+impl Display for StreamReplayerCreationError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      StreamReplayerCreationError::Replay(err) => Display::fmt(err, f),
+      StreamReplayerCreationError::Configuration(err) => Display::fmt(err, f),
+    }
+  }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ReplayStatistics {
+  pub observed_events: usize,
+  pub failed_events: usize,
+  pub conformance_alerts: usize,
+}
+
+// Feeds events of the log one by one as kafka traces into the same processor which is used for kafka subscriptions,
+// all traces of the log belong to one case named after the log file, each trace gets its own stable case id.
+pub struct StreamReplayer {
+  replay: StreamReplay,
+  processor: TracesProcessor,
+  case_name: Arc<str>,
+}
+
+impl StreamReplayer {
+  pub fn new(
+    log_path: &str,
+    streaming_config: &GrpcPipelineStreamingConfiguration,
+    clock_config: Option<&GrpcReplayClockConfiguration>,
+  ) -> Result<Self, StreamReplayerCreationError> {
+    let config = StreamingConfiguration::new(streaming_config).map_err(StreamReplayerCreationError::Configuration)?;
+    let replay = StreamReplay::read(log_path, Self::clock_mode(clock_config)).map_err(StreamReplayerCreationError::Replay)?;
+
+    let time = StreamingConfiguration::create_streaming_time_with_clock(streaming_config, replay.clock());
+
+    let case_name = Path::new(log_path)
+      .file_stem()
+      .and_then(|stem| stem.to_str())
+      .unwrap_or(log_path)
+      .into();
+
+    Ok(Self {
+      processor: config.create_processor(time),
+      replay,
+      case_name,
+    })
+  }
+
+  fn clock_mode(clock_config: Option<&GrpcReplayClockConfiguration>) -> ReplayClockMode {
+    match clock_config.and_then(|config| config.clock.as_ref()) {
+      Some(Clock::Accelerated(accelerated)) => ReplayClockMode::Accelerated(accelerated.speedup),
+      Some(Clock::Simulated(_)) | None => ReplayClockMode::Simulated,
+    }
+  }
+
+  pub fn replay(&self, execution_dto: &PipelineExecutionDto) -> ReplayStatistics {
+    let mut statistics = ReplayStatistics::default();
+
+    self.replay.replay(|event| {
+      statistics.observed_events += 1;
+      if let Err(err) = self.observe_event(event, execution_dto) {
+        statistics.failed_events += 1;
+        warn!("Failed to observe replayed event of trace {}, error: {}", event.trace_index, err);
+      }
+
+      for deviation in self.processor.take_deviations() {
+        statistics.conformance_alerts += 1;
+        info!(
+          "Case {} deviated from the reference model on {}",
+          deviation.case_id, deviation.activity
+        );
+      }
+    });
+
+    info!(
+      "Replayed {} events of {}, {} failed, {} conformance alerts",
+      statistics.observed_events, self.case_name, statistics.failed_events, statistics.conformance_alerts
+    );
+
+    statistics
+  }
+
+  pub fn fill_pipeline_context(&self, context: &mut PipelineContext) {
+    self.processor.fill_pipeline_context(context, self.case_name.as_ref());
+  }
+
+  fn observe_event(&self, event: &ReplayedEvent, execution_dto: &PipelineExecutionDto) -> Result<(), KafkaTraceProcessingError> {
+    self.processor.observe(KafkaTraceProcessingContext {
+      trace: self.create_kafka_trace(event),
+      execution_dto: execution_dto.clone(),
+    })
+  }
+
+  fn create_kafka_trace(&self, event: &ReplayedEvent) -> BxesKafkaTrace {
+    let case_name = Arc::new(BxesValue::String(self.case_name.clone()));
+
+    let metadata = HashMap::from_iter([
+      (
+        KAFKA_CASE_ID.into(),
+        Arc::new(BxesValue::Guid(Uuid::from_u64_pair(0, event.trace_index as u64))),
+      ),
+      (KAFKA_CASE_NAME_PARTS.into(), case_name.clone()),
+      (KAFKA_CASE_DISPLAY_NAME.into(), case_name.clone()),
+      (KAFKA_PROCESS_NAME.into(), case_name),
+    ]);
+
+    BxesKafkaTrace::new(metadata, vec![xes_event_to_bxes_event(&event.event)])
+  }
+}
@@ -1,38 +1,91 @@
 use crate::{
-  ficus_proto::{GrpcT1StreamingConfiguration, grpc_t1_streaming_configuration::Configuration},
+  ficus_proto::{GrpcT1CaseCompletionConfiguration, GrpcT1StreamingConfiguration, grpc_t1_streaming_configuration::Configuration},
   grpc::kafka::streaming::t1::processors::T1StreamingProcessor,
 };
-use ficus::features::streaming::t1::{
-  configs::{EventsTimeoutConfiguration, TracesQueueConfiguration, TracesTimeoutConfiguration},
-  filterers::{EventsTimeoutFiltererImpl, T1LogFilterer, TracesQueueFiltererImpl, TracesTimeoutFiltererImpl},
+use ficus::features::streaming::{
+  t1::{
+    completion::{CaseCompletionDetector, CaseCompletionRule},
+    configs::{EventsTimeoutConfiguration, TracesQueueConfiguration, TracesTimeoutConfiguration},
+    filterers::{EventsTimeoutFiltererImpl, T1LogFilterer, TracesQueueFiltererImpl, TracesTimeoutFiltererImpl},
+  },
+  time::StreamingTime,
 };
+use log::warn;
 
-pub enum T1StreamingConfiguration {
+pub struct T1StreamingConfiguration {
+  filterer: T1FiltererConfiguration,
+  case_completion: CaseCompletionDetector,
+}
+
+impl T1StreamingConfiguration {
+  pub fn new(grpc_config: &GrpcT1StreamingConfiguration) -> Option<Self> {
+    T1FiltererConfiguration::new(grpc_config).map(|filterer| Self {
+      filterer,
+      case_completion: grpc_config
+        .case_completion
+        .as_ref()
+        .map(Self::create_case_completion_detector)
+        .unwrap_or_default(),
+    })
+  }
+
+  fn create_case_completion_detector(grpc_config: &GrpcT1CaseCompletionConfiguration) -> CaseCompletionDetector {
+    let mut rules = vec![];
+
+    if let Some(regex) = grpc_config.end_activity_regex.as_ref() {
+      match CaseCompletionRule::end_activity(regex) {
+        Ok(rule) => rules.push(rule),
+        Err(err) => warn!("Failed to create end activity regex {}, error: {}", regex, err),
+      }
+    }
+
+    if let Some(gap_ms) = grpc_config.inactivity_gap_ms {
+      rules.push(CaseCompletionRule::InactivityGap(gap_ms.max(0) as u64));
+    }
+
+    if grpc_config.use_end_marker {
+      rules.push(CaseCompletionRule::EndMarker);
+    }
+
+    CaseCompletionDetector::new(rules)
+  }
+
+  pub fn create_processor(&self, time: StreamingTime) -> T1StreamingProcessor {
+    self
+      .filterer
+      .create_processor(time)
+      .with_case_completion(self.case_completion.clone())
+  }
+}
+
+pub enum T1FiltererConfiguration {
   EventsTimeout(EventsTimeoutConfiguration),
   TracesTimeout(TracesTimeoutConfiguration),
   TracesQueue(TracesQueueConfiguration),
 }
 
-impl T1StreamingConfiguration {
+impl T1FiltererConfiguration {
   pub fn new(grpc_config: &GrpcT1StreamingConfiguration) -> Option<Self> {
     grpc_config.configuration.as_ref().map(|c| match c {
       Configuration::EventsTimeout(et) => {
-        T1StreamingConfiguration::EventsTimeout(EventsTimeoutConfiguration::new(et.events_timeout_ms as u64))
+        T1FiltererConfiguration::EventsTimeout(EventsTimeoutConfiguration::new(et.events_timeout_ms as u64))
       }
       Configuration::TracesTimeout(tt) => {
-        T1StreamingConfiguration::TracesTimeout(TracesTimeoutConfiguration::new(tt.traces_timeout_ms as u64))
+        T1FiltererConfiguration::TracesTimeout(TracesTimeoutConfiguration::new(tt.traces_timeout_ms as u64))
       }
       Configuration::TracesQueueConfiguration(tq) => {
-        T1StreamingConfiguration::TracesQueue(TracesQueueConfiguration::new(tq.queue_capacity as u64))
+        T1FiltererConfiguration::TracesQueue(TracesQueueConfiguration::new(tq.queue_capacity as u64))
       }
     })
   }
 
-  pub fn create_processor(&self) -> T1StreamingProcessor {
-    T1StreamingProcessor::new(match self {
-      T1StreamingConfiguration::EventsTimeout(c) => T1LogFilterer::EventsTimeoutFilterer(EventsTimeoutFiltererImpl::new(c.clone())),
-      T1StreamingConfiguration::TracesTimeout(c) => T1LogFilterer::TracesTimeoutFilterer(TracesTimeoutFiltererImpl::new(c.clone())),
-      T1StreamingConfiguration::TracesQueue(c) => T1LogFilterer::TracesQueueFilterer(TracesQueueFiltererImpl::new(c.clone())),
-    })
+  pub fn create_processor(&self, time: StreamingTime) -> T1StreamingProcessor {
+    let filterer = match self {
+      T1FiltererConfiguration::EventsTimeout(c) => T1LogFilterer::EventsTimeoutFilterer(EventsTimeoutFiltererImpl::new(c.clone())),
+      T1FiltererConfiguration::TracesTimeout(c) => T1LogFilterer::TracesTimeoutFilterer(TracesTimeoutFiltererImpl::new(c.clone())),
+      T1FiltererConfiguration::TracesQueue(c) => T1LogFilterer::TracesQueueFilterer(TracesQueueFiltererImpl::new(c.clone())),
+    };
+
+    T1StreamingProcessor::new(filterer, time)
   }
 }
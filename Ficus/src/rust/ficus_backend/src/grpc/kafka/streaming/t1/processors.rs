@@ -1,28 +1,44 @@
 use crate::grpc::{
   kafka::{
+    checkpoints::CheckpointError,
     models::{
       ExtractedTraceMetadata, KAFKA_CASE_ID, KAFKA_CASE_NAME_PARTS, KAFKA_TRACE_ID, KafkaTraceProcessingError,
-      XesFromBxesKafkaTraceCreatingError, string_value_or_err, uuid_or_err,
+      XesFromBxesKafkaTraceCreatingError, is_case_completed, string_value_or_err, uuid_or_err,
     },
     streaming::processors::ProcessorState,
   },
   logs_handler::ConsoleLogMessageHandler,
 };
 use bxes_kafka::consumer::bxes_kafka_consumer::BxesKafkaTrace;
+use chrono::{DateTime, Utc};
 use ficus::{
   event_log::{
-    bxes::bxes_to_xes_converter::read_bxes_events,
-    core::{event::event::EventPayloadValue, event_log::EventLog, trace::trace::Trace},
+    bxes::{
+      bxes_to_xes_converter::{read_bxes_events, read_bxes_into_xes_log_from_bytes},
+      xes_to_bxes_converter::write_event_log_to_bxes_bytes,
+    },
+    core::{
+      event::event::{Event, EventPayloadValue},
+      event_log::EventLog,
+      trace::trace::Trace,
+    },
     xes::xes_event_log::XesEventLogImpl,
   },
-  features::streaming::t1::filterers::T1LogFilterer,
+  features::streaming::{
+    t1::{
+      completion::{CASE_COMPLETED_MARKER, CaseCompletionDetector},
+      filterers::T1LogFilterer,
+    },
+    time::StreamingTime,
+  },
   pipelines::{
     context::{LogMessageHandler, PipelineContext},
-    keys::context_keys::EVENT_LOG_KEY,
+    keys::context_keys::{CLOSED_CASES_EVENT_LOG_KEY, EVENT_LOG_KEY},
   },
   utils::user_data::user_data::UserData,
 };
 use log::{info, warn};
+use serde::{Deserialize, Serialize};
 use std::{
   cell::RefCell,
   collections::HashMap,
@@ -31,22 +47,66 @@ use std::{
 };
 use uuid::Uuid;
 
+#[derive(Serialize, Deserialize)]
+struct T1CaseSnapshot {
+  case_key: String,
+  log: Vec<u8>,
+  trace_ids: Vec<Option<Uuid>>,
+  metadata: Arc<ExtractedTraceMetadata>,
+  max_event_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct T1ClosedCasesSnapshot {
+  case_key: String,
+  log: Vec<u8>,
+  trace_ids: Vec<Option<Uuid>>,
+}
+
+// Trace metadata is not written to bxes logs, so ids of traces are stored separately in the order of traces.
+#[derive(Serialize, Deserialize)]
+pub struct T1ProcessorSnapshot {
+  cases: Vec<T1CaseSnapshot>,
+  #[serde(default)]
+  closed_cases: Vec<T1ClosedCasesSnapshot>,
+}
+
+#[derive(Clone, Debug)]
+pub struct T1CompletedCase {
+  pub case_id: Uuid,
+  pub completion_rule: String,
+  pub events_count: u64,
+}
+
 #[derive(Clone)]
 pub struct T1StreamingProcessor {
   logger: ConsoleLogMessageHandler,
   names_to_processed_data: Arc<Mutex<HashMap<String, ProcessorState<XesEventLogImpl>>>>,
+  names_to_closed_cases: Arc<Mutex<HashMap<String, XesEventLogImpl>>>,
+  completed_cases: Arc<Mutex<Vec<T1CompletedCase>>>,
   filterer: T1LogFilterer,
+  case_completion: CaseCompletionDetector,
+  time: StreamingTime,
 }
 
 impl T1StreamingProcessor {
-  pub fn new(filterer: T1LogFilterer) -> Self {
+  pub fn new(filterer: T1LogFilterer, time: StreamingTime) -> Self {
     Self {
       logger: ConsoleLogMessageHandler::new(),
       names_to_processed_data: Arc::new(Mutex::new(HashMap::new())),
+      names_to_closed_cases: Arc::new(Mutex::new(HashMap::new())),
+      completed_cases: Arc::new(Mutex::new(vec![])),
       filterer,
+      case_completion: CaseCompletionDetector::default(),
+      time,
     }
   }
 
+  pub fn with_case_completion(mut self, case_completion: CaseCompletionDetector) -> Self {
+    self.case_completion = case_completion;
+    self
+  }
+
   pub fn observe(&self, trace: &BxesKafkaTrace) -> Result<(), KafkaTraceProcessingError> {
     match self.update_log(trace) {
       Ok(_) => Ok(()),
@@ -69,6 +129,98 @@ impl T1StreamingProcessor {
     } else {
       warn!("Failed to find log for case {case_name:?}");
     }
+
+    if let Some(closed_cases) = self.names_to_closed_cases.lock().unwrap().get(case_name).cloned() {
+      context.put_concrete(CLOSED_CASES_EVENT_LOG_KEY.key(), closed_cases);
+    }
+  }
+
+  pub fn take_completed_cases(&self) -> Vec<T1CompletedCase> {
+    std::mem::take(&mut *self.completed_cases.lock().unwrap())
+  }
+
+  pub fn snapshot(&self) -> Result<T1ProcessorSnapshot, CheckpointError> {
+    let names_to_processed_data = self.names_to_processed_data.lock().unwrap();
+
+    let mut cases = vec![];
+    for (case_key, state) in names_to_processed_data.iter() {
+      cases.push(T1CaseSnapshot {
+        case_key: case_key.to_owned(),
+        log: write_event_log_to_bxes_bytes(&state.data, None).map_err(CheckpointError::LogWrite)?,
+        trace_ids: Self::trace_ids(&state.data),
+        metadata: state.metadata.clone(),
+        max_event_time: state.time.max_event_time(),
+      });
+    }
+
+    let mut closed_cases = vec![];
+    for (case_key, log) in self.names_to_closed_cases.lock().unwrap().iter() {
+      closed_cases.push(T1ClosedCasesSnapshot {
+        case_key: case_key.to_owned(),
+        log: write_event_log_to_bxes_bytes(log, None).map_err(CheckpointError::LogWrite)?,
+        trace_ids: Self::trace_ids(log),
+      });
+    }
+
+    Ok(T1ProcessorSnapshot { cases, closed_cases })
+  }
+
+  pub fn restore(&self, snapshot: T1ProcessorSnapshot) -> Result<(), CheckpointError> {
+    let mut restored_data = HashMap::new();
+
+    for case in snapshot.cases {
+      let log = Self::restore_log(&case.log, case.trace_ids)?;
+
+      let time = self.time.fork();
+      time.restore_max_event_time(case.max_event_time);
+
+      restored_data.insert(
+        case.case_key,
+        ProcessorState {
+          data: log,
+          metadata: case.metadata,
+          time,
+        },
+      );
+    }
+
+    let mut restored_closed_cases = HashMap::new();
+    for closed_cases in snapshot.closed_cases {
+      let log = Self::restore_log(&closed_cases.log, closed_cases.trace_ids)?;
+      restored_closed_cases.insert(closed_cases.case_key, log);
+    }
+
+    info!("Restored event logs for {} cases", restored_data.len());
+    *self.names_to_processed_data.lock().unwrap() = restored_data;
+    *self.names_to_closed_cases.lock().unwrap() = restored_closed_cases;
+
+    Ok(())
+  }
+
+  fn trace_ids(log: &XesEventLogImpl) -> Vec<Option<Uuid>> {
+    log
+      .traces()
+      .iter()
+      .map(|trace| match trace.borrow().metadata().get(KAFKA_TRACE_ID) {
+        Some(EventPayloadValue::Guid(id)) => Some(*id),
+        _ => None,
+      })
+      .collect()
+  }
+
+  fn restore_log(bytes: &[u8], trace_ids: Vec<Option<Uuid>>) -> Result<XesEventLogImpl, CheckpointError> {
+    let log = read_bxes_into_xes_log_from_bytes(bytes).map_err(CheckpointError::LogRead)?.xes_log;
+
+    for (trace, trace_id) in log.traces().iter().zip(trace_ids) {
+      if let Some(trace_id) = trace_id {
+        let mut trace = trace.borrow_mut();
+        trace
+          .metadata_mut()
+          .insert(KAFKA_TRACE_ID.to_owned(), EventPayloadValue::Guid(trace_id));
+      }
+    }
+
+    Ok(log)
   }
 }
 
@@ -77,7 +229,46 @@ impl T1StreamingProcessor {
     let case_id = uuid_or_err(trace.metadata(), KAFKA_CASE_ID)?;
     let case_name_parts_joined = string_value_or_err(trace.metadata(), KAFKA_CASE_NAME_PARTS)?;
 
-    self.get_or_create_event_log(trace, case_id, case_name_parts_joined.as_ref())
+    self.get_or_create_event_log(trace, case_id, case_name_parts_joined.as_ref())?;
+    self.close_completed_cases(case_name_parts_joined.as_ref());
+
+    Ok(())
+  }
+
+  fn close_completed_cases(&self, case_key: &str) {
+    if self.case_completion.rules().is_empty() {
+      return;
+    }
+
+    let mut names_to_processed_data = self.names_to_processed_data.lock().unwrap();
+    let Some(existing_data) = names_to_processed_data.get_mut(case_key) else {
+      return;
+    };
+
+    let mut names_to_closed_cases = self.names_to_closed_cases.lock().unwrap();
+    let closed_cases = names_to_closed_cases.entry(case_key.to_owned()).or_default();
+
+    let completed_cases = self
+      .case_completion
+      .close_completed_cases(&mut existing_data.data, closed_cases, &existing_data.time);
+
+    self.filterer.filter(closed_cases, &existing_data.time);
+
+    let mut completed = self.completed_cases.lock().unwrap();
+    for case in completed_cases {
+      let trace = case.trace.borrow();
+      let Some(EventPayloadValue::Guid(case_id)) = trace.metadata().get(KAFKA_TRACE_ID).cloned() else {
+        continue;
+      };
+
+      info!("Case {} was completed by rule {}", case_id, case.rule);
+
+      completed.push(T1CompletedCase {
+        case_id,
+        completion_rule: case.rule.to_string(),
+        events_count: trace.events().len() as u64,
+      });
+    }
   }
 
   fn get_or_create_event_log(
@@ -99,19 +290,28 @@ impl T1StreamingProcessor {
         ProcessorState {
           data: new_log,
           metadata: Arc::new(ExtractedTraceMetadata::create_from(trace.metadata())?),
+          time: self.time.fork(),
         },
       );
     }
 
     let existing_data = names_to_processed_data.get_mut(case_key).expect("Log should be present");
 
-    self.filterer.filter(&mut existing_data.data);
-
     let mut read_xes_trace = match read_bxes_events(trace.events()) {
       Ok(xes_trace) => xes_trace,
       Err(err) => return Err(XesFromBxesKafkaTraceCreatingError::BxesToXexConversionError(err)),
     };
 
+    let time = &existing_data.time;
+    read_xes_trace.remove_events_by(|event| !time.accept(*event.timestamp()));
+
+    self.filterer.filter(&mut existing_data.data, &existing_data.time);
+
+    if read_xes_trace.events().is_empty() {
+      warn!("All events of trace {} are late, skipping it", trace_id);
+      return Ok(());
+    }
+
     for existing_xes_trace in existing_data.data.traces() {
       let mut existing_xes_trace = existing_xes_trace.borrow_mut();
       if let Some(EventPayloadValue::Guid(id)) = existing_xes_trace.metadata().get(KAFKA_TRACE_ID).cloned() {
@@ -122,6 +322,10 @@ impl T1StreamingProcessor {
             existing_xes_trace.push(event.clone());
           }
 
+          if is_case_completed(trace.metadata()) {
+            Self::mark_as_completed(existing_xes_trace.metadata_mut());
+          }
+
           drop(existing_xes_trace);
           return Ok(());
         }
@@ -132,6 +336,10 @@ impl T1StreamingProcessor {
       .metadata_mut()
       .insert(KAFKA_TRACE_ID.to_owned(), EventPayloadValue::Guid(trace_id));
 
+    if is_case_completed(trace.metadata()) {
+      Self::mark_as_completed(read_xes_trace.metadata_mut());
+    }
+
     let read_xes_trace = Rc::new(RefCell::new(read_xes_trace));
     existing_data.data.push(read_xes_trace);
 
@@ -139,4 +347,8 @@ impl T1StreamingProcessor {
 
     Ok(())
   }
+
+  fn mark_as_completed(metadata: &mut HashMap<String, EventPayloadValue>) {
+    metadata.insert(CASE_COMPLETED_MARKER.to_owned(), EventPayloadValue::Boolean(true));
+  }
 }
@@ -1,33 +1,110 @@
 use crate::{
-  ficus_proto::{GrpcPipeline, GrpcT2StreamingConfiguration, grpc_t2_streaming_configuration::Configuration},
-  grpc::kafka::streaming::t2::processors::T2StreamingProcessor,
+  ficus_proto::{
+    GrpcDriftStatisticalTest, GrpcPipeline, GrpcT2DriftDetectionConfiguration, GrpcT2StreamingConfiguration,
+    grpc_t2_streaming_configuration::Configuration,
+  },
+  grpc::kafka::streaming::{configs::StreamingConfigurationError, t2::processors::T2StreamingProcessor},
+};
+use ficus::features::{
+  analysis::drift::{online::OnlineDriftDetector, statistics::DriftStatisticalTest},
+  streaming::{counters::count_min::validate_count_min_sketch_parameters, time::StreamingTime},
 };
 use std::time::Duration;
 
-pub enum T2StreamingConfiguration {
+pub struct T2StreamingConfiguration {
+  counters: T2CountersConfiguration,
+  drift_detection: Option<DriftDetectionConfiguration>,
+}
+
+impl T2StreamingConfiguration {
+  pub fn new(grpc_config: &GrpcT2StreamingConfiguration) -> Result<Option<Self>, StreamingConfigurationError> {
+    Ok(T2CountersConfiguration::new(grpc_config)?.map(|counters| Self {
+      counters,
+      drift_detection: grpc_config.drift_detection.as_ref().map(DriftDetectionConfiguration::new),
+    }))
+  }
+
+  pub fn create_processor(&self, time: StreamingTime) -> T2StreamingProcessor {
+    let processor = self.counters.create_processor(time);
+    match self.drift_detection.as_ref() {
+      None => processor,
+      Some(drift_detection) => processor.with_drift_detector(drift_detection.create_detector()),
+    }
+  }
+}
+
+pub struct DriftDetectionConfiguration {
+  test: DriftStatisticalTest,
+  significance: f64,
+  min_observations: f64,
+}
+
+impl DriftDetectionConfiguration {
+  pub fn new(grpc_config: &GrpcT2DriftDetectionConfiguration) -> Self {
+    Self {
+      test: match grpc_config.test() {
+        GrpcDriftStatisticalTest::ChiSquare => DriftStatisticalTest::ChiSquare,
+        GrpcDriftStatisticalTest::GTest => DriftStatisticalTest::GTest,
+      },
+      significance: grpc_config.significance.clamp(0., 1.),
+      min_observations: grpc_config.min_observations.max(0.),
+    }
+  }
+
+  pub fn create_detector(&self) -> OnlineDriftDetector {
+    OnlineDriftDetector::new(self.test, self.significance, self.min_observations)
+  }
+}
+
+pub enum T2CountersConfiguration {
   LossyCount(LossyCountConfiguration),
   SlidingWindow(TimedSlidingWindowConfiguration),
+  SpaceSaving(SpaceSavingConfiguration),
+  CountMinSketch(CountMinSketchConfiguration),
+  ExponentialDecay(ExponentialDecayConfiguration),
 }
 
-impl T2StreamingConfiguration {
-  pub fn new(grpc_config: &GrpcT2StreamingConfiguration) -> Option<Self> {
-    grpc_config.configuration.as_ref().map(|c| match c {
-      Configuration::LossyCount(lc) => T2StreamingConfiguration::LossyCount(LossyCountConfiguration {
+impl T2CountersConfiguration {
+  pub fn new(grpc_config: &GrpcT2StreamingConfiguration) -> Result<Option<Self>, StreamingConfigurationError> {
+    if let Some(Configuration::CountMinSketch(cm)) = grpc_config.configuration.as_ref() {
+      validate_count_min_sketch_parameters(cm.error, cm.failure_probability, cm.support)
+        .map_err(StreamingConfigurationError::InvalidCountMinSketch)?;
+    }
+
+    Ok(grpc_config.configuration.as_ref().map(|c| match c {
+      Configuration::LossyCount(lc) => T2CountersConfiguration::LossyCount(LossyCountConfiguration {
         error: lc.error,
         support: lc.support,
         trace_preprocessing_pipeline: grpc_config.incoming_traces_filtering_pipeline.clone(),
       }),
-      Configuration::TimedSlidingWindow(sc) => T2StreamingConfiguration::SlidingWindow(TimedSlidingWindowConfiguration {
+      Configuration::TimedSlidingWindow(sc) => T2CountersConfiguration::SlidingWindow(TimedSlidingWindowConfiguration {
         element_lifetime: Duration::from_millis(sc.lifespan_ms as u64),
         trace_preprocessing_pipeline: grpc_config.incoming_traces_filtering_pipeline.clone(),
       }),
-    })
+      Configuration::SpaceSaving(ss) => T2CountersConfiguration::SpaceSaving(SpaceSavingConfiguration {
+        capacity: ss.capacity.max(1) as usize,
+        trace_preprocessing_pipeline: grpc_config.incoming_traces_filtering_pipeline.clone(),
+      }),
+      Configuration::CountMinSketch(cm) => T2CountersConfiguration::CountMinSketch(CountMinSketchConfiguration {
+        error: cm.error,
+        failure_probability: cm.failure_probability,
+        support: cm.support,
+        trace_preprocessing_pipeline: grpc_config.incoming_traces_filtering_pipeline.clone(),
+      }),
+      Configuration::ExponentialDecay(ed) => T2CountersConfiguration::ExponentialDecay(ExponentialDecayConfiguration {
+        half_life: Duration::from_millis(ed.half_life_ms as u64),
+        trace_preprocessing_pipeline: grpc_config.incoming_traces_filtering_pipeline.clone(),
+      }),
+    }))
   }
 
-  pub fn create_processor(&self) -> T2StreamingProcessor {
+  pub fn create_processor(&self, time: StreamingTime) -> T2StreamingProcessor {
     match self {
-      T2StreamingConfiguration::LossyCount(lc) => lc.create_processor(),
-      T2StreamingConfiguration::SlidingWindow(sw) => sw.create_processor(),
+      T2CountersConfiguration::LossyCount(lc) => lc.create_processor(time),
+      T2CountersConfiguration::SlidingWindow(sw) => sw.create_processor(time),
+      T2CountersConfiguration::SpaceSaving(ss) => ss.create_processor(time),
+      T2CountersConfiguration::CountMinSketch(cm) => cm.create_processor(time),
+      T2CountersConfiguration::ExponentialDecay(ed) => ed.create_processor(time),
     }
   }
 }
@@ -39,8 +116,8 @@ pub struct LossyCountConfiguration {
 }
 
 impl LossyCountConfiguration {
-  pub fn create_processor(&self) -> T2StreamingProcessor {
-    T2StreamingProcessor::new_lossy_count(self.error, self.trace_preprocessing_pipeline.clone())
+  pub fn create_processor(&self, time: StreamingTime) -> T2StreamingProcessor {
+    T2StreamingProcessor::new_lossy_count(self.error, self.trace_preprocessing_pipeline.clone(), time)
   }
 }
 
@@ -50,7 +127,48 @@ pub struct TimedSlidingWindowConfiguration {
 }
 
 impl TimedSlidingWindowConfiguration {
-  pub fn create_processor(&self) -> T2StreamingProcessor {
-    T2StreamingProcessor::new_sliding_window(self.element_lifetime, self.trace_preprocessing_pipeline.clone())
+  pub fn create_processor(&self, time: StreamingTime) -> T2StreamingProcessor {
+    T2StreamingProcessor::new_sliding_window(self.element_lifetime, self.trace_preprocessing_pipeline.clone(), time)
+  }
+}
+
+pub struct SpaceSavingConfiguration {
+  capacity: usize,
+  trace_preprocessing_pipeline: Option<GrpcPipeline>,
+}
+
+impl SpaceSavingConfiguration {
+  pub fn create_processor(&self, time: StreamingTime) -> T2StreamingProcessor {
+    T2StreamingProcessor::new_space_saving(self.capacity, self.trace_preprocessing_pipeline.clone(), time)
+  }
+}
+
+pub struct CountMinSketchConfiguration {
+  error: f64,
+  failure_probability: f64,
+  support: f64,
+  trace_preprocessing_pipeline: Option<GrpcPipeline>,
+}
+
+impl CountMinSketchConfiguration {
+  pub fn create_processor(&self, time: StreamingTime) -> T2StreamingProcessor {
+    T2StreamingProcessor::new_count_min_sketch(
+      self.error,
+      self.failure_probability,
+      self.support,
+      self.trace_preprocessing_pipeline.clone(),
+      time,
+    )
+  }
+}
+
+pub struct ExponentialDecayConfiguration {
+  half_life: Duration,
+  trace_preprocessing_pipeline: Option<GrpcPipeline>,
+}
+
+impl ExponentialDecayConfiguration {
+  pub fn create_processor(&self, time: StreamingTime) -> T2StreamingProcessor {
+    T2StreamingProcessor::new_exponential_decay(self.half_life, self.trace_preprocessing_pipeline.clone(), time)
   }
 }
@@ -1,53 +1,110 @@
 use crate::grpc::kafka::models::{CaseMetadata, ProcessMetadata, XesFromBxesKafkaTraceCreatingError};
 use bxes::models::domain::bxes_value::BxesValue;
+use chrono::{DateTime, Utc};
 use ficus::{
   event_log::{
     core::{event::event::Event, trace::trace::Trace},
     xes::xes_trace::XesTraceImpl,
   },
   features::{
-    analysis::log_info::event_log_info::OfflineEventLogInfo,
-    streaming::counters::{
-      core::{StreamingCounter, ValueUpdateKind},
-      lossy_count::LossyCount,
-      sliding_window::SlidingWindow,
+    analysis::{
+      drift::{
+        distributions::relations_counter_distribution,
+        online::{OnlineDriftChangePoint, OnlineDriftDetector},
+      },
+      log_info::event_log_info::OfflineEventLogInfo,
+    },
+    streaming::{
+      counters::{
+        core::{StreamingCounter, StreamingCounterRestoreError, StreamingCounterSnapshot, ValueUpdateKind},
+        count_min::CountMinSketch,
+        exponential_decay::ExponentialDecay,
+        lossy_count::LossyCount,
+        sliding_window::SlidingWindow,
+        space_saving::SpaceSaving,
+      },
+      t2::relations::{CaseHistory, ClassesPair, observe_proximity, proximity_correlation_from_counter, triangle_relation_from_counter},
+      time::StreamingTime,
     },
   },
-  pipelines::{context::PipelineContext, keys::context_keys::EVENT_LOG_INFO_KEY},
+  pipelines::{
+    context::PipelineContext,
+    keys::context_keys::{EVENT_LOG_INFO_KEY, ONLINE_CONCEPT_DRIFT_CHANGE_POINTS_KEY, PROXIMITY_CORRELATION_KEY, TRIANGLE_RELATION_KEY},
+  },
   utils::user_data::user_data::UserData,
 };
 use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
 use std::{cell::RefCell, collections::HashMap, hash::Hash, rc::Rc, sync::Arc, time::Duration};
 use uuid::Uuid;
 
 #[derive(Clone)]
 enum StreamingCounterFactory {
   LossyCount(f64),
-  SlidingWindow(Duration),
+  SlidingWindow(Duration, StreamingTime),
+  SpaceSaving(usize),
+  CountMinSketch(f64, f64, f64),
+  ExponentialDecay(Duration, StreamingTime),
 }
 
 impl StreamingCounterFactory {
   pub fn create<TKey: Hash + Eq + Clone + 'static, TValue: Clone + 'static>(&self) -> Rc<RefCell<dyn StreamingCounter<TKey, TValue>>> {
     match self {
       StreamingCounterFactory::LossyCount(error) => Rc::new(RefCell::new(LossyCount::new(*error))),
-      StreamingCounterFactory::SlidingWindow(lifetime) => Rc::new(RefCell::new(SlidingWindow::new_time(*lifetime))),
+      StreamingCounterFactory::SlidingWindow(lifetime, time) => {
+        Rc::new(RefCell::new(SlidingWindow::new_time_with(*lifetime, time.clone())))
+      }
+      StreamingCounterFactory::SpaceSaving(capacity) => Rc::new(RefCell::new(SpaceSaving::new(*capacity))),
+      StreamingCounterFactory::CountMinSketch(error, failure_probability, support) => {
+        let sketch = CountMinSketch::new(*error, *failure_probability, *support)
+          .expect("Count-min sketch parameters are validated when streaming configuration is created");
+
+        Rc::new(RefCell::new(sketch))
+      }
+      StreamingCounterFactory::ExponentialDecay(half_life, time) => {
+        Rc::new(RefCell::new(ExponentialDecay::new_with(*half_life, time.clone())))
+      }
     }
   }
 }
 
+type CountersSnapshots<TKey, TValue> = HashMap<Arc<str>, StreamingCounterSnapshot<TKey, TValue>>;
+type Counters<TKey, TValue> = HashMap<Arc<str>, Rc<RefCell<dyn StreamingCounter<TKey, TValue>>>>;
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct DfgDataStructuresSnapshot {
+  processes_dfg: CountersSnapshots<(Arc<str>, Arc<str>), ()>,
+  traces_last_event_classes: StreamingCounterSnapshot<Uuid, Arc<str>>,
+  event_classes_count: CountersSnapshots<Arc<str>, ()>,
+  #[serde(default)]
+  drift_detectors: HashMap<Arc<str>, OnlineDriftDetector>,
+  #[serde(default)]
+  processes_triangles: CountersSnapshots<ClassesPair, ()>,
+  #[serde(default)]
+  processes_proximities: CountersSnapshots<ClassesPair, f64>,
+  #[serde(default)]
+  traces_histories: Option<StreamingCounterSnapshot<Uuid, CaseHistory>>,
+}
+
 #[derive(Clone)]
 struct DfgDataStructureBase {
   factory: StreamingCounterFactory,
-  processes_dfg: HashMap<Arc<str>, Rc<RefCell<dyn StreamingCounter<(Arc<str>, Arc<str>), ()>>>>,
+  processes_dfg: Counters<(Arc<str>, Arc<str>), ()>,
   traces_last_event_classes: Rc<RefCell<dyn StreamingCounter<Uuid, Arc<str>>>>,
-  event_classes_count: HashMap<Arc<str>, Rc<RefCell<dyn StreamingCounter<Arc<str>, ()>>>>,
+  event_classes_count: Counters<Arc<str>, ()>,
+  drift_detector: Option<OnlineDriftDetector>,
+  drift_detectors: HashMap<Arc<str>, OnlineDriftDetector>,
+  drift_detected: bool,
+  processes_triangles: Counters<ClassesPair, ()>,
+  processes_proximities: Counters<ClassesPair, f64>,
+  traces_histories: Rc<RefCell<dyn StreamingCounter<Uuid, CaseHistory>>>,
 }
 
 unsafe impl Send for DfgDataStructureBase {}
 unsafe impl Sync for DfgDataStructureBase {}
 
 impl DfgDataStructureBase {
-  pub fn observe_dfg_relation(&mut self, process_name: &Arc<str>, relation: (Arc<str>, Arc<str>)) {
+  pub fn observe_dfg_relation(&mut self, process_name: &Arc<str>, relation: (Arc<str>, Arc<str>), stamp: DateTime<Utc>) {
     debug!(
       "Observing relation, process: {}, relation: ({}, {})",
       process_name, &relation.0, &relation.1
@@ -58,10 +115,10 @@ impl DfgDataStructureBase {
       .entry(process_name.to_owned())
       .or_insert(self.factory.create())
       .borrow_mut()
-      .observe(relation, ValueUpdateKind::DoNothing);
+      .observe_at(relation, ValueUpdateKind::DoNothing, stamp);
   }
 
-  pub fn observe_event_class(&mut self, process_name: &Arc<str>, event_class: Arc<str>) {
+  pub fn observe_event_class(&mut self, process_name: &Arc<str>, event_class: Arc<str>, stamp: DateTime<Utc>) {
     debug!("Observing event class, process: {}, event class: {}", process_name, event_class);
 
     self
@@ -69,10 +126,47 @@ impl DfgDataStructureBase {
       .entry(process_name.to_owned())
       .or_insert(self.factory.create())
       .borrow_mut()
-      .observe(event_class, ValueUpdateKind::DoNothing);
+      .observe_at(event_class, ValueUpdateKind::DoNothing, stamp);
+  }
+
+  pub fn observe_case_event(&mut self, process_name: &Arc<str>, case_id: Uuid, event_class: Arc<str>, stamp: DateTime<Utc>) {
+    let mut history = self
+      .traces_histories
+      .borrow()
+      .get(&case_id)
+      .and_then(|entry| entry.value().cloned())
+      .unwrap_or_default();
+
+    let relations = history.observe(event_class, stamp);
+    self
+      .traces_histories
+      .borrow_mut()
+      .observe_at(case_id, ValueUpdateKind::Replace(history), stamp);
+
+    if let Some(triangle) = relations.triangle {
+      self
+        .processes_triangles
+        .entry(process_name.to_owned())
+        .or_insert(self.factory.create())
+        .borrow_mut()
+        .observe_at(triangle, ValueUpdateKind::DoNothing, stamp);
+    }
+
+    if relations.proximities.is_empty() {
+      return;
+    }
+
+    let proximities = self
+      .processes_proximities
+      .entry(process_name.to_owned())
+      .or_insert(self.factory.create());
+
+    for (pair, proximity) in relations.proximities {
+      observe_proximity(&mut *proximities.borrow_mut(), pair, proximity);
+    }
   }
 
-  pub fn observe_last_trace_class(&self, case_id: Uuid, last_class: Arc<str>) {
+  pub fn observe_last_trace_class(&self, case_id: Uuid, last_class: Arc<str>, stamp: DateTime<Utc>) {
     debug!(
       "Observing last trace class, case id: {}, last class: {}",
       &case_id,
@@ -82,7 +176,7 @@ impl DfgDataStructureBase {
     self
       .traces_last_event_classes
       .borrow_mut()
-      .observe(case_id, ValueUpdateKind::Replace(last_class))
+      .observe_at(case_id, ValueUpdateKind::Replace(last_class), stamp)
   }
 
   pub fn last_seen_event_class(&self, case_id: &Uuid) -> Option<Arc<str>> {
@@ -93,6 +187,35 @@ impl DfgDataStructureBase {
       .map(|value| value.value().unwrap().clone())
   }
 
+  pub fn detect_drift(&mut self, process_name: &Arc<str>, stamp: DateTime<Utc>) {
+    let Some(detector) = self.drift_detector.as_ref() else {
+      return;
+    };
+
+    let Some(dfg) = self.processes_dfg.get(process_name) else {
+      return;
+    };
+
+    let distribution = relations_counter_distribution(&*dfg.borrow());
+    let detector = self
+      .drift_detectors
+      .entry(process_name.to_owned())
+      .or_insert_with(|| detector.clone());
+
+    if let Some(change_point) = detector.observe(distribution, stamp) {
+      info!(
+        "Detected concept drift in process {}, p-value: {}, stamp: {}",
+        process_name, change_point.p_value, change_point.stamp
+      );
+
+      self.drift_detected = true;
+    }
+  }
+
+  pub fn drift_change_points(&self, process_name: &str) -> Option<&Vec<OnlineDriftChangePoint>> {
+    self.drift_detectors.get(process_name).map(|detector| detector.change_points())
+  }
+
   pub fn to_event_log_info(&self, process_name: &str) -> Option<OfflineEventLogInfo> {
     let event_classes_count = match self.event_classes_count.get(process_name) {
       None => return None,
@@ -123,10 +246,75 @@ impl DfgDataStructureBase {
       sw.borrow_mut().invalidate();
     }
 
+    for (_, sw) in self.processes_triangles.iter() {
+      sw.borrow_mut().invalidate();
+    }
+
+    for (_, sw) in self.processes_proximities.iter() {
+      sw.borrow_mut().invalidate();
+    }
+
     self.traces_last_event_classes.borrow_mut().invalidate();
+    self.traces_histories.borrow_mut().invalidate();
 
     debug!("Finished invalidating DfgDataStructureBase");
   }
+
+  pub fn snapshot(&self) -> DfgDataStructuresSnapshot {
+    DfgDataStructuresSnapshot {
+      processes_dfg: Self::snapshot_counters(&self.processes_dfg),
+      traces_last_event_classes: self.traces_last_event_classes.borrow().snapshot(),
+      event_classes_count: Self::snapshot_counters(&self.event_classes_count),
+      drift_detectors: self.drift_detectors.clone(),
+      processes_triangles: Self::snapshot_counters(&self.processes_triangles),
+      processes_proximities: Self::snapshot_counters(&self.processes_proximities),
+      traces_histories: Some(self.traces_histories.borrow().snapshot()),
+    }
+  }
+
+  fn snapshot_counters<TKey: Hash + Eq + Clone, TValue: Clone>(counters: &Counters<TKey, TValue>) -> CountersSnapshots<TKey, TValue> {
+    counters
+      .iter()
+      .map(|(process_name, counter)| (process_name.clone(), counter.borrow().snapshot()))
+      .collect()
+  }
+
+  pub fn restore(&mut self, snapshot: DfgDataStructuresSnapshot) -> Result<(), StreamingCounterRestoreError> {
+    let traces_last_event_classes = self.factory.create::<Uuid, Arc<str>>();
+    traces_last_event_classes.borrow_mut().restore(snapshot.traces_last_event_classes)?;
+
+    let traces_histories = self.factory.create::<Uuid, CaseHistory>();
+    if let Some(histories_snapshot) = snapshot.traces_histories {
+      traces_histories.borrow_mut().restore(histories_snapshot)?;
+    }
+
+    self.processes_dfg = self.restore_counters(snapshot.processes_dfg)?;
+    self.event_classes_count = self.restore_counters(snapshot.event_classes_count)?;
+    self.processes_triangles = self.restore_counters(snapshot.processes_triangles)?;
+    self.processes_proximities = self.restore_counters(snapshot.processes_proximities)?;
+    self.traces_last_event_classes = traces_last_event_classes;
+    self.traces_histories = traces_histories;
+    self.drift_detectors = match self.drift_detector.is_some() {
+      true => snapshot.drift_detectors,
+      false => HashMap::new(),
+    };
+
+    Ok(())
+  }
+
+  fn restore_counters<TKey: Hash + Eq + Clone + 'static, TValue: Clone + 'static>(
+    &self,
+    snapshots: CountersSnapshots<TKey, TValue>,
+  ) -> Result<Counters<TKey, TValue>, StreamingCounterRestoreError> {
+    let mut counters = HashMap::new();
+    for (process_name, snapshot) in snapshots {
+      let counter = self.factory.create::<TKey, TValue>();
+      counter.borrow_mut().restore(snapshot)?;
+      counters.insert(process_name, counter);
+    }
+
+    Ok(counters)
+  }
 }
 
 #[derive(Clone)]
@@ -136,26 +324,50 @@ pub(super) struct DfgDataStructures {
 
 impl DfgDataStructures {
   pub fn new_lossy_count(error: f64) -> Self {
-    Self {
-      base: DfgDataStructureBase {
-        factory: StreamingCounterFactory::LossyCount(error),
-        traces_last_event_classes: Rc::new(RefCell::new(LossyCount::new(error))),
-        processes_dfg: HashMap::new(),
-        event_classes_count: HashMap::new(),
-      },
-    }
+    Self::from_factory(StreamingCounterFactory::LossyCount(error))
   }
 
-  pub fn new_sliding_window(lifetime: Duration) -> Self {
+  pub fn new_sliding_window(lifetime: Duration, time: StreamingTime) -> Self {
+    Self::from_factory(StreamingCounterFactory::SlidingWindow(lifetime, time))
+  }
+
+  pub fn new_space_saving(capacity: usize) -> Self {
+    Self::from_factory(StreamingCounterFactory::SpaceSaving(capacity))
+  }
+
+  pub fn new_count_min_sketch(error: f64, failure_probability: f64, support: f64) -> Self {
+    Self::from_factory(StreamingCounterFactory::CountMinSketch(error, failure_probability, support))
+  }
+
+  pub fn new_exponential_decay(half_life: Duration, time: StreamingTime) -> Self {
+    Self::from_factory(StreamingCounterFactory::ExponentialDecay(half_life, time))
+  }
+
+  fn from_factory(factory: StreamingCounterFactory) -> Self {
     Self {
       base: DfgDataStructureBase {
-        factory: StreamingCounterFactory::SlidingWindow(lifetime),
-        traces_last_event_classes: Rc::new(RefCell::new(SlidingWindow::new_time(lifetime))),
+        traces_last_event_classes: factory.create(),
+        traces_histories: factory.create(),
+        factory,
         processes_dfg: HashMap::new(),
         event_classes_count: HashMap::new(),
+        drift_detector: None,
+        drift_detectors: HashMap::new(),
+        drift_detected: false,
+        processes_triangles: HashMap::new(),
+        processes_proximities: HashMap::new(),
       },
     }
   }
+
+  pub fn set_drift_detector(&mut self, detector: OnlineDriftDetector) {
+    self.base.drift_detector = Some(detector);
+    self.base.drift_detectors.clear();
+  }
+
+  pub fn take_drift_detected(&mut self) -> bool {
+    std::mem::take(&mut self.base.drift_detected)
+  }
 }
 
 impl DfgDataStructures {
@@ -173,26 +385,44 @@ impl DfgDataStructures {
     let process_name = &process_metadata.process_name;
 
     for i in 0..(xes_trace.events().len() - 1) {
-      let first_name = xes_trace.events().get(i).unwrap().borrow().name_pointer().clone();
-      let second_name = xes_trace.events().get(i + 1).unwrap().borrow().name_pointer().clone();
+      let first = xes_trace.events().get(i).unwrap().borrow();
+      let second = xes_trace.events().get(i + 1).unwrap().borrow();
+      let (first_name, second_name) = (first.name_pointer().clone(), second.name_pointer().clone());
 
       self
         .base
-        .observe_dfg_relation(process_name, (first_name.clone(), second_name.clone()));
-      self.base.observe_event_class(process_name, first_name.clone());
+        .observe_dfg_relation(process_name, (first_name.clone(), second_name), *second.timestamp());
+      self.base.observe_event_class(process_name, first_name, *first.timestamp());
     }
 
     if let Some(last_seen_class) = self.base.last_seen_event_class(&case_metadata.case_id) {
-      let first_class = xes_trace.events().first().unwrap().borrow().name_pointer().clone();
-      self.base.observe_dfg_relation(process_name, (last_seen_class, first_class.clone()));
+      let first_event = xes_trace.events().first().unwrap().borrow();
+      self.base.observe_dfg_relation(
+        process_name,
+        (last_seen_class, first_event.name_pointer().clone()),
+        *first_event.timestamp(),
+      );
+    }
+
+    for event in xes_trace.events() {
+      let event = event.borrow();
+      self.base.observe_case_event(
+        process_name,
+        case_metadata.case_id,
+        event.name_pointer().clone(),
+        *event.timestamp(),
+      );
     }
 
     let new_trace_last_class = xes_trace.events().last().unwrap().borrow().name_pointer().clone();
+    let stamp = *xes_trace.events().last().unwrap().borrow().timestamp();
 
-    self.base.observe_event_class(process_name, new_trace_last_class.clone());
+    self.base.observe_event_class(process_name, new_trace_last_class.clone(), stamp);
     self
       .base
-      .observe_last_trace_class(case_metadata.case_id.to_owned(), new_trace_last_class.clone());
+      .observe_last_trace_class(case_metadata.case_id.to_owned(), new_trace_last_class.clone(), stamp);
+
+    self.base.detect_drift(process_name, stamp);
 
     Ok(())
   }
@@ -201,6 +431,14 @@ impl DfgDataStructures {
     self.base.invalidate();
   }
 
+  pub fn snapshot(&self) -> DfgDataStructuresSnapshot {
+    self.base.snapshot()
+  }
+
+  pub fn restore(&mut self, snapshot: DfgDataStructuresSnapshot) -> Result<(), StreamingCounterRestoreError> {
+    self.base.restore(snapshot)
+  }
+
   pub fn fill_pipeline_context(&self, context: &mut PipelineContext, case_name: &str) {
     match self.base.to_event_log_info(case_name) {
       None => {
@@ -211,5 +449,20 @@ impl DfgDataStructures {
         info!("Filled offline event log info for case {case_name}");
       }
     }
+
+    if let Some(triangles) = self.base.processes_triangles.get(case_name) {
+      context.put_concrete(TRIANGLE_RELATION_KEY.key(), triangle_relation_from_counter(&*triangles.borrow()));
+    }
+
+    if let Some(proximities) = self.base.processes_proximities.get(case_name) {
+      context.put_concrete(
+        PROXIMITY_CORRELATION_KEY.key(),
+        proximity_correlation_from_counter(&*proximities.borrow()),
+      );
+    }
+
+    if let Some(change_points) = self.base.drift_change_points(case_name) {
+      context.put_concrete(ONLINE_CONCEPT_DRIFT_CHANGE_POINTS_KEY.key(), change_points.clone());
+    }
   }
 }
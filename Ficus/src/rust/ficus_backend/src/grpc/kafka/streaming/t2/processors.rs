@@ -2,14 +2,24 @@ use crate::{
   ficus_proto::GrpcPipeline,
   grpc::{
     kafka::{
+      checkpoints::CheckpointError,
       models::{KafkaTraceProcessingError, XesFromBxesKafkaTraceCreatingError},
-      streaming::{processors::KafkaTraceProcessingContext, t2::dfg_data_structures::DfgDataStructures},
+      streaming::{
+        processors::KafkaTraceProcessingContext,
+        t2::dfg_data_structures::{DfgDataStructures, DfgDataStructuresSnapshot},
+      },
     },
     pipeline_executor::ServicePipelineExecutionContext,
   },
 };
+use chrono::{DateTime, Utc};
 use ficus::{
-  event_log::{bxes::bxes_to_xes_converter::read_bxes_events, core::event_log::EventLog, xes::xes_event_log::XesEventLogImpl},
+  event_log::{
+    bxes::bxes_to_xes_converter::read_bxes_events,
+    core::{event::event::Event, event_log::EventLog, trace::trace::Trace},
+    xes::xes_event_log::XesEventLogImpl,
+  },
+  features::{analysis::drift::online::OnlineDriftDetector, streaming::time::StreamingTime},
   pipelines::{
     context::{PipelineContext, PipelineInfrastructure},
     keys::context_keys::EVENT_LOG_KEY,
@@ -17,6 +27,7 @@ use ficus::{
   },
   utils::user_data::user_data::UserData,
 };
+use serde::{Deserialize, Serialize};
 use std::{
   cell::RefCell,
   rc::Rc,
@@ -24,29 +35,68 @@ use std::{
   time::Duration,
 };
 
+#[derive(Serialize, Deserialize)]
+pub struct T2ProcessorSnapshot {
+  dfg_data_structure: DfgDataStructuresSnapshot,
+  max_event_time: Option<DateTime<Utc>>,
+}
+
 #[derive(Clone)]
 pub struct T2StreamingProcessor {
   dfg_data_structure: Arc<Mutex<DfgDataStructures>>,
   trace_preprocessing_pipeline: Option<GrpcPipeline>,
+  time: StreamingTime,
 }
 
 impl T2StreamingProcessor {
-  pub fn new_sliding_window(element_lifetime: Duration, preprocessing_pipeline: Option<GrpcPipeline>) -> Self {
-    Self {
-      dfg_data_structure: Arc::new(Mutex::new(DfgDataStructures::new_sliding_window(element_lifetime))),
-      trace_preprocessing_pipeline: preprocessing_pipeline,
-    }
+  pub fn new_sliding_window(element_lifetime: Duration, preprocessing_pipeline: Option<GrpcPipeline>, time: StreamingTime) -> Self {
+    let dfg_data_structure = DfgDataStructures::new_sliding_window(element_lifetime, time.clone());
+    Self::new(dfg_data_structure, preprocessing_pipeline, time)
+  }
+
+  pub fn new_lossy_count(error: f64, preprocessing_pipeline: Option<GrpcPipeline>, time: StreamingTime) -> Self {
+    Self::new(DfgDataStructures::new_lossy_count(error), preprocessing_pipeline, time)
+  }
+
+  pub fn new_space_saving(capacity: usize, preprocessing_pipeline: Option<GrpcPipeline>, time: StreamingTime) -> Self {
+    Self::new(DfgDataStructures::new_space_saving(capacity), preprocessing_pipeline, time)
+  }
+
+  pub fn new_count_min_sketch(
+    error: f64,
+    failure_probability: f64,
+    support: f64,
+    preprocessing_pipeline: Option<GrpcPipeline>,
+    time: StreamingTime,
+  ) -> Self {
+    let dfg_data_structure = DfgDataStructures::new_count_min_sketch(error, failure_probability, support);
+    Self::new(dfg_data_structure, preprocessing_pipeline, time)
+  }
+
+  pub fn new_exponential_decay(half_life: Duration, preprocessing_pipeline: Option<GrpcPipeline>, time: StreamingTime) -> Self {
+    let dfg_data_structure = DfgDataStructures::new_exponential_decay(half_life, time.clone());
+    Self::new(dfg_data_structure, preprocessing_pipeline, time)
   }
 
-  pub fn new_lossy_count(error: f64, preprocessing_pipeline: Option<GrpcPipeline>) -> Self {
+  fn new(dfg_data_structure: DfgDataStructures, preprocessing_pipeline: Option<GrpcPipeline>, time: StreamingTime) -> Self {
     Self {
-      dfg_data_structure: Arc::new(Mutex::new(DfgDataStructures::new_lossy_count(error))),
+      dfg_data_structure: Arc::new(Mutex::new(dfg_data_structure)),
       trace_preprocessing_pipeline: preprocessing_pipeline,
+      time,
     }
   }
 
+  pub fn with_drift_detector(self, detector: OnlineDriftDetector) -> Self {
+    self
+      .dfg_data_structure
+      .lock()
+      .expect("Must acquire lock")
+      .set_drift_detector(detector);
+    self
+  }
+
   pub fn observe(&self, context: &mut KafkaTraceProcessingContext) -> Result<(), KafkaTraceProcessingError> {
-    let xes_trace = match read_bxes_events(context.trace.events()) {
+    let mut xes_trace = match read_bxes_events(context.trace.events()) {
       Ok(xes_trace) => xes_trace,
       Err(err) => {
         let err = XesFromBxesKafkaTraceCreatingError::BxesToXexConversionError(err);
@@ -54,6 +104,8 @@ impl T2StreamingProcessor {
       }
     };
 
+    xes_trace.remove_events_by(|event| !self.time.accept(*event.timestamp()));
+
     let xes_trace = if let Some(preprocessing_pipeline) = self.trace_preprocessing_pipeline.as_ref() {
       let mut preprocessing_context = PipelineContext::default();
       let mut log = XesEventLogImpl::default();
@@ -97,7 +149,29 @@ impl T2StreamingProcessor {
     }
   }
 
+  pub fn take_drift_detected(&self) -> bool {
+    self.dfg_data_structure.lock().expect("Must acquire lock").take_drift_detected()
+  }
+
   pub fn fill_pipeline_context(&self, context: &mut PipelineContext, case_name: &str) {
     self.dfg_data_structure.lock().unwrap().fill_pipeline_context(context, case_name);
   }
+
+  pub fn snapshot(&self) -> T2ProcessorSnapshot {
+    let dfg_data_structure = self.dfg_data_structure.lock().expect("Must acquire lock");
+
+    T2ProcessorSnapshot {
+      dfg_data_structure: dfg_data_structure.snapshot(),
+      max_event_time: self.time.max_event_time(),
+    }
+  }
+
+  pub fn restore(&self, snapshot: T2ProcessorSnapshot) -> Result<(), CheckpointError> {
+    self.time.restore_max_event_time(snapshot.max_event_time);
+
+    let mut dfg_data_structure = self.dfg_data_structure.lock().expect("Must acquire lock");
+    dfg_data_structure.restore(snapshot.dfg_data_structure)?;
+
+    Ok(())
+  }
 }
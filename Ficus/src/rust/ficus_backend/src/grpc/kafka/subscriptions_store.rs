@@ -0,0 +1,94 @@
+use crate::ficus_proto::GrpcKafkaSubscriptionsSet;
+use log::info;
+use prost::Message;
+use std::{
+  fmt::{Display, Formatter},
+  fs::{self, OpenOptions},
+  io::{self, Write},
+  path::{Path, PathBuf},
+  sync::Mutex,
+};
+
+#[derive(Debug)]
+pub enum SubscriptionsStoreError {
+  Io(PathBuf, io::Error),
+  Decode(prost::DecodeError),
+}
+
+impl Display for SubscriptionsStoreError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      SubscriptionsStoreError::Io(path, err) => write!(f, "Failed to access subscriptions file {}: {}", path.display(), err),
+      SubscriptionsStoreError::Decode(err) => write!(f, "Failed to decode subscriptions: {}", err),
+    }
+  }
+}
+
+// Keeps definitions of all kafka subscriptions and their pipelines as a single prost-encoded file,
+// the file is rewritten on every change so that subscriptions can be re-created after a backend restart.
+// Connection metadata may contain credentials, so the file is readable only by its owner.
+pub struct KafkaSubscriptionsStore {
+  path: PathBuf,
+  lock: Mutex<()>,
+}
+
+impl KafkaSubscriptionsStore {
+  pub fn new(path: PathBuf) -> Self {
+    info!("Kafka subscriptions are persisted to {}", path.display());
+
+    Self {
+      path,
+      lock: Mutex::new(()),
+    }
+  }
+
+  pub fn load(&self) -> Result<GrpcKafkaSubscriptionsSet, SubscriptionsStoreError> {
+    let _guard = self.lock.lock().expect("Must acquire lock");
+    if !self.path.is_file() {
+      return Ok(GrpcKafkaSubscriptionsSet::default());
+    }
+
+    let bytes = fs::read(&self.path).map_err(|err| SubscriptionsStoreError::Io(self.path.to_owned(), err))?;
+    decode_subscriptions(&bytes)
+  }
+
+  /// The snapshot is taken under the store lock, so a snapshot of older state never overwrites a newer one
+  pub fn save(&self, snapshot: impl FnOnce() -> GrpcKafkaSubscriptionsSet) -> Result<(), SubscriptionsStoreError> {
+    let _guard = self.lock.lock().expect("Must acquire lock");
+    let subscriptions = snapshot();
+
+    if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+      fs::create_dir_all(parent).map_err(|err| SubscriptionsStoreError::Io(parent.to_owned(), err))?;
+    }
+
+    let temp_path = self.path.with_extension("tmp");
+    write_owner_only_file(&temp_path, &subscriptions.encode_to_vec())
+      .and_then(|_| fs::rename(&temp_path, &self.path))
+      .map_err(|err| SubscriptionsStoreError::Io(self.path.to_owned(), err))
+  }
+}
+
+fn write_owner_only_file(path: &Path, bytes: &[u8]) -> io::Result<()> {
+  // The mode is applied only to created files, so a leftover file must not be reused
+  match fs::remove_file(path) {
+    Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+    _ => {}
+  }
+
+  let mut options = OpenOptions::new();
+  options.write(true).create_new(true);
+
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::OpenOptionsExt;
+    options.mode(0o600);
+  }
+
+  let mut file = options.open(path)?;
+  file.write_all(bytes)?;
+  file.sync_all()
+}
+
+pub fn decode_subscriptions(bytes: &[u8]) -> Result<GrpcKafkaSubscriptionsSet, SubscriptionsStoreError> {
+  GrpcKafkaSubscriptionsSet::decode(bytes).map_err(SubscriptionsStoreError::Decode)
+}
@@ -1,4 +1,4 @@
-use ficus::pipelines::{context::LogMessageHandler, errors::pipeline_errors::PipelinePartExecutionError};
+use ficus::pipelines::{context::LogMessageHandler, errors::pipeline_errors::PipelinePartExecutionError, results_cache::CacheHitHandler};
 use log::info;
 use std::sync::Arc;
 
@@ -21,6 +21,23 @@ impl GrpcLogMessageHandlerImpl {
   }
 }
 
+pub struct GrpcCacheHitHandlerImpl {
+  sender: Arc<dyn PipelineEventsHandler>,
+}
+
+impl CacheHitHandler for GrpcCacheHitHandlerImpl {
+  fn handle(&self, part_name: &str) {
+    info!("Reused cached results of pipeline part {}", part_name);
+    self.sender.handle(&PipelineEvent::CacheHit(part_name.to_string()));
+  }
+}
+
+impl GrpcCacheHitHandlerImpl {
+  pub fn new(sender: Arc<dyn PipelineEventsHandler>) -> Self {
+    Self { sender }
+  }
+}
+
 #[derive(Clone)]
 pub struct ConsoleLogMessageHandler {}
 
@@ -0,0 +1,34 @@
+use ficus::utils::allocations_tracking::record_allocation;
+use std::alloc::{GlobalAlloc, Layout, System};
+
+// Reports allocations to the counter entered by the allocating thread, so that a memory budget of a pipeline
+// is checked against bytes allocated by all threads of its execution without measuring memory of the whole process.
+pub struct AllocationsTrackingAllocator;
+
+#[global_allocator]
+static GLOBAL_ALLOCATOR: AllocationsTrackingAllocator = AllocationsTrackingAllocator;
+
+unsafe impl GlobalAlloc for AllocationsTrackingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    let ptr = unsafe { System.alloc(layout) };
+    if !ptr.is_null() {
+      record_allocation(layout.size() as isize);
+    }
+
+    ptr
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    unsafe { System.dealloc(ptr, layout) };
+    record_allocation(-(layout.size() as isize));
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+    if !new_ptr.is_null() {
+      record_allocation(new_size as isize - layout.size() as isize);
+    }
+
+    new_ptr
+  }
+}
@@ -0,0 +1,423 @@
+use axum::{Router, extract::State, http::header, response::IntoResponse, routing::get};
+use ficus::pipelines::{
+  context::{PipelineContext, PipelineInfrastructure},
+  errors::pipeline_errors::PipelinePartExecutionError,
+  pipelines::PipelinePart,
+};
+use log::info;
+use std::{
+  collections::HashMap,
+  fmt::Write,
+  net::SocketAddr,
+  sync::{
+    Arc, Mutex,
+    atomic::{AtomicU64, Ordering},
+  },
+  time::Duration,
+};
+use tokio::net::TcpListener;
+
+const METRICS_PATH: &str = "/metrics";
+const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+const PIPELINE_DURATION_BUCKETS: [f64; 10] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+struct Histogram {
+  buckets_counts: Vec<u64>,
+  sum: f64,
+  count: u64,
+}
+
+impl Histogram {
+  fn new() -> Self {
+    Self {
+      buckets_counts: vec![0; PIPELINE_DURATION_BUCKETS.len()],
+      sum: 0.0,
+      count: 0,
+    }
+  }
+
+  fn observe(&mut self, value: f64) {
+    for (index, bound) in PIPELINE_DURATION_BUCKETS.iter().enumerate() {
+      if value <= *bound {
+        self.buckets_counts[index] += 1;
+      }
+    }
+
+    self.sum += value;
+    self.count += 1;
+  }
+}
+
+pub enum PipelineExecutionOutcome {
+  Success,
+  Failure,
+  Cancelled,
+}
+
+pub struct BackendMetrics {
+  pipelines_started: AtomicU64,
+  pipelines_succeeded: AtomicU64,
+  pipelines_failed: AtomicU64,
+  pipelines_cancelled: AtomicU64,
+  pipeline_duration: Mutex<Histogram>,
+  part_failures: Mutex<HashMap<String, u64>>,
+  kafka_traces_consumed: Mutex<HashMap<String, u64>>,
+  kafka_traces_failed: Mutex<HashMap<String, u64>>,
+  kafka_consumer_lag: Mutex<HashMap<String, i64>>,
+  context_values_count: AtomicU64,
+}
+
+impl Default for BackendMetrics {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl BackendMetrics {
+  pub fn new() -> Self {
+    Self {
+      pipelines_started: AtomicU64::new(0),
+      pipelines_succeeded: AtomicU64::new(0),
+      pipelines_failed: AtomicU64::new(0),
+      pipelines_cancelled: AtomicU64::new(0),
+      pipeline_duration: Mutex::new(Histogram::new()),
+      part_failures: Mutex::new(HashMap::new()),
+      kafka_traces_consumed: Mutex::new(HashMap::new()),
+      kafka_traces_failed: Mutex::new(HashMap::new()),
+      kafka_consumer_lag: Mutex::new(HashMap::new()),
+      context_values_count: AtomicU64::new(0),
+    }
+  }
+
+  pub fn pipeline_started(&self) {
+    self.pipelines_started.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn pipeline_finished(&self, outcome: PipelineExecutionOutcome, duration: Duration) {
+    let counter = match outcome {
+      PipelineExecutionOutcome::Success => &self.pipelines_succeeded,
+      PipelineExecutionOutcome::Failure => &self.pipelines_failed,
+      PipelineExecutionOutcome::Cancelled => &self.pipelines_cancelled,
+    };
+
+    counter.fetch_add(1, Ordering::Relaxed);
+    self.pipeline_duration.lock().unwrap().observe(duration.as_secs_f64());
+  }
+
+  pub fn pipeline_part_failed(&self, part_name: &str) {
+    Self::increment(&self.part_failures, part_name);
+  }
+
+  pub fn kafka_trace_consumed(&self, subscription_name: &str) {
+    Self::increment(&self.kafka_traces_consumed, subscription_name);
+  }
+
+  pub fn kafka_trace_failed(&self, subscription_name: &str) {
+    Self::increment(&self.kafka_traces_failed, subscription_name);
+  }
+
+  pub fn set_kafka_consumer_lag(&self, subscription_name: &str, lag: i64) {
+    self.kafka_consumer_lag.lock().unwrap().insert(subscription_name.to_owned(), lag);
+  }
+
+  pub fn remove_kafka_consumer_lag(&self, subscription_name: &str) {
+    self.kafka_consumer_lag.lock().unwrap().remove(subscription_name);
+  }
+
+  pub fn set_context_values_count(&self, count: usize) {
+    self.context_values_count.store(count as u64, Ordering::Relaxed);
+  }
+
+  fn increment(counters: &Mutex<HashMap<String, u64>>, label: &str) {
+    *counters.lock().unwrap().entry(label.to_owned()).or_insert(0) += 1;
+  }
+
+  pub fn render(&self) -> String {
+    let mut out = String::new();
+
+    Self::write_header(&mut out, "ficus_pipelines_started_total", "Started pipelines executions", "counter");
+    Self::write_value(
+      &mut out,
+      "ficus_pipelines_started_total",
+      self.pipelines_started.load(Ordering::Relaxed),
+    );
+
+    Self::write_header(
+      &mut out,
+      "ficus_pipelines_finished_total",
+      "Finished pipelines executions",
+      "counter",
+    );
+    for (outcome, counter) in [
+      ("success", &self.pipelines_succeeded),
+      ("failure", &self.pipelines_failed),
+      ("cancelled", &self.pipelines_cancelled),
+    ] {
+      let name = format!("ficus_pipelines_finished_total{{outcome=\"{}\"}}", outcome);
+      Self::write_value(&mut out, &name, counter.load(Ordering::Relaxed));
+    }
+
+    self.write_pipeline_duration(&mut out);
+
+    Self::write_labeled(
+      &mut out,
+      "ficus_pipeline_part_failures_total",
+      "Failed pipeline parts executions",
+      "counter",
+      "part",
+      &self.part_failures.lock().unwrap(),
+    );
+
+    Self::write_labeled(
+      &mut out,
+      "ficus_kafka_traces_consumed_total",
+      "Traces consumed from kafka",
+      "counter",
+      "subscription",
+      &self.kafka_traces_consumed.lock().unwrap(),
+    );
+
+    Self::write_labeled(
+      &mut out,
+      "ficus_kafka_traces_failed_total",
+      "Traces consumed from kafka which failed to be processed",
+      "counter",
+      "subscription",
+      &self.kafka_traces_failed.lock().unwrap(),
+    );
+
+    Self::write_labeled(
+      &mut out,
+      "ficus_kafka_consumer_lag",
+      "Messages which are not yet consumed by kafka subscription",
+      "gauge",
+      "subscription",
+      &self.kafka_consumer_lag.lock().unwrap(),
+    );
+
+    Self::write_header(&mut out, "ficus_context_values", "Context values in store", "gauge");
+    Self::write_value(&mut out, "ficus_context_values", self.context_values_count.load(Ordering::Relaxed));
+
+    out
+  }
+
+  fn write_pipeline_duration(&self, out: &mut String) {
+    const NAME: &str = "ficus_pipeline_duration_seconds";
+    Self::write_header(out, NAME, "Pipelines execution duration", "histogram");
+
+    let histogram = self.pipeline_duration.lock().unwrap();
+    for (bound, count) in PIPELINE_DURATION_BUCKETS.iter().zip(histogram.buckets_counts.iter()) {
+      Self::write_value(out, &format!("{}_bucket{{le=\"{}\"}}", NAME, bound), count);
+    }
+
+    Self::write_value(out, &format!("{}_bucket{{le=\"+Inf\"}}", NAME), histogram.count);
+    Self::write_value(out, &format!("{}_sum", NAME), histogram.sum);
+    Self::write_value(out, &format!("{}_count", NAME), histogram.count);
+  }
+
+  fn write_header(out: &mut String, name: &str, help: &str, metric_type: &str) {
+    writeln!(out, "# HELP {} {}", name, help).unwrap();
+    writeln!(out, "# TYPE {} {}", name, metric_type).unwrap();
+  }
+
+  fn write_value(out: &mut String, name: &str, value: impl std::fmt::Display) {
+    writeln!(out, "{} {}", name, value).unwrap();
+  }
+
+  fn write_labeled<T: std::fmt::Display>(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    metric_type: &str,
+    label: &str,
+    values: &HashMap<String, T>,
+  ) {
+    Self::write_header(out, name, help, metric_type);
+
+    let mut values = values.iter().collect::<Vec<_>>();
+    values.sort_by(|first, second| first.0.cmp(second.0));
+
+    for (label_value, value) in values {
+      let label_value = label_value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+      Self::write_value(out, &format!("{}{{{}=\"{}\"}}", name, label, label_value), value);
+    }
+  }
+}
+
+pub struct MeteredPipelinePart {
+  name: String,
+  part: Box<dyn PipelinePart>,
+  metrics: Arc<BackendMetrics>,
+}
+
+impl MeteredPipelinePart {
+  pub fn new(name: String, part: Box<dyn PipelinePart>, metrics: Arc<BackendMetrics>) -> Self {
+    Self { name, part, metrics }
+  }
+}
+
+impl PipelinePart for MeteredPipelinePart {
+  fn execute(&self, context: &mut PipelineContext, infra: &PipelineInfrastructure) -> Result<(), PipelinePartExecutionError> {
+    let result = self.part.execute(context, infra);
+
+    match &result {
+      Ok(()) | Err(PipelinePartExecutionError::Cancelled) => {}
+      Err(_) => self.metrics.pipeline_part_failed(&self.name),
+    }
+
+    result
+  }
+}
+
+pub fn metrics_router(metrics: Arc<BackendMetrics>) -> Router {
+  Router::new().route(METRICS_PATH, get(render_metrics)).with_state(metrics)
+}
+
+async fn render_metrics(State(metrics): State<Arc<BackendMetrics>>) -> impl IntoResponse {
+  ([(header::CONTENT_TYPE, METRICS_CONTENT_TYPE)], metrics.render())
+}
+
+pub async fn serve_metrics(metrics: Arc<BackendMetrics>, address: SocketAddr) -> std::io::Result<()> {
+  let listener = TcpListener::bind(address).await?;
+  info!("Serving metrics at {}{}", address, METRICS_PATH);
+
+  axum::serve(listener, metrics_router(metrics)).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+  fn rendered_lines(metrics: &BackendMetrics) -> Vec<String> {
+    metrics.render().lines().map(|line| line.to_owned()).collect()
+  }
+
+  fn assert_contains(lines: &[String], expected: &[&str]) {
+    for line in expected {
+      assert!(lines.iter().any(|l| l == line), "{} is not rendered", line);
+    }
+  }
+
+  #[test]
+  fn test_render_pipelines_counters() {
+    let metrics = BackendMetrics::new();
+    metrics.pipeline_started();
+    metrics.pipeline_started();
+    metrics.pipeline_finished(PipelineExecutionOutcome::Success, Duration::from_millis(10));
+    metrics.pipeline_finished(PipelineExecutionOutcome::Cancelled, Duration::from_millis(10));
+
+    assert_contains(
+      &rendered_lines(&metrics),
+      &[
+        "# HELP ficus_pipelines_started_total Started pipelines executions",
+        "# TYPE ficus_pipelines_started_total counter",
+        "ficus_pipelines_started_total 2",
+        "ficus_pipelines_finished_total{outcome=\"success\"} 1",
+        "ficus_pipelines_finished_total{outcome=\"failure\"} 0",
+        "ficus_pipelines_finished_total{outcome=\"cancelled\"} 1",
+      ],
+    );
+  }
+
+  #[test]
+  fn test_render_pipeline_duration_histogram() {
+    let metrics = BackendMetrics::new();
+    metrics.pipeline_finished(PipelineExecutionOutcome::Success, Duration::from_millis(200));
+    metrics.pipeline_finished(PipelineExecutionOutcome::Failure, Duration::from_secs(2));
+    metrics.pipeline_finished(PipelineExecutionOutcome::Success, Duration::from_secs(100));
+
+    assert_contains(
+      &rendered_lines(&metrics),
+      &[
+        "# TYPE ficus_pipeline_duration_seconds histogram",
+        "ficus_pipeline_duration_seconds_bucket{le=\"0.1\"} 0",
+        "ficus_pipeline_duration_seconds_bucket{le=\"0.25\"} 1",
+        "ficus_pipeline_duration_seconds_bucket{le=\"2.5\"} 2",
+        "ficus_pipeline_duration_seconds_bucket{le=\"60\"} 2",
+        "ficus_pipeline_duration_seconds_bucket{le=\"+Inf\"} 3",
+        "ficus_pipeline_duration_seconds_sum 102.2",
+        "ficus_pipeline_duration_seconds_count 3",
+      ],
+    );
+  }
+
+  #[test]
+  fn test_render_labeled_values() {
+    let metrics = BackendMetrics::new();
+    metrics.kafka_trace_consumed("second");
+    metrics.kafka_trace_consumed("first");
+    metrics.kafka_trace_consumed("first");
+    metrics.pipeline_part_failed("Part \"quoted\"\\");
+    metrics.set_kafka_consumer_lag("first", 5);
+    metrics.set_kafka_consumer_lag("second", 7);
+    metrics.remove_kafka_consumer_lag("second");
+    metrics.set_context_values_count(3);
+
+    let lines = rendered_lines(&metrics);
+    assert_contains(
+      &lines,
+      &[
+        "ficus_pipeline_part_failures_total{part=\"Part \\\"quoted\\\"\\\\\"} 1",
+        "ficus_kafka_consumer_lag{subscription=\"first\"} 5",
+        "# TYPE ficus_context_values gauge",
+        "ficus_context_values 3",
+      ],
+    );
+
+    let consumed = lines
+      .iter()
+      .filter(|line| line.starts_with("ficus_kafka_traces_consumed_total{"))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      consumed,
+      vec![
+        "ficus_kafka_traces_consumed_total{subscription=\"first\"} 2",
+        "ficus_kafka_traces_consumed_total{subscription=\"second\"} 1",
+      ]
+    );
+
+    assert!(
+      !lines
+        .iter()
+        .any(|line| line.starts_with("ficus_kafka_consumer_lag{subscription=\"second\"}"))
+    );
+    assert!(!lines.iter().any(|line| line.starts_with("ficus_kafka_traces_failed_total{")));
+  }
+
+  async fn send_request(address: SocketAddr, request: &str) -> String {
+    let mut stream = tokio::net::TcpStream::connect(address).await.unwrap();
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    response
+  }
+
+  #[tokio::test]
+  async fn test_metrics_endpoint() {
+    let metrics = Arc::new(BackendMetrics::new());
+    metrics.pipeline_started();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(axum::serve(listener, metrics_router(metrics.clone())).into_future());
+
+    let long_header = "x".repeat(4096);
+    let request = format!(
+      "GET {} HTTP/1.1\r\nHost: localhost\r\nX-Padding: {}\r\nConnection: close\r\n\r\n",
+      METRICS_PATH, long_header
+    );
+
+    let response = send_request(address, &request).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains(&format!("content-type: {}", METRICS_CONTENT_TYPE)));
+    assert!(response.ends_with(&metrics.render()));
+
+    let response = send_request(address, "GET /unknown HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+  }
+}
@@ -1,9 +1,16 @@
 pub mod backend_service;
 pub mod context_values_service;
 pub mod converters;
+pub mod cv_store;
 pub mod events;
+pub mod execution_registry;
 pub mod get_context_pipeline;
 pub mod grpc_utils;
+pub mod health;
 pub mod kafka;
 pub mod logs_handler;
+pub mod memory_tracking;
+pub mod metrics;
 pub mod pipeline_executor;
+pub mod pipeline_validation;
+pub mod scheduler;
@@ -1,11 +1,12 @@
 use super::events::events_handler::PipelineEventsHandler;
 use crate::{
-  ficus_proto::{GrpcContextKey, GrpcContextKeyValue, GrpcPipeline, GrpcPipelinePart, grpc_pipeline_part_base::Part},
+  ficus_proto::{GrpcContextKey, GrpcContextKeyValue, GrpcPipeline, GrpcPipelinePart, GrpcPipelinePartBase, grpc_pipeline_part_base::Part},
   grpc::{
     context_values_service::ContextValueService,
     converters::{convert_to_grpc_context_value, put_into_user_data},
     get_context_pipeline::GetContextValuePipelinePart,
-    logs_handler::{ConsoleLogMessageHandler, DelegatingLogMessageHandler, GrpcLogMessageHandlerImpl},
+    logs_handler::{ConsoleLogMessageHandler, DelegatingLogMessageHandler, GrpcCacheHitHandlerImpl, GrpcLogMessageHandlerImpl},
+    metrics::{BackendMetrics, MeteredPipelinePart, PipelineExecutionOutcome},
   },
 };
 use ficus::{
@@ -14,14 +15,17 @@ use ficus::{
     errors::pipeline_errors::PipelinePartExecutionError,
     keys::context_keys::{EXECUTION_ID_KEY, find_context_key},
     pipeline_parts::PipelineParts,
-    pipelines::{DefaultPipelinePart, Pipeline, PipelinePart},
+    pipelines::{DefaultPipelinePart, ParallelPipelinePart, Pipeline, PipelinePart},
+    results_cache::PipelinePartsResultsCache,
   },
   utils::{
+    cancellation::CancellationToken,
     context_key::DefaultContextKey,
+    execution_budget::ExecutionBudget,
     user_data::user_data::{UserData, UserDataImpl},
   },
 };
-use std::{collections::HashMap, str::FromStr, sync::Arc};
+use std::{collections::HashMap, str::FromStr, sync::Arc, time::Instant};
 use uuid::Uuid;
 
 pub(super) struct ServicePipelineExecutionContext<'a> {
@@ -30,6 +34,10 @@ pub(super) struct ServicePipelineExecutionContext<'a> {
   pipeline_parts: Arc<PipelineParts>,
   handler: Arc<dyn PipelineEventsHandler>,
   log_message_handler: Arc<dyn LogMessageHandler>,
+  cancellation_token: CancellationToken,
+  execution_budget: ExecutionBudget,
+  results_cache: Option<Arc<PipelinePartsResultsCache>>,
+  metrics: Option<Arc<BackendMetrics>>,
 }
 
 impl<'a> ServicePipelineExecutionContext<'a> {
@@ -47,9 +55,33 @@ impl<'a> ServicePipelineExecutionContext<'a> {
       pipeline_parts,
       handler,
       log_message_handler,
+      cancellation_token: CancellationToken::new(),
+      execution_budget: ExecutionBudget::unlimited(),
+      results_cache: None,
+      metrics: None,
     }
   }
 
+  pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+    self.cancellation_token = cancellation_token;
+    self
+  }
+
+  pub fn with_execution_budget(mut self, execution_budget: ExecutionBudget) -> Self {
+    self.execution_budget = execution_budget;
+    self
+  }
+
+  pub fn with_results_cache(mut self, results_cache: Option<Arc<PipelinePartsResultsCache>>) -> Self {
+    self.results_cache = results_cache;
+    self
+  }
+
+  pub fn with_metrics(mut self, metrics: Option<Arc<BackendMetrics>>) -> Self {
+    self.metrics = metrics;
+    self
+  }
+
   fn create_log_message_handler(sender: Arc<dyn PipelineEventsHandler>) -> Arc<dyn LogMessageHandler> {
     let grpc_handler = GrpcLogMessageHandlerImpl::new(sender.clone());
     let grpc_handler = Box::new(grpc_handler) as Box<dyn LogMessageHandler>;
@@ -89,6 +121,10 @@ impl<'a> ServicePipelineExecutionContext<'a> {
       pipeline_parts: self.pipeline_parts.clone(),
       handler: self.handler.clone(),
       log_message_handler: self.log_message_handler.clone(),
+      cancellation_token: self.cancellation_token.clone(),
+      execution_budget: self.execution_budget.clone(),
+      results_cache: self.results_cache.clone(),
+      metrics: self.metrics.clone(),
     }
   }
 
@@ -104,9 +140,34 @@ impl<'a> ServicePipelineExecutionContext<'a> {
 
     context_mutator(&mut pipeline_context)?;
 
-    let infra = PipelineInfrastructure::new(Some(self.log_message_handler()));
+    let mut infra = PipelineInfrastructure::new(Some(self.log_message_handler()))
+      .with_cancellation_token(self.cancellation_token.clone())
+      .with_execution_budget(self.execution_budget.clone());
+
+    if let Some(results_cache) = self.results_cache.as_ref() {
+      infra = infra
+        .with_results_cache(results_cache.clone())
+        .with_cache_hit_handler(Arc::new(GrpcCacheHitHandlerImpl::new(self.sender())));
+    }
+
+    if let Some(metrics) = self.metrics.as_ref() {
+      metrics.pipeline_started();
+    }
+
+    let start_time = Instant::now();
+    let result = infra.execute_watched(&pipeline, &mut pipeline_context);
+
+    if let Some(metrics) = self.metrics.as_ref() {
+      let outcome = match &result {
+        Ok(()) => PipelineExecutionOutcome::Success,
+        Err(PipelinePartExecutionError::Cancelled) => PipelineExecutionOutcome::Cancelled,
+        Err(_) => PipelineExecutionOutcome::Failure,
+      };
+
+      metrics.pipeline_finished(outcome, start_time.elapsed());
+    }
 
-    match pipeline.execute(&mut pipeline_context, &infra) {
+    match result {
       Ok(()) => Ok((id, pipeline_context.devastate_user_data())),
       Err(err) => Err(err),
     }
@@ -146,16 +207,28 @@ impl<'a> ServicePipelineExecutionContext<'a> {
   }
 
   pub(super) fn to_pipeline(&self) -> Pipeline {
+    self.to_pipeline_from_parts(&self.grpc_pipeline().parts)
+  }
+
+  fn to_pipeline_from_parts(&self, grpc_parts: &[GrpcPipelinePartBase]) -> Pipeline {
     let mut pipeline = Pipeline::default();
-    for grpc_part in &self.grpc_pipeline().parts {
+    for grpc_part in grpc_parts {
       match grpc_part.part.as_ref().unwrap() {
         Part::DefaultPart(grpc_default_part) => match self.find_default_part(grpc_default_part) {
           Some(found_part) => {
-            pipeline.push(found_part);
+            pipeline.push(self.metered(&grpc_default_part.name, found_part));
           }
           None => todo!(),
         },
-        Part::ParallelPart(_) => todo!(),
+        Part::ParallelPart(part) => {
+          let branches = part
+            .pipeline_parts
+            .iter()
+            .map(|branch_part| self.to_pipeline_from_parts(std::slice::from_ref(branch_part)))
+            .collect();
+
+          pipeline.push(Box::new(ParallelPipelinePart::new(branches)));
+        }
         Part::SimpleContextRequestPart(part) => {
           let key_name = part.key.as_ref().unwrap().name.clone();
           let uuid = Uuid::from_str(&part.frontend_part_uuid.as_ref().unwrap().guid).ok().unwrap();
@@ -188,6 +261,13 @@ impl<'a> ServicePipelineExecutionContext<'a> {
     pipeline
   }
 
+  fn metered(&self, part_name: &str, part: Box<dyn PipelinePart>) -> Box<dyn PipelinePart> {
+    match self.metrics.as_ref() {
+      Some(metrics) => Box::new(MeteredPipelinePart::new(part_name.to_owned(), part, metrics.clone())),
+      None => part,
+    }
+  }
+
   fn create_get_context_part(
     key_names: Vec<String>,
     uuid: Uuid,
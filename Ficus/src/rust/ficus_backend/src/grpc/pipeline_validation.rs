@@ -0,0 +1,79 @@
+use crate::{
+  ficus_proto::{GrpcPipeline, GrpcPipelinePart, GrpcPipelineValidationIssue, grpc_pipeline_part_base::Part},
+  grpc::converters::context_value_type_name,
+};
+use ficus::{
+  pipelines::{
+    keys::context_keys::EXECUTION_ID_KEY,
+    pipeline_parts::PipelineParts,
+    validation::{PipelineValidationConfigValue, PipelineValidationPart, PipelineValidationStep, validate_pipeline},
+  },
+  utils::user_data::keys::Key,
+};
+
+pub(super) fn validate_grpc_pipeline(
+  parts: &PipelineParts,
+  grpc_pipeline: &GrpcPipeline,
+  mut initial_keys: Vec<String>,
+) -> Vec<GrpcPipelineValidationIssue> {
+  initial_keys.push(EXECUTION_ID_KEY.key().name().to_owned());
+
+  validate_pipeline(parts, &to_validation_steps(grpc_pipeline), &initial_keys)
+    .into_iter()
+    .map(|issue| GrpcPipelineValidationIssue {
+      location: issue.location().iter().map(|i| *i as u32).collect(),
+      message: issue.kind().to_string(),
+    })
+    .collect()
+}
+
+fn to_validation_steps(grpc_pipeline: &GrpcPipeline) -> Vec<PipelineValidationStep> {
+  grpc_pipeline
+    .parts
+    .iter()
+    .filter_map(|part| part.part.as_ref().map(to_validation_step))
+    .collect()
+}
+
+fn to_validation_step(part: &Part) -> PipelineValidationStep {
+  match part {
+    Part::DefaultPart(part) => PipelineValidationStep::Part(to_validation_part(part)),
+    Part::ParallelPart(part) => PipelineValidationStep::Parallel(
+      part
+        .pipeline_parts
+        .iter()
+        .map(|branch| branch.part.as_ref().map(to_validation_step).into_iter().collect())
+        .collect(),
+    ),
+    Part::SimpleContextRequestPart(part) => PipelineValidationStep::ContextRequest {
+      keys: part.key.iter().map(|k| k.name.to_owned()).collect(),
+      before_part: None,
+    },
+    Part::ComplexContextRequestPart(part) => PipelineValidationStep::ContextRequest {
+      keys: part.keys.iter().map(|k| k.name.to_owned()).collect(),
+      before_part: part.before_pipeline_part.as_ref().map(to_validation_part),
+    },
+  }
+}
+
+fn to_validation_part(part: &GrpcPipelinePart) -> PipelineValidationPart {
+  let config = match part.configuration.as_ref() {
+    None => vec![],
+    Some(configuration) => configuration
+      .configuration_parameters
+      .iter()
+      .filter_map(|kv| {
+        let key_name = kv.key.as_ref()?.name.to_owned();
+        let type_name = kv
+          .value
+          .as_ref()
+          .and_then(|v| v.context_value.as_ref())
+          .and_then(context_value_type_name);
+
+        Some(PipelineValidationConfigValue::new(key_name, type_name))
+      })
+      .collect(),
+  };
+
+  PipelineValidationPart::new(part.name.to_owned(), config)
+}
@@ -0,0 +1,12 @@
+use ficus::pipelines::scheduler::{PipelineScheduler, PipelineSchedulerConfig};
+use tokio::runtime::Handle;
+
+/// Pipelines are executed on the blocking pool of the runtime
+pub fn create_scheduler(config: PipelineSchedulerConfig, runtime: Handle) -> PipelineScheduler {
+  PipelineScheduler::new(
+    config,
+    Box::new(move |job| {
+      runtime.spawn_blocking(job);
+    }),
+  )
+}
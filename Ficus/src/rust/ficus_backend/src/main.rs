@@ -1,4 +1,5 @@
 use crate::{
+  config::BackendConfig,
   ficus_proto::{
     grpc_backend_service_server::GrpcBackendServiceServer, grpc_context_values_service_server::GrpcContextValuesServiceServer,
     grpc_kafka_service_server::GrpcKafkaServiceServer,
@@ -6,37 +7,140 @@ use crate::{
   grpc::{
     backend_service::FicusService,
     context_values_service::{ContextValueService, GrpcContextValueService},
-    kafka::grpc_kafka_service::GrpcKafkaServiceImpl,
+    cv_store::config::create_context_value_store,
+    health::BackendHealth,
+    kafka::{checkpoints::CheckpointStore, grpc_kafka_service::GrpcKafkaServiceImpl, subscriptions_store::KafkaSubscriptionsStore},
+    metrics::{BackendMetrics, serve_metrics},
   },
 };
-use log::{LevelFilter, info};
-use std::sync::Arc;
-use tonic::transport::Server;
+use futures::{FutureExt, future};
+use log::{error, info};
+use std::{fs, process, sync::Arc};
+use tokio::signal;
+use tonic::transport::{Identity, Server, ServerTlsConfig, server::TcpIncoming};
 
 pub mod ficus_proto {
   tonic::include_proto!("ficus");
 }
 
+mod config;
 mod grpc;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-  colog::basic_builder().filter_level(LevelFilter::Info).init();
+  let config = match BackendConfig::load() {
+    Ok(config) => config,
+    Err(err) => {
+      eprintln!("Invalid backend configuration: {}", err);
+      process::exit(1);
+    }
+  };
 
-  let cv_service = Arc::new(ContextValueService::new());
-  let grpc_cv_service = GrpcContextValuesServiceServer::new(GrpcContextValueService::new(cv_service.clone()));
+  colog::basic_builder().filter_level(config.log_level_filter()?).init();
 
-  let backend_service = GrpcBackendServiceServer::new(FicusService::new(cv_service.clone()));
-  let kafka_service = GrpcKafkaServiceServer::new(GrpcKafkaServiceImpl::new(cv_service.clone()));
+  let metrics = Arc::new(BackendMetrics::new());
 
-  info!("Starting server");
+  let cv_store = create_context_value_store(&config.context_value_store_kind()?, config.context_value_store_limits())?;
+  let cv_service = Arc::new(ContextValueService::new(cv_store, metrics.clone()));
+  let grpc_cv_service = GrpcContextValuesServiceServer::new(GrpcContextValueService::new(
+    cv_service.clone(),
+    config.context_value_chunk_size_bytes,
+  ))
+  .max_decoding_message_size(config.max_message_size_bytes)
+  .max_encoding_message_size(config.max_message_size_bytes);
 
-  Server::builder()
-    .add_service(grpc_cv_service)
-    .add_service(backend_service)
-    .add_service(kafka_service)
-    .serve("[::]:8080".parse()?)
-    .await?;
+  let backend_service = FicusService::new(
+    cv_service.clone(),
+    config.scheduler_config()?,
+    &config.results_cache,
+    metrics.clone(),
+  );
+  let backend_service = GrpcBackendServiceServer::new(backend_service)
+    .max_decoding_message_size(config.max_message_size_bytes)
+    .max_encoding_message_size(config.max_message_size_bytes);
+
+  let checkpoints = CheckpointStore::create(&config.kafka_persistence);
+  let subscriptions_store = config
+    .kafka_persistence
+    .subscriptions_file
+    .clone()
+    .map(KafkaSubscriptionsStore::new);
+  let kafka_service = GrpcKafkaServiceImpl::new(
+    cv_service.clone(),
+    metrics.clone(),
+    config.kafka.clone(),
+    checkpoints,
+    subscriptions_store,
+  );
+  let kafka_service = GrpcKafkaServiceServer::new(kafka_service)
+    .max_decoding_message_size(config.max_message_size_bytes)
+    .max_encoding_message_size(config.max_message_size_bytes);
+
+  let (health_reporter, health_service) = tonic_health::server::health_reporter();
+  let health = Arc::new(BackendHealth::new(health_reporter).await);
+
+  let metrics_address = config.metrics_socket_address()?;
+  tokio::spawn(async move {
+    if let Err(err) = serve_metrics(metrics, metrics_address).await {
+      error!("Metrics endpoint stopped, error: {}", err);
+    }
+  });
+
+  let tls_config = match config.tls_paths()? {
+    Some((cert_path, key_path)) => Some(ServerTlsConfig::new().identity(Identity::from_pem(fs::read(cert_path)?, fs::read(key_path)?))),
+    None => None,
+  };
+
+  let shutdown = wait_for_shutdown(health.clone()).shared();
+
+  let mut servers = vec![];
+  for address in config.socket_addresses()? {
+    let mut builder = Server::builder();
+    if let Some(tls_config) = tls_config.clone() {
+      builder = builder.tls_config(tls_config)?;
+    }
+
+    info!("Starting server at {}, TLS enabled: {}", address, tls_config.is_some());
+
+    let router = builder
+      .add_service(health_service.clone())
+      .add_service(grpc_cv_service.clone())
+      .add_service(backend_service.clone())
+      .add_service(kafka_service.clone());
+
+    servers.push(router.serve_with_incoming_shutdown(TcpIncoming::bind(address)?, shutdown.clone()));
+  }
+
+  health.set_ready().await;
+  future::try_join_all(servers).await?;
 
   Ok(())
 }
+
+async fn wait_for_shutdown(health: Arc<BackendHealth>) {
+  let interrupt = Box::pin(async {
+    if let Err(err) = signal::ctrl_c().await {
+      error!("Failed to listen for interrupt signal, error: {}", err);
+      future::pending::<()>().await;
+    }
+  });
+
+  #[cfg(unix)]
+  let terminate = Box::pin(async {
+    match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+      Ok(mut terminate) => {
+        terminate.recv().await;
+      }
+      Err(err) => {
+        error!("Failed to listen for terminate signal, error: {}", err);
+        future::pending::<()>().await;
+      }
+    }
+  });
+
+  #[cfg(not(unix))]
+  let terminate = Box::pin(future::pending::<()>());
+
+  future::select(interrupt, terminate).await;
+  health.set_shutting_down().await;
+}
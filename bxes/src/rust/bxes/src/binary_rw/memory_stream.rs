@@ -0,0 +1,49 @@
+use crate::binary_rw::{
+  core::{SeekStream, WriteStream},
+  error::BinaryError,
+};
+use std::io::{Cursor, Seek, SeekFrom, Write};
+
+#[derive(Default)]
+pub struct MemoryStream {
+  cursor: Cursor<Vec<u8>>,
+}
+
+impl MemoryStream {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn into_bytes(self) -> Vec<u8> {
+    self.cursor.into_inner()
+  }
+}
+
+impl Write for MemoryStream {
+  fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+    self.cursor.write(bytes)
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    self.cursor.flush()
+  }
+}
+
+impl SeekStream for MemoryStream {
+  fn seek(&mut self, to: usize) -> crate::binary_rw::core::Result<usize> {
+    match self.cursor.seek(SeekFrom::Start(to as u64)) {
+      Ok(result) => Ok(result as usize),
+      Err(_err) => Err(BinaryError::ReadPastEof),
+    }
+  }
+
+  fn tell(&mut self) -> crate::binary_rw::core::Result<usize> {
+    Ok(self.cursor.position() as usize)
+  }
+
+  fn len(&self) -> crate::binary_rw::core::Result<usize> {
+    Ok(self.cursor.get_ref().len())
+  }
+}
+
+impl WriteStream for MemoryStream {}
@@ -2,3 +2,4 @@ pub mod core;
 pub mod cursor_stream;
 pub mod error;
 pub mod file_stream;
+pub mod memory_stream;
@@ -0,0 +1,312 @@
+use num_derive::{FromPrimitive, ToPrimitive};
+use num_traits::{FromPrimitive, ToPrimitive};
+use std::{
+  fs::{self, File},
+  io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+  path::Path,
+};
+use tempfile::NamedTempFile;
+use zip::{ZipArchive, ZipWriter, read::read_zipfile_from_stream, write::FileOptions};
+
+use crate::{
+  constants::{CODEC_HEADER_MAGIC, DEFAULT_EXTRACTED_LOG_NAME},
+  read::errors::BxesReadError,
+  writer::errors::BxesWriteError,
+};
+
+#[derive(Debug, Default, FromPrimitive, ToPrimitive, PartialEq, Eq, Clone, Copy)]
+pub enum BxesCompressionCodec {
+  None = 0,
+  #[default]
+  Deflate = 1,
+  Zstd = 2,
+}
+
+pub trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+pub trait BxesCodec {
+  fn compress(&self, log_path: &str, save_path: &str) -> Result<(), BxesWriteError>;
+
+  /// Compresses the log into `stream`, the archive is written in the same format as by `compress`.
+  fn compress_to_stream(&self, log_path: &str, stream: &mut dyn Write) -> Result<(), BxesWriteError>;
+
+  /// Decompresses the payload which starts at the current position of the stream into `extract_dir`.
+  /// When `salvage` is set, decompression errors are ignored and everything decompressed before them is kept.
+  fn decompress(&self, stream: &mut dyn ReadSeek, extract_dir: &Path, salvage: bool) -> Result<(), BxesReadError>;
+
+  /// Same as `decompress`, but the payload is read sequentially from the stream which can not be seeked.
+  fn decompress_stream(&self, stream: &mut dyn Read, extract_dir: &Path, salvage: bool) -> Result<(), BxesReadError>;
+}
+
+impl BxesCompressionCodec {
+  pub fn create(&self) -> Box<dyn BxesCodec> {
+    match self {
+      BxesCompressionCodec::None => Box::new(NoneCodec),
+      BxesCompressionCodec::Deflate => Box::new(DeflateCodec),
+      BxesCompressionCodec::Zstd => Box::new(ZstdCodec { level: 3 }),
+    }
+  }
+}
+
+/// Detects codec of an archive, the stream is left positioned at the start of the codec payload.
+/// Archives without codec header are zip archives which were written before codecs were introduced.
+pub fn try_detect_codec(stream: &mut dyn ReadSeek) -> Result<BxesCompressionCodec, BxesReadError> {
+  let to_error = |err: io::Error| BxesReadError::FailedToOpenFile(err.to_string());
+
+  let mut magic = [0u8; CODEC_HEADER_MAGIC.len()];
+  let has_header = match stream.read_exact(&mut magic) {
+    Ok(_) => &magic == CODEC_HEADER_MAGIC,
+    Err(_) => false,
+  };
+
+  if !has_header {
+    stream.seek(SeekFrom::Start(0)).map_err(to_error)?;
+    return Ok(BxesCompressionCodec::Deflate);
+  }
+
+  let mut codec = [0u8; 1];
+  stream.read_exact(&mut codec).map_err(to_error)?;
+
+  match BxesCompressionCodec::from_u8(codec[0]) {
+    Some(codec) => Ok(codec),
+    None => Err(BxesReadError::UnknownCompressionCodec(codec[0])),
+  }
+}
+
+/// Same as `try_detect_codec` for a stream which can not be seeked, the bytes which were read while looking for
+/// the codec header and which belong to the payload are returned along with the codec.
+pub fn try_detect_stream_codec(stream: &mut dyn Read) -> Result<(BxesCompressionCodec, Vec<u8>), BxesReadError> {
+  let to_error = |err: io::Error| BxesReadError::FailedToOpenFile(err.to_string());
+
+  let mut magic = vec![];
+  stream
+    .take(CODEC_HEADER_MAGIC.len() as u64)
+    .read_to_end(&mut magic)
+    .map_err(to_error)?;
+
+  if magic != CODEC_HEADER_MAGIC {
+    return Ok((BxesCompressionCodec::Deflate, magic));
+  }
+
+  let mut codec = [0u8; 1];
+  stream.read_exact(&mut codec).map_err(to_error)?;
+
+  match BxesCompressionCodec::from_u8(codec[0]) {
+    Some(codec) => Ok((codec, vec![])),
+    None => Err(BxesReadError::UnknownCompressionCodec(codec[0])),
+  }
+}
+
+fn create_archive_with_header(save_path: &str, codec: BxesCompressionCodec) -> Result<BufWriter<File>, BxesWriteError> {
+  let file = File::create(save_path).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+  let mut writer = BufWriter::new(file);
+
+  write_codec_header(&mut writer, codec)?;
+
+  Ok(writer)
+}
+
+fn write_codec_header(writer: &mut dyn Write, codec: BxesCompressionCodec) -> Result<(), BxesWriteError> {
+  writer
+    .write_all(CODEC_HEADER_MAGIC)
+    .map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+  writer
+    .write_all(&[codec.to_u8().unwrap()])
+    .map_err(|_| BxesWriteError::FailedToCreateArchive)
+}
+
+fn open_log(log_path: &str) -> Result<BufReader<File>, BxesWriteError> {
+  match File::open(log_path) {
+    Ok(file) => Ok(BufReader::new(file)),
+    Err(err) => Err(BxesWriteError::Default(err.to_string())),
+  }
+}
+
+fn create_extracted_log(extract_dir: &Path) -> Result<BufWriter<File>, BxesReadError> {
+  match File::create(extract_dir.join(DEFAULT_EXTRACTED_LOG_NAME)) {
+    Ok(file) => Ok(BufWriter::new(file)),
+    Err(_) => Err(BxesReadError::FailedToExtractArchive),
+  }
+}
+
+pub struct NoneCodec;
+
+impl BxesCodec for NoneCodec {
+  fn compress(&self, log_path: &str, save_path: &str) -> Result<(), BxesWriteError> {
+    let mut writer = create_archive_with_header(save_path, BxesCompressionCodec::None)?;
+    io::copy(&mut open_log(log_path)?, &mut writer).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+
+    writer.flush().map_err(|_| BxesWriteError::FailedToCreateArchive)
+  }
+
+  fn compress_to_stream(&self, log_path: &str, stream: &mut dyn Write) -> Result<(), BxesWriteError> {
+    write_codec_header(stream, BxesCompressionCodec::None)?;
+    io::copy(&mut open_log(log_path)?, stream).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+
+    stream.flush().map_err(|_| BxesWriteError::FailedToCreateArchive)
+  }
+
+  fn decompress(&self, stream: &mut dyn ReadSeek, extract_dir: &Path, salvage: bool) -> Result<(), BxesReadError> {
+    self.decompress_stream(stream, extract_dir, salvage)
+  }
+
+  fn decompress_stream(&self, stream: &mut dyn Read, extract_dir: &Path, _: bool) -> Result<(), BxesReadError> {
+    let mut writer = create_extracted_log(extract_dir)?;
+    io::copy(stream, &mut writer).map_err(|_| BxesReadError::FailedToExtractArchive)?;
+
+    writer.flush().map_err(|_| BxesReadError::FailedToExtractArchive)
+  }
+}
+
+pub struct DeflateCodec;
+
+impl BxesCodec for DeflateCodec {
+  fn compress(&self, log_path: &str, save_path: &str) -> Result<(), BxesWriteError> {
+    let file = File::create(save_path).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+    let mut zip_writer = ZipWriter::new(file);
+
+    let archive_log_name = Path::new(save_path)
+      .file_name()
+      .and_then(|name| name.to_str())
+      .ok_or(BxesWriteError::FailedToCreateArchive)?;
+    let options = FileOptions::<()>::default()
+      .compression_method(zip::CompressionMethod::Deflated)
+      .compression_level(Some(8));
+
+    zip_writer
+      .start_file(archive_log_name, options)
+      .map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+
+    io::copy(&mut open_log(log_path)?, &mut zip_writer).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+
+    zip_writer.flush().map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+
+    zip_writer.finish().map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+
+    Ok(())
+  }
+
+  /// Zip local headers are patched after the file is written, so the archive is created in a temporary file first
+  fn compress_to_stream(&self, log_path: &str, stream: &mut dyn Write) -> Result<(), BxesWriteError> {
+    let archive = NamedTempFile::new().map_err(|_| BxesWriteError::FailedToCreateTempFile)?;
+    self.compress(log_path, archive.path().to_str().unwrap())?;
+
+    let mut archive = File::open(archive.path()).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+    io::copy(&mut archive, stream).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+    stream.flush().map_err(|_| BxesWriteError::FailedToCreateArchive)
+  }
+
+  fn decompress(&self, stream: &mut dyn ReadSeek, extract_dir: &Path, salvage: bool) -> Result<(), BxesReadError> {
+    let mut archive = match ZipArchive::new(stream) {
+      Ok(archive) => archive,
+      Err(err) => return Err(BxesReadError::FailedToOpenFile(err.to_string())),
+    };
+
+    if !salvage {
+      return match archive.extract(extract_dir) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(BxesReadError::FailedToExtractArchive),
+      };
+    }
+
+    if archive.len() != 1 {
+      return Err(BxesReadError::TooManyFilesInArchive);
+    }
+
+    let mut file = match archive.by_index(0) {
+      Ok(file) => file,
+      Err(_) => return Err(BxesReadError::FailedToExtractArchive),
+    };
+
+    //CRC or decompression errors are ignored, everything that was decompressed before them is kept
+    let mut bytes = vec![];
+    let _ = file.read_to_end(&mut bytes);
+
+    let file_name = Path::new(file.name()).file_name().map(|name| name.to_owned());
+    let file_name = file_name.unwrap_or_else(|| DEFAULT_EXTRACTED_LOG_NAME.into());
+
+    match fs::write(extract_dir.join(file_name), bytes) {
+      Ok(_) => Ok(()),
+      Err(_) => Err(BxesReadError::FailedToExtractArchive),
+    }
+  }
+
+  fn decompress_stream(&self, mut stream: &mut dyn Read, extract_dir: &Path, salvage: bool) -> Result<(), BxesReadError> {
+    let mut file = match read_zipfile_from_stream(&mut stream) {
+      Ok(Some(file)) => file,
+      Ok(None) => return Err(BxesReadError::FailedToExtractArchive),
+      Err(err) => return Err(BxesReadError::FailedToOpenFile(err.to_string())),
+    };
+
+    let file_name = Path::new(file.name()).file_name().map(|name| name.to_owned());
+    let file_name = file_name.unwrap_or_else(|| DEFAULT_EXTRACTED_LOG_NAME.into());
+
+    let mut writer = match File::create(extract_dir.join(file_name)) {
+      Ok(file) => BufWriter::new(file),
+      Err(_) => return Err(BxesReadError::FailedToExtractArchive),
+    };
+
+    //As in `decompress`, CRC or decompression errors are ignored when salvaging
+    let copy_result = io::copy(&mut file, &mut writer);
+    if copy_result.is_err() && !salvage {
+      return Err(BxesReadError::FailedToExtractArchive);
+    }
+
+    writer.flush().map_err(|_| BxesReadError::FailedToExtractArchive)?;
+    drop(file);
+
+    if salvage {
+      return Ok(());
+    }
+
+    match read_zipfile_from_stream(&mut stream) {
+      Ok(None) => Ok(()),
+      Ok(Some(_)) => Err(BxesReadError::TooManyFilesInArchive),
+      Err(err) => Err(BxesReadError::FailedToOpenFile(err.to_string())),
+    }
+  }
+}
+
+pub struct ZstdCodec {
+  level: i32,
+}
+
+impl BxesCodec for ZstdCodec {
+  fn compress(&self, log_path: &str, save_path: &str) -> Result<(), BxesWriteError> {
+    let writer = create_archive_with_header(save_path, BxesCompressionCodec::Zstd)?;
+
+    let mut encoder = zstd::Encoder::new(writer, self.level).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+    io::copy(&mut open_log(log_path)?, &mut encoder).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+
+    let mut writer = encoder.finish().map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+    writer.flush().map_err(|_| BxesWriteError::FailedToCreateArchive)
+  }
+
+  fn compress_to_stream(&self, log_path: &str, stream: &mut dyn Write) -> Result<(), BxesWriteError> {
+    write_codec_header(stream, BxesCompressionCodec::Zstd)?;
+
+    let mut encoder = zstd::Encoder::new(stream, self.level).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+    io::copy(&mut open_log(log_path)?, &mut encoder).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+
+    let stream = encoder.finish().map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+    stream.flush().map_err(|_| BxesWriteError::FailedToCreateArchive)
+  }
+
+  fn decompress(&self, stream: &mut dyn ReadSeek, extract_dir: &Path, salvage: bool) -> Result<(), BxesReadError> {
+    self.decompress_stream(stream, extract_dir, salvage)
+  }
+
+  fn decompress_stream(&self, stream: &mut dyn Read, extract_dir: &Path, salvage: bool) -> Result<(), BxesReadError> {
+    let mut decoder = zstd::Decoder::new(stream).map_err(|_| BxesReadError::FailedToExtractArchive)?;
+    let mut writer = create_extracted_log(extract_dir)?;
+
+    let copy_result = io::copy(&mut decoder, &mut writer);
+    if copy_result.is_err() && !salvage {
+      return Err(BxesReadError::FailedToExtractArchive);
+    }
+
+    writer.flush().map_err(|_| BxesReadError::FailedToExtractArchive)
+  }
+}
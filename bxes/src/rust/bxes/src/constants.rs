@@ -5,3 +5,11 @@ pub const METADATA_FILE_NAME: &str = "metadata.bxes";
 pub const SYSTEM_METADATA_FILE_NAME: &str = "system_metadata.bxes";
 
 pub const BXES_EXT: &str = "bxes";
+
+pub const CHECKSUMS_FOOTER_MAGIC: &[u8; 8] = b"BXESCRC1";
+pub const CHECKSUM_ENTRY_SIZE: usize = 1 + 8 + 8 + 4;
+
+pub const CODEC_HEADER_MAGIC: &[u8; 8] = b"BXESARCH";
+pub const DEFAULT_EXTRACTED_LOG_NAME: &str = "log.bxes";
+
+pub const EVENT_ATTRIBUTES_SCHEMA_FLAG: u32 = 0x8000_0000;
@@ -1,6 +1,8 @@
 pub mod binary_rw;
+pub mod compression;
 pub mod constants;
 pub mod models;
+pub mod operations;
 pub mod read;
 pub mod utils;
 pub mod writer;
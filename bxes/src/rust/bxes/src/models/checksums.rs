@@ -0,0 +1,37 @@
+use num_derive::{FromPrimitive, ToPrimitive};
+use std::fmt::Display;
+
+#[derive(Debug, FromPrimitive, ToPrimitive, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum BxesSection {
+  SystemMetadata = 0,
+  Values = 1,
+  KeyValues = 2,
+  Metadata = 3,
+  Variants = 4,
+  Variant = 5,
+}
+
+impl Display for BxesSection {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{:?}", self)
+  }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BxesSectionChecksum {
+  pub section: BxesSection,
+  pub offset: u64,
+  pub length: u64,
+  pub checksum: u32,
+}
+
+impl BxesSectionChecksum {
+  pub fn new(section: BxesSection, offset: u64, length: u64, checksum: u32) -> Self {
+    Self {
+      section,
+      offset,
+      length,
+      checksum,
+    }
+  }
+}
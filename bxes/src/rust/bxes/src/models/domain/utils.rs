@@ -12,18 +12,25 @@ pub fn compare_list_of_attributes(
   if let Some(self_attributes) = first_attributes.as_ref()
     && let Some(other_attributes) = second_attributes.as_ref()
   {
-    if self_attributes.len() != other_attributes.len() {
-      return false;
-    }
-
-    let first_set = self_attributes.iter().collect::<HashSet<&(Arc<BxesValue>, Arc<BxesValue>)>>();
+    return compare_attributes(self_attributes, other_attributes);
+  }
 
-    let second_set = other_attributes.iter().collect::<HashSet<&(Arc<BxesValue>, Arc<BxesValue>)>>();
+  false
+}
 
-    return first_set.eq(&second_set);
+pub fn compare_attributes(
+  first_attributes: &[(Arc<BxesValue>, Arc<BxesValue>)],
+  second_attributes: &[(Arc<BxesValue>, Arc<BxesValue>)],
+) -> bool {
+  if first_attributes.len() != second_attributes.len() {
+    return false;
   }
 
-  false
+  let first_set = first_attributes.iter().collect::<HashSet<&(Arc<BxesValue>, Arc<BxesValue>)>>();
+
+  let second_set = second_attributes.iter().collect::<HashSet<&(Arc<BxesValue>, Arc<BxesValue>)>>();
+
+  first_set.eq(&second_set)
 }
 
 pub fn attributes_equals(first_attribute: &(Arc<BxesValue>, Arc<BxesValue>), second_attribute: &(Arc<BxesValue>, Arc<BxesValue>)) -> bool {
@@ -1,2 +1,3 @@
+pub mod checksums;
 pub mod domain;
 pub mod system_models;
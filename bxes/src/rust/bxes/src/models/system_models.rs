@@ -1,5 +1,9 @@
-use crate::models::domain::type_ids::TypeIds;
-use std::sync::Arc;
+use crate::models::domain::{
+  bxes_event_log::BxesEvent,
+  bxes_value::BxesValue,
+  type_ids::{TypeIds, get_type_id},
+};
+use std::{fmt::Display, sync::Arc};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct ValueAttributeDescriptor {
@@ -16,15 +20,149 @@ impl ValueAttributeDescriptor {
   }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EventAttributeSchema {
+  pub name: Arc<str>,
+  pub type_id: TypeIds,
+  pub required: bool,
+  pub allowed_values: Option<Vec<BxesValue>>,
+}
+
+impl EventAttributeSchema {
+  pub fn new(name: &str, type_id: TypeIds, required: bool) -> Self {
+    Self {
+      name: Arc::from(name),
+      type_id,
+      required,
+      allowed_values: None,
+    }
+  }
+
+  pub fn with_allowed_values(mut self, allowed_values: Vec<BxesValue>) -> Self {
+    self.allowed_values = Some(allowed_values);
+    self
+  }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EventAttributesSchema {
+  pub attributes: Vec<EventAttributeSchema>,
+  pub allow_unknown_attributes: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EventSchemaViolation {
+  MissingRequiredAttribute(Arc<str>),
+  TypeMismatch {
+    name: Arc<str>,
+    expected: TypeIds,
+    actual: TypeIds,
+  },
+  ValueIsNotAllowed {
+    name: Arc<str>,
+    value: BxesValue,
+  },
+  UnknownAttribute(Arc<str>),
+  AttributeKeyIsNotAString(BxesValue),
+}
+
+impl Display for EventSchemaViolation {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      EventSchemaViolation::MissingRequiredAttribute(name) => write!(f, "Required attribute {} is missing", name),
+      EventSchemaViolation::TypeMismatch { name, expected, actual } => {
+        write!(f, "Attribute {} has type {:?}, expected {:?}", name, actual, expected)
+      }
+      EventSchemaViolation::ValueIsNotAllowed { name, value } => write!(f, "Value {:?} is not allowed for attribute {}", value, name),
+      EventSchemaViolation::UnknownAttribute(name) => write!(f, "Attribute {} is not declared in schema", name),
+      EventSchemaViolation::AttributeKeyIsNotAString(key) => write!(f, "Attribute key {:?} is not a string", key),
+    }
+  }
+}
+
+impl EventAttributesSchema {
+  pub fn new(attributes: Vec<EventAttributeSchema>, allow_unknown_attributes: bool) -> Self {
+    Self {
+      attributes,
+      allow_unknown_attributes,
+    }
+  }
+
+  pub fn find(&self, name: &str) -> Option<&EventAttributeSchema> {
+    self.attributes.iter().find(|attr| attr.name.as_ref() == name)
+  }
+
+  pub fn validate(&self, event: &BxesEvent) -> Result<(), EventSchemaViolation> {
+    let mut seen = vec![false; self.attributes.len()];
+
+    if let Some(attributes) = event.attributes.as_ref() {
+      for (key, value) in attributes {
+        let BxesValue::String(name) = key.as_ref() else {
+          return Err(EventSchemaViolation::AttributeKeyIsNotAString(key.as_ref().clone()));
+        };
+
+        let Some(index) = self.attributes.iter().position(|attr| attr.name == *name) else {
+          if self.allow_unknown_attributes {
+            continue;
+          }
+
+          return Err(EventSchemaViolation::UnknownAttribute(name.clone()));
+        };
+
+        let schema = &self.attributes[index];
+        let actual = get_type_id(value);
+
+        if actual == TypeIds::Null && schema.type_id != TypeIds::Null {
+          continue;
+        }
+
+        if actual != schema.type_id {
+          return Err(EventSchemaViolation::TypeMismatch {
+            name: name.clone(),
+            expected: schema.type_id.clone(),
+            actual,
+          });
+        }
+
+        if let Some(allowed_values) = schema.allowed_values.as_ref()
+          && !allowed_values.contains(value.as_ref())
+        {
+          return Err(EventSchemaViolation::ValueIsNotAllowed {
+            name: name.clone(),
+            value: value.as_ref().clone(),
+          });
+        }
+
+        seen[index] = true;
+      }
+    }
+
+    for (schema, seen) in self.attributes.iter().zip(seen) {
+      if schema.required && !seen {
+        return Err(EventSchemaViolation::MissingRequiredAttribute(schema.name.clone()));
+      }
+    }
+
+    Ok(())
+  }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct SystemMetadata {
   pub values_attrs: Option<Vec<ValueAttributeDescriptor>>,
+  pub event_attributes_schema: Option<EventAttributesSchema>,
 }
 
 impl SystemMetadata {
   pub fn new(value_attributes: Option<Vec<ValueAttributeDescriptor>>) -> Self {
     Self {
       values_attrs: value_attributes,
+      event_attributes_schema: None,
     }
   }
+
+  pub fn with_event_attributes_schema(mut self, schema: EventAttributesSchema) -> Self {
+    self.event_attributes_schema = Some(schema);
+    self
+  }
 }
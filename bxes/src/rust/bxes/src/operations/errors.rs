@@ -0,0 +1,29 @@
+use crate::{read::errors::BxesReadError, writer::errors::BxesWriteError};
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum BxesOperationError {
+  ReadError(BxesReadError),
+  WriteError(BxesWriteError),
+  NoLogsToMerge,
+  IncompatibleSchemas,
+  InvalidSplitCriterion(String),
+  FailedToCreateDirectory(String),
+}
+
+impl Display for BxesOperationError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        BxesOperationError::ReadError(err) => err.to_string(),
+        BxesOperationError::WriteError(err) => err.to_string(),
+        BxesOperationError::NoLogsToMerge => "NoLogsToMerge".to_string(),
+        BxesOperationError::IncompatibleSchemas => "Logs have different event attributes schemas".to_string(),
+        BxesOperationError::InvalidSplitCriterion(message) => format!("Invalid split criterion: {}", message),
+        BxesOperationError::FailedToCreateDirectory(err) => format!("Failed to create directory: {}", err),
+      }
+    )
+  }
+}
@@ -0,0 +1,175 @@
+use std::{
+  collections::{HashMap, hash_map::DefaultHasher},
+  hash::{Hash, Hasher},
+  sync::Arc,
+};
+
+use crate::{
+  models::{
+    domain::{
+      bxes_event_log::{BxesEventLog, BxesTraceVariant},
+      bxes_log_metadata::{BxesEventLogMetadata, BxesGlobal},
+      bxes_value::BxesValue,
+      utils::compare_attributes,
+    },
+    system_models::{SystemMetadata, ValueAttributeDescriptor},
+  },
+  read::{read_utils::BxesEventLogReadResult, single_file_bxes_reader::read_bxes},
+  writer::{
+    single_file_bxes_writer::write_bxes_with_options,
+    writer_utils::{BxesLogWriteData, BxesWriteOptions},
+  },
+};
+
+use super::errors::BxesOperationError;
+
+pub fn merge_bxes_files(paths: &[&str], save_path: &str, options: &BxesWriteOptions) -> Result<(), BxesOperationError> {
+  let mut logs = vec![];
+  for path in paths {
+    logs.push(read_bxes(path).map_err(BxesOperationError::ReadError)?);
+  }
+
+  let data = merge_bxes_logs(logs)?;
+
+  write_bxes_with_options(save_path, &data, options).map_err(BxesOperationError::WriteError)
+}
+
+pub fn merge_bxes_logs(logs: Vec<BxesEventLogReadResult>) -> Result<BxesLogWriteData, BxesOperationError> {
+  let mut logs = logs.into_iter();
+  let Some(first) = logs.next() else {
+    return Err(BxesOperationError::NoLogsToMerge);
+  };
+
+  let mut merged_log = BxesEventLog {
+    version: first.log.version,
+    metadata: first.log.metadata,
+    variants: vec![],
+  };
+
+  let mut system_metadata = first.system_metadata;
+  let mut variants = VariantsMerger::default();
+  variants.add_all(first.log.variants);
+
+  for result in logs {
+    if result.system_metadata.event_attributes_schema != system_metadata.event_attributes_schema {
+      return Err(BxesOperationError::IncompatibleSchemas);
+    }
+
+    merge_values_attributes(&mut system_metadata, result.system_metadata.values_attrs);
+    merge_metadata(&mut merged_log.metadata, result.log.metadata);
+    variants.add_all(result.log.variants);
+  }
+
+  merged_log.variants = variants.variants;
+
+  Ok(BxesLogWriteData {
+    log: merged_log,
+    system_metadata,
+  })
+}
+
+fn merge_values_attributes(system_metadata: &mut SystemMetadata, values_attrs: Option<Vec<ValueAttributeDescriptor>>) {
+  let Some(values_attrs) = values_attrs else {
+    return;
+  };
+
+  let merged = system_metadata.values_attrs.get_or_insert_with(Vec::new);
+  for attr in values_attrs {
+    if !merged.contains(&attr) {
+      merged.push(attr);
+    }
+  }
+}
+
+fn merge_metadata(target: &mut BxesEventLogMetadata, source: BxesEventLogMetadata) {
+  merge_distinct(&mut target.extensions, source.extensions);
+  merge_distinct(&mut target.classifiers, source.classifiers);
+  merge_distinct(&mut target.properties, source.properties);
+  merge_globals(&mut target.globals, source.globals);
+}
+
+fn merge_distinct<T: PartialEq>(target: &mut Option<Vec<T>>, source: Option<Vec<T>>) {
+  let Some(source) = source else {
+    return;
+  };
+
+  let target = target.get_or_insert_with(Vec::new);
+  for item in source {
+    if !target.contains(&item) {
+      target.push(item);
+    }
+  }
+}
+
+fn merge_globals(target: &mut Option<Vec<BxesGlobal>>, source: Option<Vec<BxesGlobal>>) {
+  let Some(source) = source else {
+    return;
+  };
+
+  let target = target.get_or_insert_with(Vec::new);
+  for global in source {
+    let Some(existing) = target.iter_mut().find(|g| g.entity_kind == global.entity_kind) else {
+      target.push(global);
+      continue;
+    };
+
+    for (key, value) in global.globals {
+      if !existing.globals.iter().any(|(existing_key, _)| *existing_key == key) {
+        existing.globals.push((key, value));
+      }
+    }
+  }
+}
+
+#[derive(Default)]
+struct VariantsMerger {
+  variants: Vec<BxesTraceVariant>,
+  indices_by_hash: HashMap<u64, Vec<usize>>,
+}
+
+impl VariantsMerger {
+  fn add_all(&mut self, variants: Vec<BxesTraceVariant>) {
+    for variant in variants {
+      self.add(variant);
+    }
+  }
+
+  fn add(&mut self, variant: BxesTraceVariant) {
+    let hash = calculate_variant_hash(&variant);
+    let indices = self.indices_by_hash.entry(hash).or_default();
+
+    for index in indices.iter() {
+      let existing = &mut self.variants[*index];
+      if existing.events == variant.events && compare_attributes(&existing.metadata, &variant.metadata) {
+        existing.traces_count += variant.traces_count;
+        return;
+      }
+    }
+
+    indices.push(self.variants.len());
+    self.variants.push(variant);
+  }
+}
+
+fn calculate_variant_hash(variant: &BxesTraceVariant) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  variant.events.len().hash(&mut hasher);
+
+  for event in &variant.events {
+    event.name.hash(&mut hasher);
+    event.timestamp.hash(&mut hasher);
+    calculate_attributes_hash(event.attributes.as_deref().unwrap_or(&[])).hash(&mut hasher);
+  }
+
+  calculate_attributes_hash(&variant.metadata).hash(&mut hasher);
+
+  hasher.finish()
+}
+
+fn calculate_attributes_hash(attributes: &[(Arc<BxesValue>, Arc<BxesValue>)]) -> u64 {
+  attributes.iter().fold(0, |acc, attribute| {
+    let mut hasher = DefaultHasher::new();
+    attribute.hash(&mut hasher);
+    acc ^ hasher.finish()
+  })
+}
@@ -0,0 +1,155 @@
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  sync::Arc,
+};
+
+use crate::{
+  models::domain::{
+    bxes_event_log::{BxesEventLog, BxesTraceVariant},
+    bxes_value::BxesValue,
+  },
+  read::{read_utils::BxesEventLogReadResult, single_file_bxes_reader::read_bxes},
+  writer::{
+    single_file_bxes_writer::write_bxes_with_options,
+    writer_utils::{BxesLogWriteData, BxesWriteOptions},
+  },
+};
+
+use super::errors::BxesOperationError;
+
+#[derive(Debug, Clone)]
+pub enum BxesSplitCriterion {
+  TraceAttribute(Arc<str>),
+  TimeBoundaries(Vec<i64>),
+  VariantsCount(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BxesSplitPartKey {
+  TraceAttribute(Option<Arc<BxesValue>>),
+  TimeRange { start: Option<i64>, end: Option<i64> },
+  VariantsChunk(usize),
+}
+
+pub struct BxesSplitPart {
+  pub key: BxesSplitPartKey,
+  pub data: BxesLogWriteData,
+}
+
+pub fn split_bxes_file(
+  path: &str,
+  save_dir: &str,
+  criterion: &BxesSplitCriterion,
+  options: &BxesWriteOptions,
+) -> Result<Vec<(BxesSplitPartKey, PathBuf)>, BxesOperationError> {
+  let result = read_bxes(path).map_err(BxesOperationError::ReadError)?;
+  let parts = split_bxes_log(result, criterion)?;
+
+  if let Err(err) = fs::create_dir_all(save_dir) {
+    return Err(BxesOperationError::FailedToCreateDirectory(err.to_string()));
+  }
+
+  let mut written_parts = vec![];
+  for (index, part) in parts.into_iter().enumerate() {
+    let part_path = Path::new(save_dir).join(format!("part_{}.bxes", index));
+    write_bxes_with_options(part_path.to_str().unwrap(), &part.data, options).map_err(BxesOperationError::WriteError)?;
+
+    written_parts.push((part.key, part_path));
+  }
+
+  Ok(written_parts)
+}
+
+pub fn split_bxes_log(result: BxesEventLogReadResult, criterion: &BxesSplitCriterion) -> Result<Vec<BxesSplitPart>, BxesOperationError> {
+  let groups = match criterion {
+    BxesSplitCriterion::TraceAttribute(name) => split_by_trace_attribute(result.log.variants, name),
+    BxesSplitCriterion::TimeBoundaries(boundaries) => split_by_time_boundaries(result.log.variants, boundaries)?,
+    BxesSplitCriterion::VariantsCount(count) => split_by_variants_count(result.log.variants, *count)?,
+  };
+
+  Ok(
+    groups
+      .into_iter()
+      .map(|(key, variants)| BxesSplitPart {
+        key,
+        data: BxesLogWriteData {
+          log: BxesEventLog {
+            version: result.log.version,
+            metadata: result.log.metadata.clone(),
+            variants,
+          },
+          system_metadata: result.system_metadata.clone(),
+        },
+      })
+      .collect(),
+  )
+}
+
+type VariantsGroups = Vec<(BxesSplitPartKey, Vec<BxesTraceVariant>)>;
+
+fn split_by_trace_attribute(variants: Vec<BxesTraceVariant>, name: &str) -> VariantsGroups {
+  let mut groups: VariantsGroups = vec![];
+
+  for variant in variants {
+    let value = variant
+      .metadata
+      .iter()
+      .find(|(key, _)| matches!(key.as_ref(), BxesValue::String(key) if key.as_ref() == name))
+      .map(|(_, value)| value.clone());
+
+    let key = BxesSplitPartKey::TraceAttribute(value);
+    match groups.iter_mut().find(|(group_key, _)| *group_key == key) {
+      Some((_, group)) => group.push(variant),
+      None => groups.push((key, vec![variant])),
+    }
+  }
+
+  groups
+}
+
+fn split_by_time_boundaries(variants: Vec<BxesTraceVariant>, boundaries: &[i64]) -> Result<VariantsGroups, BxesOperationError> {
+  if boundaries.is_empty() || boundaries.windows(2).any(|pair| pair[0] >= pair[1]) {
+    let message = "time boundaries should be non-empty and strictly increasing".to_string();
+    return Err(BxesOperationError::InvalidSplitCriterion(message));
+  }
+
+  let mut groups: Vec<Vec<BxesTraceVariant>> = (0..=boundaries.len()).map(|_| vec![]).collect();
+  for variant in variants {
+    let start = variant.events.first().map(|event| event.timestamp).unwrap_or(i64::MIN);
+    let index = boundaries.partition_point(|boundary| *boundary <= start);
+    groups[index].push(variant);
+  }
+
+  Ok(
+    groups
+      .into_iter()
+      .enumerate()
+      .filter(|(_, group)| !group.is_empty())
+      .map(|(index, group)| {
+        let start = if index == 0 { None } else { Some(boundaries[index - 1]) };
+        let end = boundaries.get(index).cloned();
+
+        (BxesSplitPartKey::TimeRange { start, end }, group)
+      })
+      .collect(),
+  )
+}
+
+fn split_by_variants_count(variants: Vec<BxesTraceVariant>, count: usize) -> Result<VariantsGroups, BxesOperationError> {
+  if count == 0 {
+    return Err(BxesOperationError::InvalidSplitCriterion(
+      "variants count should be positive".to_string(),
+    ));
+  }
+
+  let mut groups: VariantsGroups = vec![];
+  for variant in variants {
+    match groups.last_mut() {
+      Some((_, group)) if group.len() < count => group.push(variant),
+      _ => groups.push((BxesSplitPartKey::VariantsChunk(groups.len()), vec![variant])),
+    }
+  }
+
+  Ok(groups)
+}
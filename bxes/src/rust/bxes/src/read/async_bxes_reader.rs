@@ -0,0 +1,56 @@
+use std::io::Read;
+use tokio::{
+  io::{AsyncRead, AsyncReadExt},
+  sync::mpsc,
+};
+
+use super::{
+  errors::BxesReadError,
+  read_utils::BxesEventLogReadResult,
+  single_file_bxes_reader::{BxesSalvageResult, read_bxes_from_stream, read_bxes_salvaging_from_stream},
+};
+use crate::utils::channel_stream::{CHANNEL_CHUNK_SIZE, CHANNEL_CHUNKS_COUNT, ChannelReader};
+
+pub async fn read_bxes_async<R: AsyncRead + Unpin>(reader: &mut R) -> Result<BxesEventLogReadResult, BxesReadError> {
+  read_with_blocking_reader(reader, read_bxes_from_stream).await
+}
+
+pub async fn read_bxes_salvaging_async<R: AsyncRead + Unpin>(reader: &mut R) -> Result<BxesSalvageResult, BxesReadError> {
+  read_with_blocking_reader(reader, read_bxes_salvaging_from_stream).await
+}
+
+/// Chunks of `reader` are passed to `read` which is executed on a blocking thread, so the archive is decompressed
+/// while it is being received and runtime worker threads are not blocked.
+async fn read_with_blocking_reader<R: AsyncRead + Unpin, T: Send + 'static>(
+  reader: &mut R,
+  read: fn(&mut dyn Read) -> Result<T, BxesReadError>,
+) -> Result<T, BxesReadError> {
+  let (sender, receiver) = mpsc::channel(CHANNEL_CHUNKS_COUNT);
+  let blocking_read = tokio::task::spawn_blocking(move || read(&mut ChannelReader::new(receiver)));
+
+  loop {
+    let mut chunk = vec![0; CHANNEL_CHUNK_SIZE];
+    let read_bytes = reader
+      .read(&mut chunk)
+      .await
+      .map_err(|err| BxesReadError::FailedToReadStream(err.to_string()))?;
+
+    if read_bytes == 0 {
+      break;
+    }
+
+    chunk.truncate(read_bytes);
+
+    //The blocking read has already finished, its result tells why
+    if sender.send(chunk).await.is_err() {
+      break;
+    }
+  }
+
+  drop(sender);
+
+  match blocking_read.await {
+    Ok(result) => result,
+    Err(err) => Err(BxesReadError::FailedToReadStream(err.to_string())),
+  }
+}
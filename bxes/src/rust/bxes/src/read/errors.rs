@@ -1,4 +1,4 @@
-use crate::models::domain::bxes_value::BxesValue;
+use crate::models::{checksums::BxesSection, domain::bxes_value::BxesValue, system_models::EventSchemaViolation};
 use std::{fmt::Display, string::FromUtf8Error};
 
 #[derive(Debug)]
@@ -20,6 +20,11 @@ pub enum BxesReadError {
   ExpectedString(BxesValue),
   Leb128ReadError(String),
   ValueAttributeNameIsNotAString,
+  ChecksumMismatch { section: BxesSection, offset: u64 },
+  InvalidChecksumsFooter(String),
+  UnknownCompressionCodec(u8),
+  FailedToReadStream(String),
+  SchemaViolation(EventSchemaViolation),
 }
 
 impl Display for BxesReadError {
@@ -61,6 +66,13 @@ impl Display for BxesReadError {
           format!("Failed to read LEB128 encoded value: {}", message)
         }
         BxesReadError::ValueAttributeNameIsNotAString => "Value attribute name was not a string".to_string(),
+        BxesReadError::ChecksumMismatch { section, offset } => {
+          format!("Checksum mismatch in section {} at offset {}", section, offset)
+        }
+        BxesReadError::InvalidChecksumsFooter(message) => format!("Invalid checksums footer: {}", message),
+        BxesReadError::UnknownCompressionCodec(codec) => format!("Unknown compression codec: {}", codec),
+        BxesReadError::FailedToReadStream(err) => format!("Failed to read stream: {}", err),
+        BxesReadError::SchemaViolation(violation) => format!("Event violates attributes schema: {}", violation),
       }
     )
   }
@@ -1,3 +1,5 @@
+#[cfg(feature = "async")]
+pub mod async_bxes_reader;
 pub mod errors;
 pub mod multiple_files_bxes_reader;
 pub mod read_context;
@@ -114,5 +114,7 @@ fn open_file(directory_path: &str, file_name: &str) -> Result<BufferedReadFileSt
   let directory_path = Path::new(directory_path);
   let file_path = directory_path.join(file_name);
   let file_path = file_path.to_str().unwrap();
+
+  try_verify_file_checksums(file_path)?;
   try_open_file_stream(file_path)
 }
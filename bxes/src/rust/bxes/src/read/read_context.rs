@@ -1,8 +1,21 @@
 use crate::{
-  binary_rw::core::BinaryReader,
+  binary_rw::{
+    core::{BinaryReader, BinaryWriter, Endian},
+    cursor_stream::CursorStream,
+    memory_stream::MemoryStream,
+  },
   models::{domain::bxes_value::BxesValue, system_models::SystemMetadata},
+  read::{
+    errors::BxesReadError,
+    read_utils::{try_read_key_values, try_read_values},
+  },
+  writer::{
+    errors::BxesWriteError,
+    write_context::BxesWriteContext,
+    writer_utils::{try_write_key_values_table, try_write_values_table},
+  },
 };
-use std::sync::Arc;
+use std::{io::Cursor, sync::Arc};
 
 #[derive(Default)]
 pub struct ReadMetadata {
@@ -11,6 +24,38 @@ pub struct ReadMetadata {
   pub system_metadata: Option<SystemMetadata>,
 }
 
+impl ReadMetadata {
+  /// Serializes values and key-values tables, system metadata is not included as it is read anew for each bxes chunk.
+  pub fn to_snapshot_bytes(&self) -> Result<Vec<u8>, BxesWriteError> {
+    let mut stream = MemoryStream::new();
+
+    {
+      let mut writer = BinaryWriter::new(&mut stream, Endian::Little);
+      let mut context = BxesWriteContext::new(&mut writer, None);
+
+      try_write_values_table(self.values.as_deref().unwrap_or_default(), &mut context)?;
+      try_write_key_values_table(self.kv_pairs.as_deref().unwrap_or_default(), context.writer.as_mut().unwrap())?;
+    }
+
+    Ok(stream.into_bytes())
+  }
+
+  pub fn from_snapshot_bytes(bytes: &[u8]) -> Result<Self, BxesReadError> {
+    let mut metadata = ReadMetadata::default();
+
+    {
+      let mut stream = CursorStream::new(Cursor::new(bytes));
+      let mut reader = BinaryReader::new(&mut stream, Endian::Little);
+      let mut context = ReadContext::new(&mut reader, &mut metadata);
+
+      try_read_values(&mut context)?;
+      try_read_key_values(&mut context)?;
+    }
+
+    Ok(metadata)
+  }
+}
+
 pub struct ReadContext<'a, 'b> {
   pub reader: Option<&'a mut BinaryReader<'a>>,
   pub metadata: &'b mut ReadMetadata,
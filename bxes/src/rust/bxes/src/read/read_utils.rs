@@ -1,13 +1,12 @@
 use num_traits::FromPrimitive;
 use std::{
   fs::File,
-  io::{Cursor, Read, Seek},
+  io::{Cursor, Read, Seek, SeekFrom},
   rc::Rc,
   sync::Arc,
 };
 use tempfile::TempDir;
 use uuid::Uuid;
-use zip::ZipArchive;
 
 use super::errors::*;
 use crate::{
@@ -15,7 +14,10 @@ use crate::{
     core::{BinaryReader, SeekStream},
     file_stream::FileStream,
   },
+  compression::codecs::{ReadSeek, try_detect_codec, try_detect_stream_codec},
+  constants::{CHECKSUM_ENTRY_SIZE, CHECKSUMS_FOOTER_MAGIC, EVENT_ATTRIBUTES_SCHEMA_FLAG},
   models::{
+    checksums::{BxesSection, BxesSectionChecksum},
     domain::{
       bxes_artifact::{BxesArtifact, BxesArtifactItem},
       bxes_driver::{BxesDriver, BxesDrivers},
@@ -26,10 +28,10 @@ use crate::{
       software_event_type::SoftwareEventType,
       type_ids::{TypeIds, get_type_id},
     },
-    system_models::{SystemMetadata, ValueAttributeDescriptor},
+    system_models::{EventAttributeSchema, EventAttributesSchema, SystemMetadata, ValueAttributeDescriptor},
   },
   read::{errors::BxesReadError::ValueAttributeNameIsNotAString, read_context::ReadContext},
-  utils::buffered_stream::BufferedReadFileStream,
+  utils::{buffered_stream::BufferedReadFileStream, checksum::calculate_checksum},
 };
 
 #[derive(Debug)]
@@ -53,16 +55,25 @@ pub fn try_read_event_log_metadata(context: &mut ReadContext) -> Result<BxesEven
 }
 
 pub fn try_read_system_metadata(context: &mut ReadContext) -> Result<(), BxesReadError> {
+  let count = try_read_u32(context.reader.as_mut().unwrap())?;
+  let values_attrs = try_read_value_attributes(context, count & !EVENT_ATTRIBUTES_SCHEMA_FLAG)?;
+
+  let event_attributes_schema = if count & EVENT_ATTRIBUTES_SCHEMA_FLAG != 0 {
+    Some(try_read_event_attributes_schema(context)?)
+  } else {
+    None
+  };
+
   context.metadata.system_metadata = Some(SystemMetadata {
-    values_attrs: try_read_value_attributes(context)?,
+    values_attrs,
+    event_attributes_schema,
   });
 
   Ok(())
 }
 
-fn try_read_value_attributes(context: &mut ReadContext) -> Result<Option<Vec<ValueAttributeDescriptor>>, BxesReadError> {
+fn try_read_value_attributes(context: &mut ReadContext, count: u32) -> Result<Option<Vec<ValueAttributeDescriptor>>, BxesReadError> {
   let reader = context.reader.as_mut().unwrap();
-  let count = try_read_u32(reader)?;
   if count == 0 {
     Ok(None)
   } else {
@@ -83,6 +94,45 @@ fn try_read_value_attributes(context: &mut ReadContext) -> Result<Option<Vec<Val
   }
 }
 
+fn try_read_event_attributes_schema(context: &mut ReadContext) -> Result<EventAttributesSchema, BxesReadError> {
+  let count = try_read_u32(context.reader.as_mut().unwrap())?;
+  let mut attributes = vec![];
+
+  for _ in 0..count {
+    let name = match try_read_bxes_value(context)? {
+      BxesValue::String(name) => name,
+      other => return Err(BxesReadError::ExpectedString(other)),
+    };
+
+    let reader = context.reader.as_mut().unwrap();
+    let type_id = try_read_type_id(reader)?;
+    let required = try_read_bool(reader)?;
+    let allowed_values_count = try_read_u32(reader)?;
+
+    let allowed_values = if allowed_values_count == 0 {
+      None
+    } else {
+      let mut allowed_values = vec![];
+      for _ in 0..allowed_values_count {
+        allowed_values.push(try_read_bxes_value(context)?);
+      }
+
+      Some(allowed_values)
+    };
+
+    attributes.push(EventAttributeSchema {
+      name,
+      type_id,
+      required,
+      allowed_values,
+    });
+  }
+
+  let allow_unknown_attributes = try_read_bool(context.reader.as_mut().unwrap())?;
+
+  Ok(EventAttributesSchema::new(attributes, allow_unknown_attributes))
+}
+
 pub fn try_read_classifiers(context: &mut ReadContext) -> Result<Option<Vec<BxesClassifier>>, BxesReadError> {
   let count = try_read_u32(context.reader.as_mut().unwrap())?;
   if count == 0 {
@@ -237,11 +287,23 @@ fn try_read_event(context: &mut ReadContext) -> Result<BxesEvent, BxesReadError>
 
   let timestamp = try_read_i64(context.reader.as_mut().unwrap())?;
 
-  Ok(BxesEvent {
+  let event = BxesEvent {
     name: name.unwrap().clone(),
     timestamp,
     attributes: try_read_event_attributes(context)?,
-  })
+  };
+
+  if let Some(schema) = context
+    .metadata
+    .system_metadata
+    .as_ref()
+    .and_then(|m| m.event_attributes_schema.as_ref())
+    && let Err(violation) = schema.validate(&event)
+  {
+    return Err(BxesReadError::SchemaViolation(violation));
+  }
+
+  Ok(event)
 }
 
 fn try_read_event_attributes(context: &mut ReadContext) -> Result<Option<Vec<(Arc<BxesValue>, Arc<BxesValue>)>>, BxesReadError> {
@@ -555,25 +617,143 @@ pub fn try_extract_archive(path: &str) -> Result<TempDir, BxesReadError> {
   try_extract_archive_internal(fs)
 }
 
-fn try_extract_archive_internal(stream: impl Read + Seek) -> Result<TempDir, BxesReadError> {
-  let mut archive = match ZipArchive::new(stream) {
-    Ok(archive) => archive,
+fn try_extract_archive_internal(mut stream: impl Read + Seek) -> Result<TempDir, BxesReadError> {
+  try_extract_archive_with_codec(&mut stream, false)
+}
+
+pub fn try_extract_archive_salvaging(path: &str) -> Result<TempDir, BxesReadError> {
+  let mut fs = match File::open(path) {
+    Ok(fs) => fs,
     Err(err) => return Err(BxesReadError::FailedToOpenFile(err.to_string())),
   };
 
-  let temp_dir = match TempDir::new() {
-    Ok(temp_dir) => temp_dir,
-    Err(_) => return Err(BxesReadError::FailedToCreateTempDir),
-  };
+  try_extract_archive_with_codec(&mut fs, true)
+}
 
-  match archive.extract(temp_dir.path()) {
-    Ok(_) => {}
-    Err(_) => return Err(BxesReadError::FailedToExtractArchive),
-  };
+pub fn try_extract_archive_bytes_salvaging(bytes: &[u8]) -> Result<TempDir, BxesReadError> {
+  try_extract_archive_with_codec(&mut Cursor::new(bytes), true)
+}
+
+fn try_extract_archive_with_codec(stream: &mut dyn ReadSeek, salvage: bool) -> Result<TempDir, BxesReadError> {
+  let codec = try_detect_codec(stream)?;
+  let temp_dir = try_create_temp_dir()?;
+
+  codec.create().decompress(stream, temp_dir.path(), salvage)?;
+
+  Ok(temp_dir)
+}
+
+/// Archive is decompressed while it is read from the stream, so the compressed archive is never stored as a whole
+pub fn try_extract_stream(stream: &mut dyn Read, salvage: bool) -> Result<TempDir, BxesReadError> {
+  let (codec, payload_prefix) = try_detect_stream_codec(stream)?;
+  let temp_dir = try_create_temp_dir()?;
+
+  let mut payload = Cursor::new(payload_prefix).chain(stream);
+  codec.create().decompress_stream(&mut payload, temp_dir.path(), salvage)?;
 
   Ok(temp_dir)
 }
 
+fn try_create_temp_dir() -> Result<TempDir, BxesReadError> {
+  match TempDir::new() {
+    Ok(temp_dir) => Ok(temp_dir),
+    Err(_) => Err(BxesReadError::FailedToCreateTempDir),
+  }
+}
+
+pub fn try_read_checksums_footer(path: &str) -> Result<Option<Vec<BxesSectionChecksum>>, BxesReadError> {
+  let to_error = |err: std::io::Error| BxesReadError::InvalidChecksumsFooter(err.to_string());
+  let mut file = File::open(path).map_err(|err| BxesReadError::FailedToOpenFile(err.to_string()))?;
+
+  let file_length = file.metadata().map_err(to_error)?.len() as usize;
+  let footer_tail_length = size_of::<u32>() + CHECKSUMS_FOOTER_MAGIC.len();
+  if file_length < footer_tail_length {
+    return Ok(None);
+  }
+
+  let mut footer_tail = vec![0; footer_tail_length];
+  file
+    .seek(SeekFrom::Start((file_length - footer_tail_length) as u64))
+    .map_err(to_error)?;
+  file.read_exact(&mut footer_tail).map_err(to_error)?;
+
+  if &footer_tail[size_of::<u32>()..] != CHECKSUMS_FOOTER_MAGIC {
+    return Ok(None);
+  }
+
+  let entries_count = u32::from_le_bytes(footer_tail[..size_of::<u32>()].try_into().unwrap()) as usize;
+  let entries_length = entries_count * CHECKSUM_ENTRY_SIZE;
+  if entries_length + footer_tail_length > file_length {
+    return Err(BxesReadError::InvalidChecksumsFooter(format!(
+      "{} entries do not fit into file of length {}",
+      entries_count, file_length
+    )));
+  }
+
+  let mut entries = vec![0; entries_length];
+  file
+    .seek(SeekFrom::Start((file_length - footer_tail_length - entries_length) as u64))
+    .map_err(to_error)?;
+  file.read_exact(&mut entries).map_err(to_error)?;
+
+  let mut checksums = vec![];
+  for entry in entries.chunks(CHECKSUM_ENTRY_SIZE) {
+    let section = match BxesSection::from_u8(entry[0]) {
+      Some(section) => section,
+      None => return Err(BxesReadError::InvalidChecksumsFooter(format!("Unknown section {}", entry[0]))),
+    };
+
+    checksums.push(BxesSectionChecksum::new(
+      section,
+      u64::from_le_bytes(entry[1..9].try_into().unwrap()),
+      u64::from_le_bytes(entry[9..17].try_into().unwrap()),
+      u32::from_le_bytes(entry[17..21].try_into().unwrap()),
+    ));
+  }
+
+  Ok(Some(checksums))
+}
+
+pub fn try_verify_checksum(file: &mut File, checksum: &BxesSectionChecksum) -> Result<bool, BxesReadError> {
+  let start = checksum.offset as usize;
+  let end = start + checksum.length as usize;
+
+  match calculate_checksum(file, start, end) {
+    Ok(actual) => Ok(actual == checksum.checksum),
+    Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+    Err(err) => Err(BxesReadError::FailedToReadValue(FailedToReadValueError::new(
+      start,
+      err.to_string(),
+    ))),
+  }
+}
+
+pub fn try_verify_file_checksums(path: &str) -> Result<(), BxesReadError> {
+  let checksums = match try_read_checksums_footer(path)? {
+    None => return Ok(()),
+    Some(checksums) => checksums,
+  };
+
+  let mut file = File::open(path).map_err(|err| BxesReadError::FailedToOpenFile(err.to_string()))?;
+  for checksum in &checksums {
+    if !try_verify_checksum(&mut file, checksum)? {
+      return Err(BxesReadError::ChecksumMismatch {
+        section: checksum.section,
+        offset: checksum.offset,
+      });
+    }
+  }
+
+  Ok(())
+}
+
+pub fn try_seek(reader: &mut BinaryReader, pos: usize) -> Result<(), BxesReadError> {
+  match reader.seek(pos) {
+    Ok(_) => Ok(()),
+    Err(err) => Err(BxesReadError::FailedToReadPos(err.to_string())),
+  }
+}
+
 pub fn try_open_file_stream(path: &str) -> Result<BufferedReadFileStream, BxesReadError> {
   match FileStream::open(path) {
     Ok(fs) => Ok(BufferedReadFileStream::new(fs, 1024 * 8)),
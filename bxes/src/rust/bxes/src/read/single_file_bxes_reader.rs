@@ -1,15 +1,31 @@
-use std::fs;
+use std::{
+  fs::{self, File},
+  io::Read,
+};
 
 use tempfile::TempDir;
 
 use crate::{
   binary_rw::core::{BinaryReader, Endian},
-  models::domain::bxes_event_log::BxesEventLog,
+  models::{
+    checksums::{BxesSection, BxesSectionChecksum},
+    domain::{
+      bxes_event_log::{BxesEventLog, BxesTraceVariant},
+      bxes_log_metadata::BxesEventLogMetadata,
+    },
+  },
   read::read_context::ReadContext,
 };
 
 use super::{errors::BxesReadError, read_utils::*};
 
+#[derive(Debug)]
+pub struct BxesSalvageResult {
+  pub result: BxesEventLogReadResult,
+  pub damaged_sections: Vec<BxesSection>,
+  pub damaged_variants: Vec<usize>,
+}
+
 pub fn read_bxes_from_archive_bytes(bytes: &[u8]) -> Result<BxesEventLogReadResult, BxesReadError> {
   let extracted_files_dir = try_extract_archive_bytes(bytes)?;
   read_bxes_internal(extracted_files_dir)
@@ -20,10 +36,28 @@ pub fn read_bxes(path: &str) -> Result<BxesEventLogReadResult, BxesReadError> {
   read_bxes_internal(extracted_files_dir)
 }
 
-fn read_bxes_internal(extracted_files_dir: TempDir) -> Result<BxesEventLogReadResult, BxesReadError> {
-  let extracted_files_dir = extracted_files_dir.path();
+pub fn read_bxes_from_stream(stream: &mut dyn Read) -> Result<BxesEventLogReadResult, BxesReadError> {
+  let extracted_files_dir = try_extract_stream(stream, false)?;
+  read_bxes_internal(extracted_files_dir)
+}
+
+pub fn read_bxes_salvaging_from_archive_bytes(bytes: &[u8]) -> Result<BxesSalvageResult, BxesReadError> {
+  let extracted_files_dir = try_extract_archive_bytes_salvaging(bytes)?;
+  read_bxes_salvaging_internal(extracted_files_dir)
+}
 
-  let files = fs::read_dir(extracted_files_dir)
+pub fn read_bxes_salvaging(path: &str) -> Result<BxesSalvageResult, BxesReadError> {
+  let extracted_files_dir = try_extract_archive_salvaging(path)?;
+  read_bxes_salvaging_internal(extracted_files_dir)
+}
+
+pub fn read_bxes_salvaging_from_stream(stream: &mut dyn Read) -> Result<BxesSalvageResult, BxesReadError> {
+  let extracted_files_dir = try_extract_stream(stream, true)?;
+  read_bxes_salvaging_internal(extracted_files_dir)
+}
+
+fn get_single_extracted_file(extracted_files_dir: &TempDir) -> Result<String, BxesReadError> {
+  let files = fs::read_dir(extracted_files_dir.path())
     .unwrap()
     .map(|r| r.unwrap().path().to_str().unwrap().to_string())
     .collect::<Vec<String>>();
@@ -32,7 +66,14 @@ fn read_bxes_internal(extracted_files_dir: TempDir) -> Result<BxesEventLogReadRe
     return Err(BxesReadError::InvalidArchive(format!("Expected one file, got {:?}", files)));
   }
 
-  let mut stream = try_open_file_stream(files[0].as_str())?;
+  Ok(files[0].clone())
+}
+
+fn read_bxes_internal(extracted_files_dir: TempDir) -> Result<BxesEventLogReadResult, BxesReadError> {
+  let file_path = get_single_extracted_file(&extracted_files_dir)?;
+  try_verify_file_checksums(file_path.as_str())?;
+
+  let mut stream = try_open_file_stream(file_path.as_str())?;
   let mut reader = BinaryReader::new(&mut stream, Endian::Little);
   let version = try_read_u32(&mut reader)?;
 
@@ -57,3 +98,131 @@ fn read_bxes_internal(extracted_files_dir: TempDir) -> Result<BxesEventLogReadRe
     system_metadata: context.metadata.system_metadata.clone().unwrap(),
   })
 }
+
+fn read_bxes_salvaging_internal(extracted_files_dir: TempDir) -> Result<BxesSalvageResult, BxesReadError> {
+  let file_path = get_single_extracted_file(&extracted_files_dir)?;
+
+  //A damaged footer is treated as an absent one, variants are then read until the first failure
+  let checksums = try_read_checksums_footer(file_path.as_str()).unwrap_or(None);
+  let mut damaged_sections = vec![];
+  let mut variants_checksums = None;
+
+  if let Some(checksums) = checksums {
+    let mut file = try_open_file(file_path.as_str())?;
+    let mut section_variants_checksums = vec![];
+
+    for checksum in checksums {
+      if checksum.section == BxesSection::Variant {
+        section_variants_checksums.push(checksum);
+        continue;
+      }
+
+      if !try_verify_checksum(&mut file, &checksum)? {
+        if checksum.section != BxesSection::Metadata && checksum.section != BxesSection::Variants {
+          return Err(BxesReadError::ChecksumMismatch {
+            section: checksum.section,
+            offset: checksum.offset,
+          });
+        }
+
+        damaged_sections.push(checksum.section);
+      }
+    }
+
+    variants_checksums = Some(section_variants_checksums);
+  }
+
+  let mut stream = try_open_file_stream(file_path.as_str())?;
+  let mut reader = BinaryReader::new(&mut stream, Endian::Little);
+  let version = try_read_u32(&mut reader)?;
+
+  let mut read_metadata = Default::default();
+  let mut context = ReadContext::new(&mut reader, &mut read_metadata);
+  try_read_system_metadata(&mut context)?;
+
+  try_read_values(&mut context)?;
+  try_read_key_values(&mut context)?;
+
+  let metadata = if damaged_sections.contains(&BxesSection::Metadata) {
+    BxesEventLogMetadata {
+      extensions: None,
+      classifiers: None,
+      properties: None,
+      globals: None,
+    }
+  } else {
+    try_read_event_log_metadata(&mut context)?
+  };
+
+  let mut damaged_variants = vec![];
+  let variants = match variants_checksums {
+    Some(variants_checksums) => {
+      let mut file = try_open_file(file_path.as_str())?;
+      read_variants_with_checksums(&mut context, &mut file, &variants_checksums, &mut damaged_variants)?
+    }
+    None => read_variants_until_failure(&mut context, &mut damaged_variants),
+  };
+
+  let log = BxesEventLog {
+    version,
+    metadata,
+    variants,
+  };
+
+  Ok(BxesSalvageResult {
+    result: BxesEventLogReadResult {
+      log,
+      system_metadata: context.metadata.system_metadata.clone().unwrap(),
+    },
+    damaged_sections,
+    damaged_variants,
+  })
+}
+
+fn try_open_file(path: &str) -> Result<File, BxesReadError> {
+  File::open(path).map_err(|err| BxesReadError::FailedToOpenFile(err.to_string()))
+}
+
+fn read_variants_with_checksums(
+  context: &mut ReadContext,
+  file: &mut File,
+  variants_checksums: &[BxesSectionChecksum],
+  damaged_variants: &mut Vec<usize>,
+) -> Result<Vec<BxesTraceVariant>, BxesReadError> {
+  let mut variants = vec![];
+
+  for (index, checksum) in variants_checksums.iter().enumerate() {
+    if !try_verify_checksum(file, checksum)? {
+      damaged_variants.push(index);
+      continue;
+    }
+
+    try_seek(context.reader.as_mut().unwrap(), checksum.offset as usize)?;
+    match try_read_trace_variant(context) {
+      Ok(variant) => variants.push(variant),
+      Err(_) => damaged_variants.push(index),
+    }
+  }
+
+  Ok(variants)
+}
+
+fn read_variants_until_failure(context: &mut ReadContext, damaged_variants: &mut Vec<usize>) -> Vec<BxesTraceVariant> {
+  let mut variants = vec![];
+  let variants_count = match try_read_u32(context.reader.as_mut().unwrap()) {
+    Ok(count) => count as usize,
+    Err(_) => return variants,
+  };
+
+  for index in 0..variants_count {
+    match try_read_trace_variant(context) {
+      Ok(variant) => variants.push(variant),
+      Err(_) => {
+        damaged_variants.extend(index..variants_count);
+        break;
+      }
+    }
+  }
+
+  variants
+}
@@ -81,6 +81,7 @@ impl SeekStream for BufferedReadFileStream {
   fn seek(&mut self, to: usize) -> crate::binary_rw::core::Result<usize> {
     self.next_buffer_index = 0;
     self.occupied_size = 0;
+    self.total_read_bytes = to;
 
     self.stream.seek(to)
   }
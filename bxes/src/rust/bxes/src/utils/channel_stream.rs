@@ -0,0 +1,89 @@
+use std::io::{self, Read, Write};
+use tokio::sync::mpsc::{Receiver, Sender};
+
+/// Size of chunks which are passed between an async stream and a blocking reader or writer
+pub const CHANNEL_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Number of chunks which may be in flight, so at most `CHANNEL_CHUNKS_COUNT * CHANNEL_CHUNK_SIZE` bytes are buffered
+pub const CHANNEL_CHUNKS_COUNT: usize = 16;
+
+/// Blocking reader of chunks which are sent from an async stream, must not be used on a runtime worker thread.
+pub struct ChannelReader {
+  receiver: Receiver<Vec<u8>>,
+  chunk: Vec<u8>,
+  chunk_position: usize,
+}
+
+impl ChannelReader {
+  pub fn new(receiver: Receiver<Vec<u8>>) -> Self {
+    Self {
+      receiver,
+      chunk: vec![],
+      chunk_position: 0,
+    }
+  }
+}
+
+impl Read for ChannelReader {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    while self.chunk_position == self.chunk.len() {
+      match self.receiver.blocking_recv() {
+        Some(chunk) => {
+          self.chunk = chunk;
+          self.chunk_position = 0;
+        }
+        None => return Ok(0),
+      }
+    }
+
+    let read_bytes = buf.len().min(self.chunk.len() - self.chunk_position);
+    buf[..read_bytes].copy_from_slice(&self.chunk[self.chunk_position..self.chunk_position + read_bytes]);
+    self.chunk_position += read_bytes;
+
+    Ok(read_bytes)
+  }
+}
+
+/// Blocking writer which sends chunks to an async stream, must not be used on a runtime worker thread.
+pub struct ChannelWriter {
+  sender: Sender<Vec<u8>>,
+  chunk: Vec<u8>,
+}
+
+impl ChannelWriter {
+  pub fn new(sender: Sender<Vec<u8>>) -> Self {
+    Self {
+      sender,
+      chunk: Vec::with_capacity(CHANNEL_CHUNK_SIZE),
+    }
+  }
+
+  fn send_chunk(&mut self) -> io::Result<()> {
+    if self.chunk.is_empty() {
+      return Ok(());
+    }
+
+    let chunk = std::mem::replace(&mut self.chunk, Vec::with_capacity(CHANNEL_CHUNK_SIZE));
+    self
+      .sender
+      .blocking_send(chunk)
+      .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "Stream was closed"))
+  }
+}
+
+impl Write for ChannelWriter {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let written_bytes = buf.len().min(CHANNEL_CHUNK_SIZE - self.chunk.len());
+    self.chunk.extend_from_slice(&buf[..written_bytes]);
+
+    if self.chunk.len() == CHANNEL_CHUNK_SIZE {
+      self.send_chunk()?;
+    }
+
+    Ok(written_bytes)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.send_chunk()
+  }
+}
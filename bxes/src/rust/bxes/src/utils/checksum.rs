@@ -0,0 +1,21 @@
+use std::{
+  fs::File,
+  io::{Read, Seek, SeekFrom},
+};
+
+pub fn calculate_checksum(file: &mut File, start: usize, end: usize) -> std::io::Result<u32> {
+  file.seek(SeekFrom::Start(start as u64))?;
+
+  let mut hasher = crc32fast::Hasher::new();
+  let mut buffer = vec![0; 1024 * 8];
+  let mut remaining = end - start;
+
+  while remaining > 0 {
+    let to_read = remaining.min(buffer.len());
+    file.read_exact(&mut buffer[..to_read])?;
+    hasher.update(&buffer[..to_read]);
+    remaining -= to_read;
+  }
+
+  Ok(hasher.finalize())
+}
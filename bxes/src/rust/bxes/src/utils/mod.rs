@@ -1 +1,4 @@
 pub mod buffered_stream;
+#[cfg(feature = "async")]
+pub mod channel_stream;
+pub mod checksum;
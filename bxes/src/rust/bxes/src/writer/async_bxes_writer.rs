@@ -0,0 +1,32 @@
+use std::sync::Arc;
+use tokio::{
+  io::{AsyncWrite, AsyncWriteExt},
+  sync::mpsc,
+};
+
+use super::{errors::BxesWriteError, single_file_bxes_writer::write_bxes_to_stream, writer_utils::*};
+use crate::utils::channel_stream::{CHANNEL_CHUNKS_COUNT, ChannelWriter};
+
+/// The log is serialized and compressed on a blocking thread, compressed chunks are written to `writer`
+/// as soon as they are produced.
+pub async fn write_bxes_async<W: AsyncWrite + Unpin>(
+  writer: &mut W,
+  data: Arc<BxesLogWriteData>,
+  options: BxesWriteOptions,
+) -> Result<(), BxesWriteError> {
+  let to_error = |err: std::io::Error| BxesWriteError::FailedToWriteStream(err.to_string());
+
+  let (sender, mut receiver) = mpsc::channel(CHANNEL_CHUNKS_COUNT);
+  let blocking_write = tokio::task::spawn_blocking(move || write_bxes_to_stream(data.as_ref(), &options, &mut ChannelWriter::new(sender)));
+
+  while let Some(chunk) = receiver.recv().await {
+    writer.write_all(&chunk).await.map_err(to_error)?;
+  }
+
+  match blocking_write.await {
+    Ok(result) => result?,
+    Err(err) => return Err(BxesWriteError::FailedToWriteStream(err.to_string())),
+  }
+
+  writer.flush().await.map_err(to_error)
+}
@@ -1,4 +1,7 @@
-use crate::{binary_rw::error::BinaryError, models::domain::bxes_value::BxesValue};
+use crate::{
+  binary_rw::error::BinaryError,
+  models::{domain::bxes_value::BxesValue, system_models::EventSchemaViolation},
+};
 use std::{fmt::Display, sync::Arc};
 
 #[derive(Debug)]
@@ -12,6 +15,10 @@ pub enum BxesWriteError {
   FailedToCreateTempFile,
   FailedToCreateArchive,
   LebWriteError(String),
+  FailedToWriteChecksums(String),
+  FailedToWriteStream(String),
+  SchemaViolation(EventSchemaViolation),
+  UnsupportedSchemaAllowedValue(BxesValue),
   Default(String),
 }
 
@@ -30,6 +37,10 @@ impl Display for BxesWriteError {
         BxesWriteError::FailedToCreateTempFile => "FailedToCreateTempFile".to_string(),
         BxesWriteError::FailedToCreateArchive => "FailedToCreateArchive".to_string(),
         BxesWriteError::LebWriteError(err) => err.to_string(),
+        BxesWriteError::FailedToWriteChecksums(err) => format!("Failed to write checksums: {}", err),
+        BxesWriteError::FailedToWriteStream(err) => format!("Failed to write stream: {}", err),
+        BxesWriteError::SchemaViolation(violation) => format!("Event violates attributes schema: {}", violation),
+        BxesWriteError::UnsupportedSchemaAllowedValue(value) => format!("Schema allowed value must be primitive, found: {:?}", value),
         BxesWriteError::Default(err) => err.to_owned(),
       }
     )
@@ -1,3 +1,5 @@
+#[cfg(feature = "async")]
+pub mod async_bxes_writer;
 pub mod errors;
 pub mod multiple_file_bxes_writer;
 pub mod single_file_bxes_writer;
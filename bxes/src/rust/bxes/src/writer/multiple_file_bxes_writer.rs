@@ -1,7 +1,8 @@
 use crate::{
   binary_rw::core::{BinaryWriter, Endian},
   constants,
-  writer::writer_utils::{BxesLogWriteData, try_write_system_metadata},
+  models::checksums::BxesSection,
+  writer::writer_utils::{BxesLogWriteData, BxesWriteOptions, try_write_system_metadata},
 };
 use std::{cell::RefCell, path::Path, rc::Rc};
 
@@ -9,14 +10,27 @@ use super::{
   errors::BxesWriteError,
   write_context::BxesWriteContext,
   writer_utils::{
-    try_open_write, try_write_key_values, try_write_log_metadata, try_write_u32_no_type_id, try_write_values, try_write_variants,
+    try_append_checksums_footer, try_open_write, try_tell_pos, try_write_key_values, try_write_log_metadata, try_write_u32_no_type_id,
+    try_write_values, try_write_variants,
   },
 };
 
 type WriterFunc = dyn Fn(&BxesLogWriteData, Rc<RefCell<BxesWriteContext>>) -> Result<(), BxesWriteError>;
 
 pub fn write_bxes_multiple_files(data: &BxesLogWriteData, directory_path: &str) -> Result<(), BxesWriteError> {
-  let context = BxesWriteContext::empty(data.system_metadata.values_attrs.clone());
+  write_bxes_multiple_files_with_options(data, directory_path, &BxesWriteOptions::default())
+}
+
+pub fn write_bxes_multiple_files_with_options(
+  data: &BxesLogWriteData,
+  directory_path: &str,
+  options: &BxesWriteOptions,
+) -> Result<(), BxesWriteError> {
+  let mut context = BxesWriteContext::empty(data.system_metadata.values_attrs.clone())
+    .with_event_attributes_schema(data.system_metadata.event_attributes_schema.clone());
+  if options.write_checksums {
+    context.track_variants_ranges();
+  }
 
   let writer = |file_path: &'static str, action: Box<WriterFunc>| execute_with_writer(data, directory_path, file_path, &context, action);
 
@@ -46,6 +60,16 @@ pub fn write_bxes_multiple_files(data: &BxesLogWriteData, directory_path: &str)
   )
 }
 
+fn get_file_section(file_name: &str) -> BxesSection {
+  match file_name {
+    constants::SYSTEM_METADATA_FILE_NAME => BxesSection::SystemMetadata,
+    constants::VALUES_FILE_NAME => BxesSection::Values,
+    constants::KEY_VALUES_FILE_NAME => BxesSection::KeyValues,
+    constants::METADATA_FILE_NAME => BxesSection::Metadata,
+    _ => BxesSection::Variants,
+  }
+}
+
 fn execute_with_writer<'a, T>(
   data: &'a BxesLogWriteData,
   directory_path: &'a str,
@@ -60,9 +84,32 @@ where
   let file_path = directory_path.join(file_name);
   let file_path = file_path.to_str().unwrap();
 
-  let mut file_stream = try_open_write(file_path)?;
-  let mut writer = BinaryWriter::new(&mut file_stream, Endian::Little);
+  let (start, end) = {
+    let mut file_stream = try_open_write(file_path)?;
+    let mut writer = BinaryWriter::new(&mut file_stream, Endian::Little);
+
+    try_write_u32_no_type_id(&mut writer, data.log.version)?;
+    let start = try_tell_pos(&mut writer)?;
+
+    let context = Rc::new(RefCell::new(context.with_writer(&mut writer)));
+    action(data, context.clone())?;
+
+    let end = try_tell_pos(context.borrow_mut().writer.as_mut().unwrap())?;
+    (start, end)
+  };
+
+  if let Some(variants_ranges) = context.variants_ranges.as_ref() {
+    let section = get_file_section(file_name);
+    let mut sections = vec![(section, start, end)];
+
+    if section == BxesSection::Variants {
+      for (variant_start, variant_end) in variants_ranges.borrow().iter() {
+        sections.push((BxesSection::Variant, *variant_start, *variant_end));
+      }
+    }
+
+    try_append_checksums_footer(file_path, &sections)?;
+  }
 
-  try_write_u32_no_type_id(&mut writer, data.log.version)?;
-  action(data, Rc::new(RefCell::new(context.with_writer(&mut writer))))
+  Ok(())
 }
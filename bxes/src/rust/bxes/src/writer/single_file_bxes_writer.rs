@@ -1,39 +1,53 @@
-use std::{cell::RefCell, fs::File, io::Read, rc::Rc};
+use std::{
+  cell::RefCell,
+  fs::File,
+  io::{Read, Write},
+  rc::Rc,
+};
 use tempfile::NamedTempFile;
 
 use crate::{
   binary_rw::core::{BinaryWriter, Endian},
-  writer::writer_utils::{BxesLogWriteData, try_write_system_metadata},
+  models::checksums::BxesSection,
+  writer::writer_utils::{BxesLogWriteData, BxesWriteOptions, try_write_system_metadata},
 };
 
 use super::{
   errors::BxesWriteError,
   write_context::BxesWriteContext,
   writer_utils::{
-    compress_to_archive, try_open_write, try_write_key_values, try_write_log_metadata, try_write_values, try_write_variants,
-    try_write_version,
+    compress_to_archive_with_codec, compress_to_stream_with_codec, try_append_checksums_footer, try_open_write, try_tell_pos,
+    try_write_key_values, try_write_log_metadata, try_write_values, try_write_variants, try_write_version,
   },
 };
 
 pub fn write_bxes(path: &str, data: &BxesLogWriteData) -> Result<(), BxesWriteError> {
+  write_bxes_with_options(path, data, &BxesWriteOptions::default())
+}
+
+pub fn write_bxes_with_options(path: &str, data: &BxesLogWriteData, options: &BxesWriteOptions) -> Result<(), BxesWriteError> {
   let temp_file = create_temp_file()?;
   let raw_log_path = temp_file.path().to_str().unwrap();
 
-  write_bxes_to_temp_file(data, raw_log_path)?;
-  compress_to_archive(raw_log_path, path)?;
+  write_bxes_to_temp_file(data, raw_log_path, options)?;
+  compress_to_archive_with_codec(raw_log_path, path, options.codec)?;
 
   Ok(())
 }
 
 pub fn write_bxes_to_bytes(data: &BxesLogWriteData) -> Result<Vec<u8>, BxesWriteError> {
+  write_bxes_to_bytes_with_options(data, &BxesWriteOptions::default())
+}
+
+pub fn write_bxes_to_bytes_with_options(data: &BxesLogWriteData, options: &BxesWriteOptions) -> Result<Vec<u8>, BxesWriteError> {
   let temp_file = create_temp_file()?;
   let raw_log_path = temp_file.path().to_str().unwrap();
 
-  write_bxes_to_temp_file(data, raw_log_path)?;
+  write_bxes_to_temp_file(data, raw_log_path, options)?;
   let archive_temp_file = create_temp_file()?;
   let archive_path = archive_temp_file.path().to_str().unwrap();
 
-  compress_to_archive(raw_log_path, archive_path)?;
+  compress_to_archive_with_codec(raw_log_path, archive_path, options.codec)?;
 
   let mut bytes = vec![];
 
@@ -46,6 +60,14 @@ pub fn write_bxes_to_bytes(data: &BxesLogWriteData) -> Result<Vec<u8>, BxesWrite
   }
 }
 
+pub fn write_bxes_to_stream(data: &BxesLogWriteData, options: &BxesWriteOptions, stream: &mut dyn Write) -> Result<(), BxesWriteError> {
+  let temp_file = create_temp_file()?;
+  let raw_log_path = temp_file.path().to_str().unwrap();
+
+  write_bxes_to_temp_file(data, raw_log_path, options)?;
+  compress_to_stream_with_codec(raw_log_path, stream, options.codec)
+}
+
 fn create_temp_file() -> Result<NamedTempFile, BxesWriteError> {
   match NamedTempFile::new() {
     Ok(file) => Ok(file),
@@ -53,20 +75,50 @@ fn create_temp_file() -> Result<NamedTempFile, BxesWriteError> {
   }
 }
 
-fn write_bxes_to_temp_file(data: &BxesLogWriteData, raw_log_path: &str) -> Result<(), BxesWriteError> {
-  let mut stream = try_open_write(raw_log_path)?;
-  let mut writer = BinaryWriter::new(&mut stream, Endian::Little);
+fn write_bxes_to_temp_file(data: &BxesLogWriteData, raw_log_path: &str, options: &BxesWriteOptions) -> Result<(), BxesWriteError> {
+  let mut sections = vec![];
+  let mut variants_ranges = None;
 
-  let context = BxesWriteContext::new(&mut writer, data.system_metadata.values_attrs.clone());
-  let context = Rc::new(RefCell::new(context));
+  {
+    let mut stream = try_open_write(raw_log_path)?;
+    let mut writer = BinaryWriter::new(&mut stream, Endian::Little);
 
-  let log = &data.log;
-  try_write_version(context.borrow_mut().writer.as_mut().unwrap(), log.version)?;
-  try_write_system_metadata(&data.system_metadata, context.clone())?;
-  try_write_values(log, context.clone())?;
-  try_write_key_values(log, context.clone())?;
-  try_write_log_metadata(log, context.clone())?;
-  try_write_variants(log, context.clone())?;
+    let mut context = BxesWriteContext::new(&mut writer, data.system_metadata.values_attrs.clone())
+      .with_event_attributes_schema(data.system_metadata.event_attributes_schema.clone());
+    if options.write_checksums {
+      variants_ranges = Some(context.track_variants_ranges());
+    }
+
+    let context = Rc::new(RefCell::new(context));
+
+    let log = &data.log;
+    try_write_version(context.borrow_mut().writer.as_mut().unwrap(), log.version)?;
+
+    let mut write_section = |section: BxesSection, action: &dyn Fn() -> Result<(), BxesWriteError>| -> Result<(), BxesWriteError> {
+      let start = try_tell_pos(context.borrow_mut().writer.as_mut().unwrap())?;
+      action()?;
+      let end = try_tell_pos(context.borrow_mut().writer.as_mut().unwrap())?;
+
+      sections.push((section, start, end));
+      Ok(())
+    };
+
+    write_section(BxesSection::SystemMetadata, &|| {
+      try_write_system_metadata(&data.system_metadata, context.clone())
+    })?;
+    write_section(BxesSection::Values, &|| try_write_values(log, context.clone()))?;
+    write_section(BxesSection::KeyValues, &|| try_write_key_values(log, context.clone()))?;
+    write_section(BxesSection::Metadata, &|| try_write_log_metadata(log, context.clone()))?;
+    write_section(BxesSection::Variants, &|| try_write_variants(log, context.clone()))?;
+  }
+
+  if let Some(variants_ranges) = variants_ranges {
+    for (start, end) in variants_ranges.borrow().iter() {
+      sections.push((BxesSection::Variant, *start, *end));
+    }
+
+    try_append_checksums_footer(raw_log_path, &sections)?;
+  }
 
   Ok(())
 }
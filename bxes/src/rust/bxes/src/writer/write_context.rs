@@ -1,6 +1,9 @@
 use crate::{
   binary_rw::core::BinaryWriter,
-  models::{domain::bxes_value::BxesValue, system_models::ValueAttributeDescriptor},
+  models::{
+    domain::bxes_value::BxesValue,
+    system_models::{EventAttributesSchema, ValueAttributeDescriptor},
+  },
 };
 use std::{
   cell::RefCell,
@@ -9,12 +12,16 @@ use std::{
   sync::Arc,
 };
 
+pub type VariantsRanges = Rc<RefCell<Vec<(usize, usize)>>>;
+
 pub struct BxesWriteContext<'b> {
   pub values_indices: Rc<RefCell<HashMap<Arc<BxesValue>, usize>>>,
   pub kv_indices: Rc<RefCell<HashMap<(Arc<BxesValue>, Arc<BxesValue>), usize>>>,
   pub writer: Option<&'b mut BinaryWriter<'b>>,
   pub value_attributes: Option<Vec<ValueAttributeDescriptor>>,
   pub value_attributes_set: Option<HashSet<ValueAttributeDescriptor>>,
+  pub variants_ranges: Option<VariantsRanges>,
+  pub event_attributes_schema: Option<EventAttributesSchema>,
 }
 
 impl<'b> BxesWriteContext<'b> {
@@ -25,6 +32,8 @@ impl<'b> BxesWriteContext<'b> {
       writer: None,
       value_attributes_set: Self::create_value_attributes_set(value_attributes.as_ref()),
       value_attributes,
+      variants_ranges: None,
+      event_attributes_schema: None,
     }
   }
 
@@ -39,6 +48,8 @@ impl<'b> BxesWriteContext<'b> {
       writer: Some(writer),
       value_attributes_set: Self::create_value_attributes_set(value_attributes.as_ref()),
       value_attributes,
+      variants_ranges: None,
+      event_attributes_schema: None,
     }
   }
 
@@ -49,6 +60,20 @@ impl<'b> BxesWriteContext<'b> {
       writer: Some(writer),
       value_attributes: self.value_attributes.clone(),
       value_attributes_set: self.value_attributes_set.clone(),
+      variants_ranges: self.variants_ranges.clone(),
+      event_attributes_schema: self.event_attributes_schema.clone(),
     }
   }
+
+  pub fn with_event_attributes_schema(mut self, schema: Option<EventAttributesSchema>) -> Self {
+    self.event_attributes_schema = schema;
+    self
+  }
+
+  pub fn track_variants_ranges(&mut self) -> VariantsRanges {
+    let ranges = Rc::new(RefCell::new(vec![]));
+    self.variants_ranges = Some(ranges.clone());
+
+    ranges
+  }
 }
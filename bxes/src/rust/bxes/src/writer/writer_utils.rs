@@ -1,34 +1,29 @@
 use num_traits::ToPrimitive;
-use std::{
-  cell::RefCell,
-  collections::HashMap,
-  fs::{self, File},
-  io::Write,
-  path::Path,
-  rc::Rc,
-  sync::Arc,
-};
-use zip::{ZipWriter, write::FileOptions};
+use std::{cell::RefCell, collections::HashMap, fs::OpenOptions, io::Write, rc::Rc, sync::Arc};
 
 use crate::{
   binary_rw::{
     core::{BinaryWriter, SeekStream},
     file_stream::FileStream,
   },
+  compression::codecs::BxesCompressionCodec,
+  constants::{CHECKSUMS_FOOTER_MAGIC, EVENT_ATTRIBUTES_SCHEMA_FLAG},
   models::{
+    checksums::BxesSection,
     domain::{
       bxes_artifact::BxesArtifact,
       bxes_driver::BxesDrivers,
-      bxes_event_log::{BxesEvent, BxesEventLog},
+      bxes_event_log::{BxesEvent, BxesEventLog, BxesTraceVariant},
       bxes_lifecycle::{BrafLifecycle, Lifecycle, StandardLifecycle},
       bxes_log_metadata::{BxesClassifier, BxesExtension, BxesGlobal},
       bxes_value::BxesValue,
       software_event_type::SoftwareEventType,
       type_ids::{TypeIds, get_type_id},
     },
-    system_models::{SystemMetadata, ValueAttributeDescriptor},
+    system_models::{EventAttributesSchema, SystemMetadata, ValueAttributeDescriptor},
   },
   read::read_utils::string_or_err,
+  utils::checksum::calculate_checksum,
 };
 
 use super::{errors::BxesWriteError, write_context::BxesWriteContext};
@@ -38,16 +33,32 @@ pub struct BxesLogWriteData {
   pub system_metadata: SystemMetadata,
 }
 
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BxesWriteOptions {
+  pub write_checksums: bool,
+  pub codec: BxesCompressionCodec,
+}
+
 pub fn try_write_system_metadata(system_metadata: &SystemMetadata, context: Rc<RefCell<BxesWriteContext>>) -> Result<(), BxesWriteError> {
-  try_write_values_attributes(system_metadata.values_attrs.as_ref(), context)
+  let schema = system_metadata.event_attributes_schema.as_ref();
+  try_write_values_attributes(system_metadata.values_attrs.as_ref(), schema.is_some(), context.clone())?;
+
+  if let Some(schema) = schema {
+    try_write_event_attributes_schema(schema, context)?;
+  }
+
+  Ok(())
 }
 
 fn try_write_values_attributes(
   value_attributes: Option<&Vec<ValueAttributeDescriptor>>,
+  has_schema: bool,
   context: Rc<RefCell<BxesWriteContext>>,
 ) -> Result<(), BxesWriteError> {
+  let flag = if has_schema { EVENT_ATTRIBUTES_SCHEMA_FLAG } else { 0 };
+
   if let Some(value_attributes) = value_attributes {
-    write_collection_and_count(context.clone(), false, value_attributes.len() as u32, || {
+    write_collection_and_count(context.clone(), false, value_attributes.len() as u32 | flag, || {
       for attr in value_attributes {
         try_write_u8_no_type_id(context.borrow_mut().writer.as_mut().unwrap(), attr.type_id.to_u8().unwrap())?;
         try_write_string(context.borrow_mut().writer.as_mut().unwrap(), attr.name.as_ref())?;
@@ -56,24 +67,63 @@ fn try_write_values_attributes(
       Ok(())
     })
   } else {
-    try_write_u32_no_type_id(context.borrow_mut().writer.as_mut().unwrap(), 0)
+    try_write_u32_no_type_id(context.borrow_mut().writer.as_mut().unwrap(), flag)
   }
 }
 
+fn try_write_event_attributes_schema(schema: &EventAttributesSchema, context: Rc<RefCell<BxesWriteContext>>) -> Result<(), BxesWriteError> {
+  write_collection_and_count(context.clone(), false, schema.attributes.len() as u32, || {
+    for attr in &schema.attributes {
+      let mut context = context.borrow_mut();
+      try_write_string(context.writer.as_mut().unwrap(), attr.name.as_ref())?;
+      try_write_u8_no_type_id(context.writer.as_mut().unwrap(), attr.type_id.to_u8().unwrap())?;
+      try_write_u8_no_type_id(context.writer.as_mut().unwrap(), if attr.required { 1 } else { 0 })?;
+
+      let allowed_values = attr.allowed_values.as_deref().unwrap_or(&[]);
+      try_write_u32_no_type_id(context.writer.as_mut().unwrap(), allowed_values.len() as u32)?;
+
+      for value in allowed_values {
+        if let BxesValue::Artifact(_) | BxesValue::Drivers(_) = value {
+          return Err(BxesWriteError::UnsupportedSchemaAllowedValue(value.clone()));
+        }
+
+        try_write_value(&mut context, value)?;
+      }
+    }
+
+    Ok(())
+  })?;
+
+  let allow_unknown = if schema.allow_unknown_attributes { 1 } else { 0 };
+  try_write_u8_no_type_id(context.borrow_mut().writer.as_mut().unwrap(), allow_unknown)
+}
+
 pub fn try_write_variants(log: &BxesEventLog, context: Rc<RefCell<BxesWriteContext>>) -> Result<(), BxesWriteError> {
   write_collection_and_count(context.clone(), false, log.variants.len() as u32, || {
     for variant in &log.variants {
-      try_write_u32_no_type_id(context.borrow_mut().writer.as_mut().unwrap(), variant.traces_count)?;
+      let variant_start = try_tell_pos(context.borrow_mut().writer.as_mut().unwrap())?;
 
-      try_write_attributes(context.clone(), Some(&variant.metadata), false)?;
+      try_write_variant(variant, context.clone())?;
 
-      write_collection_and_count(context.clone(), false, variant.events.len() as u32, || {
-        for event in &variant.events {
-          try_write_event(event, context.clone())?;
-        }
+      let variants_ranges = context.borrow().variants_ranges.clone();
+      if let Some(ranges) = variants_ranges {
+        let variant_end = try_tell_pos(context.borrow_mut().writer.as_mut().unwrap())?;
+        ranges.borrow_mut().push((variant_start, variant_end));
+      }
+    }
 
-        Ok(())
-      })?;
+    Ok(())
+  })
+}
+
+fn try_write_variant(variant: &BxesTraceVariant, context: Rc<RefCell<BxesWriteContext>>) -> Result<(), BxesWriteError> {
+  try_write_u32_no_type_id(context.borrow_mut().writer.as_mut().unwrap(), variant.traces_count)?;
+
+  try_write_attributes(context.clone(), Some(&variant.metadata), false)?;
+
+  write_collection_and_count(context.clone(), false, variant.events.len() as u32, || {
+    for event in &variant.events {
+      try_write_event(event, context.clone())?;
     }
 
     Ok(())
@@ -81,6 +131,12 @@ pub fn try_write_variants(log: &BxesEventLog, context: Rc<RefCell<BxesWriteConte
 }
 
 pub fn try_write_event(event: &BxesEvent, context: Rc<RefCell<BxesWriteContext>>) -> Result<(), BxesWriteError> {
+  if let Some(schema) = context.borrow().event_attributes_schema.as_ref()
+    && let Err(violation) = schema.validate(event)
+  {
+    return Err(BxesWriteError::SchemaViolation(violation));
+  }
+
   let exists = context.borrow().values_indices.borrow().contains_key(&event.name);
 
   if exists {
@@ -450,6 +506,29 @@ pub fn try_write_values(log: &BxesEventLog, context: Rc<RefCell<BxesWriteContext
   })
 }
 
+/// Writes the values table keeping the indices of values, so it can be read back with `try_read_values`.
+pub fn try_write_values_table(values: &[Arc<BxesValue>], context: &mut BxesWriteContext) -> Result<(), BxesWriteError> {
+  try_write_u32_no_type_id(context.writer.as_mut().unwrap(), values.len() as u32)?;
+
+  for (index, value) in values.iter().enumerate() {
+    try_write_value(context, value.as_ref())?;
+    context.values_indices.borrow_mut().entry(value.clone()).or_insert(index);
+  }
+
+  Ok(())
+}
+
+pub fn try_write_key_values_table(kv_pairs: &[(u32, u32)], writer: &mut BinaryWriter) -> Result<(), BxesWriteError> {
+  try_write_u32_no_type_id(writer, kv_pairs.len() as u32)?;
+
+  for (key_index, value_index) in kv_pairs {
+    try_write_leb_128(writer, *key_index)?;
+    try_write_leb_128(writer, *value_index)?;
+  }
+
+  Ok(())
+}
+
 fn write_collection_and_count(
   context: Rc<RefCell<BxesWriteContext>>,
   write_leb_128_count: bool,
@@ -490,7 +569,7 @@ fn try_seek(writer: &mut BinaryWriter, pos: usize) -> Result<(), BxesWriteError>
   }
 }
 
-fn try_tell_pos(writer: &mut BinaryWriter) -> Result<usize, BxesWriteError> {
+pub fn try_tell_pos(writer: &mut BinaryWriter) -> Result<usize, BxesWriteError> {
   match writer.tell() {
     Ok(pos) => Ok(pos),
     Err(err) => Err(BxesWriteError::FailedToGetWriterPosition(err.to_string())),
@@ -730,24 +809,34 @@ pub fn try_open_write(path: &str) -> Result<FileStream, BxesWriteError> {
 }
 
 pub fn compress_to_archive(log_path: &str, save_path: &str) -> Result<(), BxesWriteError> {
-  let file = File::create(save_path).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
-  let mut zip_writer = ZipWriter::new(file);
+  compress_to_archive_with_codec(log_path, save_path, BxesCompressionCodec::Deflate)
+}
+
+pub fn compress_to_archive_with_codec(log_path: &str, save_path: &str, codec: BxesCompressionCodec) -> Result<(), BxesWriteError> {
+  codec.create().compress(log_path, save_path)
+}
 
-  let archive_log_name = Path::new(save_path).file_name().unwrap().to_str().unwrap();
-  let options = FileOptions::<()>::default()
-    .compression_method(zip::CompressionMethod::Deflated)
-    .compression_level(Some(8));
+pub fn compress_to_stream_with_codec(log_path: &str, stream: &mut dyn Write, codec: BxesCompressionCodec) -> Result<(), BxesWriteError> {
+  codec.create().compress_to_stream(log_path, stream)
+}
 
-  zip_writer
-    .start_file(archive_log_name, options)
-    .map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+pub fn try_append_checksums_footer(path: &str, sections: &[(BxesSection, usize, usize)]) -> Result<(), BxesWriteError> {
+  let to_error = |err: std::io::Error| BxesWriteError::FailedToWriteChecksums(err.to_string());
+  let mut file = OpenOptions::new().read(true).append(true).open(path).map_err(to_error)?;
 
-  let bytes = fs::read(log_path).unwrap();
-  zip_writer.write_all(&bytes).map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+  let mut footer = vec![];
+  for (section, start, end) in sections {
+    let checksum = calculate_checksum(&mut file, *start, *end).map_err(to_error)?;
 
-  zip_writer.flush().map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+    footer.push(section.to_u8().unwrap());
+    footer.extend_from_slice(&(*start as u64).to_le_bytes());
+    footer.extend_from_slice(&((end - start) as u64).to_le_bytes());
+    footer.extend_from_slice(&checksum.to_le_bytes());
+  }
 
-  zip_writer.finish().map_err(|_| BxesWriteError::FailedToCreateArchive)?;
+  footer.extend_from_slice(&(sections.len() as u32).to_le_bytes());
+  footer.extend_from_slice(CHECKSUMS_FOOTER_MAGIC);
 
-  Ok(())
+  file.write_all(&footer).map_err(to_error)?;
+  file.flush().map_err(to_error)
 }
@@ -0,0 +1 @@
+pub mod test_async_read_write;
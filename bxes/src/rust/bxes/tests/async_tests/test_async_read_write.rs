@@ -0,0 +1,93 @@
+use bxes::{
+  compression::codecs::BxesCompressionCodec,
+  read::{
+    async_bxes_reader::{read_bxes_async, read_bxes_salvaging_async},
+    errors::BxesReadError,
+  },
+  writer::{
+    async_bxes_writer::write_bxes_async, single_file_bxes_writer::write_bxes_to_bytes_with_options, writer_utils::BxesWriteOptions,
+  },
+};
+use std::sync::Arc;
+use tokio::io::{AsyncWriteExt, duplex};
+
+use crate::test_core::random_log::generate_random_bxes_write_data;
+
+const CODECS: [BxesCompressionCodec; 3] = [
+  BxesCompressionCodec::None,
+  BxesCompressionCodec::Deflate,
+  BxesCompressionCodec::Zstd,
+];
+
+//Small buffer makes the writer wait for the reader, so the log can only be transferred if both sides stream
+const DUPLEX_BUFFER_SIZE: usize = 1024;
+
+fn options(codec: BxesCompressionCodec) -> BxesWriteOptions {
+  BxesWriteOptions {
+    write_checksums: true,
+    codec,
+  }
+}
+
+#[tokio::test]
+pub async fn test_async_read_write_through_duplex() {
+  let data = Arc::new(generate_random_bxes_write_data());
+
+  for codec in CODECS {
+    let (mut write_end, mut read_end) = duplex(DUPLEX_BUFFER_SIZE);
+
+    let write = async {
+      let result = write_bxes_async(&mut write_end, data.clone(), options(codec)).await;
+      write_end.shutdown().await.unwrap();
+      result
+    };
+
+    let (write_result, read_result) = tokio::join!(write, read_bxes_async(&mut read_end));
+
+    assert!(write_result.is_ok());
+
+    let read_result = read_result.unwrap();
+    assert!(read_result.log.eq(&data.log));
+    assert!(read_result.system_metadata.eq(&data.system_metadata));
+  }
+}
+
+#[tokio::test]
+pub async fn test_async_read_archive_written_to_bytes() {
+  let data = generate_random_bxes_write_data();
+
+  for codec in CODECS {
+    let bytes = write_bxes_to_bytes_with_options(&data, &options(codec)).ok().unwrap();
+
+    let (mut write_end, mut read_end) = duplex(DUPLEX_BUFFER_SIZE);
+    let write = async {
+      write_end.write_all(&bytes).await.unwrap();
+      write_end.shutdown().await.unwrap();
+    };
+
+    let (_, read_result) = tokio::join!(write, read_bxes_salvaging_async(&mut read_end));
+
+    let read_result = read_result.unwrap();
+    assert!(read_result.damaged_sections.is_empty());
+    assert!(read_result.damaged_variants.is_empty());
+    assert!(read_result.result.log.eq(&data.log));
+  }
+}
+
+#[tokio::test]
+pub async fn test_async_read_truncated_stream() {
+  let data = generate_random_bxes_write_data();
+  let bytes = write_bxes_to_bytes_with_options(&data, &options(BxesCompressionCodec::Zstd))
+    .ok()
+    .unwrap();
+
+  let (mut write_end, mut read_end) = duplex(DUPLEX_BUFFER_SIZE);
+  let write = async {
+    write_end.write_all(&bytes[..bytes.len() / 2]).await.unwrap();
+    write_end.shutdown().await.unwrap();
+  };
+
+  let (_, read_result) = tokio::join!(write, read_bxes_async(&mut read_end));
+
+  assert!(matches!(read_result, Err(BxesReadError::FailedToExtractArchive)));
+}
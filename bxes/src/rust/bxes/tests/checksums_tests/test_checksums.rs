@@ -0,0 +1,122 @@
+use bxes::{
+  compression::codecs::BxesCompressionCodec,
+  constants::VALUES_FILE_NAME,
+  models::checksums::BxesSection,
+  read::{
+    errors::BxesReadError,
+    multiple_files_bxes_reader::read_bxes_multiple_files,
+    read_utils::try_read_checksums_footer,
+    single_file_bxes_reader::{read_bxes, read_bxes_salvaging},
+  },
+  writer::{
+    multiple_file_bxes_writer::write_bxes_multiple_files_with_options,
+    single_file_bxes_writer::write_bxes_with_options,
+    writer_utils::{BxesWriteOptions, compress_to_archive},
+  },
+};
+use std::{fs, io::Read, path::Path};
+use tempfile::TempDir;
+use zip::ZipArchive;
+
+use crate::test_core::random_log::generate_random_bxes_write_data;
+
+const CHECKSUMS_OPTIONS: BxesWriteOptions = BxesWriteOptions {
+  write_checksums: true,
+  codec: BxesCompressionCodec::Deflate,
+};
+
+#[test]
+pub fn test_single_file_read_write_with_checksums() {
+  let temp_dir = TempDir::new().unwrap();
+  let log_save_path = temp_dir.path().join("log.bxes");
+  let log_save_path = log_save_path.to_str().unwrap();
+
+  let data = generate_random_bxes_write_data();
+  write_bxes_with_options(log_save_path, &data, &CHECKSUMS_OPTIONS).ok().unwrap();
+
+  let read_result = read_bxes(log_save_path).unwrap();
+  assert!(read_result.log.eq(&data.log));
+  assert!(read_result.system_metadata.eq(&data.system_metadata));
+}
+
+#[test]
+pub fn test_multiple_files_checksum_mismatch() {
+  let temp_dir = TempDir::new().unwrap();
+  let temp_dir_path = temp_dir.path().to_str().unwrap();
+
+  let data = generate_random_bxes_write_data();
+  write_bxes_multiple_files_with_options(&data, temp_dir_path, &CHECKSUMS_OPTIONS)
+    .ok()
+    .unwrap();
+
+  assert!(read_bxes_multiple_files(temp_dir_path).is_ok());
+
+  let values_path = Path::new(temp_dir_path).join(VALUES_FILE_NAME);
+  let values_section = find_section(values_path.to_str().unwrap(), BxesSection::Values, 0);
+  flip_byte(values_path.to_str().unwrap(), values_section + 1);
+
+  match read_bxes_multiple_files(temp_dir_path) {
+    Err(BxesReadError::ChecksumMismatch { section, offset }) => {
+      assert_eq!(section, BxesSection::Values);
+      assert_eq!(offset, values_section as u64);
+    }
+    other => panic!("Expected checksum mismatch, got {:?}", other.map(|_| ())),
+  }
+}
+
+#[test]
+pub fn test_salvage_damaged_variant() {
+  let temp_dir = TempDir::new().unwrap();
+  let log_save_path = temp_dir.path().join("log.bxes");
+  let log_save_path = log_save_path.to_str().unwrap();
+
+  let data = generate_random_bxes_write_data();
+  write_bxes_with_options(log_save_path, &data, &CHECKSUMS_OPTIONS).ok().unwrap();
+
+  let raw_log_path = temp_dir.path().join("raw_log.bxes");
+  let raw_log_path = raw_log_path.to_str().unwrap();
+  extract_single_file(log_save_path, raw_log_path);
+
+  let first_variant_offset = find_section(raw_log_path, BxesSection::Variant, 0);
+  flip_byte(raw_log_path, first_variant_offset + 2);
+  compress_to_archive(raw_log_path, log_save_path).ok().unwrap();
+
+  match read_bxes(log_save_path) {
+    Err(BxesReadError::ChecksumMismatch { section, .. }) => assert_eq!(section, BxesSection::Variants),
+    other => panic!("Expected checksum mismatch, got {:?}", other.map(|_| ())),
+  }
+
+  let salvage_result = read_bxes_salvaging(log_save_path).unwrap();
+  assert_eq!(salvage_result.damaged_sections, vec![BxesSection::Variants]);
+  assert_eq!(salvage_result.damaged_variants, vec![0]);
+
+  let salvaged_variants = &salvage_result.result.log.variants;
+  assert_eq!(salvaged_variants.len(), data.log.variants.len() - 1);
+
+  for (salvaged, original) in salvaged_variants.iter().zip(data.log.variants.iter().skip(1)) {
+    assert!(salvaged.eq(original));
+  }
+
+  assert!(salvage_result.result.log.metadata.eq(&data.log.metadata));
+}
+
+fn find_section(path: &str, section: BxesSection, index: usize) -> usize {
+  let checksums = try_read_checksums_footer(path).ok().unwrap().unwrap();
+  let checksum = checksums.iter().filter(|c| c.section == section).nth(index).unwrap();
+
+  checksum.offset as usize
+}
+
+fn flip_byte(path: &str, offset: usize) {
+  let mut bytes = fs::read(path).unwrap();
+  bytes[offset] = !bytes[offset];
+  fs::write(path, bytes).unwrap();
+}
+
+fn extract_single_file(archive_path: &str, save_path: &str) {
+  let mut archive = ZipArchive::new(fs::File::open(archive_path).unwrap()).unwrap();
+  let mut bytes = vec![];
+  archive.by_index(0).unwrap().read_to_end(&mut bytes).unwrap();
+
+  fs::write(save_path, bytes).unwrap();
+}
@@ -0,0 +1,90 @@
+use bxes::{
+  compression::codecs::BxesCompressionCodec,
+  read::single_file_bxes_reader::{read_bxes, read_bxes_from_archive_bytes},
+  writer::{
+    single_file_bxes_writer::{write_bxes_to_bytes_with_options, write_bxes_with_options},
+    writer_utils::BxesWriteOptions,
+  },
+};
+use std::{fs, time::Instant};
+use tempfile::TempDir;
+
+use crate::test_core::random_log::generate_random_bxes_write_data;
+
+const CODECS: [BxesCompressionCodec; 3] = [
+  BxesCompressionCodec::None,
+  BxesCompressionCodec::Deflate,
+  BxesCompressionCodec::Zstd,
+];
+
+fn options(codec: BxesCompressionCodec) -> BxesWriteOptions {
+  BxesWriteOptions {
+    write_checksums: false,
+    codec,
+  }
+}
+
+#[test]
+pub fn test_codecs_read_write() {
+  let data = generate_random_bxes_write_data();
+
+  for codec in CODECS {
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    write_bxes_with_options(log_save_path, &data, &options(codec)).ok().unwrap();
+
+    let read_result = read_bxes(log_save_path).unwrap();
+    assert!(read_result.log.eq(&data.log));
+    assert!(read_result.system_metadata.eq(&data.system_metadata));
+
+    let bytes = write_bxes_to_bytes_with_options(&data, &options(codec)).ok().unwrap();
+    let read_result = read_bxes_from_archive_bytes(&bytes).unwrap();
+    assert!(read_result.log.eq(&data.log));
+  }
+}
+
+#[test]
+#[ignore]
+pub fn bench_codecs() {
+  const ITERATIONS: usize = 5;
+  let data = generate_random_bxes_write_data();
+
+  // Throughput is measured relative to the uncompressed log, otherwise stronger codecs look slower
+  let raw_size = write_bxes_to_bytes_with_options(&data, &options(BxesCompressionCodec::None))
+    .ok()
+    .unwrap()
+    .len() as f64;
+
+  for codec in CODECS {
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    let write_start = Instant::now();
+    for _ in 0..ITERATIONS {
+      write_bxes_with_options(log_save_path, &data, &options(codec)).ok().unwrap();
+    }
+
+    let write_time = write_start.elapsed() / ITERATIONS as u32;
+
+    let read_start = Instant::now();
+    for _ in 0..ITERATIONS {
+      read_bxes(log_save_path).unwrap();
+    }
+
+    let read_time = read_start.elapsed() / ITERATIONS as u32;
+    let size = fs::metadata(log_save_path).unwrap().len();
+
+    println!(
+      "{:?}: size {} bytes, write {:?} ({:.2} MB/s), read {:?} ({:.2} MB/s)",
+      codec,
+      size,
+      write_time,
+      raw_size / 1024.0 / 1024.0 / write_time.as_secs_f64(),
+      read_time,
+      raw_size / 1024.0 / 1024.0 / read_time.as_secs_f64(),
+    );
+  }
+}
@@ -1,3 +1,10 @@
+#[cfg(feature = "async")]
+pub mod async_tests;
+pub mod checksums_tests;
+pub mod codecs_tests;
 pub mod multiple_files_tests;
+pub mod operations_tests;
+pub mod read_metadata_tests;
+pub mod schema_tests;
 pub mod single_file_tests;
 pub mod test_core;
@@ -0,0 +1,162 @@
+use bxes::{
+  models::{
+    domain::{
+      bxes_driver::{BxesDriver, BxesDrivers},
+      bxes_event_log::{BxesEvent, BxesEventLog, BxesTraceVariant},
+      bxes_log_metadata::BxesEventLogMetadata,
+      bxes_value::BxesValue,
+    },
+    system_models::SystemMetadata,
+  },
+  operations::{
+    merge::merge_bxes_files,
+    split::{BxesSplitCriterion, BxesSplitPartKey, split_bxes_file},
+  },
+  read::single_file_bxes_reader::read_bxes,
+  writer::{
+    single_file_bxes_writer::write_bxes,
+    writer_utils::{BxesLogWriteData, BxesWriteOptions},
+  },
+};
+use std::{path::Path, sync::Arc};
+use tempfile::TempDir;
+
+fn string_value(value: &str) -> Arc<BxesValue> {
+  Arc::new(BxesValue::String(Arc::from(value)))
+}
+
+fn create_variant(department: &str, names: &[&str], start: i64) -> BxesTraceVariant {
+  let drivers = BxesValue::Drivers(BxesDrivers {
+    drivers: vec![BxesDriver {
+      amount: BxesValue::Float64(0.5),
+      name: string_value("driver"),
+      driver_type: string_value("type"),
+    }],
+  });
+
+  BxesTraceVariant {
+    traces_count: 1,
+    metadata: vec![(string_value("department"), string_value(department))],
+    events: names
+      .iter()
+      .enumerate()
+      .map(|(index, name)| BxesEvent {
+        name: string_value(name),
+        timestamp: start + index as i64,
+        attributes: Some(vec![(string_value("drivers"), Arc::new(drivers.clone()))]),
+      })
+      .collect(),
+  }
+}
+
+fn create_write_data(variants: Vec<BxesTraceVariant>) -> BxesLogWriteData {
+  BxesLogWriteData {
+    log: BxesEventLog {
+      version: 1,
+      metadata: BxesEventLogMetadata {
+        extensions: None,
+        classifiers: None,
+        properties: Some(vec![(string_value("name"), string_value("log"))]),
+        globals: None,
+      },
+      variants,
+    },
+    system_metadata: SystemMetadata::new(None),
+  }
+}
+
+fn write_log(dir: &Path, name: &str, data: &BxesLogWriteData) -> String {
+  let path = dir.join(name).to_str().unwrap().to_string();
+  write_bxes(&path, data).ok().unwrap();
+
+  path
+}
+
+#[test]
+pub fn test_merge() {
+  let temp_dir = TempDir::new().unwrap();
+  let first = write_log(
+    temp_dir.path(),
+    "first.bxes",
+    &create_write_data(vec![create_variant("a", &["A", "B"], 0), create_variant("b", &["A", "C"], 10)]),
+  );
+
+  let second = write_log(
+    temp_dir.path(),
+    "second.bxes",
+    &create_write_data(vec![create_variant("a", &["A", "B"], 0), create_variant("c", &["D"], 20)]),
+  );
+
+  let merged_path = temp_dir.path().join("merged.bxes");
+  let merged_path = merged_path.to_str().unwrap();
+  merge_bxes_files(&[&first, &second], merged_path, &BxesWriteOptions::default())
+    .ok()
+    .unwrap();
+
+  let merged = read_bxes(merged_path).unwrap();
+  let mut expected = create_write_data(vec![
+    create_variant("a", &["A", "B"], 0),
+    create_variant("b", &["A", "C"], 10),
+    create_variant("c", &["D"], 20),
+  ]);
+
+  expected.log.variants[0].traces_count = 2;
+
+  assert_eq!(merged.log.variants.len(), 3);
+  assert!(merged.log.eq(&expected.log));
+}
+
+#[test]
+pub fn test_split() {
+  let temp_dir = TempDir::new().unwrap();
+  let data = create_write_data(vec![
+    create_variant("a", &["A", "B"], 0),
+    create_variant("b", &["A", "C"], 10),
+    create_variant("a", &["D"], 20),
+  ]);
+
+  let path = write_log(temp_dir.path(), "log.bxes", &data);
+  let split = |criterion: BxesSplitCriterion, dir: &str| {
+    let save_dir = temp_dir.path().join(dir);
+    split_bxes_file(&path, save_dir.to_str().unwrap(), &criterion, &BxesWriteOptions::default())
+      .ok()
+      .unwrap()
+      .into_iter()
+      .map(|(key, path)| (key, read_bxes(path.to_str().unwrap()).unwrap().log.variants.len()))
+      .collect::<Vec<(BxesSplitPartKey, usize)>>()
+  };
+
+  assert_eq!(
+    split(BxesSplitCriterion::TraceAttribute(Arc::from("department")), "attribute"),
+    vec![
+      (BxesSplitPartKey::TraceAttribute(Some(string_value("a"))), 2),
+      (BxesSplitPartKey::TraceAttribute(Some(string_value("b"))), 1),
+    ]
+  );
+
+  assert_eq!(
+    split(BxesSplitCriterion::TimeBoundaries(vec![5, 15]), "time"),
+    vec![
+      (BxesSplitPartKey::TimeRange { start: None, end: Some(5) }, 1),
+      (
+        BxesSplitPartKey::TimeRange {
+          start: Some(5),
+          end: Some(15)
+        },
+        1
+      ),
+      (
+        BxesSplitPartKey::TimeRange {
+          start: Some(15),
+          end: None
+        },
+        1
+      ),
+    ]
+  );
+
+  assert_eq!(
+    split(BxesSplitCriterion::VariantsCount(2), "count"),
+    vec![(BxesSplitPartKey::VariantsChunk(0), 2), (BxesSplitPartKey::VariantsChunk(1), 1)]
+  );
+}
@@ -0,0 +1 @@
+pub mod test_read_metadata_snapshot;
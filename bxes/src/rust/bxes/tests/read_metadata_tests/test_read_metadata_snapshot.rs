@@ -0,0 +1,57 @@
+use bxes::{
+  models::domain::{
+    bxes_artifact::{BxesArtifact, BxesArtifactItem},
+    bxes_value::BxesValue,
+  },
+  read::read_context::ReadMetadata,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[test]
+pub fn test_read_metadata_snapshot_round_trip() {
+  let model = Arc::new(BxesValue::String(Arc::from("model")));
+  let instance = Arc::new(BxesValue::String(Arc::from("instance")));
+  let transition = Arc::new(BxesValue::String(Arc::from("transition")));
+
+  let values = vec![
+    model.clone(),
+    instance.clone(),
+    transition.clone(),
+    Arc::new(BxesValue::Int64(-42)),
+    Arc::new(BxesValue::Uint32(42)),
+    Arc::new(BxesValue::Float64(1.5)),
+    Arc::new(BxesValue::Bool(true)),
+    Arc::new(BxesValue::Timestamp(1_700_000_000)),
+    Arc::new(BxesValue::Guid(Uuid::new_v4())),
+    Arc::new(BxesValue::Artifact(BxesArtifact {
+      items: vec![BxesArtifactItem {
+        model,
+        instance,
+        transition,
+      }],
+    })),
+  ];
+
+  let metadata = ReadMetadata {
+    values: Some(values.clone()),
+    kv_pairs: Some(vec![(0, 3), (1, 4), (2, 9)]),
+    system_metadata: None,
+  };
+
+  let bytes = metadata.to_snapshot_bytes().unwrap();
+  let restored = ReadMetadata::from_snapshot_bytes(&bytes).unwrap();
+
+  assert_eq!(restored.values.unwrap(), values);
+  assert_eq!(restored.kv_pairs.unwrap(), vec![(0, 3), (1, 4), (2, 9)]);
+  assert!(restored.system_metadata.is_none());
+}
+
+#[test]
+pub fn test_empty_read_metadata_snapshot() {
+  let bytes = ReadMetadata::default().to_snapshot_bytes().unwrap();
+  let restored = ReadMetadata::from_snapshot_bytes(&bytes).unwrap();
+
+  assert!(restored.values.unwrap().is_empty());
+  assert!(restored.kv_pairs.unwrap().is_empty());
+}
@@ -0,0 +1,157 @@
+use bxes::{
+  models::{
+    domain::{
+      bxes_event_log::{BxesEvent, BxesEventLog, BxesTraceVariant},
+      bxes_log_metadata::BxesEventLogMetadata,
+      bxes_value::BxesValue,
+      type_ids::TypeIds,
+    },
+    system_models::{EventAttributeSchema, EventAttributesSchema, EventSchemaViolation, SystemMetadata, ValueAttributeDescriptor},
+  },
+  read::single_file_bxes_reader::read_bxes,
+  writer::{errors::BxesWriteError, single_file_bxes_writer::write_bxes, writer_utils::BxesLogWriteData},
+};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn create_schema(allow_unknown_attributes: bool) -> EventAttributesSchema {
+  EventAttributesSchema::new(
+    vec![
+      EventAttributeSchema::new("org:resource", TypeIds::String, true)
+        .with_allowed_values(vec![BxesValue::String(Arc::from("alice")), BxesValue::String(Arc::from("bob"))]),
+      EventAttributeSchema::new("cost", TypeIds::F64, false),
+    ],
+    allow_unknown_attributes,
+  )
+}
+
+fn create_event(name: &str, attributes: Vec<(&str, BxesValue)>) -> BxesEvent {
+  BxesEvent {
+    name: Arc::new(BxesValue::String(Arc::from(name))),
+    timestamp: 123,
+    attributes: Some(
+      attributes
+        .into_iter()
+        .map(|(key, value)| (Arc::new(BxesValue::String(Arc::from(key))), Arc::new(value)))
+        .collect(),
+    ),
+  }
+}
+
+fn create_write_data(events: Vec<BxesEvent>, schema: EventAttributesSchema) -> BxesLogWriteData {
+  let value_attributes = vec![ValueAttributeDescriptor::new(TypeIds::F64, "cost".to_string())];
+
+  BxesLogWriteData {
+    log: BxesEventLog {
+      version: 1,
+      metadata: BxesEventLogMetadata {
+        extensions: None,
+        classifiers: None,
+        properties: None,
+        globals: None,
+      },
+      variants: vec![BxesTraceVariant {
+        traces_count: 1,
+        metadata: vec![],
+        events,
+      }],
+    },
+    system_metadata: SystemMetadata::new(Some(value_attributes)).with_event_attributes_schema(schema),
+  }
+}
+
+fn write_to_temp_file(data: &BxesLogWriteData) -> (TempDir, Result<(), BxesWriteError>) {
+  let temp_dir = TempDir::new().unwrap();
+  let path = temp_dir.path().join("log.bxes");
+  let result = write_bxes(path.to_str().unwrap(), data);
+
+  (temp_dir, result)
+}
+
+#[test]
+pub fn test_schema_read_write() {
+  let events = vec![
+    create_event(
+      "a",
+      vec![
+        ("org:resource", BxesValue::String(Arc::from("alice"))),
+        ("cost", BxesValue::Float64(1.5)),
+      ],
+    ),
+    create_event("b", vec![("org:resource", BxesValue::String(Arc::from("bob")))]),
+  ];
+
+  let data = create_write_data(events, create_schema(false));
+  let (temp_dir, result) = write_to_temp_file(&data);
+  result.ok().unwrap();
+
+  let read_result = read_bxes(temp_dir.path().join("log.bxes").to_str().unwrap()).unwrap();
+  assert!(read_result.log.eq(&data.log));
+  assert_eq!(read_result.system_metadata, data.system_metadata);
+}
+
+#[test]
+pub fn test_missing_required_attribute() {
+  let events = vec![create_event("a", vec![("cost", BxesValue::Float64(1.5))])];
+  let (_temp_dir, result) = write_to_temp_file(&create_write_data(events, create_schema(false)));
+
+  assert!(matches!(
+    result,
+    Err(BxesWriteError::SchemaViolation(EventSchemaViolation::MissingRequiredAttribute(name))) if name.as_ref() == "org:resource"
+  ));
+}
+
+#[test]
+pub fn test_type_mismatch() {
+  let events = vec![create_event(
+    "a",
+    vec![
+      ("org:resource", BxesValue::String(Arc::from("alice"))),
+      ("cost", BxesValue::Int64(1)),
+    ],
+  )];
+
+  let (_temp_dir, result) = write_to_temp_file(&create_write_data(events, create_schema(false)));
+
+  assert!(matches!(
+    result,
+    Err(BxesWriteError::SchemaViolation(EventSchemaViolation::TypeMismatch {
+      expected: TypeIds::F64,
+      actual: TypeIds::I64,
+      ..
+    }))
+  ));
+}
+
+#[test]
+pub fn test_value_is_not_allowed() {
+  let events = vec![create_event("a", vec![("org:resource", BxesValue::String(Arc::from("eve")))])];
+  let (_temp_dir, result) = write_to_temp_file(&create_write_data(events, create_schema(false)));
+
+  assert!(matches!(
+    result,
+    Err(BxesWriteError::SchemaViolation(EventSchemaViolation::ValueIsNotAllowed { .. }))
+  ));
+}
+
+#[test]
+pub fn test_unknown_attributes() {
+  let create_events = || {
+    vec![create_event(
+      "a",
+      vec![
+        ("org:resource", BxesValue::String(Arc::from("bob"))),
+        ("extra", BxesValue::Bool(true)),
+      ],
+    )]
+  };
+
+  let (_temp_dir, result) = write_to_temp_file(&create_write_data(create_events(), create_schema(false)));
+  assert!(matches!(
+    result,
+    Err(BxesWriteError::SchemaViolation(EventSchemaViolation::UnknownAttribute(_)))
+  ));
+
+  let (_temp_dir, result) = write_to_temp_file(&create_write_data(create_events(), create_schema(true)));
+  assert!(result.is_ok());
+}
@@ -69,6 +69,7 @@ pub fn generate_random_system_metadata(rng: &mut ThreadRng, log: &BxesEventLog)
         })
         .collect(),
     ),
+    event_attributes_schema: None,
   }
 }
 
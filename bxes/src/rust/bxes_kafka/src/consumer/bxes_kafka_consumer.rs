@@ -11,10 +11,11 @@ use bxes::{
       try_read_key_values, try_read_system_metadata, try_read_trace_variant_events, try_read_trace_variant_metadata, try_read_values,
     },
   },
+  writer::errors::BxesWriteError,
 };
 use log::info;
 use rdkafka::{
-  Message,
+  Message, Offset, TopicPartitionList,
   consumer::{BaseConsumer, CommitMode, Consumer},
   error::KafkaError,
 };
@@ -46,6 +47,10 @@ pub struct BxesKafkaTrace {
 }
 
 impl BxesKafkaTrace {
+  pub fn new(metadata: HashMap<Arc<str>, Arc<BxesValue>>, events: Vec<BxesEvent>) -> Self {
+    Self { metadata, events }
+  }
+
   pub fn metadata(&self) -> &HashMap<Arc<str>, Arc<BxesValue>> {
     &self.metadata
   }
@@ -55,10 +60,18 @@ impl BxesKafkaTrace {
   }
 }
 
+#[derive(Debug, Clone)]
+pub struct BxesKafkaPartitionOffset {
+  pub topic: String,
+  pub partition: i32,
+  pub offset: i64,
+}
+
 #[derive(Debug)]
 pub enum BxesKafkaError {
   Kafka(KafkaError),
   Bxes(BxesReadError),
+  BxesWrite(BxesWriteError),
 }
 
 impl From<BxesReadError> for BxesKafkaError {
@@ -67,6 +80,12 @@ impl From<BxesReadError> for BxesKafkaError {
   }
 }
 
+impl From<BxesWriteError> for BxesKafkaError {
+  fn from(value: BxesWriteError) -> Self {
+    Self::BxesWrite(value)
+  }
+}
+
 impl From<KafkaError> for BxesKafkaError {
   fn from(value: KafkaError) -> Self {
     Self::Kafka(value)
@@ -85,6 +104,68 @@ impl BxesKafkaConsumer {
     self.consumer.unsubscribe()
   }
 
+  pub fn lag(&self) -> Result<i64, BxesKafkaError> {
+    let mut lag = 0;
+
+    for partition in self.consumer.position()?.elements() {
+      let (_, high_watermark) = self
+        .consumer
+        .fetch_watermarks(partition.topic(), partition.partition(), Duration::from_millis(1000))?;
+
+      if let Offset::Offset(position) = partition.offset() {
+        lag += (high_watermark - position).max(0);
+      }
+    }
+
+    Ok(lag)
+  }
+
+  pub fn positions(&self) -> Result<Vec<BxesKafkaPartitionOffset>, BxesKafkaError> {
+    let mut offsets = vec![];
+
+    for partition in self.consumer.position()?.elements() {
+      if let Offset::Offset(offset) = partition.offset() {
+        offsets.push(BxesKafkaPartitionOffset {
+          topic: partition.topic().to_owned(),
+          partition: partition.partition(),
+          offset,
+        });
+      }
+    }
+
+    Ok(offsets)
+  }
+
+  /// Commits offsets for the consumer group, should be called before `subscribe` to start consuming from the given offsets.
+  pub fn commit_offsets(&self, offsets: &[BxesKafkaPartitionOffset]) -> Result<(), BxesKafkaError> {
+    let mut partitions = TopicPartitionList::new();
+    for offset in offsets {
+      partitions.add_partition_offset(&offset.topic, offset.partition, Offset::Offset(offset.offset))?;
+    }
+
+    self.consumer.commit(&partitions, CommitMode::Sync)?;
+
+    Ok(())
+  }
+
+  pub fn read_metadata_snapshot(&self) -> Result<HashMap<Uuid, Vec<u8>>, BxesKafkaError> {
+    let mut snapshot = HashMap::new();
+    for (session_id, read_metadata) in &self.session_id_to_read_metadata {
+      snapshot.insert(*session_id, read_metadata.to_snapshot_bytes()?);
+    }
+
+    Ok(snapshot)
+  }
+
+  pub fn restore_read_metadata(&mut self, snapshot: &HashMap<Uuid, Vec<u8>>) -> Result<(), BxesKafkaError> {
+    for (session_id, bytes) in snapshot {
+      let read_metadata = ReadMetadata::from_snapshot_bytes(bytes)?;
+      self.session_id_to_read_metadata.insert(*session_id, read_metadata);
+    }
+
+    Ok(())
+  }
+
   pub fn consume(&mut self) -> Result<Option<BxesKafkaTrace>, BxesKafkaError> {
     match self.consumer.poll(Duration::from_millis(1000)) {
       Some(message) => match message {